@@ -0,0 +1,137 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use libproto::VerifyTxResp;
+use lru_cache::LruCache;
+use std::cmp;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use util::{H256, Mutex};
+
+const SHARD_NUM: usize = 16;
+
+/// Bounded cache of tx verification results.
+///
+/// Entries are spread over `SHARD_NUM` independently locked LRU shards by tx hash,
+/// so concurrent verification threads rarely contend on the same lock.
+pub struct VerifyCache {
+    shards: Vec<Mutex<LruCache<H256, VerifyTxResp>>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    evictions: AtomicUsize,
+}
+
+impl VerifyCache {
+    /// Create a cache holding at most about `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        let shard_capacity = cmp::max(1, capacity / SHARD_NUM);
+        VerifyCache {
+            shards: (0..SHARD_NUM).map(|_| Mutex::new(LruCache::new(shard_capacity))).collect(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            evictions: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard(&self, tx_hash: &H256) -> &Mutex<LruCache<H256, VerifyTxResp>> {
+        &self.shards[tx_hash.low_u64() as usize % SHARD_NUM]
+    }
+
+    pub fn get(&self, tx_hash: &H256) -> Option<VerifyTxResp> {
+        let resp = self.shard(tx_hash).lock().get_mut(tx_hash).map(|resp| resp.clone());
+        if resp.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        resp
+    }
+
+    pub fn insert(&self, tx_hash: H256, resp: VerifyTxResp) {
+        let mut shard = self.shard(&tx_hash).lock();
+        if !shard.contains_key(&tx_hash) && shard.len() == shard.capacity() {
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        shard.insert(tx_hash, resp);
+    }
+
+    pub fn remove(&self, tx_hash: &H256) {
+        self.shard(tx_hash).lock().remove(tx_hash);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    pub fn evictions(&self) -> usize {
+        self.evictions.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resp(tx_hash: &H256) -> VerifyTxResp {
+        let mut resp = VerifyTxResp::new();
+        resp.set_tx_hash(tx_hash.to_vec());
+        resp
+    }
+
+    #[test]
+    fn bounded() {
+        let cache = VerifyCache::new(SHARD_NUM * 2);
+        for i in 0..(SHARD_NUM * 10) {
+            let hash = H256::from(i as u64);
+            cache.insert(hash, resp(&hash));
+        }
+        assert_eq!(cache.len(), SHARD_NUM * 2);
+        assert_eq!(cache.evictions(), SHARD_NUM * 8);
+    }
+
+    #[test]
+    fn hit_and_miss() {
+        let cache = VerifyCache::new(SHARD_NUM);
+        let hash = H256::from(1);
+        assert_eq!(cache.get(&hash), None);
+        cache.insert(hash, resp(&hash));
+        assert_eq!(cache.get(&hash), Some(resp(&hash)));
+        cache.remove(&hash);
+        assert_eq!(cache.get(&hash), None);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        // one entry per shard
+        let cache = VerifyCache::new(SHARD_NUM);
+        let first = H256::from(1);
+        let second = H256::from(1 + SHARD_NUM as u64);
+        cache.insert(first, resp(&first));
+        cache.insert(second, resp(&second));
+        assert_eq!(cache.get(&first), None);
+        assert_eq!(cache.get(&second), Some(resp(&second)));
+    }
+}
@@ -8,12 +8,21 @@ pub struct Config {
     pub buffer_duration: u32,
     pub tx_verify_thread_num: usize,
     pub tx_verify_num_per_thread: usize,
+    #[serde(default = "default_tx_verify_cache_size")]
+    pub tx_verify_cache_size: usize,
     pub tx_pool_limit: usize,
+    /// 0 leaves the pending txs of a sender unlimited.
+    #[serde(default)]
+    pub tx_pool_sender_limit: usize,
     pub block_packet_tx_limit: usize,
     pub prof_start: u64,
     pub prof_duration: u64,
 }
 
+fn default_tx_verify_cache_size() -> usize {
+    100000
+}
+
 impl Config {
     pub fn new(path: &str) -> Self {
         let config_file = File::open(path).unwrap();
@@ -18,31 +18,31 @@
 extern crate tx_pool;
 
 use error::ErrorCode;
-use libproto::{submodules, topics, factory, communication, Response, TxResponse, Request, BatchRequest};
+use libproto::{submodules, Response, TxResponse, Request, BatchRequest};
 use libproto::blockchain::{BlockBody, SignedTransaction, BlockTxs, AccountGasLimit};
+use libproto::router::{AuthBlockTxs, AuthRpc, AuthTx, Route};
 use protobuf::{Message, RepeatedField};
 use serde_json;
 
 use std::cell::RefCell;
 use std::collections::HashSet;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering, AtomicUsize};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
-use std::thread;
 use std::time::SystemTime;
+use tx_pool::EnqueueResult;
 use txwal::Txwal;
 use util::H256;
 use uuid::Uuid;
 
 pub struct Dispatchtx {
     txs_pool: RefCell<tx_pool::Pool>,
-    tx_pool_cap: Arc<AtomicUsize>,
     wal: Txwal,
     filter_wal: Txwal,
     wal_enable: bool,
-    pool_limit: usize,
     data_from_pool: AtomicBool,
     batch_forward_info: BatchForwardInfo,
+    /// Answers to the txs dealt since the last `commit`, held until their records are durable
+    responses: Vec<Response>,
     response_jsonrpc_cnt: u64,
     start_verify_time: SystemTime,
     add_to_pool_cnt: u64,
@@ -56,7 +56,7 @@ pub struct BatchForwardInfo {
 }
 
 impl Dispatchtx {
-    pub fn new(package_limit: usize, limit: usize, count_per_batch: usize, buffer_duration: u32, wal_enable: bool) -> Self {
+    pub fn new(package_limit: usize, limit: usize, sender_limit: usize, count_per_batch: usize, buffer_duration: u32, wal_enable: bool) -> Self {
         let batch_forward_info = BatchForwardInfo {
             count_per_batch: count_per_batch,
             buffer_duration: buffer_duration,
@@ -65,14 +65,13 @@ impl Dispatchtx {
         };
 
         let mut dispatch = Dispatchtx {
-            txs_pool: RefCell::new(tx_pool::Pool::new(package_limit)),
-            tx_pool_cap: Arc::new(AtomicUsize::new(limit)),
+            txs_pool: RefCell::new(tx_pool::Pool::new_with_limit(package_limit, limit, sender_limit)),
             wal: Txwal::new("/txwal"),
             filter_wal: Txwal::new("/filterwal"),
             wal_enable: wal_enable,
-            pool_limit: limit,
             data_from_pool: AtomicBool::new(false),
             batch_forward_info: batch_forward_info,
+            responses: Vec::new(),
             response_jsonrpc_cnt: 0,
             start_verify_time: SystemTime::now(),
             add_to_pool_cnt: 0,
@@ -86,26 +85,14 @@ impl Dispatchtx {
         dispatch
     }
 
-    pub fn tx_pool_capacity(&self) -> Arc<AtomicUsize> {
-        self.tx_pool_cap.clone()
-    }
-
-    fn update_capacity(&mut self) {
-        let tx_pool_len = self.txs_pool.borrow().len();
-        if self.pool_limit >= tx_pool_len {
-            let capacity = self.pool_limit - tx_pool_len;
-            self.tx_pool_cap.store(capacity, Ordering::SeqCst);
-        } else {
-            self.tx_pool_cap.store(0, Ordering::SeqCst);
-        }
-    }
-
-    pub fn deal_tx(&mut self, modid: u32, req_id: Vec<u8>, tx_response: TxResponse, tx: &SignedTransaction, mq_pub: &Sender<(String, Vec<u8>)>) {
+    /// Add a verified tx to the pool. Its answer and its forward to the peers
+    /// wait for `commit`.
+    pub fn deal_tx(&mut self, modid: u32, req_id: Vec<u8>, tx_response: TxResponse, tx: &SignedTransaction) {
         let mut error_msg: Option<String> = None;
-        if self.add_tx_to_pool(&tx) {
-            self.update_capacity();
-        } else {
-            error_msg = Some(String::from("Dup"));
+        match self.add_tx_to_pool(&tx) {
+            EnqueueResult::Dup => error_msg = Some(String::from("Dup")),
+            EnqueueResult::SenderLimit => error_msg = Some(String::from("SenderLimit")),
+            _ => {}
         }
 
         if modid == submodules::JSON_RPC {
@@ -125,21 +112,9 @@ impl Dispatchtx {
                 request.set_un_tx(tx.get_transaction_with_sig().clone());
                 request.set_request_id(request_id);
                 self.batch_forward_info.new_tx_request_buffer.push(request);
-
-                let count_buffered = self.batch_forward_info.new_tx_request_buffer.len();
-                let time_elapsed = self.batch_forward_info.forward_stamp.elapsed().unwrap().subsec_nanos();
-
-                if count_buffered > self.batch_forward_info.count_per_batch || time_elapsed > self.batch_forward_info.buffer_duration {
-                    trace!("Going to send new tx batch to peer auth with {} new tx and buffer {} ns", count_buffered, time_elapsed);
-
-                    self.batch_forward_tx_to_peer(mq_pub);
-                }
             }
 
-            let msg = factory::create_msg(submodules::AUTH, topics::RESPONSE, communication::MsgType::RESPONSE, response.write_to_bytes().unwrap());
-            self.response_jsonrpc_cnt += 1;
-            trace!("response new tx {:?}, with response_jsonrpc_cnt = {}", response, self.response_jsonrpc_cnt);
-            mq_pub.send(("auth.rpc".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            self.responses.push(response);
         }
         if 0 == self.add_to_pool_cnt {
             self.start_verify_time = SystemTime::now();
@@ -152,9 +127,13 @@ impl Dispatchtx {
         let mut body = BlockBody::new();
 
         trace!("deal_txs inner txs height {} ", txs.len());
+        // commit the txs added so far first, so their records are not written after being deleted
+        self.commit_wal();
         if !txs.is_empty() {
             self.del_txs_from_pool_with_hash(txs);
         }
+        self.del_expired_txs_from_pool(height as u64);
+        self.commit_wal();
 
         let out_txs = self.get_txs_from_pool(height as u64, block_gas_limit, account_gas_limit);
         info!("public block txs height {} with {:?} txs on timestamp: {:?}", height, out_txs.len(), SystemTime::now());
@@ -167,15 +146,13 @@ impl Dispatchtx {
             self.add_to_pool_cnt = 0;
         }
 
-        self.update_capacity();
         if !out_txs.is_empty() {
             body.set_transactions(RepeatedField::from_vec(out_txs));
         }
         block_txs.set_height(height as u64);
         block_txs.set_body(body);
         trace!("deal_txs send height {}", height);
-        let msg = factory::create_msg(submodules::AUTH, topics::BLOCK_TXS, communication::MsgType::BLOCK_TXS, block_txs.write_to_bytes().unwrap());
-        mq_pub.send(("auth.block_txs".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        AuthBlockTxs::publish(mq_pub, &block_txs);
     }
 
     pub fn wait_timeout_process(&mut self, mq_pub: &Sender<(String, Vec<u8>)>) {
@@ -187,19 +164,51 @@ impl Dispatchtx {
         }
     }
 
-    pub fn add_tx_to_pool(&self, tx: &SignedTransaction) -> bool {
+    pub fn add_tx_to_pool(&self, tx: &SignedTransaction) -> EnqueueResult {
         //交易放入pool，
         //放入pool完成后，持久化
         let ref mut txs_pool = self.txs_pool.borrow_mut();
-        let success = txs_pool.enqueue(tx.clone());
+        let result = txs_pool.enqueue(tx.clone());
         if self.wal_enable {
-            if success {
+            match result {
+                EnqueueResult::Replaced(hash) | EnqueueResult::Evicted(hash) => {
+                    self.wal.delete_with_hash(&hash);
+                }
+                _ => {}
+            }
+            if result.is_ok() {
                 self.wal.write(&tx);
             } else {
                 self.filter_wal.write(&tx);
             }
         }
-        success
+        result
+    }
+
+    /// Make the txs dealt since the last call durable, then answer them and
+    /// forward them, called once per group of verified txs.
+    pub fn commit(&mut self, mq_pub: &Sender<(String, Vec<u8>)>) {
+        self.commit_wal();
+        for response in self.responses.drain(..) {
+            self.response_jsonrpc_cnt += 1;
+            trace!("response new tx {:?}, with response_jsonrpc_cnt = {}", response, self.response_jsonrpc_cnt);
+            AuthRpc::publish(mq_pub, &response);
+        }
+
+        let count_buffered = self.batch_forward_info.new_tx_request_buffer.len();
+        let time_elapsed = self.batch_forward_info.forward_stamp.elapsed().unwrap().subsec_nanos();
+        if count_buffered > self.batch_forward_info.count_per_batch || time_elapsed > self.batch_forward_info.buffer_duration {
+            trace!("Going to send new tx batch to peer auth with {} new tx and buffer {} ns", count_buffered, time_elapsed);
+            self.batch_forward_tx_to_peer(mq_pub);
+        }
+    }
+
+    /// Flush the WAL records buffered since the last commit.
+    fn commit_wal(&self) {
+        if self.wal_enable {
+            let ops = self.wal.commit() + self.filter_wal.commit();
+            trace!("commit {} ops to tx wal", ops);
+        }
     }
 
     pub fn get_txs_from_pool(&self, height: u64, block_gas_limit: u64, account_gas_limit: AccountGasLimit) -> Vec<SignedTransaction> {
@@ -218,13 +227,22 @@ impl Dispatchtx {
         {
             self.txs_pool.borrow_mut().update_with_hash(txs);
         }
-        //改成多线程删除数据
         if self.wal_enable {
-            let mut wal = self.wal.clone();
-            let txs = txs.clone();
-            thread::spawn(move || for tx in txs {
-                              wal.delete_with_hash(&tx);
-                          });
+            for tx in txs {
+                self.wal.delete_with_hash(tx);
+            }
+        }
+    }
+
+    pub fn del_expired_txs_from_pool(&self, height: u64) {
+        let expired = self.txs_pool.borrow_mut().remove_expired(height);
+        if !expired.is_empty() {
+            trace!("drop {} expired txs from pool at height {}", expired.len(), height);
+        }
+        if self.wal_enable {
+            for tx in expired {
+                self.wal.delete_with_hash(&tx);
+            }
         }
     }
 
@@ -233,19 +251,16 @@ impl Dispatchtx {
         {
             self.txs_pool.borrow_mut().update(&txs);
         }
-        //改成多线程删除数据
         if self.wal_enable {
-            let mut wal = self.wal.clone();
-            thread::spawn(move || for tx in txs {
-                              wal.delete(&tx);
-                          });
+            for tx in txs {
+                self.wal.delete_with_hash(&H256::from_slice(tx.get_tx_hash()));
+            }
+            self.wal.commit();
         }
     }
 
     pub fn read_tx_from_wal(&mut self) -> u64 {
-        let size = self.wal.read(&mut self.txs_pool.borrow_mut());
-        self.update_capacity();
-        size
+        self.wal.read(&mut self.txs_pool.borrow_mut())
     }
 
     fn batch_forward_tx_to_peer(&mut self, mq_pub: &Sender<(String, Vec<u8>)>) {
@@ -258,8 +273,7 @@ impl Dispatchtx {
         request.set_batch_req(batch_request);
         request.set_request_id(request_id);
 
-        let msg = factory::create_msg(submodules::AUTH, topics::REQUEST, communication::MsgType::REQUEST, request.write_to_bytes().unwrap());
-        mq_pub.send(("auth.tx".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        AuthTx::publish(mq_pub, &request);
 
         self.batch_forward_info.forward_stamp = SystemTime::now();
         self.batch_forward_info.new_tx_request_buffer.clear();
@@ -15,11 +15,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use cache::VerifyCache;
 use error::ErrorCode;
 use libproto::*;
 use libproto::blockchain::{SignedTransaction, AccountGasLimit, UnverifiedTransaction};
+use libproto::router::{AuthRpc, AuthVerdict, AuthVerifyBlockResp, Route};
 use protobuf::Message;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering, ATOMIC_U64_INIT};
 use std::sync::mpsc::{Sender, Receiver};
@@ -43,6 +45,8 @@ pub enum VerifyRequestResponse {
 #[derive(Debug, Clone)]
 pub struct VerifyRequestResponseInfo {
     pub sub_module: u32,
+    /// Peer the request came from, `ZERO_ORIGIN` when local
+    pub origin: Origin,
     pub verify_type: VerifyType,
     pub request_id: VerifyRequestID,
     pub time_stamp: SystemTime,
@@ -113,18 +117,22 @@ fn verfiy_tx(req: &VerifyTxReq, verifier: &Verifier) -> VerifyTxResp {
     resp
 }
 
-
-pub fn process_flow_control_failed(mut verify_info: VerifyRequestResponseInfo, resp_sender: &Sender<VerifyRequestResponseInfo>) {
-    let mut response = VerifyTxResp::new();
-    if let VerifyRequestResponse::AuthRequest(req) = verify_info.req_resp {
-        response.set_tx_hash(req.get_tx_hash().to_vec());
-        response.set_ret(Ret::Busy);
-        verify_info.req_resp = VerifyRequestResponse::AuthResponse(response);
-        resp_sender.send(verify_info).unwrap();
+// Only new txs carry the unverified tx, so block verification is never affected.
+fn check_admission(un_tx: &Option<UnverifiedTransaction>, verifier: &Verifier, resp: &mut VerifyTxResp) {
+    if resp.get_ret() != Ret::Ok {
+        return;
+    }
+    if let Some(ref un_tx) = *un_tx {
+        let ret = verifier.verify_admission(un_tx, resp.get_signer());
+        if ret != Ret::Ok {
+            trace!("tx with hash {:?} is not admitted, ret={:?}", resp.get_tx_hash(), ret);
+            resp.set_ret(ret);
+        }
     }
 }
 
-pub fn verify_tx_group_service(mut req_grp: Vec<VerifyRequestResponseInfo>, verifier: Arc<RwLock<Verifier>>, cache: Arc<RwLock<HashMap<H256, VerifyTxResp>>>, resp_sender: Sender<VerifyRequestResponseInfo>) {
+
+pub fn verify_tx_group_service(mut req_grp: Vec<VerifyRequestResponseInfo>, verifier: Arc<RwLock<Verifier>>, cache: Arc<VerifyCache>, resp_sender: Sender<VerifyRequestResponseInfo>) {
     let now = SystemTime::now();
     let len = req_grp.len();
 
@@ -132,8 +140,10 @@ pub fn verify_tx_group_service(mut req_grp: Vec<VerifyRequestResponseInfo>, veri
         if let Some(mut req_info) = req_grp.pop() {
             if let VerifyRequestResponse::AuthRequest(req) = req_info.req_resp {
                 let tx_hash = H256::from_slice(req.get_tx_hash());
-                let response = verfiy_tx(&req, &verifier.read());
-                cache.write().insert(tx_hash, response.clone());
+                let verifier = verifier.read();
+                let mut response = verfiy_tx(&req, &verifier);
+                cache.insert(tx_hash, response.clone());
+                check_admission(&req_info.un_tx, &verifier, &mut response);
                 req_info.req_resp = VerifyRequestResponse::AuthResponse(response);
                 resp_sender.send(req_info).unwrap();
             }
@@ -145,7 +155,7 @@ pub fn verify_tx_group_service(mut req_grp: Vec<VerifyRequestResponseInfo>, veri
     trace!("verify_tx_group_service Time cost {} ns for {} req ...", now.elapsed().unwrap().subsec_nanos(), len);
 }
 
-pub fn check_verify_request_preprocess(mut req_info: VerifyRequestResponseInfo, verifier: Arc<RwLock<Verifier>>, cache: Arc<RwLock<HashMap<H256, VerifyTxResp>>>, resp_sender: &Sender<VerifyRequestResponseInfo>) -> VerifyResult {
+pub fn check_verify_request_preprocess(mut req_info: VerifyRequestResponseInfo, verifier: Arc<RwLock<Verifier>>, cache: Arc<VerifyCache>, resp_sender: &Sender<VerifyRequestResponseInfo>) -> VerifyResult {
     if let VerifyRequestResponse::AuthRequest(req) = req_info.req_resp {
         let tx_hash = H256::from_slice(req.get_tx_hash());
         let mut final_response = VerifyTxResp::new();
@@ -160,7 +170,8 @@ pub fn check_verify_request_preprocess(mut req_info: VerifyRequestResponseInfo,
             processed = true;
             final_response = response;
         } else {
-            if let Some(resp) = get_resp_from_cache(&tx_hash, cache.clone()) {
+            if let Some(mut resp) = get_resp_from_cache(&tx_hash, cache.clone()) {
+                check_admission(&req_info.un_tx, &verifier.read(), &mut resp);
                 processed = true;
                 final_response = resp;
             }
@@ -183,12 +194,12 @@ pub fn check_verify_request_preprocess(mut req_info: VerifyRequestResponseInfo,
     }
 }
 
-fn get_resp_from_cache(tx_hash: &H256, cache: Arc<RwLock<HashMap<H256, VerifyTxResp>>>) -> Option<VerifyTxResp> {
-    if let Some(resp) = cache.read().get(tx_hash) { Some(resp.clone()) } else { None }
+fn get_resp_from_cache(tx_hash: &H256, cache: Arc<VerifyCache>) -> Option<VerifyTxResp> {
+    cache.get(tx_hash)
 }
 
-pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_req_block: &Sender<VerifyRequestResponseInfo>, tx_req_single: &Sender<VerifyRequestResponseInfo>, tx_pub: &Sender<(String, Vec<u8>)>, block_verify_status: Arc<RwLock<BlockVerifyStatus>>, cache: Arc<RwLock<HashMap<H256, VerifyTxResp>>>, txs_sender: &Sender<(usize, HashSet<H256>, u64, AccountGasLimit)>, resp_sender: &Sender<VerifyRequestResponseInfo>) {
-    let (cmdid, _origin, content) = parse_msg(payload.as_slice());
+pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_req_block: &Sender<VerifyRequestResponseInfo>, tx_req_single: &Sender<VerifyRequestResponseInfo>, tx_pub: &Sender<(String, Vec<u8>)>, block_verify_status: Arc<RwLock<BlockVerifyStatus>>, cache: Arc<VerifyCache>, txs_sender: &Sender<(usize, HashSet<H256>, u64, AccountGasLimit)>, resp_sender: &Sender<VerifyRequestResponseInfo>) {
+    let (cmdid, origin, content) = parse_msg(payload.as_slice());
     let (submodule, _topic) = de_cmd_id(cmdid);
     match content {
         MsgClass::BLOCKTXHASHES(block_tx_hashes) => {
@@ -196,15 +207,12 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
             info!("get block tx hashs for height {:?}", height);
             let tx_hashes = block_tx_hashes.get_tx_hashes();
             let mut tx_hashes_in_h256 = HashSet::with_capacity(tx_hashes.len());
-            {
-                let mut cache_guard = cache.write();
-
-                for data in tx_hashes.iter() {
-                    let hash = H256::from_slice(data);
-                    cache_guard.remove(&hash);
-                    tx_hashes_in_h256.insert(hash);
-                }
+            for data in tx_hashes.iter() {
+                let hash = H256::from_slice(data);
+                cache.remove(&hash);
+                tx_hashes_in_h256.insert(hash);
             }
+            info!("verify cache has {} entries, {} hits, {} misses, {} evictions", cache.len(), cache.hits(), cache.misses(), cache.evictions());
 
             {
                 let mut flag = false;
@@ -221,6 +229,7 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
                     info!("Auth rich status block gas limit: {:?}, account gas limit {:?}", block_gas_limit, account_gas_limit);
 
                     let _ = txs_sender.send((height as usize, tx_hashes_in_h256.clone(), block_gas_limit, account_gas_limit));
+                    verifier.write().update_admission(&block_tx_hashes);
                 }
             }
             verifier.write().update_hashes(height, tx_hashes_in_h256, &tx_pub);
@@ -251,6 +260,7 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
                     for req in blkreq.get_reqs() {
                         let verify_request_info = VerifyRequestResponseInfo {
                             sub_module: submodule,
+                            origin: origin,
                             verify_type: VerifyType::BlockVerify,
                             request_id: VerifyRequestID::BlockVerifyRequestID(request_id),
                             time_stamp: now,
@@ -281,6 +291,7 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
                             _ => {
                                 let verify_request_info = VerifyRequestResponseInfo {
                                     sub_module: submodule,
+                                    origin: origin,
                                     verify_type: VerifyType::BlockVerify,
                                     request_id: VerifyRequestID::BlockVerifyRequestID(request_id),
                                     time_stamp: now,
@@ -313,6 +324,7 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
                     let verify_tx_req = tx_req.get_un_tx().tx_verify_req_msg();
                     let verify_request_info = VerifyRequestResponseInfo {
                         sub_module: submodule,
+                        origin: origin,
                         verify_type: VerifyType::SingleVerify,
                         request_id: VerifyRequestID::SingleVerifyRequestID(tx_req.get_request_id().to_vec()),
                         time_stamp: now,
@@ -327,6 +339,7 @@ pub fn handle_remote_msg(payload: Vec<u8>, verifier: Arc<RwLock<Verifier>>, tx_r
                 let verify_tx_req = newtx_req.get_un_tx().tx_verify_req_msg();
                 let verify_request_info = VerifyRequestResponseInfo {
                     sub_module: submodule,
+                    origin: origin,
                     verify_type: VerifyType::SingleVerify,
                     request_id: VerifyRequestID::SingleVerifyRequestID(newtx_req.get_request_id().to_vec()),
                     time_stamp: now,
@@ -366,7 +379,11 @@ pub fn handle_verificaton_result(result_receiver: &Receiver<VerifyRequestRespons
                                                      .unwrap();
                                     trace!("Send singed tx to txpool");
                                 }
-                                _ => {
+                                ret => {
+                                    if ret == Ret::BadSig && Verdict::is_remote(verify_response_info.origin) {
+                                        let verdict = Verdict::new(verify_response_info.origin, Offence::InvalidTx, format!("bad signature of tx {:?}", tx_hash));
+                                        AuthVerdict::publish(tx_pub, &verdict);
+                                    }
                                     if verify_response_info.sub_module == submodules::JSON_RPC {
                                         let tx_response = TxResponse::new(tx_hash, result);
 
@@ -375,9 +392,8 @@ pub fn handle_verificaton_result(result_receiver: &Receiver<VerifyRequestRespons
                                         response.set_code(ErrorCode::tx_auth_error());
                                         response.set_error_msg(tx_response.status);
 
-                                        let msg = factory::create_msg(submodules::AUTH, topics::RESPONSE, communication::MsgType::RESPONSE, response.write_to_bytes().unwrap());
                                         trace!("response new tx {:?}", response);
-                                        tx_pub.send(("auth.rpc".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+                                        AuthRpc::publish(tx_pub, &response);
                                     }
                                 }
                             }
@@ -418,7 +434,7 @@ pub fn handle_verificaton_result(result_receiver: &Receiver<VerifyRequestRespons
     }
 }
 
-pub fn publish_block_verification_fail_result(request_id: u64, hash: &H256, cache: Arc<RwLock<HashMap<H256, VerifyTxResp>>>, tx_pub: &Sender<(String, Vec<u8>)>) {
+pub fn publish_block_verification_fail_result(request_id: u64, hash: &H256, cache: Arc<VerifyCache>, tx_pub: &Sender<(String, Vec<u8>)>) {
     let ret: Ret;
     if let Some(resp) = get_resp_from_cache(hash, cache) {
         ret = resp.get_ret();
@@ -434,6 +450,5 @@ fn publish_block_verification_result(request_id: u64, ret: Ret, tx_pub: &Sender<
     blkresp.set_id(request_id);
     blkresp.set_ret(ret);
 
-    let msg = factory::create_msg(submodules::AUTH, topics::VERIFY_BLK_RESP, communication::MsgType::VERIFY_BLK_RESP, blkresp.write_to_bytes().unwrap());
-    tx_pub.send((String::from("verify_blk_consensus"), msg.write_to_bytes().unwrap())).unwrap();
+    AuthVerifyBlockResp::publish(tx_pub, &blkresp);
 }
@@ -16,17 +16,45 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use chain_core::db;
+use libproto::BlockTxHashes;
 use libproto::blockchain::SignedTransaction;
 use protobuf::core::{Message, parse_from_bytes};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tx_pool::Pool;
-use util::H256;
+use util::{H256, Mutex};
 use util::datapath::DataPath;
-use util::kvdb::{DatabaseConfig, Database, KeyValueDB};
+use util::kvdb::{DatabaseConfig, Database, DBTransaction, KeyValueDB};
 
+// every tx record is prefixed with its big endian sequence number
+const SEQ_BYTES_LEN: usize = 8;
+
+/// The tx of a record keyed by its hash, with the sequence number unless
+/// the record was written without one. The hash tells the two formats apart.
+fn decode_record(key: &[u8], value: &[u8]) -> Option<(Option<u64>, SignedTransaction)> {
+    if value.len() > SEQ_BYTES_LEN {
+        if let Ok(tx) = parse_from_bytes::<SignedTransaction>(&value[SEQ_BYTES_LEN..]) {
+            if tx.get_tx_hash() == key {
+                let seq = value[..SEQ_BYTES_LEN].iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+                return Some((Some(seq), tx));
+            }
+        }
+    }
+    match parse_from_bytes::<SignedTransaction>(value) {
+        Ok(tx) => if tx.get_tx_hash() == key { Some((None, tx)) } else { None },
+        Err(_) => None,
+    }
+}
+
+/// Write-ahead log of the txs in the pool.
+///
+/// `write` and `delete_with_hash` only buffer the operation, `commit` flushes
+/// everything buffered since the last commit in a single db write.
 #[derive(Clone)]
 pub struct Txwal {
     db: Arc<KeyValueDB>,
+    seq: Arc<AtomicU64>,
+    batch: Arc<Mutex<(DBTransaction, usize)>>,
 }
 
 impl Txwal {
@@ -34,39 +62,118 @@ impl Txwal {
         let nosql_path = DataPath::root_node_path() + path;
         let config = DatabaseConfig::with_columns(db::NUM_COLUMNS);
         let db = Database::open(&config, &nosql_path).unwrap();
-        Txwal { db: Arc::new(db) }
+        Txwal {
+            db: Arc::new(db),
+            seq: Arc::new(AtomicU64::new(0)),
+            batch: Arc::new(Mutex::new((DBTransaction::new(), 0))),
+        }
     }
 
     pub fn write(&self, tx: &SignedTransaction) {
-        let tx = tx.clone();
-        let mut batch = self.db.transaction();
-        let block_binary = tx.write_to_bytes().unwrap();
-        batch.put_vec(None, tx.get_tx_hash(), block_binary);
-        let _ = self.db.write(batch);
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst);
+        let mut record = Vec::with_capacity(SEQ_BYTES_LEN + tx.compute_size() as usize);
+        for i in 0..SEQ_BYTES_LEN {
+            record.push((seq >> (8 * (SEQ_BYTES_LEN - 1 - i))) as u8);
+        }
+        record.extend(tx.write_to_bytes().unwrap());
+        let mut batch = self.batch.lock();
+        batch.0.put_vec(None, tx.get_tx_hash(), record);
+        batch.1 += 1;
     }
 
-    pub fn delete(&mut self, tx: &SignedTransaction) {
-        let tx = tx.clone();
-        let mut batch = self.db.transaction();
-        batch.delete(None, tx.get_tx_hash());
-        let _ = self.db.write(batch);
+    pub fn delete_with_hash(&self, txhash: &H256) {
+        let mut batch = self.batch.lock();
+        batch.0.delete(None, txhash);
+        batch.1 += 1;
     }
 
-    pub fn delete_with_hash(&mut self, txhash: &H256) {
-        let mut batch = self.db.transaction();
-        batch.delete(None, txhash);
-        let _ = self.db.write(batch);
+    /// Write all buffered operations at once, return how many there were.
+    pub fn commit(&self) -> usize {
+        let (transaction, ops) = {
+            let mut batch = self.batch.lock();
+            ::std::mem::replace(&mut *batch, (DBTransaction::new(), 0))
+        };
+        if ops > 0 {
+            if let Err(e) = self.db.write(transaction) {
+                error!("failed to commit {} ops to tx wal: {:?}", ops, e);
+            }
+        }
+        ops
     }
 
+    /// Replay the txs into `pool` in the order they were written, skipping corrupt records.
+    /// Records written before the sequence numbers are replayed first, and the
+    /// log is renumbered so that they keep their place.
     pub fn read(&self, pool: &mut Pool) -> u64 {
-        let mut num: u64 = 0;
+        let mut txs = Vec::new();
         let mut ite = self.db.iter(None);
-        while let Some(item) = ite.next() {
-            let tx = parse_from_bytes::<SignedTransaction>(item.1.as_ref()).unwrap();
-            num = num + 1;
+        while let Some((key, value)) = ite.next() {
+            match decode_record(&key, &value) {
+                Some(record) => txs.push(record),
+                None => warn!("skip corrupt tx wal record with key {:?}", key),
+            }
+        }
+        txs.sort_by_key(|&(seq, _)| seq);
+        if txs.iter().any(|&(seq, _)| seq.is_none()) {
+            info!("renumber {} tx wal records", txs.len());
+            self.seq.store(0, Ordering::SeqCst);
+            for &(_, ref tx) in &txs {
+                self.write(tx);
+            }
+            self.commit();
+        } else if let Some(&(Some(seq), _)) = txs.last() {
+            self.seq.store(seq + 1, Ordering::SeqCst);
+        }
+        let num = txs.len() as u64;
+        for (_, tx) in txs {
             pool.enqueue(tx);
         }
         info!("read tx num [{}] from pool.", num);
         num
     }
+
+    /// Save the tx hashes of a block, keyed by its height.
+    pub fn write_hashes(&self, block_tx_hashes: &BlockTxHashes) {
+        let mut batch = self.db.transaction();
+        batch.put_vec(None, &H256::from(block_tx_hashes.get_height()), block_tx_hashes.write_to_bytes().unwrap());
+        let _ = self.db.write(batch);
+    }
+
+    pub fn delete_hashes(&self, height: u64) {
+        let mut batch = self.db.transaction();
+        batch.delete(None, &H256::from(height));
+        let _ = self.db.write(batch);
+    }
+
+    pub fn read_hashes(&self) -> Vec<BlockTxHashes> {
+        let mut blocks = Vec::new();
+        let mut ite = self.db.iter(None);
+        while let Some((key, value)) = ite.next() {
+            match parse_from_bytes::<BlockTxHashes>(&value) {
+                Ok(block_tx_hashes) => blocks.push(block_tx_hashes),
+                Err(e) => warn!("skip corrupt tx hashes wal record with key {:?}: {:?}", key, e),
+            }
+        }
+        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_both_formats() {
+        let mut tx = SignedTransaction::new();
+        tx.set_tx_hash(H256::from(7).to_vec());
+        let hash = tx.get_tx_hash().to_vec();
+        let bytes = tx.write_to_bytes().unwrap();
+
+        let mut record = vec![0, 0, 0, 0, 0, 0, 1, 2];
+        record.extend_from_slice(&bytes);
+        assert_eq!(decode_record(&hash, &record), Some((Some(258), tx.clone())));
+        assert_eq!(decode_record(&hash, &bytes), Some((None, tx.clone())));
+        assert_eq!(decode_record(&H256::from(8), &bytes), None);
+        assert_eq!(decode_record(&hash, &record[..4]), None);
+    }
 }
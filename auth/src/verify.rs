@@ -16,21 +16,33 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 
-use crypto::{PubKey, Signature, Sign, SIGNATURE_BYTES_LEN};
+use crypto::{PubKey, Signature, Sign, SIGNATURE_BYTES_LEN, pubkey_to_address};
 use libproto::*;
 use libproto::blockchain::*;
+use libproto::router::{AuthBlockTxHashesReq, Route};
 use protobuf::Message;
-use std::collections::{HashMap, HashSet};
+use protobuf::RepeatedField;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::result::Result;
 use std::sync::mpsc::Sender;
-use util::{H256, BLOCKLIMIT};
+use txwal::Txwal;
+use util::{H256, Address, BLOCKLIMIT};
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Verifier {
     inited: bool,
     height_latest: Option<u64>,
     height_low: Option<u64>,
-    hashes: HashMap<u64, HashSet<H256>>,
+    // tx hash -> height of the block it is in
+    hashes: HashMap<H256, u64>,
+    // height -> tx hashes of the block, used to expire `hashes` by height
+    heights: BTreeMap<u64, Vec<H256>>,
+    wal: Option<Txwal>,
+    senders: HashSet<Address>,
+    creators: HashSet<Address>,
+    check_permission: bool,
+    check_quota: bool,
+    account_gas_limit: AccountGasLimit,
 }
 
 impl Verifier {
@@ -39,7 +51,14 @@ impl Verifier {
             inited: false,
             height_latest: None,
             height_low: None,
-            hashes: HashMap::with_capacity(BLOCKLIMIT as usize),
+            hashes: HashMap::new(),
+            heights: BTreeMap::new(),
+            wal: None,
+            senders: HashSet::new(),
+            creators: HashSet::new(),
+            check_permission: false,
+            check_quota: false,
+            account_gas_limit: AccountGasLimit::new(),
         }
     }
 
@@ -55,46 +74,155 @@ impl Verifier {
         self.height_low
     }
 
+    /// Create a verifier which persists the tx hash index to `wal` and reloads it,
+    /// so only the blocks chain added while auth was down are fetched again.
+    pub fn with_wal(wal: Txwal) -> Self {
+        let mut verifier = Verifier::new();
+        let blocks = wal.read_hashes();
+        let num = blocks.len();
+        for height in verifier.restore(blocks) {
+            wal.delete_hashes(height);
+        }
+        info!("read tx hashes of {} blocks from wal, {} in the window", num, verifier.heights.len());
+        verifier.wal = Some(wal);
+        verifier
+    }
+
+    /// Load the tx hashes of `blocks` and return the heights below the window.
+    /// It stays uninited, chain may be past the latest of them, until the first
+    /// `update_hashes` requests the heights in between.
+    fn restore(&mut self, blocks: Vec<BlockTxHashes>) -> Vec<u64> {
+        for block_tx_hashes in blocks {
+            let hashes: Vec<H256> = block_tx_hashes.get_tx_hashes().iter().map(|hash| H256::from_slice(hash)).collect();
+            self.insert_hashes(block_tx_hashes.get_height(), hashes);
+        }
+        let mut expired = Vec::new();
+        if let Some(h) = self.heights.keys().next_back().cloned() {
+            self.set_height(h);
+            expired = self.heights.range(..self.height_low.unwrap()).map(|(height, _)| *height).collect();
+            for height in expired.iter() {
+                self.remove_hashes(*height);
+            }
+        }
+        expired
+    }
+
     pub fn update_hashes(&mut self, h: u64, hashes: HashSet<H256>, tx_pub: &Sender<(String, Vec<u8>)>) {
         if self.height_latest.is_none() && self.height_low.is_none() {
-            self.height_latest = Some(h);
-            self.height_low = if h < BLOCKLIMIT { Some(0) } else { Some(h - BLOCKLIMIT + 1) };
-            for i in self.height_low.unwrap()..h {
-                let mut req = BlockTxHashesReq::new();
-                req.set_height(i as u64);
-                let msg = factory::create_msg(submodules::AUTH, topics::BLOCK_TXHASHES_REQ, communication::MsgType::BLOCK_TXHASHES_REQ, req.write_to_bytes().unwrap());
-                tx_pub.send(("auth.blk_tx_hashs_req".to_string(), msg.write_to_bytes().unwrap())).unwrap();
-            }
+            self.set_height(h);
+            self.request_hashes(self.height_low.unwrap(), h, tx_pub);
         } else {
             let current_height = self.height_latest.unwrap();
-            let current_height_low = self.height_low.unwrap();
             if h > current_height {
-                self.height_latest = Some(h);
-                self.height_low = if h < BLOCKLIMIT { Some(0) } else { Some(h - BLOCKLIMIT + 1) };
-                for i in current_height_low..self.height_low.unwrap() {
-                    self.hashes.remove(&i);
+                self.set_height(h);
+                let expired: Vec<u64> = self.heights.range(..self.height_low.unwrap()).map(|(height, _)| *height).collect();
+                for height in expired {
+                    self.remove_hashes(height);
+                    if let Some(ref wal) = self.wal {
+                        wal.delete_hashes(height);
+                    }
                 }
+                // blocks missed while auth was down
+                let low = ::std::cmp::max(current_height + 1, self.height_low.unwrap());
+                self.request_hashes(low, h, tx_pub);
             }
             if h < self.height_low.unwrap() {
                 return;
             }
         }
         trace!("update block's tx hashes for height:{} and the current low height:{} and latest height:{}", h, self.height_low.unwrap(), self.height_latest.unwrap());
-        self.hashes.insert(h, hashes);
-        if self.hashes.len() as u64 == (self.height_latest.unwrap() - self.height_low.unwrap() + 1) {
-            self.inited = true;
+        let hashes: Vec<H256> = hashes.into_iter().collect();
+        if let Some(ref wal) = self.wal {
+            let mut block_tx_hashes = BlockTxHashes::new();
+            block_tx_hashes.set_height(h);
+            block_tx_hashes.set_tx_hashes(RepeatedField::from_vec(hashes.iter().map(|hash| hash.to_vec()).collect()));
+            wal.write_hashes(&block_tx_hashes);
+        }
+        self.insert_hashes(h, hashes);
+        self.check_inited();
+    }
+
+    fn set_height(&mut self, h: u64) {
+        self.height_latest = Some(h);
+        self.height_low = if h < BLOCKLIMIT { Some(0) } else { Some(h - BLOCKLIMIT + 1) };
+    }
+
+    /// Inited while the tx hashes of every height in the window are known
+    fn check_inited(&mut self) {
+        self.inited = self.heights.len() as u64 == (self.height_latest.unwrap() - self.height_low.unwrap() + 1);
+    }
+
+    fn request_hashes(&self, from: u64, to: u64, tx_pub: &Sender<(String, Vec<u8>)>) {
+        for i in from..to {
+            if self.heights.contains_key(&i) {
+                continue;
+            }
+            let mut req = BlockTxHashesReq::new();
+            req.set_height(i as u64);
+            AuthBlockTxHashesReq::publish(tx_pub, &req);
+        }
+    }
+
+    fn insert_hashes(&mut self, h: u64, hashes: Vec<H256>) {
+        self.remove_hashes(h);
+        for hash in hashes.iter() {
+            self.hashes.insert(*hash, h);
+        }
+        self.heights.insert(h, hashes);
+    }
+
+    fn remove_hashes(&mut self, h: u64) {
+        if let Some(hashes) = self.heights.remove(&h) {
+            for hash in hashes.iter() {
+                if self.hashes.get(hash) == Some(&h) {
+                    self.hashes.remove(hash);
+                }
+            }
+        }
+    }
+
+    /// Update the permission and quota settings from the latest block's tx hashes
+    pub fn update_admission(&mut self, block_tx_hashes: &BlockTxHashes) {
+        self.senders = block_tx_hashes.get_senders().iter().map(|sender| Address::from_slice(sender)).collect();
+        self.creators = block_tx_hashes.get_creators().iter().map(|creator| Address::from_slice(creator)).collect();
+        self.check_permission = block_tx_hashes.get_check_permission();
+        self.check_quota = block_tx_hashes.get_check_quota();
+        self.account_gas_limit = block_tx_hashes.get_account_gas_limit().clone();
+    }
+
+    /// Check the same permission and quota rules as chain does when executing the tx,
+    /// so a tx that can never be executed is rejected before entering the pool.
+    pub fn verify_admission(&self, un_tx: &UnverifiedTransaction, signer: &[u8]) -> Ret {
+        let sender = pubkey_to_address(&PubKey::from_slice(signer));
+        if sender == Address::zero() {
+            return Ret::Ok;
         }
+        let tx = un_tx.get_transaction();
+        if self.check_permission {
+            if tx.get_to().is_empty() {
+                if !self.creators.contains(&sender) {
+                    return Ret::NoContractPermission;
+                }
+            } else if !self.senders.contains(&sender) && !self.creators.contains(&sender) {
+                return Ret::NoTransactionPermission;
+            }
+        }
+        if self.check_quota {
+            let gas_limit = self.account_gas_limit.get_specific_gas_limit().get(&sender.hex()).cloned().unwrap_or(self.account_gas_limit.get_common_gas_limit());
+            if tx.get_quota() > gas_limit {
+                return Ret::AccountGasLimitReached;
+            }
+        }
+        Ret::Ok
     }
 
     pub fn check_hash_exist(&self, hash: &H256) -> bool {
         if !self.inited {
             return true;
         }
-        for (height, hashes) in self.hashes.iter() {
-            if hashes.contains(hash) {
-                trace!("Tx with hash {:?} has already existed in height:{}", hash.0, height);
-                return true;
-            }
+        if let Some(height) = self.hashes.get(hash) {
+            trace!("Tx with hash {:?} has already existed in height:{}", hash.0, height);
+            return true;
         }
         return false;
     }
@@ -134,8 +262,17 @@ impl Verifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crypto::{KeyPair, CreateKey};
+    use protobuf::RepeatedField;
     use std::sync::mpsc::channel;
 
+    fn generate_un_tx(to: &str, quota: u64) -> UnverifiedTransaction {
+        let mut un_tx = UnverifiedTransaction::new();
+        un_tx.mut_transaction().set_to(to.to_string());
+        un_tx.mut_transaction().set_quota(quota);
+        un_tx
+    }
+
     #[test]
     fn verify_init() {
         let mut v = Verifier::new();
@@ -166,4 +303,102 @@ mod tests {
         assert_eq!(v.get_height_latest(), Some(101));
         assert_eq!(v.get_height_low(), Some(2));
     }
+    #[test]
+    fn verify_hash_expiry() {
+        let mut v = Verifier::new();
+        let (tx_pub, _rx_pub) = channel();
+        let hash = H256::from(1);
+        let mut hashes = HashSet::new();
+        hashes.insert(hash);
+        v.update_hashes(0, hashes, &tx_pub);
+        assert_eq!(v.check_hash_exist(&hash), true);
+        assert_eq!(v.check_hash_exist(&H256::from(2)), false);
+
+        for i in 1..BLOCKLIMIT {
+            v.update_hashes(i, HashSet::new(), &tx_pub);
+        }
+        assert_eq!(v.check_hash_exist(&hash), true);
+        v.update_hashes(BLOCKLIMIT, HashSet::new(), &tx_pub);
+        assert_eq!(v.get_height_low(), Some(1));
+        assert_eq!(v.check_hash_exist(&hash), false);
+    }
+
+    #[test]
+    fn verify_request_missed_heights() {
+        let mut v = Verifier::new();
+        let (tx_pub, rx_pub) = channel();
+        v.update_hashes(0, HashSet::new(), &tx_pub);
+        assert!(rx_pub.try_recv().is_err());
+        v.update_hashes(5, HashSet::new(), &tx_pub);
+        assert_eq!(rx_pub.try_iter().count(), 4);
+        v.update_hashes(3, HashSet::new(), &tx_pub);
+        v.update_hashes(7, HashSet::new(), &tx_pub);
+        // only height 6 is missing between 5 and 7
+        assert_eq!(rx_pub.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn verify_restore_waits_for_chain() {
+        let mut v = Verifier::new();
+        let blocks: Vec<BlockTxHashes> = (0..5).map(|height| {
+            let mut block_tx_hashes = BlockTxHashes::new();
+            block_tx_hashes.set_height(height);
+            block_tx_hashes
+        }).collect();
+        assert!(v.restore(blocks).is_empty());
+        assert_eq!(v.get_height_latest(), Some(4));
+        assert_eq!(v.is_inited(), false);
+
+        let (tx_pub, rx_pub) = channel();
+        v.update_hashes(7, HashSet::new(), &tx_pub);
+        assert_eq!(rx_pub.try_iter().count(), 2);
+        assert_eq!(v.is_inited(), false);
+        v.update_hashes(5, HashSet::new(), &tx_pub);
+        v.update_hashes(6, HashSet::new(), &tx_pub);
+        assert_eq!(v.is_inited(), true);
+    }
+
+    #[test]
+    fn verify_admission_permission() {
+        let sender = KeyPair::gen_keypair();
+        let creator = KeyPair::gen_keypair();
+        let mut block_tx_hashes = BlockTxHashes::new();
+        block_tx_hashes.set_senders(RepeatedField::from_vec(vec![sender.address().to_vec()]));
+        block_tx_hashes.set_creators(RepeatedField::from_vec(vec![creator.address().to_vec()]));
+
+        let mut v = Verifier::new();
+        let call = generate_un_tx("1234567", 100);
+        let create = generate_un_tx("", 100);
+        assert_eq!(v.verify_admission(&create, &sender.pubkey().to_vec()), Ret::Ok);
+
+        block_tx_hashes.set_check_permission(true);
+        v.update_admission(&block_tx_hashes);
+        assert_eq!(v.verify_admission(&call, &sender.pubkey().to_vec()), Ret::Ok);
+        assert_eq!(v.verify_admission(&create, &sender.pubkey().to_vec()), Ret::NoContractPermission);
+        assert_eq!(v.verify_admission(&call, &creator.pubkey().to_vec()), Ret::Ok);
+        assert_eq!(v.verify_admission(&create, &creator.pubkey().to_vec()), Ret::Ok);
+
+        let stranger = KeyPair::gen_keypair();
+        assert_eq!(v.verify_admission(&call, &stranger.pubkey().to_vec()), Ret::NoTransactionPermission);
+    }
+
+    #[test]
+    fn verify_admission_quota() {
+        let common = KeyPair::gen_keypair();
+        let specific = KeyPair::gen_keypair();
+        let mut account_gas_limit = AccountGasLimit::new();
+        account_gas_limit.set_common_gas_limit(1000);
+        account_gas_limit.mut_specific_gas_limit().insert(specific.address().hex(), 2000);
+        let mut block_tx_hashes = BlockTxHashes::new();
+        block_tx_hashes.set_account_gas_limit(account_gas_limit);
+        block_tx_hashes.set_check_quota(true);
+
+        let mut v = Verifier::new();
+        v.update_admission(&block_tx_hashes);
+        let tx = generate_un_tx("1234567", 1500);
+        assert_eq!(v.verify_admission(&tx, &common.pubkey().to_vec()), Ret::AccountGasLimitReached);
+        assert_eq!(v.verify_admission(&tx, &specific.pubkey().to_vec()), Ret::Ok);
+        let tx = generate_un_tx("1234567", 2500);
+        assert_eq!(v.verify_admission(&tx, &specific.pubkey().to_vec()), Ret::AccountGasLimitReached);
+    }
 }
@@ -55,6 +55,24 @@ pub fn parse_string_to_addresses(data: &Vec<u8>) -> Vec<Address> {
     nodes
 }
 
+/// Parse solidity return data `uint64[]` to rust `Vec<u64>`
+pub fn parse_uint_array(data: &Vec<u8>) -> Vec<u64> {
+    let mut values = Vec::new();
+    if data.len() >= 64 {
+        let offset = U256::from(&data[0..32]).low_u64() as usize;
+        if offset <= data.len() - 32 {
+            let len = U256::from(&data[offset..offset + 32]).low_u64() as usize;
+            let start = offset + 32;
+            if len <= (data.len() - start) / 32 {
+                for i in 0..len {
+                    values.push(U256::from(&data[start + i * 32..start + (i + 1) * 32]).low_u64());
+                }
+            }
+        }
+    }
+    values
+}
+
 /// parse quota
 pub fn parse_string_to_quota(data: &Vec<u8>) -> Vec<u64> {
     let mut quotas = Vec::new();
@@ -17,7 +17,7 @@
 
 //! Node manager.
 
-use super::{parse_string_to_addresses, encode_contract_name};
+use super::{parse_string_to_addresses, parse_uint_array, encode_contract_name};
 use super::ContractCallExt;
 use libchain::chain::Chain;
 use rustc_hex::ToHex;
@@ -25,9 +25,11 @@ use std::str::FromStr;
 use util::*;
 
 const LIST_NODE: &'static [u8] = &*b"listNode()";
+const LIST_WEIGHT: &'static [u8] = &*b"listWeight()";
 
 lazy_static! {
     static ref LIST_NODE_ENCODED: Vec<u8> = encode_contract_name(LIST_NODE);
+    static ref LIST_WEIGHT_ENCODED: Vec<u8> = encode_contract_name(LIST_WEIGHT);
     static ref CONTRACT_ADDRESS: H160 = H160::from_str("00000000000000000000000000000000013241a2").unwrap();
 }
 
@@ -42,6 +44,19 @@ impl NodeManager {
         trace!("nodemanager nodes: {:?}", nodes);
         nodes
     }
+
+    /// Voting power of each node of `read`, empty when the contract has no weights
+    pub fn read_weights(chain: &Chain, nodes: &[Address]) -> Vec<u64> {
+        let output = chain.call_contract_method(&*CONTRACT_ADDRESS, &*LIST_WEIGHT_ENCODED.as_slice());
+        trace!("nodemanager weights output: {:?}", ToHex::to_hex(output.as_slice()));
+
+        let weights = parse_uint_array(&output);
+        if weights.len() != nodes.len() {
+            trace!("nodemanager has {} weights for {} nodes, nodes are equal", weights.len(), nodes.len());
+            return Vec::new();
+        }
+        weights
+    }
 }
 
 #[cfg(test)]
@@ -71,4 +86,16 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_parse_weights() {
+        let mut output = vec![0u8; 32 * 4];
+        output[31] = 0x20;
+        output[63] = 2;
+        output[95] = 3;
+        output[127] = 1;
+        assert_eq!(parse_uint_array(&output), vec![3, 1]);
+        assert_eq!(parse_uint_array(&output[..96].to_vec()), Vec::<u64>::new());
+        assert_eq!(parse_uint_array(&Vec::new()), Vec::<u64>::new());
+    }
 }
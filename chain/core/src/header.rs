@@ -66,6 +66,8 @@ pub struct Header {
     gas_limit: U256,
     /// the proof of the block
     proof: Proof,
+    /// Evidences root, HASH_NULL_RLP when the block carries no evidence.
+    evidence_root: H256,
     /// The hash of the header.
     hash: HashWrap,
     /// The version of the header.
@@ -74,7 +76,7 @@ pub struct Header {
 
 impl PartialEq for Header {
     fn eq(&self, c: &Header) -> bool {
-        self.parent_hash == c.parent_hash && self.timestamp == c.timestamp && self.number == c.number && self.transactions_root == c.transactions_root && self.state_root == c.state_root && self.receipts_root == c.receipts_root && self.log_bloom == c.log_bloom && self.gas_used == c.gas_used && self.gas_limit == c.gas_limit && self.proof == c.proof
+        self.parent_hash == c.parent_hash && self.timestamp == c.timestamp && self.number == c.number && self.transactions_root == c.transactions_root && self.state_root == c.state_root && self.receipts_root == c.receipts_root && self.log_bloom == c.log_bloom && self.gas_used == c.gas_used && self.gas_limit == c.gas_limit && self.proof == c.proof && self.evidence_root == c.evidence_root
 
     }
 }
@@ -92,6 +94,7 @@ impl Default for Header {
             gas_used: U256::default(),
             gas_limit: U256::from(u64::max_value()),
             proof: Proof::new(),
+            evidence_root: HASH_NULL_RLP,
             hash: HashWrap(Cell::new(None)),
             version: 0,
         }
@@ -111,6 +114,7 @@ impl From<BlockHeader> for Header {
             gas_used: U256::zero(),
             gas_limit: U256::from(u64::max_value()),
             proof: bh.get_proof().clone(),
+            evidence_root: if bh.get_evidence_root().is_empty() { HASH_NULL_RLP } else { H256::from(bh.get_evidence_root()) },
             version: 0,
             hash: HashWrap(Cell::new(None)),
         }
@@ -163,6 +167,10 @@ impl Header {
     pub fn proof(&self) -> &Proof {
         &self.proof
     }
+    /// Get the evidence root field of the header.
+    pub fn evidence_root(&self) -> &H256 {
+        &self.evidence_root
+    }
     /// Get the version of the block
     pub fn version(&self) -> u32 {
         self.version
@@ -232,6 +240,11 @@ impl Header {
         self.proof = a;
         self.note_dirty();
     }
+    /// Set the evidence root field of the header.
+    pub fn set_evidence_root(&mut self, a: H256) {
+        self.evidence_root = a;
+        self.note_dirty();
+    }
 
     /// Get the hash of this header (sha3 of the RLP).
     pub fn hash(&self) -> H256 {
@@ -253,8 +266,11 @@ impl Header {
 
     // TODO: make these functions traity
     /// Place this header into an RLP stream `s`.
+    /// The evidence root is only appended when set, so headers of blocks
+    /// without evidence keep the 11 items and the hash they always had.
     pub fn stream_rlp(&self, s: &mut RlpStream) {
-        s.begin_list(11);
+        let with_evidence = self.evidence_root != HASH_NULL_RLP;
+        s.begin_list(if with_evidence { 12 } else { 11 });
         s.append(&self.parent_hash);
         s.append(&self.state_root);
         s.append(&self.transactions_root);
@@ -266,7 +282,9 @@ impl Header {
         s.append(&self.timestamp);
         s.append(&self.version);
         s.append(&self.proof);
-
+        if with_evidence {
+            s.append(&self.evidence_root);
+        }
     }
 
     /// Get the RLP of this header.
@@ -293,6 +311,9 @@ impl Header {
         bh.set_gas_used(u64::from(self.gas_used));
         bh.set_gas_limit(self.gas_limit.low_u64());
         bh.set_proof(self.proof.clone());
+        if self.evidence_root != HASH_NULL_RLP {
+            bh.set_evidence_root(self.evidence_root.to_vec());
+        }
         bh
     }
 }
@@ -311,6 +332,11 @@ impl Decodable for Header {
             timestamp: cmp::min(r.val_at::<U256>(8)?, u64::max_value().into()).as_u64(),
             version: r.val_at(9)?,
             proof: r.val_at(10)?,
+            evidence_root: match r.item_count()? {
+                11 => HASH_NULL_RLP,
+                12 => r.val_at(11)?,
+                _ => return Err(DecoderError::RlpIncorrectListLen),
+            },
             hash: HashWrap(Cell::new(Some(r.as_raw().crypt_hash()))),
         };
 
@@ -334,6 +360,7 @@ impl HeapSizeOf for Header {
 mod tests {
     use super::Header;
     use rlp;
+    use util::{H256, HASH_NULL_RLP};
 
     #[test]
     fn decode_and_encode_header() {
@@ -345,4 +372,22 @@ mod tests {
 
         assert_eq!(header_rlp, encoded_header);
     }
+
+    #[test]
+    fn evidence_root_only_changes_hash_when_set() {
+        let header = Header::new();
+        let hash = header.hash();
+
+        let mut with_evidence = header.clone();
+        with_evidence.set_evidence_root(H256::from(7));
+        assert!(with_evidence.hash() != hash);
+        let decoded: Header = rlp::decode(&rlp::encode(&with_evidence).into_vec());
+        assert_eq!(decoded.evidence_root(), &H256::from(7));
+        assert_eq!(decoded.hash(), with_evidence.hash());
+        assert_eq!(Header::from(with_evidence.protobuf()).evidence_root(), &H256::from(7));
+
+        with_evidence.set_evidence_root(HASH_NULL_RLP);
+        assert_eq!(with_evidence.hash(), hash);
+        assert!(with_evidence.protobuf().get_evidence_root().is_empty());
+    }
 }
@@ -133,16 +133,56 @@ impl Block {
 
 
 /// body of block.
-#[derive(Default, Debug, Clone, PartialEq, RlpEncodableWrapper, RlpDecodableWrapper)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct BlockBody {
     /// The transactions in this body.
     pub transactions: Vec<SignedTransaction>,
+    /// The equivocation evidences committed by the header's evidence root.
+    pub evidences: Vec<Vec<u8>>,
+}
+
+/// A body without evidence is stored as the bare transaction list it always was,
+/// one with evidence as the list `[transactions, evidences]`.
+impl Encodable for BlockBody {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        if self.evidences.is_empty() {
+            s.append_list(&self.transactions);
+        } else {
+            s.begin_list(2);
+            s.append_list(&self.transactions);
+            s.begin_list(self.evidences.len());
+            for evidence in &self.evidences {
+                s.append(evidence);
+            }
+        }
+    }
+}
+
+impl Decodable for BlockBody {
+    fn decode(r: &UntrustedRlp) -> Result<Self, DecoderError> {
+        if r.item_count()? == 2 && is_transaction_list(&r.at(0)?) && r.at(1)?.is_list() {
+            Ok(BlockBody {
+                   transactions: r.list_at(0)?,
+                   evidences: r.at(1)?.iter().map(|e| e.as_val()).collect::<Result<_, _>>()?,
+               })
+        } else {
+            Ok(BlockBody {
+                   transactions: r.as_list()?,
+                   evidences: Vec::new(),
+               })
+        }
+    }
 }
 
+/// A transaction starts with its nonce, never with a list, so a bare
+/// transaction list can't be mistaken for `[transactions, evidences]`.
+fn is_transaction_list(r: &UntrustedRlp) -> bool {
+    r.is_list() && (r.is_empty() || r.at(0).map(|tx| tx.is_list()).unwrap_or(false))
+}
 
 impl HeapSizeOf for BlockBody {
     fn heap_size_of_children(&self) -> usize {
-        self.transactions.heap_size_of_children()
+        self.transactions.heap_size_of_children() + self.evidences.heap_size_of_children()
     }
 }
 
@@ -153,13 +193,17 @@ impl From<ProtoBlockBody> for BlockBody {
                               .iter()
                               .map(|t| SignedTransaction::new(t).expect("transaction can not be converted"))
                               .collect(),
+            evidences: body.get_evidences().to_vec(),
         }
     }
 }
 
 impl BlockBody {
     pub fn new() -> Self {
-        BlockBody { transactions: Vec::new() }
+        BlockBody {
+            transactions: Vec::new(),
+            evidences: Vec::new(),
+        }
     }
 
     pub fn transactions(&self) -> &[SignedTransaction] {
@@ -170,10 +214,15 @@ impl BlockBody {
         self.transactions = txs;
     }
 
+    pub fn evidences(&self) -> &[Vec<u8>] {
+        &self.evidences
+    }
+
     pub fn protobuf(&self) -> ProtoBlockBody {
         let mut body = ProtoBlockBody::new();
         let txs: Vec<ProtoSignedTransaction> = self.transactions.iter().map(|t| t.protobuf()).collect();
         body.set_transactions(RepeatedField::from_slice(&txs[..]));
+        body.set_evidences(RepeatedField::from_slice(&self.evidences[..]));
         body
     }
 
@@ -416,7 +465,10 @@ mod tests {
         let mut stx = SignedTransaction::default();
         stx.data = vec![1; 200];
         let transactions = vec![stx; 200];
-        let body = BlockBody { transactions: transactions };
+        let body = BlockBody {
+            transactions: transactions,
+            evidences: Vec::new(),
+        };
         let body_rlp = rlp::encode(&body);
         let body: BlockBody = rlp::decode(&body_rlp);
         let body_encoded = rlp::encode(&body).into_vec();
@@ -424,4 +476,25 @@ mod tests {
         assert_eq!(body_rlp, body_encoded);
     }
 
+    #[test]
+    fn encode_and_decode_with_evidences() {
+        let legacy = BlockBody {
+            transactions: vec![SignedTransaction::default(); 2],
+            evidences: Vec::new(),
+        };
+        let legacy_rlp = rlp::encode(&legacy).into_vec();
+        assert_eq!(legacy_rlp, rlp::encode_list(&legacy.transactions).into_vec());
+        assert_eq!(rlp::decode::<BlockBody>(&legacy_rlp), legacy);
+
+        for count in 0..3 {
+            let body = BlockBody {
+                transactions: vec![SignedTransaction::default(); count],
+                evidences: vec![vec![1, 2, 3], vec![4; 100]],
+            };
+            let body_rlp = rlp::encode(&body).into_vec();
+            assert_eq!(rlp::decode::<BlockBody>(&body_rlp), body);
+            assert_eq!(BlockBody::from(body.protobuf()), body);
+        }
+    }
+
 }
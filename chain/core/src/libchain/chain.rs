@@ -40,9 +40,10 @@ use libchain::genesis::Genesis;
 pub use libchain::transaction::*;
 use libproto::*;
 use libproto::blockchain::{ProofType, Status as ProtoStatus, RichStatus as ProtoRichStatus, Proof as ProtoProof};
+use libproto::router::{ChainRichStatus, ChainTxHashes, ChainEvidence, Route};
 
 use native::Factory as NativeFactory;
-use proof::TendermintProof;
+use proof::{TendermintProof, Evidence, Equivocation};
 use protobuf::Message;
 use protobuf::RepeatedField;
 use receipt::{Receipt, LocalizedReceipt};
@@ -145,6 +146,7 @@ pub struct RichStatus {
     number: u64,
     hash: H256,
     nodes: Vec<Address>,
+    weights: Vec<u64>,
 }
 
 impl RichStatus {
@@ -153,6 +155,7 @@ impl RichStatus {
             number: 0,
             hash: H256::default(),
             nodes: vec![],
+            weights: vec![],
         }
     }
 
@@ -176,12 +179,17 @@ impl RichStatus {
         self.nodes = nodes
     }
 
+    fn set_weights(&mut self, weights: Vec<u64>) {
+        self.weights = weights
+    }
+
     fn protobuf(&self) -> ProtoRichStatus {
         let mut ps = ProtoRichStatus::new();
         ps.set_height(self.number());
         ps.set_hash(self.hash().to_vec());
         let node_list = self.nodes.clone().into_iter().map(|address| address.to_vec()).collect();
         ps.set_nodes(RepeatedField::from_vec(node_list));
+        ps.set_weights(self.weights.clone());
         ps
     }
 }
@@ -201,10 +209,12 @@ pub struct Chain {
     blooms_config: bc::Config,
     pub current_header: RwLock<Header>,
     pub is_sync: AtomicBool,
+    // Id of the latest sync request, blocks answering an older one are dropped
+    pub sync_request: AtomicUsize,
     pub max_height: AtomicUsize,
-    // BtreeMap key: block height  Value: proof if ,block, is_verified
+    // BtreeMap key: block height  Value: proof if ,block, is_verified, origin of the proof (of the block until the proof comes)
     // TODO: prune ancient
-    pub block_map: RwLock<BTreeMap<u64, (Option<ProtoProof>, Block, bool)>>,
+    pub block_map: RwLock<BTreeMap<u64, (Option<ProtoProof>, Block, bool, Origin)>>,
     pub db: Arc<KeyValueDB>,
     pub sync_sender: Mutex<Sender<u64>>,
     pub state_db: StateDB,
@@ -222,6 +232,10 @@ pub struct Chain {
     blocks_blooms: RwLock<HashMap<LogGroupPosition, BloomGroup>>,
     block_receipts: RwLock<HashMap<H256, BlockReceipts>>,
     pub nodes: RwLock<Vec<Address>>,
+    /// Voting power of each of `nodes`, empty when they are equal
+    pub node_weights: RwLock<Vec<u64>>,
+    // Evidence of equivocating nodes
+    evidences: RwLock<Evidences>,
     pub block_gas_limit: AtomicUsize,
     pub account_gas_limit: RwLock<AccountGasLimit>,
     // System contract config cache
@@ -295,6 +309,7 @@ impl Chain {
         };
 
         let proof: Option<ProtoProof> = db.read(db::COL_EXTRA, &CurrentProof);
+        let evidences: Evidences = db.read(db::COL_EXTRA, &CurrentEvidences).unwrap_or_default();
 
         let sc: Config = serde_json::from_reader(sconfig).expect("Failed to load json file.");
         info!("config check: {:?}", sc);
@@ -305,6 +320,7 @@ impl Chain {
             blooms_config: blooms_config,
             current_header: RwLock::new(header.clone()),
             is_sync: AtomicBool::new(false),
+            sync_request: AtomicUsize::new(0),
             max_height: max_height,
             block_map: RwLock::new(BTreeMap::new()),
             block_headers: RwLock::new(HashMap::new()),
@@ -321,6 +337,8 @@ impl Chain {
             last_hashes: RwLock::new(VecDeque::new()),
             polls_filter: Arc::new(Mutex::new(PollManager::new())),
             nodes: RwLock::new(Vec::new()),
+            node_weights: RwLock::new(Vec::new()),
+            evidences: RwLock::new(evidences),
             senders: RwLock::new(HashSet::new()),
             creators: RwLock::new(HashSet::new()),
             block_gas_limit: AtomicUsize::new(18446744073709551615),
@@ -338,7 +356,7 @@ impl Chain {
             // Insert current proof for sync
             let block = chain.block_by_hash(header.hash()).expect("Failed to load current block.");
             let mut guard = chain.block_map.write();
-            let _ = guard.insert(header.number(), (proof, block, true));
+            let _ = guard.insert(header.number(), (proof, block, true, ::libproto::factory::ZERO_ORIGIN));
         }
 
         // Generate status
@@ -347,6 +365,7 @@ impl Chain {
         status.set_number(header.number());
         let nodes: Vec<Address> = chain.nodes.read().to_vec();
         status.set_nodes(nodes);
+        status.set_weights(chain.node_weights.read().to_vec());
 
         (chain, status.protobuf())
     }
@@ -581,6 +600,43 @@ impl Chain {
         self.max_height.load(Ordering::SeqCst) as u64
     }
 
+    /// Record valid evidence against one of the nodes, whether its equivocation
+    /// was new
+    pub fn add_evidence(&self, evidence: &[u8]) -> bool {
+        let equivocation = match Evidence::from_bytes(evidence).and_then(|evidence| evidence.check()) {
+            Some(equivocation) => equivocation,
+            None => {
+                warn!("drop invalid evidence");
+                return false;
+            }
+        };
+        if !self.nodes.read().contains(&equivocation.offender) {
+            warn!("drop evidence against {:?}, not a consensus node", equivocation.offender);
+            return false;
+        }
+        let mut evidences = self.evidences.write();
+        if evidences.evidences.iter().any(|known| Evidence::from_bytes(known).and_then(|known| known.check()).as_ref() == Some(&equivocation)) {
+            return false;
+        }
+        warn!("{:?} equivocated at height {} round {}", equivocation.offender, equivocation.height, equivocation.round);
+        evidences.evidences.push(evidence.to_vec());
+        let mut batch = self.db.transaction();
+        batch.write(db::COL_EXTRA, &CurrentEvidences, &*evidences);
+        self.db.write(batch).expect("DB write failed.");
+        true
+    }
+
+    /// Recorded evidence of equivocations at `height` or later
+    pub fn evidences(&self, height: u64) -> Vec<(Equivocation, Vec<u8>)> {
+        self.evidences
+            .read()
+            .evidences
+            .iter()
+            .filter_map(|evidence| Evidence::from_bytes(evidence).and_then(|e| e.check()).map(|equivocation| (equivocation, evidence.clone())))
+            .filter(|&(ref equivocation, _)| equivocation.height as u64 >= height)
+            .collect()
+    }
+
     pub fn current_state_root(&self) -> H256 {
         *self.current_header.read().state_root()
     }
@@ -784,6 +840,7 @@ impl Chain {
         {
             block_tx_hashes.set_block_gas_limit(self.block_gas_limit.load(Ordering::SeqCst) as u64);
             block_tx_hashes.set_account_gas_limit(self.account_gas_limit.read().clone().into());
+            self.set_admission_info(&mut block_tx_hashes);
         }
         thread::spawn(move || {
             let mut tx_hashes_in_u8 = Vec::new();
@@ -791,19 +848,29 @@ impl Chain {
                 tx_hashes_in_u8.push(tx_hash_in_h256.to_vec());
             }
             block_tx_hashes.set_tx_hashes(RepeatedField::from_slice(&tx_hashes_in_u8[..]));
-            let msg = factory::create_msg(submodules::CHAIN, topics::BLOCK_TXHASHES, communication::MsgType::BLOCK_TXHASHES, block_tx_hashes.write_to_bytes().unwrap());
 
-            ctx_pub_clone.send(("chain.txhashes".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            ChainTxHashes::publish(&ctx_pub_clone, &block_tx_hashes);
             trace!("delivery block's tx hashes for height: {}", block_height);
         });
     }
 
+    /// Fill in the senders, creators and check switches auth needs to admit new txs
+    pub fn set_admission_info(&self, block_tx_hashes: &mut BlockTxHashes) {
+        let senders: Vec<Vec<u8>> = self.senders.read().iter().map(|sender| sender.to_vec()).collect();
+        let creators: Vec<Vec<u8>> = self.creators.read().iter().map(|creator| creator.to_vec()).collect();
+        block_tx_hashes.set_senders(RepeatedField::from_vec(senders));
+        block_tx_hashes.set_creators(RepeatedField::from_vec(creators));
+        block_tx_hashes.set_check_permission(self.check_permission);
+        block_tx_hashes.set_check_quota(self.check_quota);
+    }
+
     /// Delivery rich status to consensus
     /// Consensus should resend block if chain commit block failed.
     fn delivery_rich_status(&self, header: &Header, ctx_pub: &Sender<(String, Vec<u8>)>) {
         let current_hash = header.hash().clone();
         let current_height = header.number();
         let nodes: Vec<Address> = self.nodes.read().clone();
+        let weights: Vec<u64> = self.node_weights.read().clone();
         drop(self);
 
         let mut rich_status = ProtoRichStatus::new();
@@ -811,9 +878,9 @@ impl Chain {
         rich_status.set_height(current_height);
         let node_list = nodes.into_iter().map(|address| address.to_vec()).collect();
         rich_status.set_nodes(RepeatedField::from_vec(node_list));
+        rich_status.set_weights(weights);
 
-        let msg = factory::create_msg(submodules::CHAIN, topics::RICH_STATUS, communication::MsgType::RICH_STATUS, rich_status.write_to_bytes().unwrap());
-        ctx_pub.send(("chain.richstatus".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ChainRichStatus::publish(ctx_pub, &rich_status);
     }
 
     fn prune_ancient(&self, mut state_db: StateDB) -> Result<(), UtilError> {
@@ -995,6 +1062,7 @@ impl Chain {
             // Delivery block tx hashes to auth
             let tx_hashes = block.body().transaction_hashes();
             self.delivery_block_tx_hashes(height, tx_hashes, ctx_pub);
+            let evidences = block.body().evidences().to_vec();
             let now = Instant::now();
             let mut open_block = self.execute_block(block);
             let closed_block = open_block.close();
@@ -1011,6 +1079,12 @@ impl Chain {
             let new_now = Instant::now();
             info!("commit block use {:?}", new_now.duration_since(now));
             self.update_last_hashes(&header.hash());
+            // Record the evidences only once the block committing them is in,
+            // and tell consensus so it stops proposing them
+            for evidence in evidences {
+                self.add_evidence(&evidence);
+                ChainEvidence::publish(ctx_pub, &evidence);
+            }
             Some(header)
         } else {
             None
@@ -1027,7 +1101,9 @@ impl Chain {
 
         {
             // Reload consensus nodes cache
-            *self.nodes.write() = NodeManager::read(self);
+            let nodes = NodeManager::read(self);
+            *self.node_weights.write() = NodeManager::read_weights(self, &nodes);
+            *self.nodes.write() = nodes;
         }
         {
             // Reload BlockGasLimit cache
@@ -1061,7 +1137,7 @@ impl Chain {
                 let status = self.save_status(&mut batch);
                 // Save current block proof
                 match self.block_map.read().get(&height) {
-                    Some(&(Some(ref proof), _, _)) => {
+                    Some(&(Some(ref proof), _, _, _)) => {
                         batch.write(db::COL_EXTRA, &CurrentProof, &proof);
                     }
                     _ => {}
@@ -61,6 +61,16 @@ impl Key<Proof> for CurrentProof {
     }
 }
 
+pub struct CurrentEvidences;
+
+impl Key<Evidences> for CurrentEvidences {
+    type Target = H256;
+
+    fn key(&self) -> H256 {
+        H256::from("7cabfb7709b29c16d9e876e876c9988d03f9c3414e1d3ff77ec1de2d0ee59f68")
+    }
+}
+
 impl Key<Header> for H256 {
     type Target = H256;
 
@@ -235,6 +245,24 @@ impl Index<usize> for BlockReceipts {
     }
 }
 
+/// Evidence of equivocating validators, each `proof::Evidence::to_bytes`
+#[derive(Clone, Default)]
+pub struct Evidences {
+    pub evidences: Vec<Vec<u8>>,
+}
+
+impl Decodable for Evidences {
+    fn decode(rlp: &UntrustedRlp) -> Result<Self, DecoderError> {
+        Ok(Evidences { evidences: rlp.as_list()? })
+    }
+}
+
+impl Encodable for Evidences {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.append_list::<Vec<u8>, Vec<u8>>(&self.evidences);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::BlockReceipts;
@@ -26,21 +26,31 @@ use libproto;
 pub use libproto::*;
 use libproto::blockchain::Block as ProtobufBlock;
 use libproto::consensus::SignedProposeStep;
+use libproto::router::{ChainBlock, ChainRpc, ChainSync, ChainTxHashes, ChainVerdict, ConsensusEvidence, Route};
 pub use libproto::request::Request_oneof_req as Request;
 use protobuf::{Message, RepeatedField};
 use protobuf::core::parse_from_bytes;
+use pubsub::Subscription;
 use serde_json;
+use std::cmp;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
-use std::sync::mpsc::{Sender, Receiver};
+use std::sync::mpsc::Sender;
 use std::vec::Vec;
 use types::filter::Filter;
 use types::ids::BlockId;
 use util::Address;
 use util::H256;
 
+// Most blocks a node sends for one sync request
+const MAX_SYNC_BLOCKS: u64 = 100;
+
+fn request_id(msg: &[u8]) -> u64 {
+    parse_from_bytes::<communication::Message>(msg).map(|msg| msg.get_request_id()).unwrap_or(0)
+}
+
 // TODO: RPC Errors
-pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub: &Sender<(String, Vec<u8>)>) {
+pub fn chain_result(chain: Arc<Chain>, rx: &Subscription, ctx_pub: &Sender<(String, Vec<u8>)>) {
     let (key, msg) = rx.recv().unwrap();
     let (cmd_id, origin, content_ext) = parse_msg(msg.as_slice());
 
@@ -49,7 +59,6 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
         MsgClass::REQUEST(mut req) => {
             let mut response = response::Response::new();
             response.set_request_id(req.take_request_id());
-            let topic = "chain.rpc".to_string();
             match req.req.unwrap() {
                 // TODO: should check the result, parse it first!
                 Request::block_number(_) => {
@@ -238,12 +247,16 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
                     trace!("Log is: {:?}", log);
                     response.set_filter_logs(serde_json::to_string(&log).unwrap());
                 }
+
+                Request::evidence(height) => {
+                    let evidences: Vec<rpctypes::Evidence> = chain.evidences(height).into_iter().map(|(equivocation, evidence)| rpctypes::Evidence::new(equivocation, evidence)).collect();
+                    response.set_evidence(serde_json::to_string(&evidences).unwrap());
+                }
                 _ => {
                     error!("mtach error Request_oneof_req msg!!!!");
                 }
             };
-            let msg: communication::Message = response.into();
-            ctx_pub.send((topic, msg.write_to_bytes().unwrap())).unwrap();
+            ChainRpc::publish(ctx_pub, &response);
         }
 
         MsgClass::BLOCKWITHPROOF(proofblk) => {
@@ -263,7 +276,7 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
             if blk_height > current_height && blk_height < current_height + 300 {
                 if !guard.contains_key(&blk_height) || (guard.contains_key(&blk_height) && guard[&blk_height].2 == false) {
                     trace!("block insert {:?}", blk_height);
-                    guard.insert(blk_height, (Some(proof.clone()), Block::from(block.clone()), true));
+                    guard.insert(blk_height, (Some(proof.clone()), Block::from(block.clone()), true, origin));
                     let _ = chain.sync_sender.lock().send(blk_height);
                 }
             }
@@ -274,10 +287,20 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
             let max_height = chain.get_max_height();
             let blk_height = problock.get_header().get_height();
 
+            let request_id = request_id(&msg);
+            if request_id != 0 && request_id != chain.sync_request.load(Ordering::SeqCst) as u64 {
+                trace!("drop block {} of stale sync request {} from node-{:?}", blk_height, request_id, origin);
+                return;
+            }
+
             // Check transaction root
             // ignore block which height is ::std::u64::MAX, it's only a proof
             if blk_height != ::std::u64::MAX && !problock.check_hash() {
-                warn!("transactions root isn't correct, height is {}", blk_height);
+                warn!("transactions or evidence root isn't correct, height is {}", blk_height);
+                if Verdict::is_remote(origin) {
+                    let verdict = Verdict::new(origin, Offence::InvalidBlock, format!("transactions or evidence root of block {} isn't correct", blk_height));
+                    ChainVerdict::publish(ctx_pub, &verdict);
+                }
                 return;
             }
 
@@ -290,6 +313,7 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
                     let mut guard = chain.block_map.write();
                     if let Some(info) = guard.get_mut(&proof_height) {
                         info.0 = Some(problock.get_header().get_proof().clone());
+                        info.3 = origin;
                         let _ = chain.sync_sender.lock().send(proof_height);
                         trace!("blk_height == MAX proof height {}", proof_height);
                     }
@@ -308,10 +332,11 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
                 *guard = new_map;
                 if !guard.contains_key(&blk_height) {
                     trace!("block insert {:?} no proof and not verified", blk_height);
-                    guard.insert(blk_height, (None, block, false));
+                    guard.insert(blk_height, (None, block, false, origin));
                 }
                 if let Some(info) = guard.get_mut(&proof_height) {
                     info.0 = Some(problock.get_header().get_proof().clone());
+                    info.3 = origin;
                     let _ = chain.sync_sender.lock().send(proof_height);
                 }
             }
@@ -325,20 +350,17 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
             }
             let known_max_height = chain.get_max_height();
             let current_height = chain.get_current_height();
-            let target_height = ::std::cmp::min(current_height + 100, known_max_height);
+            let target_height = cmp::min(current_height + MAX_SYNC_BLOCKS, known_max_height);
             if current_height < target_height && !chain.is_sync.load(Ordering::SeqCst) {
-                let mut diff = target_height - current_height;
-                let mut start_height = current_height + 1;
-                while diff > 0 {
-                    let mut wtr = vec![0; 8];
-                    trace!("request sync {:?}", start_height);
-                    BigEndian::write_u64(&mut wtr, start_height);
-                    let msg = factory::create_msg_ex(submodules::CHAIN, topics::SYNC_BLK, communication::MsgType::MSG, communication::OperateType::SINGLE, origin, wtr);
-                    trace!("origin {:?}, chain.sync: OperateType {:?}", origin, communication::OperateType::SINGLE);
-                    ctx_pub.send(("chain.sync".to_string(), msg.write_to_bytes().unwrap())).unwrap();
-                    start_height += 1;
-                    diff -= 1;
-                }
+                // the whole range from the node which has it, rather than a request per height
+                let start_height = current_height + 1;
+                let count = target_height - current_height;
+                let request_id = chain.sync_request.fetch_add(1, Ordering::SeqCst) as u64 + 1;
+                let mut wtr = vec![0; 16];
+                BigEndian::write_u64(&mut wtr[..8], start_height);
+                BigEndian::write_u64(&mut wtr[8..], count);
+                trace!("request sync {:?} blocks from {:?} of node-{:?}, request {}", count, start_height, origin, request_id);
+                ChainSync::send(ctx_pub, ChainSync::msg_to(origin, request_id, &wtr));
                 if !chain.is_sync.load(Ordering::SeqCst) {
                     chain.is_sync.store(true, Ordering::SeqCst);
                 }
@@ -347,32 +369,42 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
 
         MsgClass::MSG(content) => {
             if libproto::cmd_id(submodules::CHAIN, topics::SYNC_BLK) == cmd_id {
-
-                let height = BigEndian::read_u64(&content);
-                trace!("Receive sync {:?} from node-{:?}", height, origin);
-                if let Some(block) = chain.block(BlockId::Number(height)) {
-                    let msg = factory::create_msg_ex(submodules::CHAIN, topics::NEW_BLK, communication::MsgType::BLOCK, communication::OperateType::SINGLE, origin, block.protobuf().write_to_bytes().unwrap());
+                // a start height, and a count unless the requester only asks for one block
+                if content.len() < 8 {
+                    warn!("invalid sync request from node-{:?}", origin);
+                    return;
+                }
+                let start_height = BigEndian::read_u64(&content);
+                let count = if content.len() >= 16 { cmp::min(BigEndian::read_u64(&content[8..]), MAX_SYNC_BLOCKS) } else { 1 };
+                let request_id = request_id(&msg);
+                trace!("Receive sync {:?} blocks from {:?} of node-{:?}, request {}", count, start_height, origin, request_id);
+                for height in start_height..start_height.saturating_add(count) {
+                    let block = match chain.block(BlockId::Number(height)) {
+                        Some(block) => block,
+                        None => break,
+                    };
                     trace!("origin {:?}, chain.blk: OperateType {:?}", origin, communication::OperateType::SINGLE);
-                    ctx_pub.send(("chain.blk".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+                    ChainBlock::send(ctx_pub, ChainBlock::msg_to(origin, request_id, &block.protobuf()));
 
                     if height == chain.get_current_height() {
                         let mut proof_block = ProtobufBlock::new();
                         let mut flag = false;
                         {
                             let guard = chain.block_map.read();
-                            if let Some(&(Some(ref proof), _, _)) = guard.get(&height) {
+                            if let Some(&(Some(ref proof), _, _, _)) = guard.get(&height) {
                                 proof_block.mut_header().set_proof(proof.clone());
                                 flag = true;
                             }
                         }
                         if flag {
                             proof_block.mut_header().set_height(::std::u64::MAX);
-                            let msg = factory::create_msg_ex(submodules::CHAIN, topics::NEW_BLK, communication::MsgType::BLOCK, communication::OperateType::SINGLE, origin, proof_block.write_to_bytes().unwrap());
                             trace!("max height {:?}, chain.blk: OperateType {:?}", height, communication::OperateType::SINGLE);
-                            ctx_pub.send(("chain.blk".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+                            ChainBlock::send(ctx_pub, ChainBlock::msg_to(origin, request_id, &proof_block));
                         }
                     }
                 }
+            } else if ConsensusEvidence::cmd_id() == cmd_id {
+                chain.add_evidence(&content);
             } else if libproto::cmd_id(submodules::CONSENSUS, topics::NEW_PROPOSAL) == cmd_id {
                 info!("Receive new proposal.");
                 let signed_propose_step = parse_from_bytes::<SignedProposeStep>(&content).unwrap();
@@ -398,10 +430,10 @@ pub fn chain_result(chain: Arc<Chain>, rx: &Receiver<(String, Vec<u8>)>, ctx_pub
                 block_tx_hashes.set_tx_hashes(RepeatedField::from_slice(&tx_hashes_in_u8[..]));
                 block_tx_hashes.set_block_gas_limit(chain.block_gas_limit.load(Ordering::SeqCst) as u64);
                 block_tx_hashes.set_account_gas_limit(chain.account_gas_limit.read().clone().into());
+                chain.set_admission_info(&mut block_tx_hashes);
 
-                let msg = factory::create_msg(submodules::CHAIN, topics::BLOCK_TXHASHES, communication::MsgType::BLOCK_TXHASHES, block_tx_hashes.write_to_bytes().unwrap());
 
-                ctx_pub.send(("chain.txhashes".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+                ChainTxHashes::publish(ctx_pub, &block_tx_hashes);
                 trace!("response block's tx hashes for height:{}", block_height);
             } else {
                 warn!("get block's tx hashes for height:{} error", block_height);
@@ -19,7 +19,8 @@ use core::libchain::block::Block;
 use core::libchain::chain::Chain;
 use libproto::*;
 use libproto::blockchain::{Status, RichStatus, ProofType};
-use proof::TendermintProof;
+use libproto::router::{ChainRichStatus, ChainStatus, ChainVerdict, Route};
+use proof::{TendermintProof, RaftProof};
 use protobuf::{Message, RepeatedField};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -62,6 +63,7 @@ impl Synchronizer {
                     let proof_option = value.0;
                     let block = value.1;
                     let is_local = value.2;
+                    let origin = value.3;
                     if let Some(proof) = proof_option {
                         if is_local {
                             self.add_block(ctx_pub, block);
@@ -69,12 +71,24 @@ impl Synchronizer {
                             // Check block proof
                             let block = Block::from(block.clone());
                             if let Some(proof_type) = block.proof_type() {
-                                if proof_type == ProofType::Tendermint {
-                                    let authorities = self.chain.nodes.read().clone();
-                                    let proof = TendermintProof::from(proof.clone());
-                                    if proof.check(height as usize, &authorities) {
-                                        self.add_block(ctx_pub, block);
+                                let authorities = self.chain.nodes.read().clone();
+                                let valid = match proof_type {
+                                    ProofType::Tendermint => {
+                                        let weights = self.chain.node_weights.read().clone();
+                                        let proof = TendermintProof::from(proof.clone());
+                                        Some(proof.check(height as usize, &authorities, &weights))
                                     }
+                                    ProofType::Raft => {
+                                        let proposal = RaftProof::proposal_hash(height as usize, block.parent_hash(), block.timestamp(), block.transactions_root());
+                                        Some(RaftProof::decode(&proof).map_or(false, |proof| proof.check(height as usize, &proposal, &authorities)))
+                                    }
+                                    ProofType::AuthorityRound => None,
+                                };
+                                if valid == Some(true) {
+                                    self.add_block(ctx_pub, block);
+                                } else if valid == Some(false) && Verdict::is_remote(origin) {
+                                    let verdict = Verdict::new(origin, Offence::InvalidBlock, format!("proof of block {} is invalid", height));
+                                    ChainVerdict::publish(ctx_pub, &verdict);
                                 }
                             };
                             trace!("block {} proof is invalid ", height);
@@ -102,6 +116,7 @@ impl Synchronizer {
         let current_height = self.chain.get_current_height();
         let max_height = self.chain.get_max_height();
         let nodes: Vec<Address> = self.chain.nodes.read().clone();
+        let weights: Vec<u64> = self.chain.node_weights.read().clone();
 
         drop(self);
         info!("sync_status {:?}, {:?}", current_hash, current_height);
@@ -111,15 +126,14 @@ impl Synchronizer {
         rich_status.set_height(current_height);
         let node_list = nodes.into_iter().map(|address| address.to_vec()).collect();
         rich_status.set_nodes(RepeatedField::from_vec(node_list));
+        rich_status.set_weights(weights);
 
-        let msg = factory::create_msg(submodules::CHAIN, topics::RICH_STATUS, communication::MsgType::RICH_STATUS, rich_status.write_to_bytes().unwrap());
         trace!("chain after sync current height {:?}  known height {:?}", current_height, max_height);
-        ctx_pub.send(("chain.richstatus".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ChainRichStatus::publish(ctx_pub, &rich_status);
 
         let status: Status = rich_status.into();
-        let sync_msg = factory::create_msg(submodules::CHAIN, topics::NEW_STATUS, communication::MsgType::STATUS, status.write_to_bytes().unwrap());
         trace!("add_block chain.status {:?}, {:?}", status.get_height(), status.get_hash());
-        ctx_pub.send(("chain.status".to_string(), sync_msg.write_to_bytes().unwrap())).unwrap();
+        ChainStatus::publish(ctx_pub, &status);
     }
 
     fn add_block(&self, ctx_pub: &Sender<(String, Vec<u8>)>, blk: Block) {
@@ -127,9 +141,8 @@ impl Synchronizer {
         let status = self.chain.set_block(blk, &ctx_pub);
 
         if let Some(status) = status {
-            let sync_msg = factory::create_msg(submodules::CHAIN, topics::NEW_STATUS, communication::MsgType::STATUS, status.write_to_bytes().unwrap());
             trace!("add_block chain.status {:?}, {:?}", status.get_height(), status.get_hash());
-            ctx_pub.send(("chain.status".to_string(), sync_msg.write_to_bytes().unwrap())).unwrap();
+            ChainStatus::publish(ctx_pub, &status);
         }
     }
 }
@@ -31,9 +31,12 @@ const LOG_TYPE_AUTHORITIES: u8 = 1;
 pub struct AuthorityManage {
     pub authorities: Vec<Address>,
     pub authority_n: usize,
+    /// Voting power of each authority, empty when they are equal
+    pub authority_weights: Vec<u64>,
     authorities_log: Wal,
     pub authorities_old: Vec<Address>,
     pub authority_n_old: usize,
+    pub authority_weights_old: Vec<u64>,
     pub authority_h_old: usize,
 }
 
@@ -44,18 +47,22 @@ impl AuthorityManage {
         let mut authority_manage = AuthorityManage {
             authorities: Vec::new(),
             authority_n: 0,
+            authority_weights: Vec::new(),
             authorities_log: Wal::new(&*logpath).unwrap(),
             authorities_old: Vec::new(),
             authority_n_old: 0,
+            authority_weights_old: Vec::new(),
             authority_h_old: 0,
         };
 
         let vec_out = authority_manage.authorities_log.load();
         if !vec_out.is_empty() {
             //out 转换成authorities;
-            if let Ok((h, authorities_old, authorities)) = deserialize(&(vec_out[0].1)) {
+            if let Ok((h, authorities_old, authorities, weights_old, weights)) = deserialize(&(vec_out[0].1)) {
                 let auth_old: Vec<Address> = authorities_old;
                 let auth: Vec<Address> = authorities;
+                authority_manage.authority_weights_old = weights_old;
+                authority_manage.authority_weights = weights;
 
                 authority_manage.authorities.extend_from_slice(&auth);
                 authority_manage.authority_n = authority_manage.authorities.len();
@@ -70,19 +77,26 @@ impl AuthorityManage {
     }
 
     pub fn receive_authorities_list(&mut self, height: usize, authorities: Vec<Address>) {
+        self.receive_weighted_authorities(height, authorities, Vec::new());
+    }
+
+    /// `weights[i]` is the voting power of `authorities[i]`
+    pub fn receive_weighted_authorities(&mut self, height: usize, authorities: Vec<Address>, weights: Vec<u64>) {
 
-        if self.authorities != authorities {
+        if self.authorities != authorities || self.authority_weights != weights {
 
             self.authorities_old.clear();
             self.authorities_old.extend_from_slice(&self.authorities);
             self.authority_n_old = self.authority_n;
+            self.authority_weights_old = self.authority_weights.clone();
             self.authority_h_old = height;
 
             self.authorities.clear();
             self.authorities.extend_from_slice(&authorities);
             self.authority_n = self.authorities.len();
+            self.authority_weights = weights;
 
-            let bmsg = serialize(&(height, self.authority_n_old.clone(), self.authorities.clone()), Infinite).unwrap();
+            let bmsg = serialize(&(height, &self.authorities_old, &self.authorities, &self.authority_weights_old, &self.authority_weights), Infinite).unwrap();
             let _ = self.authorities_log.save(LOG_TYPE_AUTHORITIES, &bmsg);
         }
     }
@@ -22,6 +22,7 @@ use engine_json;
 use error::ErrorCode;
 use libproto::*;
 use libproto::blockchain::{BlockBody, Proof, Block, SignedTransaction, RichStatus};
+use libproto::router::{ConsensusBlock, ConsensusRpc, ConsensusTx, Route};
 use proof::AuthorityRoundProof;
 use protobuf::{Message, RepeatedField};
 use rustc_serialize::hex::ToHex;
@@ -125,17 +126,15 @@ impl AuthorityRound {
     }
 
     pub fn pub_transaction(&self, tx_req: &Request, tx_pub: Sender<(String, Vec<u8>)>) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::REQUEST, communication::MsgType::REQUEST, tx_req.write_to_bytes().unwrap());
         trace!("broadcast new tx {:?}", tx_req);
-        tx_pub.send(("consensus.tx".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ConsensusTx::publish(&tx_pub, tx_req);
     }
 
 
     //call by seal_block and update_head, broadcast block to other node and also pass to chain
     pub fn pub_block(&self, block: &Block, tx_pub: Sender<(String, Vec<u8>)>) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::NEW_BLK, communication::MsgType::BLOCK, block.write_to_bytes().unwrap());
         trace!("publish block {:?}", block.crypt_hash());
-        tx_pub.send(("consensus.blk".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ConsensusBlock::publish(&tx_pub, block);
     }
 }
 
@@ -245,7 +244,7 @@ impl Engine for AuthorityRound {
             Ok(tx) => {
                 let hash = H256::from_slice(&tx.tx_hash);
                 let mut tx_pool = self.tx_pool.write();
-                let success = tx_pool.enqueue(tx);
+                let success = tx_pool.enqueue(tx).is_ok();
                 if success {
                     let tx_response = TxResponse::new(hash.clone(), String::from("Ok"));
                     let tx_state = serde_json::to_string(&tx_response).unwrap();
@@ -259,8 +258,7 @@ impl Engine for AuthorityRound {
         }
 
         if !from_broadcast {
-            let msg = factory::create_msg(submodules::CONSENSUS, topics::RESPONSE, communication::MsgType::RESPONSE, response.write_to_bytes().unwrap());
-            tx_pub.send(("consensus.rpc".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            ConsensusRpc::publish(&tx_pub, &response);
         }
     }
 
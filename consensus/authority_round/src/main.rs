@@ -43,7 +43,8 @@ use core::Spec;
 use core::handler;
 use cpuprofiler::PROFILER;
 use libproto::*;
-use pubsub::start_pubsub;
+use libproto::router::{self, ChainRichStatus, JsonrpcNewTx, NetMsg, NetTx, Route};
+use pubsub::{start_pubsub, sub_channel};
 use std::sync::mpsc::channel;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -99,9 +100,10 @@ fn main() {
 
     let threadpool = threadpool::ThreadPool::new(2);
     let (tx, rx) = channel();
-    let (tx_sub, rx_sub) = channel();
+    let (tx_sub, rx_sub) = sub_channel();
     let (tx_pub, rx_pub) = channel();
-    start_pubsub("consensus", vec!["net.tx", "jsonrpc.new_tx", "net.msg", "chain.richstatus"], tx_sub, rx_pub);
+    let keys = router::subscription(vec![NetTx::key(), JsonrpcNewTx::key(), NetMsg::key(), ChainRichStatus::key()]);
+    start_pubsub("consensus", keys, tx_sub, rx_pub);
     thread::spawn(move || loop {
                       let (key, body) = rx_sub.recv().unwrap();
                       let tx = tx.clone();
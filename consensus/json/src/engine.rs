@@ -18,6 +18,7 @@
 //! Engine deserialization.
 
 use super::AuthorityRound;
+use super::Raft;
 use super::Tendermint;
 
 
@@ -25,6 +26,7 @@ use super::Tendermint;
 #[derive(Debug, PartialEq, Deserialize)]
 pub enum Engine {
     AuthorityRound(AuthorityRound),
+    Raft(Raft),
     Tendermint(Tendermint),
 }
 
@@ -66,6 +68,25 @@ mod tests {
         let _deserialized: Engine = serde_json::from_str(&s).unwrap();
     }
 
+    #[test]
+    fn raft_engine_deserialization() {
+        let signer = generate_signer();
+        let s = format!(
+            r#"{{
+                "Raft": {{
+                    "params": {{
+                        "authorities" : ["0x5b073e9233944b5e729e46d618f0d8edf3d9c34a"],
+                        "duration": 3,
+                        "signer": "{}"
+                    }}
+                }}
+            }}"#,
+            signer
+        );
+
+        let _deserialized: Engine = serde_json::from_str(&s).unwrap();
+    }
+
     #[test]
     fn tendermint_engine_deserialization() {
         let signer = generate_signer();
@@ -25,12 +25,14 @@ extern crate util;
 
 mod engine;
 mod authority_round;
+mod raft;
 mod tendermint;
 mod spec;
 
 
 pub use self::authority_round::*;
 pub use self::engine::*;
+pub use self::raft::*;
 pub use self::spec::*;
 pub use self::tendermint::*;
 
@@ -0,0 +1,38 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crypto::PrivKey;
+use util::Address;
+
+/// Raft params deserialization.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct RaftParams {
+    /// Block duration.
+    pub duration: u64,
+    /// Valid authorities
+    pub authorities: Vec<Address>,
+    /// Validators of the raft servers on the command line, in the same order; `authorities` when absent
+    pub nodes: Option<Vec<Address>>,
+    /// Signs the proofs of the blocks this node makes as leader
+    pub signer: PrivKey,
+}
+
+/// Raft engine deserialization.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Raft {
+    pub params: RaftParams,
+}
@@ -26,6 +26,8 @@ pub struct TendermintParams {
     pub is_test: bool,
     /// Valid authorities
     pub authorities: Vec<Address>,
+    /// Whether proposers put the evidence of equivocating validators in their blocks
+    pub include_evidence: Option<bool>,
     pub signer: PrivKey,
 
     #[serde(rename = "timeoutPropose")]
@@ -81,6 +83,24 @@ mod tests {
         let _deserialize: TendermintParams = serde_json::from_str(&s).unwrap();
     }
 
+    #[test]
+    fn tendermint_evidence_deserialization() {
+        let signer = generate_signer();
+        let s = format!(
+            r#"{{
+                "authorities" : ["0x5b073e9233944b5e729e46d618f0d8edf3d9c34a"],
+                "include_evidence": true,
+                "duration": 3,
+                "signer": "{}",
+                "is_test": true
+            }}"#,
+            signer
+        );
+
+        let deserialize: TendermintParams = serde_json::from_str(&s).unwrap();
+        assert_eq!(deserialize.include_evidence, Some(true));
+    }
+
     #[test]
     fn tendermint_deserialization() {
         let signer = generate_signer();
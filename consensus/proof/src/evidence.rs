@@ -0,0 +1,180 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Evidence that a validator equivocated: two messages it signed for the same
+//! height, round and step which disagree. The signatures are all it takes to
+//! check it, whoever holds it.
+
+use bincode::{serialize, deserialize, Infinite};
+use crypto::{Signature, Sign, pubkey_to_address, SIGNATURE_BYTES_LEN};
+use libproto::consensus::SignedProposeStep;
+use protobuf::Message;
+use protobuf::core::parse_from_bytes;
+use util::{H256, Address, Hashable};
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum Evidence {
+    /// Two tendermint votes as broadcast, each a `(message, signature)`
+    DuplicateVote(Vec<u8>, Vec<u8>),
+    /// Two `SignedProposeStep`s
+    DuplicateProposal(Vec<u8>, Vec<u8>),
+}
+
+/// What a valid `Evidence` proves
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct Equivocation {
+    pub height: usize,
+    pub round: usize,
+    pub offender: Address,
+}
+
+// One signed message: height, round, step, signer and the hash of what was signed
+type Signed = (usize, usize, u32, Address, H256);
+
+fn recover(message: &[u8], signature: &[u8]) -> Option<(Address, H256)> {
+    if signature.len() != SIGNATURE_BYTES_LEN {
+        return None;
+    }
+    let hash = message.crypt_hash();
+    Signature::from(signature).recover(&hash.into()).ok().map(|pubkey| (pubkey_to_address(&pubkey), hash))
+}
+
+fn signed_vote(vote: &[u8]) -> Option<Signed> {
+    let (message, signature): (Vec<u8>, Vec<u8>) = match deserialize(vote) {
+        Ok(decoded) => decoded,
+        Err(_) => return None,
+    };
+    // the step is an enum of tendermint, only its index matters here
+    let (h, r, step, sender, _): (usize, usize, u32, Address, Option<H256>) = match deserialize(&message) {
+        Ok(decoded) => decoded,
+        Err(_) => return None,
+    };
+    match recover(&message, &signature) {
+        Some((signer, hash)) if signer == sender => Some((h, r, step, signer, hash)),
+        _ => None,
+    }
+}
+
+fn signed_proposal(proposal: &[u8]) -> Option<Signed> {
+    let signed = match parse_from_bytes::<SignedProposeStep>(proposal) {
+        Ok(signed) => signed,
+        Err(_) => return None,
+    };
+    let propose_step = signed.get_propose_step();
+    let message = propose_step.write_to_bytes().unwrap();
+    recover(&message, signed.get_signature()).map(|(signer, hash)| (propose_step.get_height() as usize, propose_step.get_round() as usize, 0, signer, hash))
+}
+
+impl Evidence {
+    /// The equivocation, when both messages are signed by one validator for the
+    /// same height, round and step and differ
+    pub fn check(&self) -> Option<Equivocation> {
+        let signed = match *self {
+            Evidence::DuplicateVote(ref first, ref second) => (signed_vote(first), signed_vote(second)),
+            Evidence::DuplicateProposal(ref first, ref second) => (signed_proposal(first), signed_proposal(second)),
+        };
+        match signed {
+            (Some((h, r, step, offender, hash)), Some((h2, r2, step2, signer2, hash2))) => {
+                if (h, r, step, &offender) != (h2, r2, step2, &signer2) || hash == hash2 {
+                    return None;
+                }
+                Some(Equivocation {
+                         height: h,
+                         round: r,
+                         offender: offender,
+                     })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serialize(self, Infinite).unwrap()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Evidence> {
+        deserialize(bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::{KeyPair, CreateKey};
+    use libproto::consensus::ProposeStep;
+    use tendermint_proof::Step;
+
+    fn vote(keypair: &KeyPair, h: usize, r: usize, step: Step, hash: Option<H256>) -> Vec<u8> {
+        let message = serialize(&(h, r, step, keypair.address(), hash), Infinite).unwrap();
+        let signature = Signature::sign(keypair.privkey(), &message.crypt_hash().into()).unwrap();
+        serialize(&(message, signature), Infinite).unwrap()
+    }
+
+    fn proposal(keypair: &KeyPair, h: u64, r: u64, lock_round: u64) -> Vec<u8> {
+        let mut propose_step = ProposeStep::new();
+        propose_step.set_height(h);
+        propose_step.set_round(r);
+        propose_step.mut_proposal().set_lock_round(lock_round);
+        let message = propose_step.write_to_bytes().unwrap();
+        let signature = Signature::sign(keypair.privkey(), &message.crypt_hash().into()).unwrap();
+        let mut signed = SignedProposeStep::new();
+        signed.set_propose_step(propose_step);
+        signed.set_signature(signature.to_vec());
+        signed.write_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn duplicate_vote() {
+        let keypair = KeyPair::gen_keypair();
+        let a = vote(&keypair, 5, 1, Step::Prevote, Some(H256::from(1)));
+        let b = vote(&keypair, 5, 1, Step::Prevote, Some(H256::from(2)));
+        let evidence = Evidence::DuplicateVote(a.clone(), b.clone());
+        assert_eq!(evidence.check(),
+                   Some(Equivocation {
+                            height: 5,
+                            round: 1,
+                            offender: keypair.address(),
+                        }));
+        assert_eq!(Evidence::from_bytes(&evidence.to_bytes()), Some(evidence));
+
+        // a nil vote conflicts too
+        let nil = vote(&keypair, 5, 1, Step::Prevote, None);
+        assert!(Evidence::DuplicateVote(a.clone(), nil).check().is_some());
+        // the same vote twice, or votes of another step, round or validator
+        assert_eq!(Evidence::DuplicateVote(a.clone(), a.clone()).check(), None);
+        assert_eq!(Evidence::DuplicateVote(a.clone(), vote(&keypair, 5, 1, Step::Precommit, Some(H256::from(2)))).check(), None);
+        assert_eq!(Evidence::DuplicateVote(a.clone(), vote(&keypair, 5, 2, Step::Prevote, Some(H256::from(2)))).check(), None);
+        assert_eq!(Evidence::DuplicateVote(a.clone(), vote(&KeyPair::gen_keypair(), 5, 1, Step::Prevote, Some(H256::from(2)))).check(), None);
+
+        // a forged signature
+        let (message, _): (Vec<u8>, Vec<u8>) = deserialize(&b).unwrap();
+        let (_, signature): (Vec<u8>, Vec<u8>) = deserialize(&vote(&KeyPair::gen_keypair(), 5, 1, Step::Prevote, None)).unwrap();
+        let forged = serialize(&(message, signature), Infinite).unwrap();
+        assert_eq!(Evidence::DuplicateVote(a, forged).check(), None);
+    }
+
+    #[test]
+    fn duplicate_proposal() {
+        let keypair = KeyPair::gen_keypair();
+        let a = proposal(&keypair, 5, 1, 0);
+        assert_eq!(Evidence::DuplicateProposal(a.clone(), proposal(&keypair, 5, 1, 1)).check().map(|e| e.offender), Some(keypair.address()));
+        assert_eq!(Evidence::DuplicateProposal(a.clone(), a.clone()).check(), None);
+        assert_eq!(Evidence::DuplicateProposal(a.clone(), proposal(&keypair, 5, 2, 1)).check(), None);
+        assert_eq!(Evidence::DuplicateProposal(a.clone(), proposal(&KeyPair::gen_keypair(), 5, 1, 1)).check(), None);
+        assert_eq!(Evidence::DuplicateProposal(a, vec![1, 2, 3]).check(), None);
+    }
+}
@@ -20,22 +20,27 @@ extern crate util;
 extern crate bincode;
 extern crate cita_crypto as crypto;
 extern crate rustc_serialize;
+extern crate protobuf;
 #[macro_use]
 extern crate serde_derive;
 
 mod authority_round_proof;
+mod raft_proof;
 mod tendermint_proof;
+mod evidence;
 
 
 pub use authority_round_proof::AuthorityRoundProof;
 use libproto::blockchain::{Proof, ProofType};
-pub use tendermint_proof::TendermintProof;
+pub use evidence::{Evidence, Equivocation};
+pub use raft_proof::RaftProof;
+pub use tendermint_proof::{TendermintProof, voting_power, total_power, is_above_threshold, proposer};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum CitaProof {
     AuthorityRound(AuthorityRoundProof),
-    Raft,
+    Raft(RaftProof),
     Tendermint(TendermintProof),
 }
 
@@ -44,7 +49,7 @@ impl From<Proof> for CitaProof {
     fn from(p: Proof) -> Self {
         match p.get_field_type() {
             ProofType::AuthorityRound => CitaProof::AuthorityRound(AuthorityRoundProof::from(p)),
-            ProofType::Raft => CitaProof::Raft,
+            ProofType::Raft => CitaProof::Raft(RaftProof::decode(&p).expect("malformed raft proof")),
             ProofType::Tendermint => CitaProof::Tendermint(TendermintProof::from(p)),
         }
 
@@ -55,7 +60,7 @@ impl Into<Proof> for CitaProof {
     fn into(self) -> Proof {
         match self {
             CitaProof::AuthorityRound(proof) => proof.into(),
-            CitaProof::Raft => Proof::new(),
+            CitaProof::Raft(proof) => proof.into(),
             CitaProof::Tendermint(proof) => proof.into(),
         }
     }
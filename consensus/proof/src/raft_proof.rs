@@ -0,0 +1,163 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use bincode::{serialize, deserialize, Infinite};
+use crypto::{Signature, Signer, Sign, pubkey_to_address};
+use libproto::blockchain::{Proof, ProofType};
+use rustc_serialize::hex::ToHex;
+use std::collections::HashMap;
+use std::fmt;
+use util::{H256, Address, Hashable};
+
+/// More than half of the raft servers acknowledging the committed log entry of the block at
+/// `height`. Every server acknowledges the first block entry for a height it finds committed, so
+/// two blocks of a height can't both gather a majority.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct RaftProof {
+    pub height: usize,
+    /// Term of the block's entry in the raft log
+    pub term: u64,
+    /// Index of the block's entry in the raft log
+    pub index: u64,
+    /// Hash of the header fields the leader made, see `proposal_hash`
+    pub proposal: H256,
+    pub commits: HashMap<Address, Signature>,
+}
+
+impl RaftProof {
+    pub fn new(height: usize, term: u64, index: u64, proposal: H256, commits: HashMap<Address, Signature>) -> RaftProof {
+        RaftProof {
+            height: height,
+            term: term,
+            index: index,
+            proposal: proposal,
+            commits: commits,
+        }
+    }
+
+    /// Hash of the header fields the leader made the block with, which the servers acknowledge
+    pub fn proposal_hash(height: usize, parent_hash: &H256, timestamp: u64, transactions_root: &H256) -> H256 {
+        serialize(&(height, parent_hash, timestamp, transactions_root), Infinite).unwrap().crypt_hash()
+    }
+
+    /// The acknowledgement of `signer` for the block entry at `index` of `term`
+    pub fn sign(height: usize, term: u64, index: u64, proposal: &H256, signer: &Signer) -> Signature {
+        let hash = RaftProof::signed_hash(height, term, index, proposal);
+        Signature::sign(signer.keypair.privkey(), &hash.into()).unwrap()
+    }
+
+    /// Whether `signature` is the acknowledgement of `sender` for the block entry
+    pub fn verify(height: usize, term: u64, index: u64, proposal: &H256, sender: &Address, signature: &Signature) -> bool {
+        let hash = RaftProof::signed_hash(height, term, index, proposal);
+        match signature.recover(&hash.into()) {
+            Ok(pubkey) => pubkey_to_address(&pubkey) == sender.clone().into(),
+            Err(_) => false,
+        }
+    }
+
+    fn signed_hash(height: usize, term: u64, index: u64, proposal: &H256) -> H256 {
+        serialize(&(height, term, index, proposal), Infinite).unwrap().crypt_hash()
+    }
+
+    /// Whether more than half of `authorities` acknowledged the block at `h` with the header
+    /// fields hashing to `proposal`
+    pub fn check(&self, h: usize, proposal: &H256, authorities: &[Address]) -> bool {
+        if h != self.height || *proposal != self.proposal || self.commits.len() * 2 <= authorities.len() {
+            return false;
+        }
+        self.commits.iter().all(|(sender, signature)| authorities.contains(sender) && RaftProof::verify(self.height, self.term, self.index, &self.proposal, sender, signature))
+    }
+
+    /// The raft proof in `proof`, `None` if it is of another kind or malformed
+    pub fn decode(proof: &Proof) -> Option<RaftProof> {
+        if proof.get_field_type() != ProofType::Raft {
+            return None;
+        }
+        deserialize(proof.get_content()).ok()
+    }
+}
+
+impl Into<Proof> for RaftProof {
+    fn into(self) -> Proof {
+        let mut proof = Proof::new();
+        let encoded_proof: Vec<u8> = serialize(&self, Infinite).unwrap();
+        proof.set_content(encoded_proof);
+        proof.set_field_type(ProofType::Raft);
+        proof
+    }
+}
+
+impl fmt::Display for RaftProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "height: {}, term: {}, index: {}, proposal: {}, commits: {}", self.height, self.term, self.index, self.proposal.to_hex(), self.commits.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::{KeyPair, CreateKey};
+
+    fn new_signers(n: usize) -> Vec<Signer> {
+        (0..n).map(|_| Signer::from(KeyPair::gen_keypair().privkey().clone())).collect()
+    }
+
+    fn acknowledge(signers: &[Signer], height: usize, term: u64, index: u64, proposal: &H256) -> HashMap<Address, Signature> {
+        signers.iter().map(|signer| (signer.address.clone().into(), RaftProof::sign(height, term, index, proposal, signer))).collect()
+    }
+
+    #[test]
+    fn proof_check() {
+        let signers = new_signers(3);
+        let authorities: Vec<Address> = signers.iter().map(|signer| signer.address.clone().into()).collect();
+        let proposal = H256::from(1);
+        let proof = RaftProof::new(5, 3, 7, proposal, acknowledge(&signers[..2], 5, 3, 7, &proposal));
+        assert!(proof.check(5, &proposal, &authorities));
+
+        assert!(!proof.check(6, &proposal, &authorities));
+        assert!(!proof.check(5, &H256::from(2), &authorities));
+        // a single acknowledgement is no majority of three
+        let minority = RaftProof::new(5, 3, 7, proposal, acknowledge(&signers[..1], 5, 3, 7, &proposal));
+        assert!(!minority.check(5, &proposal, &authorities));
+        // every acknowledgement has to be of an authority
+        let outsider = new_signers(1);
+        let mut commits = acknowledge(&signers[..2], 5, 3, 7, &proposal);
+        commits.extend(acknowledge(&outsider, 5, 3, 7, &proposal));
+        assert!(!RaftProof::new(5, 3, 7, proposal, commits).check(5, &proposal, &authorities));
+        // the acknowledgements are for the entry at this term and index
+        let mut other_entry = proof.clone();
+        other_entry.index = 8;
+        assert!(!other_entry.check(5, &proposal, &authorities));
+        let mut other_term = proof.clone();
+        other_term.term = 4;
+        assert!(!other_term.check(5, &proposal, &authorities));
+    }
+
+    #[test]
+    fn proof_convert() {
+        let signers = new_signers(1);
+        let proposal = H256::from(1);
+        let o_proof = RaftProof::new(1, 1, 2, proposal, acknowledge(&signers, 1, 1, 2, &proposal));
+        let proto_proof: Proof = o_proof.clone().into();
+        assert_eq!(Some(o_proof), RaftProof::decode(&proto_proof));
+
+        let mut malformed = Proof::new();
+        malformed.set_field_type(ProofType::Raft);
+        malformed.set_content(vec![1, 2, 3]);
+        assert_eq!(None, RaftProof::decode(&malformed));
+    }
+}
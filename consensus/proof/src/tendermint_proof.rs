@@ -26,6 +26,71 @@ use util::{H256, Address};
 use util::Hashable;
 use util::datapath::DataPath;
 
+/// Largest weight the node manager contract takes
+pub const MAX_WEIGHT: u64 = 1 << 32;
+
+/// Whether `weights` has a weight for every authority, none above `MAX_WEIGHT`,
+/// and a total which does not overflow
+fn is_weighted(authorities: &[Address], weights: &[u64]) -> bool {
+    weights.len() == authorities.len() && weights.iter().all(|weight| *weight <= MAX_WEIGHT) &&
+    weights.iter().fold(Some(0u64), |sum, weight| sum.and_then(|sum| sum.checked_add(*weight))).is_some()
+}
+
+/// Voting power of `authorities[i]` is `weights[i]`. Unless `weights` is a
+/// usable list, see `is_weighted`, each authority has one.
+pub fn voting_power(authorities: &[Address], weights: &[u64], sender: &Address) -> u64 {
+    match authorities.iter().position(|authority| authority == sender) {
+        Some(index) if is_weighted(authorities, weights) => weights[index],
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+pub fn total_power(authorities: &[Address], weights: &[u64]) -> u64 {
+    if is_weighted(authorities, weights) {
+        weights.iter().sum()
+    } else {
+        authorities.len() as u64
+    }
+}
+
+/// Whether `power` is more than two thirds of `total`
+pub fn is_above_threshold(power: u64, total: u64) -> bool {
+    match (power.checked_mul(3), total.checked_mul(2)) {
+        (Some(power), Some(total)) => power > total,
+        // 3p > 2t, with t = 3q + r, is p > 2q + 2r / 3
+        _ => power > total / 3 * 2 + total % 3 * 2 / 3,
+    }
+}
+
+/// The proposer of `nonce` (height + round). Authorities take turns in their
+/// order, and one with weight `w` stays in the rotation for `w` turns, so it
+/// proposes `w` of every `total_power` nonces.
+pub fn proposer<'a>(authorities: &'a [Address], weights: &[u64], nonce: usize) -> Option<&'a Address> {
+    let total = total_power(authorities, weights);
+    if total == 0 {
+        return None;
+    }
+    let weighted = is_weighted(authorities, weights);
+    let weight_of = |index: usize| if weighted { weights[index] } else { 1 };
+    let mut turn = nonce as u64 % total;
+    let mut passes = 0;
+    loop {
+        // authorities still in the rotation and how many passes they all stay
+        let staying: Vec<usize> = (0..authorities.len()).filter(|index| weight_of(*index) > passes).collect();
+        let stay = staying.iter().map(|index| weight_of(*index)).min().unwrap() - passes;
+        let len = staying.len() as u64;
+        // a round too long for u64 is longer than any turn
+        match len.checked_mul(stay) {
+            Some(round) if turn >= round => {
+                turn -= round;
+                passes += stay;
+            }
+            _ => return Some(&authorities[staying[(turn % len) as usize]]),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Step {
     Propose,
@@ -91,15 +156,16 @@ impl TendermintProof {
         return false;
     }
 
-    // Check proof commits
-    pub fn check(&self, h: usize, authorities: &[Address]) -> bool {
+    // Check proof commits, `weights` as in `voting_power`
+    pub fn check(&self, h: usize, authorities: &[Address], weights: &[u64]) -> bool {
         if h == 0 {
             return true;
         }
         if h != self.height {
             return false;
         }
-        if 2 * authorities.len() >= 3 * self.commits.len() {
+        let power = self.commits.keys().fold(0u64, |sum, sender| sum.saturating_add(voting_power(authorities, weights, sender)));
+        if !is_above_threshold(power, total_power(authorities, weights)) {
             return false;
         }
         self.commits.iter().all(|(sender, sig)| {
@@ -134,10 +200,64 @@ impl Into<Proof> for TendermintProof {
 
 #[cfg(test)]
 mod tests {
-    use super::{H256, TendermintProof};
+    use super::{H256, TendermintProof, Address, MAX_WEIGHT, proposer, voting_power, total_power, is_above_threshold};
     use libproto::blockchain::Proof;
     use std::collections::HashMap;
 
+    #[test]
+    fn weighted_power() {
+        let authorities: Vec<Address> = (1..4u64).map(Address::from).collect();
+        assert_eq!(voting_power(&authorities, &[], &authorities[2]), 1);
+        assert_eq!(voting_power(&authorities, &[5, 1, 3], &authorities[2]), 3);
+        assert_eq!(voting_power(&authorities, &[5, 1, 3], &Address::from(9)), 0);
+        assert_eq!(total_power(&authorities, &[5, 1, 3]), 9);
+        // a mismatched list counts every authority once
+        assert_eq!(total_power(&authorities, &[5, 1]), 3);
+
+        assert!(!is_above_threshold(6, 9));
+        assert!(is_above_threshold(7, 9));
+        assert!(is_above_threshold(3, 4));
+        assert!(!is_above_threshold(2, 3));
+    }
+
+    #[test]
+    fn huge_weights() {
+        let authorities: Vec<Address> = (1..4u64).map(Address::from).collect();
+        // the most the contract takes still counts
+        let max = [MAX_WEIGHT, MAX_WEIGHT, MAX_WEIGHT];
+        assert_eq!(total_power(&authorities, &max), 3 * MAX_WEIGHT);
+        assert!(is_above_threshold(2 * MAX_WEIGHT + 1, total_power(&authorities, &max)));
+        assert_eq!(proposer(&authorities, &max, 2 * MAX_WEIGHT as usize), Some(&authorities[2]));
+
+        // beyond it, or overflowing, every authority has one
+        let huge = [1 << 62, 1 << 62, u64::max_value()];
+        assert_eq!(voting_power(&authorities, &huge, &authorities[0]), 1);
+        assert_eq!(total_power(&authorities, &huge), 3);
+        assert_eq!(total_power(&authorities, &[MAX_WEIGHT + 1, 1, 1]), 3);
+        assert_eq!(proposer(&authorities, &huge, 4), Some(&authorities[1]));
+
+        // the threshold is exact where the products overflow
+        let total = u64::max_value() / 3 * 3;
+        assert!(is_above_threshold(total / 3 * 2 + 1, total));
+        assert!(!is_above_threshold(total / 3 * 2, total));
+        assert!(is_above_threshold(u64::max_value(), u64::max_value()));
+        assert!(!is_above_threshold(u64::max_value() / 3 * 2, u64::max_value()));
+    }
+
+    #[test]
+    fn weighted_proposer() {
+        let authorities: Vec<Address> = (1..4u64).map(Address::from).collect();
+        let turns = |weights: &[u64]| (0..total_power(&authorities, weights) as usize + 1)
+            .map(|nonce| authorities.iter().position(|a| Some(a) == proposer(&authorities, weights, nonce)).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(turns(&[]), vec![0, 1, 2, 0]);
+        assert_eq!(turns(&[1, 1, 1]), vec![0, 1, 2, 0]);
+        assert_eq!(turns(&[3, 1, 2]), vec![0, 1, 2, 0, 2, 0, 0]);
+        assert_eq!(turns(&[0, 2, 0]), vec![1, 1, 1]);
+        assert_eq!(proposer(&authorities, &[0, 0, 0], 1), None);
+        assert_eq!(proposer(&[], &[], 1), None);
+    }
+
     #[test]
     fn proof_convert() {
         let o_proof = TendermintProof::new(0, 1, H256::default(), HashMap::new());
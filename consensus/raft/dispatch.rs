@@ -16,12 +16,16 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 #![allow(unused_variables)]
+use engine::Engine;
 use libproto::*;
 use libraft::{NotifyMessage, Command, decode};
 use mio;
-use std::sync::mpsc::Receiver;
+use raft_engine::RaftEngine;
+use raft_server::AddressBook;
+use std::sync::mpsc::{Receiver, Sender};
+use util::Address;
 
-pub fn dispatch(notifix: &mio::Sender<NotifyMessage>, rx: &Receiver<(u32, u32, MsgClass)>) {
+pub fn dispatch(engine: &RaftEngine, book: &AddressBook, tx_pub: &Sender<(String, Vec<u8>)>, notifix: &mio::Sender<NotifyMessage>, rx: &Receiver<(u32, u32, MsgClass)>) {
     let (id, cmd_id, content_ext) = rx.recv().unwrap();
     match content_ext {
         MsgClass::STATUS(status) => {
@@ -31,8 +35,14 @@ pub fn dispatch(notifix: &mio::Sender<NotifyMessage>, rx: &Receiver<(u32, u32, M
             }
         }
         MsgClass::MSG(content) => {
+            if id == submodules::NET {
+                if let Err(err) = engine.handle_message(content, tx_pub.clone()) {
+                    warn!("raft acknowledgement: {}", err);
+                }
+                return;
+            }
             match decode(&content) {
-                Command::SpawnBlk(..) => {
+                Command::SpawnBlk(..) | Command::Committed(..) => {
                     info!("not expected");
                 }
                 Command::PoolSituation(height, hash, proof) => {
@@ -43,7 +53,18 @@ pub fn dispatch(notifix: &mio::Sender<NotifyMessage>, rx: &Receiver<(u32, u32, M
         }
         MsgClass::RICHSTATUS(rich_status) => {
             info!("raft dispatch rich_status is {:?}", rich_status);
-            //todo
+            let (hash, height) = (rich_status.get_hash().to_vec(), rich_status.get_height());
+            let nodes: Vec<Address> = rich_status.get_nodes().iter().map(|node| Address::from_slice(node)).collect();
+            if !nodes.is_empty() {
+                notifix.send(NotifyMessage::Members(book.members(&nodes)));
+            }
+            engine.receive_new_status(rich_status);
+            engine.acknowledge(tx_pub.clone());
+            notifix.send(NotifyMessage::NewStatus(hash, height));
+        }
+        MsgClass::BLOCKTXS(block_txs) => {
+            trace!("raft dispatch block_txs of height {}", block_txs.get_height());
+            engine.receive_block_txs(block_txs, tx_pub.clone());
         }
         _ => {
             error!("error content!!!");
@@ -19,8 +19,11 @@ use bincode::{serialize, deserialize, Infinite};
 
 #[derive(Serialize, Deserialize, PartialEq)]
 pub enum Command {
-    SpawnBlk(u64, Vec<u8>),
+    // height, parent hash and the term of the leader
+    SpawnBlk(u64, Vec<u8>, u64),
     PoolSituation(u64, Option<Vec<u8>>, Option<Vec<u8>>),
+    // term, index and the block of a block entry the server found committed
+    Committed(u64, u64, Vec<u8>),
 }
 
 pub fn encode(cmd: &Command) -> Vec<u8> {
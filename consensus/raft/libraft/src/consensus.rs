@@ -28,6 +28,14 @@
 //!
 //! In response to an event, the `Consensus` may mutate its own state, apply a command to the local
 //! `StateMachine`, or return an event to be sent to one or more remote peers or clients.
+//!
+//! The members of the cluster change one server at a time. The leader appends a configuration
+//! entry adding or removing a single server, and every server uses the latest configuration in its
+//! log, committed or not. The next change waits until the previous one is committed.
+//!
+//! A block is a log entry as well. Chain only takes a block with the acknowledgements of a
+//! majority, and every server acknowledges the first entry of a height it finds committed, so the
+//! servers agree on the block of each height.
 
 
 use {LogIndex, Term, ServerId, ClientId, messages};
@@ -36,6 +44,7 @@ use capnp::message::{Builder, HeapAllocator, Reader, ReaderSegments};
 use messages_capnp::{append_entries_request, append_entries_response, client_request, proposal_request, query_request, message, request_vote_request, request_vote_response};
 use persistent_log::Log;
 use rand::{self, Rng};
+use rustc_serialize::hex::{FromHex, ToHex};
 use serde_json;
 use serde_json::Value;
 use state::{ConsensusState, LeaderState, CandidateState, FollowerState};
@@ -44,7 +53,6 @@ use std::{cmp, fmt};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::rc::Rc;
-use uuid::Uuid;
 
 const ELECTION_MIN: u64 = 1500;
 const ELECTION_MAX: u64 = 3000;
@@ -55,6 +63,51 @@ pub enum Message {
     Get(String),
     Put(String, Value),
     Cas(String, Value, Value),
+    /// The ids and addresses of all the members from this entry on
+    Config(Vec<(u64, String)>),
+    /// The block of a height the leader made, hex of its protobuf bytes without a proof
+    Block(u64, String),
+}
+
+/// The members recorded in a configuration entry, `None` for any other entry. A member with an
+/// address that doesn't parse is left out, every server reads the entry the same way.
+fn decode_config(entry: &[u8]) -> Option<HashMap<ServerId, SocketAddr>> {
+    match serde_json::from_slice::<Message>(entry) {
+        Ok(Message::Config(members)) => {
+            let mut config = HashMap::new();
+            for (id, addr) in members {
+                match addr.parse::<SocketAddr>() {
+                    Ok(addr) => {
+                        config.insert(ServerId(id), addr);
+                    }
+                    Err(_) => warn!("configuration entry: member {} has an invalid address {:?}", id, addr),
+                }
+            }
+            Some(config)
+        }
+        _ => None,
+    }
+}
+
+/// The height and the block of a block entry, `None` for any other entry. A block that isn't
+/// hex is left empty.
+fn decode_block(entry: &[u8]) -> Option<(u64, Vec<u8>)> {
+    match serde_json::from_slice::<Message>(entry) {
+        Ok(Message::Block(height, block)) => {
+            let block = block.from_hex().unwrap_or_else(|_| {
+                warn!("block entry: block {} is not hex", height);
+                Vec::new()
+            });
+            Some((height, block))
+        }
+        _ => None,
+    }
+}
+
+fn encode_config(members: &HashMap<ServerId, SocketAddr>) -> Vec<u8> {
+    let mut members: Vec<(u64, String)> = members.iter().map(|(&id, addr)| (id.into(), addr.to_string())).collect();
+    members.sort();
+    serde_json::to_vec(&Message::Config(members)).unwrap()
 }
 
 /// Consensus timeout types.
@@ -93,6 +146,10 @@ pub struct Actions {
     pub clear_peer_messages: bool,
     /// Whether to gen new block
     pub is_new_blk: bool,
+    /// Whether the peers changed, so the connections have to follow.
+    pub peers_changed: bool,
+    /// Block entries found committed, with their term and index.
+    pub blocks: Vec<(Term, LogIndex, Vec<u8>)>,
 }
 
 impl fmt::Debug for Actions {
@@ -113,6 +170,8 @@ impl Actions {
             timeouts: vec![],
             clear_peer_messages: false,
             is_new_blk: false,
+            peers_changed: false,
+            blocks: vec![],
         }
     }
 }
@@ -122,8 +181,18 @@ impl Actions {
 pub struct Consensus<L, M> {
     /// The ID of this consensus instance.
     id: ServerId,
+    /// The address this consensus instance listens on.
+    addr: SocketAddr,
     /// The IDs of peers in the consensus group.
     peers: HashMap<ServerId, SocketAddr>,
+    /// Whether this instance is a member of its latest configuration.
+    member: bool,
+    /// Index of the latest configuration entry, 0 while the initial peers are the configuration.
+    config_index: LogIndex,
+    /// The peers of a log without configuration entries.
+    initial_peers: HashMap<ServerId, SocketAddr>,
+    /// The members the cluster is changed to, see `set_members`.
+    target: Option<HashMap<ServerId, SocketAddr>>,
 
     /// The persistent log.
     log: L,
@@ -145,6 +214,10 @@ pub struct Consensus<L, M> {
     follower_state: FollowerState,
     /// Previous hash of status
     prev_hash: Vec<u8>,
+    /// Term and height of the latest block asked for as leader.
+    spawned: (Term, u64),
+    /// Height of the latest block entry applied, later entries of a height up to it don't count.
+    block_height: u64,
 }
 
 impl<L, M> Consensus<L, M>
@@ -152,12 +225,17 @@ where
     L: Log,
     M: StateMachine,
 {
-    /// Creates a `Consensus`.
-    pub fn new(id: ServerId, peers: HashMap<ServerId, SocketAddr>, log: L, state_machine: M) -> Consensus<L, M> {
+    /// Creates a `Consensus`. `peers` are the peers until the log holds a configuration entry.
+    pub fn new(id: ServerId, addr: SocketAddr, peers: HashMap<ServerId, SocketAddr>, log: L, state_machine: M) -> Consensus<L, M> {
         let leader_state = LeaderState::new(log.latest_log_index().unwrap(), &peers.keys().cloned().collect());
-        Consensus {
+        let mut consensus = Consensus {
             id: id,
-            peers: peers,
+            addr: addr,
+            peers: peers.clone(),
+            member: true,
+            config_index: LogIndex(0),
+            initial_peers: peers,
+            target: None,
             log: log,
             state_machine: state_machine,
             commit_index: LogIndex(0),
@@ -167,7 +245,11 @@ where
             candidate_state: CandidateState::new(),
             follower_state: FollowerState::new(),
             prev_hash: Vec::new(),
-        }
+            spawned: (Term(0), 0),
+            block_height: 0,
+        };
+        consensus.load_config();
+        consensus
     }
 
     /// Returns the set of initial action which should be executed upon startup.
@@ -334,9 +416,14 @@ where
 
                                 self.log.append_entries(leader_prev_log_index + 1, &entries_vec).unwrap();
                                 self.follower_state.min_index = new_latest_log_index;
+                                // The latest configuration was either overwritten or superseded.
+                                let reconfigured = leader_prev_log_index < self.config_index || entries_vec.iter().any(|&(_, entry)| decode_config(entry).is_some());
+                                if reconfigured && self.load_config() {
+                                    actions.peers_changed = true;
+                                }
                                 // We are matching the leader's log up to and including `new_latest_log_index`.
                                 self.commit_index = cmp::min(LogIndex::from(request.get_leader_commit()), new_latest_log_index);
-                                self.apply_commits();
+                                self.apply_commits(actions);
                             } else {
                                 panic!("AppendEntriesRequest: no entry list")
                             }
@@ -393,6 +480,11 @@ where
             return;
         }
 
+        if !self.peers.contains_key(&from) {
+            scoped_debug!("AppendEntriesResponse from {} which left the cluster", from);
+            return;
+        }
+
         match response.which() {
             Ok(append_entries_response::Which::Success(follower_latest_log_index)) => {
                 scoped_trace!("AppendEntriesResponse from peer {}: success", from);
@@ -504,6 +596,9 @@ where
         } else if self.is_candidate() {
             // A vote was received!
             if let Ok(request_vote_response::Granted(_)) = response.which() {
+                if !self.peers.contains_key(&from) {
+                    return;
+                }
                 self.candidate_state.record_vote(from.clone());
                 if self.candidate_state.count_votes() >= majority {
                     scoped_info!("election for term {} won; transitioning to Leader", local_term);
@@ -513,50 +608,81 @@ where
         };
     }
 
+    /// Asks for the block after `height` once chain has the block of `height`, unless the log
+    /// holds an entry of that block already.
     pub fn sync_height(&mut self, hash: Vec<u8>, height: u64, actions: &mut Actions) {
-        let mut prev_log_index = self.latest_log_index();
-        info!("recieved height:{:?}, current log_index:{:?}", height, prev_log_index);
-        let from = ClientId(Uuid::new_v4());
+        info!("recieved height:{:?}, current log_index:{:?}", height, self.latest_log_index());
         if self.is_candidate() || (self.is_follower() && self.follower_state.leader.is_none()) {
             info!("can't know who is leader.");
         } else if self.is_follower() {
             info!("self is follower.");
+        } else if self.spawned.0 == self.current_term() && height < self.spawned.1 {
+            info!("block {} is asked for in this term already.", height + 1);
+        } else if self.latest_block_height() > height {
+            info!("block {} has an entry already.", height + 1);
         } else {
-            self.prev_hash = hash.clone();
-            if prev_log_index.0 != height {
-                warn!("current log_index not equal height");
-            }
-            prev_log_index = LogIndex(height);
-            let hash = serde_json::to_string(&Message::Put(serde_json::to_string(&height).unwrap(), serde_json::to_value(&hash).unwrap())).unwrap();
-            let prev_log_term = self.latest_log_term();
-            let term = self.current_term();
-            let log_index = LogIndex(height) + 1;
-            self.log.append_entries(log_index, &[(term, hash.as_bytes())]).unwrap();
+            self.prev_hash = hash;
+            self.spawned = (self.current_term(), height + 1);
             actions.is_new_blk = true;
-            if self.peers.is_empty() {
-                scoped_debug!("ProposalRequest from client {}: entry {}", from, log_index);
-                self.commit();
-            } else {
-                scoped_debug!("ProposalRequest from client {}: sending entry {} to peers", from, log_index);
-                let message = messages::append_entries_request(term, prev_log_index, prev_log_term, &[(term, hash.as_bytes())], self.commit_index);
-                for &peer in self.peers.keys() {
-                    if self.leader_state.next_index(&peer) == log_index {
-                        actions.peer_messages.push((peer, message.clone()));
-                        self.leader_state.set_next_index(peer, log_index + 1);
-                    }
+        }
+    }
+
+    /// Appends the entry of the block made for the latest `sync_height` of the leader.
+    pub fn append_block(&mut self, term: u64, height: u64, block: &[u8], actions: &mut Actions) {
+        if !self.is_leader() || (Term(term), height) != self.spawned || self.current_term() != Term(term) {
+            info!("block {} of term {} is no longer asked for.", height, term);
+            return;
+        }
+        if self.latest_block_height() >= height {
+            info!("block {} has an entry already.", height);
+            return;
+        }
+        let entry = serde_json::to_vec(&Message::Block(height, block.to_hex())).unwrap();
+        let prev_log_index = self.latest_log_index();
+        let prev_log_term = self.latest_log_term();
+        // Configuration entries and no-ops sit in the log as well, so the index of a block is not
+        // its height.
+        let log_index = prev_log_index + 1;
+        self.log.append_entries(log_index, &[(Term(term), &entry[..])]).unwrap();
+        if self.peers.is_empty() {
+            scoped_debug!("block {}: entry {}", height, log_index);
+            self.advance_commit_index(actions);
+        } else {
+            scoped_debug!("block {}: sending entry {} to peers", height, log_index);
+            let message = messages::append_entries_request(Term(term), prev_log_index, prev_log_term, &[(Term(term), &entry[..])], self.commit_index);
+            for &peer in self.peers.keys() {
+                if self.leader_state.next_index(&peer) == log_index {
+                    actions.peer_messages.push((peer, message.clone()));
+                    self.leader_state.set_next_index(peer, log_index + 1);
                 }
             }
         }
     }
 
+    /// Height of the latest block asked for.
     pub fn get_height(&self) -> u64 {
-        self.latest_log_index().0
+        self.spawned.1
     }
 
     pub fn prev_hash(&self) -> Vec<u8> {
         self.prev_hash.clone()
     }
 
+    pub fn get_term(&self) -> u64 {
+        self.current_term().as_u64()
+    }
+
+    /// Sets the members the cluster is changed to, including this server unless it is to leave.
+    /// The leader adds or removes one server at a time until the cluster has these members.
+    pub fn set_members(&mut self, members: HashMap<ServerId, SocketAddr>, actions: &mut Actions) {
+        if members.is_empty() {
+            scoped_warn!("ignoring a cluster without members");
+            return;
+        }
+        self.target = Some(members);
+        self.step_membership(actions);
+    }
+
     /// Applies a client proposal to the consensus state machine.
     fn proposal_request(&mut self, from: ClientId, request: proposal_request::Reader, actions: &mut Actions) {
         if self.is_candidate() || (self.is_follower() && self.follower_state.leader.is_none()) {
@@ -626,16 +752,24 @@ where
     /// Triggers an election timeout.
     fn election_timeout(&mut self, actions: &mut Actions) {
         scoped_assert!(!self.is_leader());
-        if self.peers.is_empty() {
+        if !self.may_campaign() {
+            // A server out of the cluster would only disrupt the members with its elections.
+            info!("ElectionTimeout: not a member, staying Follower");
+            self.state = ConsensusState::Follower;
+            actions.timeouts.push(ConsensusTimeout::Election);
+        } else if self.peers.is_empty() {
             // Solitary replica special case; jump straight to Leader state.
             info!("ElectionTimeout: transitioning to Leader");
+            // a durable log may remember the vote of the term before a restart
             scoped_assert!(self.is_follower());
-            scoped_assert!(self.log.voted_for().unwrap().is_none());
             self.log.inc_current_term().unwrap();
             self.log.set_voted_for(self.id).unwrap();
             let latest_log_index = self.latest_log_index();
             self.state = ConsensusState::Leader;
             self.leader_state.reinitialize(latest_log_index);
+            let (term, noop): (Term, &[u8]) = (self.current_term(), &[]);
+            self.log.append_entries(latest_log_index + 1, &[(term, noop)]).unwrap();
+            self.advance_commit_index(actions);
         } else {
             info!("ElectionTimeout: transitioning to Candidate");
             self.transition_to_candidate(actions);
@@ -651,9 +785,15 @@ where
         self.state = ConsensusState::Leader;
         self.leader_state.reinitialize(latest_log_index);
 
-        let message = messages::append_entries_request(current_term, latest_log_index, latest_log_term, &[], self.commit_index);
-        for &peer in self.peers().keys() {
+        // The entries of earlier terms are only committed along with one of the current term, so
+        // a new leader starts with a no-op (§5.4.2).
+        let noop: &[u8] = &[];
+        let log_index = latest_log_index + 1;
+        self.log.append_entries(log_index, &[(current_term, noop)]).unwrap();
+        let message = messages::append_entries_request(current_term, latest_log_index, latest_log_term, &[(current_term, noop)], self.commit_index);
+        for &peer in self.peers.keys() {
             actions.peer_messages.push((peer, message.clone()));
+            self.leader_state.set_next_index(peer, log_index + 1);
         }
 
         actions.clear_timeouts = true;
@@ -680,7 +820,7 @@ where
 
     /// Advances the commit index and applies committed entries to the state machine.
     fn advance_commit_index(&mut self, actions: &mut Actions) {
-        let results = self.commit();
+        let results = self.commit(actions);
 
         while let Some(&(client, index)) = self.leader_state.proposals.get(0) {
             if index <= self.commit_index {
@@ -695,34 +835,183 @@ where
                 break;
             }
         }
+
+        if self.is_leader() && self.config_index <= self.commit_index {
+            if self.member {
+                self.step_membership(actions);
+            } else {
+                scoped_info!("left the cluster; transitioning to Follower");
+                self.state = ConsensusState::Follower;
+                self.follower_state = FollowerState::new();
+                actions.clear_timeouts = true;
+                actions.timeouts.push(ConsensusTimeout::Election);
+            }
+        }
+    }
+
+    /// Appends the configuration entry of the next server to add or remove on the way to the
+    /// target members, once the latest configuration entry is committed. A new leader waits for an
+    /// entry of its own term to commit, the configurations of two leaders could otherwise commit
+    /// on disjoint majorities.
+    fn step_membership(&mut self, actions: &mut Actions) {
+        if !self.is_leader() || self.config_index > self.commit_index || !self.committed_in_term() {
+            return;
+        }
+        let mut members = self.peers.clone();
+        if self.member {
+            members.insert(self.id, self.addr);
+        }
+        let id = self.id;
+        let change = match self.target {
+            Some(ref target) => {
+                target.iter()
+                      .find(|&(peer, _)| !members.contains_key(peer))
+                      .map(|(&peer, &addr)| (peer, Some(addr)))
+                      .or_else(|| {
+                    let mut removed: Vec<ServerId> = members.keys().filter(|peer| !target.contains_key(peer)).cloned().collect();
+                    // The leader leaves last, it stops leading once it is out.
+                    removed.sort_by_key(|&peer| (peer == id, peer.as_u64()));
+                    removed.first().map(|&peer| (peer, None))
+                })
+            }
+            None => None,
+        };
+        let (peer, addr) = match change {
+            Some(change) => change,
+            None => return,
+        };
+        match addr {
+            Some(addr) => {
+                scoped_info!("adding {} ({}) to the cluster", peer, addr);
+                members.insert(peer, addr);
+            }
+            None => {
+                scoped_info!("removing {} from the cluster", peer);
+                members.remove(&peer);
+            }
+        }
+
+        let entry = encode_config(&members);
+        let prev_log_index = self.latest_log_index();
+        let prev_log_term = self.latest_log_term();
+        let term = self.current_term();
+        let log_index = prev_log_index + 1;
+        // A configuration takes effect as soon as it is in the log, a joining peer is sent the
+        // entry along with the others.
+        self.set_config(log_index, members);
+        actions.peers_changed = true;
+        self.log.append_entries(log_index, &[(term, &entry[..])]).unwrap();
+        if self.peers.is_empty() {
+            self.advance_commit_index(actions);
+        } else {
+            let message = messages::append_entries_request(term, prev_log_index, prev_log_term, &[(term, &entry[..])], self.commit_index);
+            for &peer in self.peers.keys() {
+                if self.leader_state.next_index(&peer) == log_index {
+                    actions.peer_messages.push((peer, message.clone()));
+                    self.leader_state.set_next_index(peer, log_index + 1);
+                }
+            }
+        }
+    }
+
+    /// Takes the configuration of the latest configuration entry in the log, or the initial peers
+    /// if there is none. Returns whether the peers changed.
+    fn load_config(&mut self) -> bool {
+        let mut index = self.latest_log_index();
+        while index > LogIndex(0) {
+            let members = decode_config(self.log.entry(index).unwrap().1);
+            if let Some(members) = members {
+                return self.set_config(index, members);
+            }
+            index = index - 1;
+        }
+        let mut members = self.initial_peers.clone();
+        members.insert(self.id, self.addr);
+        self.set_config(LogIndex(0), members)
+    }
+
+    /// Makes `members` the configuration from the entry at `index` on. Returns whether the peers
+    /// changed.
+    fn set_config(&mut self, index: LogIndex, mut members: HashMap<ServerId, SocketAddr>) -> bool {
+        self.config_index = index;
+        self.member = members.remove(&self.id).is_some();
+        if members == self.peers {
+            return false;
+        }
+        scoped_info!("configuration of entry {}: {:?}, member: {}", index, members.keys().collect::<Vec<_>>(), self.member);
+        let next_index = self.latest_log_index() + 1;
+        {
+            let (peers, leader_state) = (&self.peers, &mut self.leader_state);
+            for peer in peers.keys().filter(|peer| !members.contains_key(peer)) {
+                leader_state.remove_peer(peer);
+            }
+            for &peer in members.keys().filter(|peer| !peers.contains_key(peer)) {
+                leader_state.add_peer(peer, next_index);
+            }
+        }
+        self.peers = members;
+        true
+    }
+
+    /// Height of the latest block entry in the log, 0 without one.
+    fn latest_block_height(&self) -> u64 {
+        let mut index = self.latest_log_index();
+        while index > LogIndex(0) {
+            if let Some((height, _)) = decode_block(self.log.entry(index).unwrap().1) {
+                return height;
+            }
+            index = index - 1;
+        }
+        0
+    }
+
+    /// Whether the latest committed entry is of the current term.
+    fn committed_in_term(&self) -> bool {
+        self.commit_index > LogIndex(0) && self.log.entry(self.commit_index).unwrap().0 == self.current_term()
+    }
+
+    /// Whether this server may start an election: it has to be a member, and not about to leave.
+    fn may_campaign(&self) -> bool {
+        self.member && self.target.as_ref().map_or(true, |target| target.contains_key(&self.id))
     }
 
     /// Advances the commit index and applies committed entries, but not reply to client
-    fn commit(&mut self) -> HashMap<LogIndex, Vec<u8>> {
+    fn commit(&mut self, actions: &mut Actions) -> HashMap<LogIndex, Vec<u8>> {
         assert!(self.is_leader());
         let majority = self.majority();
-        // TODO: Figure out failure condition here.
-        while self.commit_index < self.log.latest_log_index().unwrap() {
-            if self.leader_state.count_match_indexes(self.commit_index + 1) >= majority {
-                self.commit_index = self.commit_index + 1;
+        let current_term = self.current_term();
+        // Only an entry of the current term is committed by counting its replicas, the entries
+        // before it are committed along with it (§5.4.2).
+        let mut index = self.latest_log_index();
+        while index > self.commit_index && self.log.entry(index).unwrap().0 == current_term {
+            // A leader on its way out of the cluster doesn't count itself.
+            let replicas = self.leader_state.count_followers(index) + if self.member { 1 } else { 0 };
+            if replicas >= majority {
+                self.commit_index = index;
                 scoped_debug!("commit index advanced to {}", self.commit_index);
-            } else {
-                break; // If there isn't a majority now, there won't be one later.
+                break;
             }
+            index = index - 1;
         }
 
-        self.apply_commits()
+        self.apply_commits(actions)
     }
 
-    /// Applies all committed but unapplied log entries to the state machine.  Returns the set of
-    /// return values from the commits applied.
-    fn apply_commits(&mut self) -> HashMap<LogIndex, Vec<u8>> {
+    /// Applies all committed but unapplied log entries to the state machine, and hands the block
+    /// entries to `actions`.  Returns the set of return values from the commits applied.
+    fn apply_commits(&mut self, actions: &mut Actions) -> HashMap<LogIndex, Vec<u8>> {
         let mut results = HashMap::new();
         while self.last_applied < self.commit_index {
             // Unwrap justified here since we know there is an entry here.
-            let (_, entry) = self.log.entry(self.last_applied + 1).unwrap();
+            let (term, entry) = self.log.entry(self.last_applied + 1).unwrap();
 
-            if !entry.is_empty() {
+            if let Some((height, block)) = decode_block(entry) {
+                // Only the first entry of a height counts, every server leaves out the same ones.
+                if height > self.block_height {
+                    self.block_height = height;
+                    actions.blocks.push((term, self.last_applied + 1, block));
+                }
+            } else if !entry.is_empty() && decode_config(entry).is_none() {
                 let result = self.state_machine.apply(entry);
                 results.insert(self.last_applied + 1, result);
             }
@@ -777,7 +1066,7 @@ where
     /// Get the cluster quorum majority size.
     fn majority(&self) -> usize {
         let peers = self.peers.len();
-        let cluster_members = peers.checked_add(1).expect(&format!("unable to support {} cluster members", peers));
+        let cluster_members = if self.member { peers.checked_add(1).expect(&format!("unable to support {} cluster members", peers)) } else { peers };
         (cluster_members >> 1) + 1
     }
 }
@@ -813,9 +1102,11 @@ mod tests {
     use capnp::message::{Allocator, Builder, HeapAllocator, Reader, ReaderOptions};
 
     use capnp::serialize::{self, OwnedSegments};
-    use consensus::{Actions, Consensus, ConsensusTimeout};
+    use consensus::{Actions, Consensus, ConsensusTimeout, Message, decode_block};
     use messages;
     use persistent_log::{MemLog, Log};
+    use rustc_serialize::hex::ToHex;
+    use serde_json;
     use state_machine::NullStateMachine;
     use std::collections::{HashMap, VecDeque};
     use std::io::Cursor;
@@ -835,7 +1126,7 @@ mod tests {
                     let mut peers = ids.clone();
                     peers.remove(&id);
                     let store = MemLog::new();
-                    (id, Consensus::new(id, peers, store, NullStateMachine))
+                    (id, Consensus::new(id, ids[&id], peers, store, NullStateMachine))
                 })
            .collect()
     }
@@ -885,6 +1176,28 @@ mod tests {
         assert!(peers[&leader].is_leader());
     }
 
+    /// The ids and addresses of the servers `ids`.
+    fn members(ids: &[ServerId], peers: &HashMap<ServerId, TestPeer>) -> HashMap<ServerId, SocketAddr> {
+        ids.iter().map(|id| (*id, peers[id].addr)).collect()
+    }
+
+    /// Proposes `value` to `leader` along with `actions`, and returns the number of answered
+    /// proposals.
+    fn propose(leader: ServerId, value: &[u8], mut actions: Actions, peers: &mut HashMap<ServerId, TestPeer>) -> usize {
+        let proposal = into_reader(&messages::proposal_request(value));
+        peers.get_mut(&leader).unwrap().apply_client_message(ClientId::new(), &proposal, &mut actions);
+        apply_actions(leader, actions, peers).len()
+    }
+
+    fn log(peer: &TestPeer) -> Vec<(Term, Vec<u8>)> {
+        peer.log
+            .entries(LogIndex(1), peer.latest_log_index() + 1)
+            .unwrap()
+            .into_iter()
+            .map(|(term, entry)| (term, entry.to_vec()))
+            .collect()
+    }
+
     /// Tests the majority function.
     #[test]
     fn test_majority() {
@@ -1043,7 +1356,9 @@ mod tests {
             let client_messages = apply_actions(leader, actions, &mut peers);
             assert_eq!(1, client_messages.len());
             for peer in peers.values() {
-                assert_eq!((Term(1), value), peer.log.entry(LogIndex(1)).unwrap());
+                // The leader's no-op comes first.
+                assert_eq!((Term(1), &b""[..]), peer.log.entry(LogIndex(1)).unwrap());
+                assert_eq!((Term(1), value), peer.log.entry(LogIndex(2)).unwrap());
             }
         }
     }
@@ -1069,6 +1384,208 @@ mod tests {
         assert_eq!((Term(1), value), follower.log.entry(LogIndex(1)).unwrap());
         assert_eq!((Term(1), value), follower.log.entry(LogIndex(2)).unwrap());
     }
+
+    /// A new leader starts with a no-op, and changes the members only once it is committed.
+    #[test]
+    fn test_membership_waits_for_noop() {
+        setup_test!("test_membership_waits_for_noop");
+        let mut peers = new_cluster(3);
+        let ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let leader = ids[0];
+
+        // Win the election, but hold back the no-op the leader sends.
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().apply_timeout(ConsensusTimeout::Election, &mut actions);
+        let mut noop = Actions::new();
+        for (to, message) in actions.peer_messages {
+            let mut responses = Actions::new();
+            peers.get_mut(&to).unwrap().apply_peer_message(leader, &into_reader(&*message), &mut responses);
+            for (_, response) in responses.peer_messages {
+                peers.get_mut(&leader).unwrap().apply_peer_message(to, &into_reader(&*response), &mut noop);
+            }
+        }
+        assert!(peers[&leader].is_leader());
+        assert_eq!(vec![(Term(1), vec![])], log(&peers[&leader]));
+        assert_eq!(LogIndex(0), peers[&leader].commit_index);
+
+        let target = members(&ids[..2], &peers);
+        peers.get_mut(&leader).unwrap().set_members(target, &mut noop);
+        assert!(!noop.peers_changed);
+        assert_eq!(1, log(&peers[&leader]).len());
+
+        assert!(apply_actions(leader, noop, &mut peers).is_empty());
+        assert_eq!(2, log(&peers[&leader]).len());
+        assert_eq!(peers[&leader].latest_log_index(), peers[&leader].commit_index);
+        assert_eq!(1, peers[&leader].peers.len());
+        assert!(!peers[&leader].peers.contains_key(&ids[2]));
+    }
+
+    /// The leader asks for the block after the chain's height once per term, and appends its entry
+    /// unless the log has one. Every server ends up with the entry, and a later leader doesn't ask
+    /// for the block again.
+    #[test]
+    fn test_block_entry() {
+        setup_test!("test_block_entry");
+        let mut peers = new_cluster(3);
+        let ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let leader = ids[0];
+        elect_leader(leader, &mut peers);
+
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().sync_height(vec![1], 4, &mut actions);
+        assert!(actions.is_new_blk);
+        assert_eq!(5, peers[&leader].get_height());
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().sync_height(vec![1], 4, &mut actions);
+        assert!(!actions.is_new_blk);
+
+        // made in an earlier term
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().append_block(0, 5, b"block", &mut actions);
+        assert_eq!(1, log(&peers[&leader]).len());
+
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().append_block(1, 5, b"block", &mut actions);
+        assert!(apply_actions(leader, actions, &mut peers).is_empty());
+        assert_eq!(5, peers[&leader].block_height);
+        for peer in peers.values() {
+            assert_eq!(Some((5, b"block".to_vec())), decode_block(&log(peer)[1].1));
+        }
+
+        let next = ids[1];
+        elect_leader(next, &mut peers);
+        let mut actions = Actions::new();
+        peers.get_mut(&next).unwrap().sync_height(vec![1], 4, &mut actions);
+        assert!(!actions.is_new_blk);
+        peers.get_mut(&next).unwrap().sync_height(vec![2], 5, &mut actions);
+        assert!(actions.is_new_blk);
+        assert_eq!(6, peers[&next].get_height());
+    }
+
+    /// Of the committed entries of a height, a server only hands on the first.
+    #[test]
+    fn test_first_block_entry_counts() {
+        setup_test!("test_first_block_entry_counts");
+        let mut peers = new_cluster(2);
+        let peer_ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let follower = peers.get_mut(&peer_ids[0]).unwrap();
+        let block = |height: u64, block: &[u8]| serde_json::to_vec(&Message::Block(height, block.to_hex())).unwrap();
+        let (first, second, next) = (block(5, b"first"), block(5, b"second"), block(6, b"next"));
+        let entries = vec![(Term(1), &first[..]), (Term(2), &second[..]), (Term(2), &next[..])];
+        let message = into_reader(&*messages::append_entries_request(Term(2), LogIndex(0), Term(0), &entries, LogIndex(3)));
+        let mut actions = Actions::new();
+        follower.apply_peer_message(peer_ids[1], &message, &mut actions);
+
+        assert_eq!(vec![(Term(1), LogIndex(1), b"first".to_vec()), (Term(2), LogIndex(3), b"next".to_vec())], actions.blocks);
+    }
+
+    /// Adds a server to a cluster of three while proposals keep coming. The new server catches up
+    /// with the log and every server ends up with the four members.
+    #[test]
+    fn test_add_member_under_load() {
+        setup_test!("test_add_member_under_load");
+        let mut peers = new_cluster(3);
+        let mut ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let leader = ids[0];
+        let joining = ServerId::from(3);
+        let cluster = members(&ids, &peers);
+        peers.insert(joining, Consensus::new(joining, SocketAddr::from_str("127.0.0.1:3").unwrap(), cluster, MemLog::new(), NullStateMachine));
+        elect_leader(leader, &mut peers);
+        assert_eq!(1, propose(leader, b"1", Actions::new(), &mut peers));
+
+        ids.push(joining);
+        let target = members(&ids, &peers);
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().set_members(target, &mut actions);
+        assert!(actions.peers_changed);
+        assert_eq!(1, propose(leader, b"2", actions, &mut peers));
+        for value in &[b"3", b"4", b"5"] {
+            assert_eq!(1, propose(leader, *value, Actions::new(), &mut peers));
+        }
+
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().heartbeat_timeout(joining, &mut actions);
+        assert!(apply_actions(leader, actions, &mut peers).is_empty());
+
+        assert_eq!(3, peers[&leader].majority());
+        assert_eq!(peers[&leader].latest_log_index(), peers[&leader].commit_index);
+        let leader_log = log(&peers[&leader]);
+        for id in &ids {
+            let peer = &peers[id];
+            assert!(peer.member);
+            assert_eq!(3, peer.peers.len());
+            assert!(!peer.peers.contains_key(id));
+            assert_eq!(leader_log, log(peer));
+        }
+    }
+
+    /// Removes a follower from a cluster of four while proposals keep coming. The rest carry on
+    /// without it, and it doesn't start elections once it knows it is out.
+    #[test]
+    fn test_remove_member_under_load() {
+        setup_test!("test_remove_member_under_load");
+        let mut peers = new_cluster(4);
+        let ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let leader = ids[0];
+        let leaving = ids[3];
+        let staying = &ids[..3];
+        elect_leader(leader, &mut peers);
+        assert_eq!(1, propose(leader, b"1", Actions::new(), &mut peers));
+
+        let target = members(staying, &peers);
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().set_members(target.clone(), &mut actions);
+        assert!(actions.peers_changed);
+        assert_eq!(1, propose(leader, b"2", actions, &mut peers));
+        let left_log = log(&peers[&leaving]);
+        for value in &[b"3", b"4", b"5"] {
+            assert_eq!(1, propose(leader, *value, Actions::new(), &mut peers));
+        }
+
+        assert_eq!(2, peers[&leader].majority());
+        let leader_log = log(&peers[&leader]);
+        for id in staying {
+            let peer = &peers[id];
+            assert_eq!(2, peer.peers.len());
+            assert!(!peer.peers.contains_key(&leaving));
+            assert_eq!(leader_log, log(peer));
+        }
+        assert_eq!(left_log, log(&peers[&leaving]));
+
+        let peer = peers.get_mut(&leaving).unwrap();
+        let mut actions = Actions::new();
+        peer.set_members(target, &mut actions);
+        peer.apply_timeout(ConsensusTimeout::Election, &mut actions);
+        assert!(peer.is_follower());
+        assert!(actions.peer_messages.is_empty());
+        assert_eq!(vec![ConsensusTimeout::Election], actions.timeouts);
+    }
+
+    /// The leader removes itself last and steps down once the change is committed. The remaining
+    /// members elect a leader among themselves.
+    #[test]
+    fn test_leader_leaves() {
+        setup_test!("test_leader_leaves");
+        let mut peers = new_cluster(3);
+        let ids: Vec<ServerId> = peers.keys().cloned().collect();
+        let leader = ids[0];
+        let staying = &ids[1..];
+        elect_leader(leader, &mut peers);
+
+        let target = members(staying, &peers);
+        let mut actions = Actions::new();
+        peers.get_mut(&leader).unwrap().set_members(target, &mut actions);
+        assert_eq!(1, propose(leader, b"1", actions, &mut peers));
+        assert!(peers[&leader].is_follower());
+        assert!(!peers[&leader].member);
+        for id in staying {
+            assert_eq!(1, peers[id].peers.len());
+            assert!(!peers[id].peers.contains_key(&leader));
+        }
+
+        elect_leader(staying[0], &mut peers);
+        assert_eq!(1, propose(staying[0], b"2", Actions::new(), &mut peers));
+    }
     /*
     #[bench]
     fn bench_proposal_1(b: &mut test::Bencher) {
@@ -106,6 +106,7 @@ extern crate log;
 #[macro_use]
 extern crate serde_derive;
 extern crate bincode;
+extern crate rustc_serialize;
 #[macro_use]
 extern crate scoped_log;
 #[macro_use]
@@ -186,6 +187,8 @@ pub enum RaftError {
     ClusterViolation,
     /// A remote connection attempted to use an unknown connection type in the connection preamble
     UnknownConnectionType,
+    /// A server out of the cluster attempted to connect as a peer
+    UnknownPeer,
     /// An invalid peer in in the peer set. Returned Server::new().
     InvalidPeerSet,
     /// Registering a connection failed
@@ -30,7 +30,6 @@ use capnp::message::{Builder, HeapAllocator};
 use cmd;
 use connection::{Connection, ConnectionKind};
 use consensus::{Consensus, Actions, ConsensusTimeout};
-use libproto::*;
 use messages;
 use messages_capnp::connection_preamble;
 use mio::{EventLoop, EventSet, Handler, PollOpt, Token, Sender};
@@ -39,7 +38,6 @@ use mio::Timeout as TimeoutHandle;
 use mio::tcp::TcpListener;
 use mio::util::Slab;
 use persistent_log::Log;
-use protobuf::core::Message;
 use state_machine::StateMachine;
 use std::{fmt, io};
 use std::collections::HashMap;
@@ -50,7 +48,6 @@ use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
 
 const LISTENER: Token = Token(0);
-type PubType = (String, Vec<u8>);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum ServerTimeout {
@@ -99,7 +96,8 @@ where
     /// Currently registered reconnection timeouts.
     reconnection_timeouts: HashMap<Token, TimeoutHandle>,
 
-    con: Option<mpsc::Sender<PubType>>,
+    /// Where the leader asks for the next block, and the committed blocks go.
+    spawner: Option<mpsc::Sender<cmd::Command>>,
 }
 
 /// The implementation of the Server.
@@ -115,7 +113,7 @@ where
             return Err(Error::Raft(RaftError::InvalidPeerSet));
         }
 
-        let consensus = Consensus::new(id, peers.clone(), store, state_machine);
+        let consensus = Consensus::new(id, addr, peers, store, state_machine);
         let mut event_loop = try!(EventLoop::<Server<L, M>>::new());
         let listener = try!(TcpListener::bind(&addr));
         try!(event_loop.register(&listener, LISTENER, EventSet::all(), PollOpt::level()));
@@ -129,24 +127,53 @@ where
             client_tokens: HashMap::new(),
             consensus_timeouts: HashMap::new(),
             reconnection_timeouts: HashMap::new(),
-            con: None,
+            spawner: None,
         };
 
-        for (peer_id, peer_addr) in peers {
-            let token: Token = try!(server.connections
-                                          .insert(try!(Connection::peer(peer_id, peer_addr)))
-                                          .map_err(|_| Error::Raft(RaftError::ConnectionLimitReached)));
-            scoped_assert!(server.peer_tokens.insert(peer_id, token).is_none());
-
-            try!(server.connections[token].register(&mut event_loop, token));
-            server.send_message(&mut event_loop, token, messages::server_connection_preamble(id, &addr));
+        // The log may hold a configuration other than `peers`.
+        for (peer_id, peer_addr) in server.consensus.peers().clone() {
+            try!(server.connect_peer(&mut event_loop, peer_id, peer_addr));
         }
 
         Ok((server, event_loop))
     }
 
-    pub fn set_pub(&mut self, p: mpsc::Sender<PubType>) {
-        self.con = Some(p);
+    /// Opens a connection to the peer and introduces this server to it.
+    fn connect_peer(&mut self, event_loop: &mut EventLoop<Server<L, M>>, peer_id: ServerId, peer_addr: SocketAddr) -> Result<()> {
+        let addr = try!(self.listener.local_addr());
+        let token: Token = try!(self.connections
+                                    .insert(try!(Connection::peer(peer_id, peer_addr)))
+                                    .map_err(|_| Error::Raft(RaftError::ConnectionLimitReached)));
+        scoped_assert!(self.peer_tokens.insert(peer_id, token).is_none());
+
+        try!(self.connections[token].register(event_loop, token));
+        self.send_message(event_loop, token, messages::server_connection_preamble(self.id, &addr));
+        Ok(())
+    }
+
+    /// Connects to the peers that joined the cluster and drops the connections of those that left.
+    fn sync_peers(&mut self, event_loop: &mut EventLoop<Server<L, M>>) {
+        let peers = self.consensus.peers().clone();
+        let left: Vec<ServerId> = self.peer_tokens.keys().filter(|peer| !peers.contains_key(peer)).cloned().collect();
+        for peer in left {
+            scoped_info!("{:?}: peer {} left the cluster", self, peer);
+            let token = self.peer_tokens.remove(&peer).unwrap();
+            self.reconnection_timeouts
+                .remove(&token)
+                .map(|handle| scoped_assert!(event_loop.clear_timeout(handle)));
+            self.connections.remove(token).expect("peer connection not found");
+        }
+        for (peer, peer_addr) in peers {
+            if !self.peer_tokens.contains_key(&peer) {
+                scoped_info!("{:?}: peer {} ({}) joined the cluster", self, peer, peer_addr);
+                self.connect_peer(event_loop, peer, peer_addr)
+                    .unwrap_or_else(|error| scoped_warn!("unable to connect to peer {}: {}", peer, error));
+            }
+        }
+    }
+
+    pub fn set_spawner(&mut self, spawner: mpsc::Sender<cmd::Command>) {
+        self.spawner = Some(spawner);
     }
 
     /// Runs a new Raft server in the current thread.
@@ -215,16 +242,23 @@ where
             clear_timeouts,
             clear_peer_messages,
             is_new_blk,
+            peers_changed,
+            blocks,
         } = actions;
 
+        if peers_changed {
+            self.sync_peers(event_loop);
+        }
         if clear_peer_messages {
             for &token in self.peer_tokens.values() {
                 self.connections[token].clear_messages();
             }
         }
         for (peer, message) in peer_messages {
-            let token = self.peer_tokens[&peer];
-            self.send_message(event_loop, token, message);
+            // The peer may have left the cluster meanwhile.
+            if let Some(&token) = self.peer_tokens.get(&peer) {
+                self.send_message(event_loop, token, message);
+            }
         }
         for (client, message) in client_messages {
             if let Some(&token) = self.client_tokens.get(&client) {
@@ -249,15 +283,19 @@ where
                 .map(|handle| scoped_assert!(event_loop.clear_timeout(handle), "unable to clear timeout: {:?}", timeout));
         }
         if is_new_blk {
-            // TO DO: Notify pool.
             let height = self.consensus.get_height();
             let hash = self.consensus.prev_hash();
-            info!("leader to spawn new blk, height: {}.", height);
-            let msg = factory::create_msg(submodules::CONSENSUS_CMD, topics::DEFAULT, communication::MsgType::MSG, cmd::encode(&cmd::Command::SpawnBlk(height, hash)));
-            if let Some(ref mut conn) = self.con {
-                conn.send(("consensus_cmd.default".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            let term = self.consensus.get_term();
+            info!("leader to spawn new blk, height: {}, term: {}.", height, term);
+            if let Some(ref spawner) = self.spawner {
+                spawner.send(cmd::Command::SpawnBlk(height, hash, term)).unwrap();
             } else {
-                panic!("connect tx_pool failed.");
+                panic!("no block spawner.");
+            }
+        }
+        if let Some(ref spawner) = self.spawner {
+            for (term, index, block) in blocks {
+                spawner.send(cmd::Command::Committed(term.as_u64(), index.as_u64(), block)).unwrap();
             }
         }
     }
@@ -293,8 +331,17 @@ where
     /// deserialized, an error result is returned.
     fn readable(&mut self, event_loop: &mut EventLoop<Server<L, M>>, token: Token) -> Result<()> {
         scoped_trace!("{:?}: readable event", self.connections[token]);
-        // Read messages from the connection until there are no more.
-        while let Some(message) = try!(self.connections[token].readable()) {
+        // Read messages from the connection until there are no more, or until the connection is
+        // dropped because its peer left the cluster.
+        loop {
+            let message = match self.connections.get_mut(token) {
+                Some(connection) => try!(connection.readable()),
+                None => None,
+            };
+            let message = match message {
+                Some(message) => message,
+                None => break,
+            };
             match *self.connections[token].kind() {
                 ConnectionKind::Peer(id) => {
                     let mut actions = Actions::new();
@@ -317,6 +364,9 @@ where
                             // address the peer tells us it's listening on.
                             let peer_addr = SocketAddr::from_str(try!(peer.get_addr())).unwrap();
                             scoped_debug!("received new connection from {:?} ({})", peer_id, peer_addr);
+                            if !self.peer_tokens.contains_key(&peer_id) {
+                                return Err(Error::Raft(RaftError::UnknownPeer));
+                            }
 
                             self.connections[token].set_kind(ConnectionKind::Peer(peer_id));
                             // Use the advertised address, not the remote's source
@@ -326,8 +376,8 @@ where
                             let prev_token = Some(self.peer_tokens.insert(peer_id, token).expect("peer token not found"));
 
                             // Close the existing connection, if any.
-                            // Currently, prev_token is never `None`; see above.
-                            // With config changes, this will have to be handled.
+                            // Currently, prev_token is never `None`; peers out of the
+                            // cluster are refused above.
                             match prev_token {
                                 Some(tok) => {
                                     self.connections.remove(tok).expect("peer connection not found");
@@ -390,6 +440,10 @@ where
 #[derive(Clone)]
 pub enum NotifyMessage {
     NewStatus(Vec<u8>, u64),
+    /// The members the cluster is changed to, see `Consensus::set_members`.
+    Members(HashMap<ServerId, SocketAddr>),
+    /// The term, the height and the block the leader was asked for, see `Consensus::append_block`.
+    Block(u64, u64, Vec<u8>),
 }
 
 unsafe impl Sync for NotifyMessage {}
@@ -406,6 +460,11 @@ where
         push_log_scope!("{:?}", self);
         scoped_trace!("ready; token: {:?}; events: {:?}", token, events);
 
+        if token != LISTENER && self.connections.get(token).is_none() {
+            // The connection of a peer that left the cluster was dropped meanwhile.
+            return;
+        }
+
         if events.is_error() {
             scoped_assert!(token != LISTENER, "unexpected error event from LISTENER");
             scoped_warn!("{:?}: error event", self.connections[token]);
@@ -442,7 +501,10 @@ where
                 self.readable(event_loop, token)
                     // Only reregister the connection with the event loop if no error occurs and
                     // the connection is *not* reset.
-                    .and_then(|_| self.connections[token].reregister(event_loop, token))
+                    .and_then(|_| match self.connections.get_mut(token) {
+                        Some(connection) => connection.reregister(event_loop, token),
+                        None => Ok(()),
+                    })
                     .unwrap_or_else(|error| {
                         scoped_warn!("{:?}: failed read: {}",
                                      self.connections[token], error);
@@ -459,6 +521,16 @@ where
                 self.consensus.sync_height(hash, height, &mut actions);
                 self.execute_actions(event_loop, actions);
             }
+            NotifyMessage::Members(members) => {
+                let mut actions = Actions::new();
+                self.consensus.set_members(members, &mut actions);
+                self.execute_actions(event_loop, actions);
+            }
+            NotifyMessage::Block(term, height, block) => {
+                let mut actions = Actions::new();
+                self.consensus.append_block(term, height, &block, &mut actions);
+                self.execute_actions(event_loop, actions);
+            }
         }
     }
 
@@ -724,6 +796,30 @@ mod tests {
         assert!(stream_shutdown(&mut stream));
     }
 
+    /// Tests that the server will throw away peer connections of servers out
+    /// of the cluster.
+    #[test]
+    fn test_unknown_peer_accept() {
+        setup_test!("test_unknown_peer_accept");
+
+        let (mut server, mut event_loop) = new_test_server(HashMap::new()).unwrap();
+
+        // Connect to the server.
+        let server_addr = server.listener.local_addr().unwrap();
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+        event_loop.run_once(&mut server, None).unwrap();
+
+        // Introduce the connection as a peer the server doesn't know.
+        let peer_addr = SocketAddr::from_str("127.0.0.1:12345").unwrap();
+        serialize::write_message(&mut stream, &*messages::server_connection_preamble(ServerId::from(1), &peer_addr)).unwrap();
+        stream.flush().unwrap();
+        event_loop.run_once(&mut server, None).unwrap();
+
+        // Check that the server disposes of the connection.
+        assert!(stream_shutdown(&mut stream));
+        assert!(server.peer_tokens.is_empty());
+    }
+
     /// Tests that the server will reset a peer connection when an invalid
     /// message is received.
     #[test]
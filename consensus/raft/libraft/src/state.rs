@@ -84,7 +84,24 @@ impl LeaderState {
     /// Counts the number of followers containing the given log index.
     pub fn count_match_indexes(&self, index: LogIndex) -> usize {
         // +1 for self.
-        self.match_index.values().filter(|&&i| i >= index).count() + 1
+        self.count_followers(index) + 1
+    }
+
+    /// Counts the number of followers containing the given log index, leaving the leader out.
+    pub fn count_followers(&self, index: LogIndex) -> usize {
+        self.match_index.values().filter(|&&i| i >= index).count()
+    }
+
+    /// Starts tracking a peer that joined the cluster.
+    pub fn add_peer(&mut self, peer: ServerId, next_index: LogIndex) {
+        self.next_index.insert(peer, next_index);
+        self.match_index.insert(peer, LogIndex::from(0));
+    }
+
+    /// Stops tracking a peer that left the cluster.
+    pub fn remove_peer(&mut self, peer: &ServerId) {
+        self.next_index.remove(peer);
+        self.match_index.remove(peer);
     }
 
     /// Reinitializes the state following an election.
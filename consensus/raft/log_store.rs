@@ -19,49 +19,118 @@
 
 use libraft::*;
 use std::{error, fmt, result};
-
-/// This is a `Log` implementation that stores entries in a simple in-memory vector. Other data
-/// is stored in a struct. It is chiefly intended for testing.
+use std::sync::Arc;
+use util::kvdb::{Database, DatabaseConfig, DBTransaction, KeyValueDB};
+
+const TERM_KEY: &[u8] = b"term";
+const VOTED_FOR_KEY: &[u8] = b"voted_for";
+const LATEST_INDEX_KEY: &[u8] = b"latest_index";
+// entries are keyed by this prefix and their big endian index
+const ENTRY_PREFIX: u8 = b'e';
+
+/// This is a `Log` implementation that stores the term, the vote and the entries in RocksDB,
+/// so a restarted node carries on where it stopped. A change is synced to disk before it
+/// returns, it outlives a crash of the machine as well as of the process.
+///
+/// The entries are kept in memory as well, `entry` hands out borrowed slices of them.
 ///
 /// # Panic
 ///
 /// No bounds checking is performed and attempted access to non-existing log
 /// indexes will panic.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Store {
+    db: Arc<KeyValueDB>,
     current_term: Term,
     voted_for: Option<ServerId>,
     entries: Vec<(Term, Vec<u8>)>,
 }
 
-/// Non-instantiable error type for MemLog
-pub enum Error { }
+/// A failed read or write of the database
+#[derive(Debug)]
+pub struct Error(String);
 
 impl fmt::Display for Error {
-    fn fmt(&self, _fmt: &mut fmt::Formatter) -> fmt::Result {
-        unreachable!()
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "raft log: {}", self.0)
     }
 }
 
-impl fmt::Debug for Error {
-    fn fmt(&self, _fmt: &mut fmt::Formatter) -> fmt::Result {
-        unreachable!()
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        &self.0
     }
 }
 
-impl error::Error for Error {
-    fn description(&self) -> &str {
-        unreachable!()
+impl From<String> for Error {
+    fn from(err: String) -> Error {
+        Error(err)
+    }
+}
+
+fn encode(value: u64) -> [u8; 8] {
+    let mut bytes = [0u8; 8];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (value >> (8 * (7 - i))) as u8;
+    }
+    bytes
+}
+
+fn decode(bytes: &[u8]) -> u64 {
+    bytes[..8].iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64)
+}
+
+fn entry_key(index: u64) -> Vec<u8> {
+    let mut key = vec![ENTRY_PREFIX];
+    key.extend_from_slice(&encode(index));
+    key
+}
+
+fn read(db: &KeyValueDB, key: &[u8]) -> result::Result<Option<u64>, Error> {
+    match db.get(None, key)? {
+        Some(ref value) if value.len() == 8 => Ok(Some(decode(value))),
+        Some(_) => Err(Error(format!("corrupt value of {}", String::from_utf8_lossy(key)))),
+        None => Ok(None),
     }
 }
 
 impl Store {
-    pub fn new() -> Store {
-        Store {
-            current_term: Term::from(0),
-            voted_for: None,
-            entries: Vec::new(),
+    pub fn open(path: &str) -> Store {
+        let mut config = DatabaseConfig::default();
+        // a vote or an entry lost after it was answered breaks raft's safety
+        config.sync = true;
+        let db = Database::open(&config, path).unwrap();
+        Store::load(Arc::new(db)).unwrap()
+    }
+
+    fn load(db: Arc<KeyValueDB>) -> result::Result<Store, Error> {
+        let current_term = read(&*db, TERM_KEY)?.unwrap_or(0);
+        let voted_for = read(&*db, VOTED_FOR_KEY)?.map(ServerId::from);
+        let latest_index = read(&*db, LATEST_INDEX_KEY)?.unwrap_or(0);
+        let mut entries = Vec::with_capacity(latest_index as usize);
+        for index in 1..latest_index + 1 {
+            match db.get(None, &entry_key(index))? {
+                Some(ref value) if value.len() >= 8 => entries.push((Term::from(decode(value)), value[8..].to_vec())),
+                _ => return Err(Error(format!("entry {} is missing", index))),
+            }
         }
+        info!("raft log loaded, term: {}, entries: {}", current_term, entries.len());
+        Ok(Store {
+               db: db,
+               current_term: Term::from(current_term),
+               voted_for: voted_for,
+               entries: entries,
+           })
+    }
+
+    fn write(&self, batch: DBTransaction) -> result::Result<(), Error> {
+        self.db.write(batch).map_err(Error)
+    }
+}
+
+impl fmt::Debug for Store {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "Store {{ current_term: {}, voted_for: {:?}, entries: {} }}", self.current_term, self.voted_for, self.entries.len())
     }
 }
 
@@ -73,13 +142,17 @@ impl Log for Store {
     }
 
     fn set_current_term(&mut self, term: Term) -> result::Result<(), Error> {
+        let mut batch = DBTransaction::new();
+        batch.put(None, TERM_KEY, &encode(term.as_u64()));
+        batch.delete(None, VOTED_FOR_KEY);
+        self.write(batch)?;
         self.voted_for = None;
         Ok(self.current_term = term)
     }
 
     fn inc_current_term(&mut self) -> result::Result<Term, Error> {
-        self.voted_for = None;
-        self.current_term = self.current_term + 1;
+        let term = self.current_term + 1;
+        self.set_current_term(term)?;
         self.current_term()
     }
 
@@ -88,6 +161,9 @@ impl Log for Store {
     }
 
     fn set_voted_for(&mut self, address: ServerId) -> result::Result<(), Error> {
+        let mut batch = DBTransaction::new();
+        batch.put(None, VOTED_FOR_KEY, &encode(address.into()));
+        self.write(batch)?;
         Ok(self.voted_for = Some(address))
     }
 
@@ -107,7 +183,73 @@ impl Log for Store {
 
     fn append_entries(&mut self, from: LogIndex, entries: &[(Term, &[u8])]) -> result::Result<(), Error> {
         assert!(self.latest_log_index().unwrap() + 1 >= from);
-        self.entries.truncate((from - 1).as_u64() as usize);
+        let from = from.as_u64();
+        let mut batch = DBTransaction::new();
+        for index in from..self.entries.len() as u64 + 1 {
+            batch.delete(None, &entry_key(index));
+        }
+        for (offset, &(term, command)) in entries.iter().enumerate() {
+            let mut value = encode(term.as_u64()).to_vec();
+            value.extend_from_slice(command);
+            batch.put_vec(None, &entry_key(from + offset as u64), value);
+        }
+        batch.put(None, LATEST_INDEX_KEY, &encode(from - 1 + entries.len() as u64));
+        self.write(batch)?;
+
+        self.entries.truncate((from - 1) as usize);
         Ok(self.entries.extend(entries.iter().map(|&(term, command)| (term, command.to_vec()))))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::kvdb::in_memory;
+
+    fn reopen(db: &Arc<KeyValueDB>) -> Store {
+        Store::load(db.clone()).unwrap()
+    }
+
+    #[test]
+    fn term_and_vote_survive_reopen() {
+        let db: Arc<KeyValueDB> = Arc::new(in_memory(0));
+        {
+            let mut store = reopen(&db);
+            assert_eq!(Term::from(0), store.current_term().unwrap());
+            store.set_current_term(Term::from(42)).unwrap();
+            store.set_voted_for(ServerId::from(3)).unwrap();
+        }
+        let mut store = reopen(&db);
+        assert_eq!(Term::from(42), store.current_term().unwrap());
+        assert_eq!(Some(ServerId::from(3)), store.voted_for().unwrap());
+
+        store.inc_current_term().unwrap();
+        let store = reopen(&db);
+        assert_eq!(Term::from(43), store.current_term().unwrap());
+        assert_eq!(None, store.voted_for().unwrap());
+    }
+
+    #[test]
+    fn entries_survive_reopen() {
+        let db: Arc<KeyValueDB> = Arc::new(in_memory(0));
+        {
+            let mut store = reopen(&db);
+            // [0.1, 0.2, 0.3, 1.4]
+            store.append_entries(LogIndex::from(1), &[(Term::from(0), &[1]), (Term::from(0), &[2]), (Term::from(0), &[3]), (Term::from(1), &[4])]).unwrap();
+            // [0.1, 0.2, 2.3]
+            store.append_entries(LogIndex::from(3), &[(Term::from(2), &[3])]).unwrap();
+        }
+        let mut store = reopen(&db);
+        assert_eq!(LogIndex::from(3), store.latest_log_index().unwrap());
+        assert_eq!(Term::from(2), store.latest_log_term().unwrap());
+        assert_eq!((Term::from(0), &*vec![2u8]), store.entry(LogIndex::from(2)).unwrap());
+        assert_eq!((Term::from(2), &*vec![3u8]), store.entry(LogIndex::from(3)).unwrap());
+        assert!(db.get(None, &entry_key(4)).unwrap().is_none());
+
+        // [0.1]
+        store.append_entries(LogIndex::from(2), &[]).unwrap();
+        let store = reopen(&db);
+        assert_eq!(LogIndex::from(1), store.latest_log_index().unwrap());
+        assert_eq!((Term::from(0), &*vec![1u8]), store.entry(LogIndex::from(1)).unwrap());
+    }
+}
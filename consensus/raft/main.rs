@@ -34,32 +34,50 @@ extern crate pubsub;
 extern crate dotenv;
 extern crate logger;
 extern crate util;
+extern crate cita_crypto as crypto;
+extern crate engine;
+extern crate engine_json;
+extern crate proof;
+extern crate protobuf;
+extern crate bincode;
 
 mod raft_server;
 mod machine;
 mod log_store;
 mod dispatch;
+mod raft_engine;
 
 use docopt::Docopt;
+use engine::Engine;
+use engine_json::{Engine as EngineJson, RaftParams, Spec};
 use libproto::{parse_msg, MsgClass, key_to_id};
-use pubsub::start_pubsub;
+use libproto::router::{self, AuthBlockTxs, ChainRichStatus, ConsensusDefault, NetMsg, Route};
+use libraft::{Command, NotifyMessage};
+use pubsub::{start_pubsub, sub_channel};
+use raft_engine::RaftEngine;
 use raft_server::*;
-use std::sync::mpsc::{channel, Receiver};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
+use std::time::Instant;
+use util::H256;
 use util::panichandler::set_panic_handler;
 
 
 // Using docopt we define the overall usage of the application.
 static USAGE: &'static str = "
-A replicated mutable hashmap. Operations on the register have serializable
-consistency, but no durability (once all register servers are terminated the
-map is lost).
-
-Each register server holds a replica of the map, and coordinates with its
-peers to update the maps values according to client commands. The register
-is available for reading and writing only if a majority of register servers are
-available.
+The Raft consensus of CITA. The leader makes the blocks and appends them to
+the raft log. Every server signs the block entries it finds committed, and
+hands a block to chain once a majority of the servers signed it. The term, the
+vote and the log are kept in RocksDB, so a restarted server carries on where
+it stopped.
 
+The servers elect a leader and make blocks while a majority of them is
+available. The members of the cluster follow the nodes chain reports, one
+server joining or leaving at a time, so every server that may become a member
+has to be on the command line.
 
 Commands:
 
@@ -68,11 +86,12 @@ Commands:
           peer servers.
 
 Usage:
-  raft server <id> (<node-address>)...
+  raft server [--config=<file>] <id> (<node-address>)...
   raft (-h | --help)
 
 Options:
-  -h --help   Show a help message.
+  -c --config=<file>  The consensus spec with the Raft engine params [default: consensus.json].
+  -h --help           Show a help message.
 ";
 
 fn main() {
@@ -84,27 +103,72 @@ fn main() {
     logger::init();
     let args: Args = Docopt::new(USAGE).and_then(|d| d.decode()).unwrap_or_else(|e| e.exit());
     info!("CITA:raft");
-    let (tx_sub, rx_sub) = channel();
+    let params = raft_params(&args.flag_config);
+    let book = AddressBook::new(&args, params.nodes.clone().unwrap_or_else(|| params.authorities.clone()));
+    let authorities = params.authorities.clone();
+    let engine = Arc::new(RaftEngine::new(params.into()));
+    let (tx_sub, rx_sub) = sub_channel();
     let (tx_pub, rx_pub) = channel();
     let (tx, rx) = channel();
-    start_pubsub("consensus_cmd", vec!["chain.richstatus", "consensus.default"], tx_sub, rx_pub);
+    let keys = router::subscription(vec![ChainRichStatus::key(), AuthBlockTxs::key(), ConsensusDefault::key(), NetMsg::key()]);
+    start_pubsub("consensus_cmd", keys, tx_sub, rx_pub);
     thread::spawn(move || loop {
                       let (key, body) = rx_sub.recv().unwrap();
                       let (cmd_id, _, content) = parse_msg(body.as_slice());
                       tx.send((key_to_id(&key), cmd_id, content)).unwrap();
                   });
 
-    let (mut server, mut event_loop) = server(&args);
+    let (mut server, mut event_loop) = server(&args, &book, &authorities);
     let actions = server.consensus.init();
     server.execute_actions(&mut event_loop, actions);
-    server.set_pub(tx_pub);
+    let (tx_spawn, rx_spawn) = channel();
+    server.set_spawner(tx_spawn);
+    spawn_handler(engine.clone(), rx_spawn, tx_pub.clone());
     let eventloop_notifix = event_loop.channel();
-    thread_handler(rx, eventloop_notifix);
+    engine.set_proposer(eventloop_notifix.clone());
+    let members = book.members(&authorities);
+    if !members.contains_key(&args.id()) {
+        // a server that joins later stays out of elections until chain lists its validator
+        eventloop_notifix.send(NotifyMessage::Members(members));
+    }
+    thread_handler(engine, book, tx_pub, rx, eventloop_notifix);
     event_loop.run(&mut server);
 }
 
-fn thread_handler(rx: Receiver<(u32, u32, MsgClass)>, notifix: mio::Sender<libraft::NotifyMessage>) {
+fn raft_params(path: &str) -> RaftParams {
+    let config_file = File::open(path).unwrap();
+    let spec = Spec::load(BufReader::new(config_file)).expect("spec is invalid.");
+    match spec.engine {
+        EngineJson::Raft(raft) => raft.params,
+        _ => panic!("{} is not a Raft spec.", path),
+    }
+}
+
+fn thread_handler(engine: Arc<RaftEngine>, book: AddressBook, tx_pub: Sender<(String, Vec<u8>)>, rx: Receiver<(u32, u32, MsgClass)>, notifix: mio::Sender<NotifyMessage>) {
     thread::spawn(move || loop {
-                      dispatch::dispatch(&notifix, &rx);
+                      dispatch::dispatch(&engine, &book, &tx_pub, &notifix, &rx);
                   });
 }
+
+// makes the blocks the leader asks for, at most one per block duration, and acknowledges the
+// committed ones
+fn spawn_handler(engine: Arc<RaftEngine>, rx: Receiver<Command>, tx_pub: Sender<(String, Vec<u8>)>) {
+    thread::spawn(move || {
+        let mut last = Instant::now();
+        loop {
+            match rx.recv().unwrap() {
+                Command::SpawnBlk(height, hash, term) => {
+                    if let Some(wait) = engine.duration().checked_sub(last.elapsed()) {
+                        thread::sleep(wait);
+                    }
+                    last = Instant::now();
+                    engine.set_term(term);
+                    engine.set_new_status(height as usize, H256::from_slice(&hash));
+                    engine.new_block(tx_pub.clone());
+                }
+                Command::Committed(term, index, block) => engine.receive_committed(term, index, &block, tx_pub.clone()),
+                Command::PoolSituation(..) => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,348 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use bincode::{serialize, deserialize, Infinite};
+use crypto::{Signature, Signer};
+use engine::{Engine, EngineError, unix_now, AsMillis};
+use engine_json;
+use libproto::Request;
+use libproto::blockchain::{Block, BlockTxs, RichStatus};
+use libproto::router::{ConsensusBlock, ConsensusMsg, Route};
+use libraft::NotifyMessage;
+use mio;
+use proof::RaftProof;
+use protobuf::Message;
+use protobuf::core::parse_from_bytes;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use util::{Address, H256, Mutex, RwLock};
+
+const INIT_HEIGHT: usize = 1;
+
+pub struct RaftParams {
+    pub duration: Duration,
+    /// Valid authorities, until chain reports its nodes
+    pub authorities: Vec<Address>,
+    pub signer: Signer,
+}
+
+impl From<engine_json::RaftParams> for RaftParams {
+    fn from(p: engine_json::RaftParams) -> Self {
+        RaftParams {
+            duration: Duration::from_millis(p.duration),
+            authorities: p.authorities,
+            signer: Signer::from(p.signer),
+        }
+    }
+}
+
+/// A server acknowledging the committed block entry at `index` of `term`, sent to every node
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Ack {
+    pub height: usize,
+    pub term: u64,
+    pub index: u64,
+    pub proposal: H256,
+    pub sender: Address,
+    pub signature: Signature,
+}
+
+/// A block entry raft committed which chain doesn't have yet
+struct Committed {
+    term: u64,
+    index: u64,
+    proposal: H256,
+    block: Block,
+    acknowledged: bool,
+}
+
+/// The leader makes the block raft asked for out of the transactions auth packed, and raft appends
+/// it to the log. Every node acknowledges the block entries raft commits, and hands a block to
+/// chain once a majority of the authorities acknowledged it, with their signatures as its
+/// `RaftProof`.
+pub struct RaftEngine {
+    params: RaftParams,
+    authorities: RwLock<Vec<Address>>,
+    height: AtomicUsize,
+    pre_hash: RwLock<Option<H256>>,
+    term: AtomicUsize,
+    // transactions by the chain height they were packed on
+    block_txs: RwLock<BTreeMap<usize, BlockTxs>>,
+    // height of the last block made
+    made: Mutex<usize>,
+    // where the blocks the leader makes go to raft
+    proposer: Mutex<Option<mio::Sender<NotifyMessage>>>,
+    // height of chain, unknown until its first status
+    chain_height: RwLock<Option<usize>>,
+    committed: RwLock<BTreeMap<usize, Committed>>,
+    // acknowledgements by height and sender
+    acks: RwLock<BTreeMap<usize, HashMap<Address, Ack>>>,
+}
+
+impl RaftEngine {
+    pub fn new(params: RaftParams) -> RaftEngine {
+        RaftEngine {
+            authorities: RwLock::new(params.authorities.clone()),
+            params: params,
+            height: AtomicUsize::new(0),
+            pre_hash: RwLock::new(None),
+            term: AtomicUsize::new(0),
+            block_txs: RwLock::new(BTreeMap::new()),
+            made: Mutex::new(0),
+            proposer: Mutex::new(None),
+            chain_height: RwLock::new(None),
+            committed: RwLock::new(BTreeMap::new()),
+            acks: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn set_proposer(&self, proposer: mio::Sender<NotifyMessage>) {
+        *self.proposer.lock() = Some(proposer);
+    }
+
+    /// Term of the leadership the next block is made in
+    pub fn set_term(&self, term: u64) {
+        self.term.store(term as usize, Ordering::SeqCst);
+    }
+
+    /// Keep the transactions for the block after `block_txs.height`, and make
+    /// that block if the leader already asked for it
+    pub fn receive_block_txs(&self, block_txs: BlockTxs, tx_pub: Sender<(String, Vec<u8>)>) {
+        let height = block_txs.get_height() as usize;
+        self.block_txs.write().insert(height, block_txs);
+        if height + 1 == self.height.load(Ordering::SeqCst) {
+            self.new_block(tx_pub);
+        }
+    }
+
+    /// The block at `height` without a proof, which raft appends to its log
+    pub fn generate_block(&self, height: usize) -> Option<Block> {
+        let pre_hash = match *self.pre_hash.read() {
+            Some(pre_hash) => pre_hash,
+            None => return None,
+        };
+        let mut block = Block::new();
+        match self.block_txs.read().get(&(height - 1)) {
+            Some(block_txs) => block.set_body(block_txs.get_body().clone()),
+            None if height > INIT_HEIGHT => return None,
+            None => {}
+        }
+        let transactions_root = block.get_body().transactions_root();
+        block.mut_header().set_height(height as u64);
+        block.mut_header().set_prevhash(pre_hash.to_vec());
+        block.mut_header().set_timestamp(unix_now().as_millis());
+        block.mut_header().set_transactions_root(transactions_root.to_vec());
+        Some(block)
+    }
+
+    pub fn pub_block(&self, block: &Block, tx_pub: Sender<(String, Vec<u8>)>) {
+        trace!("publish block {:?}", block.crypt_hash());
+        ConsensusBlock::publish(&tx_pub, block);
+    }
+
+    /// Keep the block of an entry raft committed, and acknowledge it
+    pub fn receive_committed(&self, term: u64, index: u64, block: &[u8], tx_pub: Sender<(String, Vec<u8>)>) {
+        let block: Block = match parse_from_bytes(block) {
+            Ok(block) => block,
+            Err(err) => {
+                warn!("entry {} of term {} is not a block: {:?}", index, term, err);
+                return;
+            }
+        };
+        let height = block.get_header().get_height() as usize;
+        if self.chain_height.read().map_or(false, |chain_height| height <= chain_height) {
+            return;
+        }
+        let proposal = proposal_hash(&block);
+        let committed = Committed {
+            term: term,
+            index: index,
+            proposal: proposal,
+            block: block,
+            acknowledged: false,
+        };
+        self.committed.write().insert(height, committed);
+        self.acknowledge(tx_pub);
+    }
+
+    /// Sign and send the acknowledgements of the committed blocks, once chain's height is known
+    pub fn acknowledge(&self, tx_pub: Sender<(String, Vec<u8>)>) {
+        if self.chain_height.read().is_none() {
+            return;
+        }
+        let signer = &self.params.signer;
+        let mut acks = Vec::new();
+        for (&height, committed) in self.committed.write().iter_mut().filter(|&(_, ref committed)| !committed.acknowledged) {
+            committed.acknowledged = true;
+            acks.push(Ack {
+                          height: height,
+                          term: committed.term,
+                          index: committed.index,
+                          proposal: committed.proposal,
+                          sender: signer.address.clone().into(),
+                          signature: RaftProof::sign(height, committed.term, committed.index, &committed.proposal, signer),
+                      });
+        }
+        for ack in acks {
+            trace!("acknowledge block {} of entry {} in term {}", ack.height, ack.index, ack.term);
+            ConsensusMsg::publish(&tx_pub, &serialize(&ack, Infinite).unwrap());
+            self.receive_ack(ack, tx_pub.clone());
+        }
+    }
+
+    fn receive_ack(&self, ack: Ack, tx_pub: Sender<(String, Vec<u8>)>) {
+        let height = ack.height;
+        if self.chain_height.read().map_or(false, |chain_height| height <= chain_height) {
+            return;
+        }
+        self.acks.write().entry(height).or_insert_with(HashMap::new).insert(ack.sender, ack);
+        self.publish(height, tx_pub);
+    }
+
+    /// Hand the committed block at `height` to chain if a majority acknowledged its entry
+    fn publish(&self, height: usize, tx_pub: Sender<(String, Vec<u8>)>) {
+        let mut committed = self.committed.write();
+        let proof = match committed.get(&height) {
+            Some(entry) => {
+                let commits: HashMap<Address, Signature> = self.acks
+                                                               .read()
+                                                               .get(&height)
+                                                               .into_iter()
+                                                               .flat_map(|acks| acks.values())
+                                                               .filter(|ack| ack.term == entry.term && ack.index == entry.index && ack.proposal == entry.proposal)
+                                                               .map(|ack| (ack.sender, ack.signature.clone()))
+                                                               .collect();
+                if commits.len() * 2 > self.authorities.read().len() {
+                    Some(RaftProof::new(height, entry.term, entry.index, entry.proposal, commits))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+        if let Some(proof) = proof {
+            let mut block = committed.remove(&height).unwrap().block;
+            info!("block {} is acknowledged by {} servers", height, proof.commits.len());
+            block.mut_header().set_proof(proof.into());
+            self.pub_block(&block, tx_pub);
+        }
+    }
+}
+
+/// The hash the servers acknowledge a block by, see `RaftProof::proposal_hash`
+fn proposal_hash(block: &Block) -> H256 {
+    let header = block.get_header();
+    RaftProof::proposal_hash(header.get_height() as usize, &H256::from_slice(header.get_prevhash()), header.get_timestamp(), &H256::from_slice(header.get_transactions_root()))
+}
+
+impl Engine for RaftEngine {
+    fn name(&self) -> &str {
+        "Raft"
+    }
+
+    fn duration(&self) -> Duration {
+        self.params.duration
+    }
+
+    fn verify_block(&self, block: &Block) -> Result<(), EngineError> {
+        let header = block.get_header();
+        let proof = RaftProof::decode(header.get_proof()).ok_or(EngineError::InvalidProof)?;
+        if !proof.check(header.get_height() as usize, &proposal_hash(block), &self.authorities.read()) {
+            return Err(EngineError::InvalidProof);
+        }
+        if header.get_timestamp() > unix_now().as_millis() {
+            return Err(EngineError::FutureBlock(header.get_timestamp()));
+        }
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    fn receive_new_transaction(&self, tx_req: &Request, tx_pub: Sender<(String, Vec<u8>)>, _origin: u32, from_broadcast: bool) {
+        trace!("raft takes the transactions auth packs, not single ones");
+    }
+
+    #[allow(unused_variables)]
+    fn receive_new_block(&self, block: &Block, tx_pub: Sender<(String, Vec<u8>)>) {
+        trace!("raft hands blocks to chain, it doesn't take them");
+    }
+
+    fn receive_new_status(&self, status: RichStatus) {
+        let authorities: Vec<Address> = status.get_nodes().iter().map(|node| Address::from_slice(node)).collect();
+        if !authorities.is_empty() {
+            *self.authorities.write() = authorities;
+        }
+        let height = status.get_height() as usize;
+        *self.chain_height.write() = Some(height);
+        let mut block_txs = self.block_txs.write();
+        *block_txs = block_txs.split_off(&height);
+        let mut committed = self.committed.write();
+        *committed = committed.split_off(&(height + 1));
+        let mut acks = self.acks.write();
+        *acks = acks.split_off(&(height + 1));
+    }
+
+    // call when the leader is asked for the next block
+    fn new_block(&self, tx_pub: Sender<(String, Vec<u8>)>) {
+        let mut made = self.made.lock();
+        let height = self.height.load(Ordering::SeqCst);
+        if *made >= height {
+            return;
+        }
+        match self.generate_block(height) {
+            Some(block) => {
+                *made = height;
+                let term = self.term.load(Ordering::SeqCst) as u64;
+                info!("leader made block {} in term {}", height, term);
+                if let Some(ref proposer) = *self.proposer.lock() {
+                    proposer.send(NotifyMessage::Block(term, height as u64, block.write_to_bytes().unwrap())).unwrap();
+                }
+            }
+            None => trace!("block {} waits for its transactions", height),
+        }
+    }
+
+    fn set_new_status(&self, height: usize, pre_hash: H256) {
+        *self.pre_hash.write() = Some(pre_hash);
+        self.height.store(height, Ordering::SeqCst);
+    }
+
+    #[allow(unused_variables)]
+    fn new_messages(&self, tx_pub: Sender<(String, Vec<u8>)>) {
+        trace!("raft sends its acknowledgements as blocks are committed");
+    }
+
+    /// An acknowledgement of another server
+    fn handle_message(&self, message: Vec<u8>, tx_pub: Sender<(String, Vec<u8>)>) -> Result<(), EngineError> {
+        let ack: Ack = deserialize(&message).map_err(|_| EngineError::UnexpectedMessage)?;
+        if !self.authorities.read().contains(&ack.sender) {
+            return Err(EngineError::NotAuthorized(ack.sender));
+        }
+        if !RaftProof::verify(ack.height, ack.term, ack.index, &ack.proposal, &ack.sender, &ack.signature) {
+            return Err(EngineError::InvalidSignature);
+        }
+        self.receive_ack(ack, tx_pub);
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    fn handle_proposal(&self, _message: Vec<u8>, tx_pub: Sender<(String, Vec<u8>)>) -> Result<(), EngineError> {
+        warn!("raft has no proposals, the blocks go through its log");
+        Err(EngineError::UnexpectedMessage)
+    }
+}
@@ -26,6 +26,8 @@ use machine::*;
 use mio::EventLoop;
 use std::collections::HashMap;
 use std::net::{SocketAddr, ToSocketAddrs};
+use util::Address;
+use util::datapath::DataPath;
 
 #[derive(Debug, RustcDecodable)]
 pub struct Args {
@@ -36,6 +38,14 @@ pub struct Args {
     // find it best to use a `toml` or `yaml` or `json` file.
     arg_id: Option<u64>,
     arg_node_address: Vec<String>,
+    pub flag_config: String,
+}
+
+impl Args {
+    /// The `ServerId` of the `<id>`-th server on the command line
+    pub fn id(&self) -> ServerId {
+        ServerId::from(self.arg_id.unwrap() + 1)
+    }
 }
 
 /// A simple convenience method since this is an example and it should exit if given invalid params.
@@ -47,33 +57,63 @@ fn parse_addr(addr: &str) -> SocketAddr {
         .unwrap()
 }
 
-/// Creates a Raft server using the specified ID from the list of nodes.
-pub fn server(args: &Args) -> (Server<Store, HashmapStateMachine>, EventLoop<Server<Store, HashmapStateMachine>>) {
+/// The validator each raft server on the command line runs. `ServerId` n is the n-th server.
+pub struct AddressBook {
+    servers: Vec<(Address, ServerId, SocketAddr)>,
+}
+
+impl AddressBook {
+    pub fn new(args: &Args, validators: Vec<Address>) -> AddressBook {
+        if validators.len() != args.arg_node_address.len() {
+            warn!("{} validators for {} raft servers", validators.len(), args.arg_node_address.len());
+        }
+        let servers = validators.into_iter()
+                                .zip(args.arg_node_address.iter())
+                                .enumerate()
+                                .map(|(i, (validator, addr))| (validator, ServerId::from(i as u64 + 1), parse_addr(addr)))
+                                .collect();
+        AddressBook { servers: servers }
+    }
+
+    /// The servers of `validators`, those without a server are left out.
+    pub fn members(&self, validators: &[Address]) -> HashMap<ServerId, SocketAddr> {
+        for validator in validators.iter().filter(|validator| !self.servers.iter().any(|server| server.0 == **validator)) {
+            warn!("validator {:?} has no raft server", validator);
+        }
+        self.servers
+            .iter()
+            .filter(|server| validators.contains(&server.0))
+            .map(|&(_, id, addr)| (id, addr))
+            .collect()
+    }
+
+    pub fn addr(&self, id: ServerId) -> SocketAddr {
+        self.servers.iter().find(|server| server.1 == id).expect("no raft server of this id").2
+    }
+}
+
+/// Creates a Raft server using the specified ID, the servers of `validators` are its peers until
+/// its log holds the members of the cluster.
+pub fn server(args: &Args, book: &AddressBook, validators: &[Address]) -> (Server<Store, HashmapStateMachine>, EventLoop<Server<Store, HashmapStateMachine>>) {
     // Creating a raft server requires several things:
 
     // A persistent log implementation, which manages the persistent, replicated log...
-    let persistent_log = Store::new();
+    let persistent_log = Store::open(&DataPath::raft_log_path());
 
     // A state machine which replicates state. This state should be the same on all nodes.
     let state_machine = HashmapStateMachine::new();
 
     // As well as a unique server id.
-    let id = ServerId::from(args.arg_id.unwrap() + 1);
+    let id = args.id();
     println!("id:{:?}", id);
-    let mut node_id: Vec<u64> = vec![];
-    for i in 0..args.arg_node_address.len() {
-        node_id.push(i as u64 + 1);
-    }
     // ...  And a list of peers.
-    let mut peers = node_id.iter()
-                           .zip(args.arg_node_address.iter())
-                           .map(|(&id, addr)| (ServerId::from(id), parse_addr(&addr)))
-                           .collect::<HashMap<_, _>>();
+    let mut peers = book.members(validators);
     println!("peers:{:?}", peers);
 
     // The Raft Server will return an error if its ID is inside of its peer set. Don't do that.
     // Instead, take it out and use it!
-    let addr = peers.remove(&id).unwrap();
+    peers.remove(&id);
+    let addr = book.addr(id);
 
     println!("addr:{:?}", addr);
     // Using all of the above components.
@@ -0,0 +1,135 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use proof::Evidence;
+use std::io;
+use std::path::Path;
+use util::wal::{Log, SyncPolicy};
+
+const LOG_TYPE_EVIDENCE: u8 = 1;
+const LOG_TYPE_INCLUDED: u8 = 2;
+/// Heights evidence waits for a block before it is dropped
+pub const EVIDENCE_MAX_AGE: usize = 100;
+
+/// Evidence of equivocations this node saw, waiting for a block to carry it.
+/// It is logged so that a restart neither loses it nor proposes it again.
+pub struct EvidencePool {
+    log: Log,
+    // height of the equivocation, the evidence bytes
+    pending: Vec<(usize, Vec<u8>)>,
+}
+
+impl EvidencePool {
+    pub fn open(path: &Path) -> io::Result<EvidencePool> {
+        let mut log = Log::open(path, SyncPolicy::Always)?;
+        let records = log.load()?;
+        let mut pool = EvidencePool {
+            log: log,
+            pending: Vec::new(),
+        };
+        for (mtype, payload) in records {
+            match mtype {
+                LOG_TYPE_EVIDENCE => {
+                    pool.insert(payload);
+                }
+                LOG_TYPE_INCLUDED => pool.pending.retain(|&(_, ref evidence)| *evidence != payload),
+                _ => warn!("unknown evidence record type {}", mtype),
+            }
+        }
+        Ok(pool)
+    }
+
+    fn insert(&mut self, evidence: Vec<u8>) -> bool {
+        let height = match Evidence::from_bytes(&evidence).and_then(|evidence| evidence.check()) {
+            Some(equivocation) => equivocation.height,
+            None => return false,
+        };
+        if self.pending.iter().any(|&(_, ref known)| *known == evidence) {
+            return false;
+        }
+        self.pending.push((height, evidence));
+        true
+    }
+
+    /// Keep valid evidence not seen before, whether it was new
+    pub fn add(&mut self, evidence: &Evidence) -> bool {
+        let bytes = evidence.to_bytes();
+        if !self.insert(bytes.clone()) {
+            return false;
+        }
+        if let Err(err) = self.log.append(LOG_TYPE_EVIDENCE, &bytes) {
+            warn!("can't log evidence: {}", err);
+        }
+        true
+    }
+
+    /// Evidence to put in the block of `height`, older evidence is dropped
+    pub fn pending(&mut self, height: usize) -> Vec<Vec<u8>> {
+        self.pending.retain(|&(h, _)| h + EVIDENCE_MAX_AGE > height);
+        self.pending.iter().map(|&(_, ref evidence)| evidence.clone()).collect()
+    }
+
+    /// Forget evidence a committed block carries
+    pub fn included(&mut self, evidences: &[Vec<u8>]) {
+        for evidence in evidences {
+            let len = self.pending.len();
+            self.pending.retain(|&(_, ref known)| known != evidence);
+            if self.pending.len() < len {
+                if let Err(err) = self.log.append(LOG_TYPE_INCLUDED, evidence) {
+                    warn!("can't log included evidence: {}", err);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate mktemp;
+    use super::*;
+    use bincode::{serialize, Infinite};
+    use core::Step;
+    use crypto::{KeyPair, CreateKey, Signature, Sign};
+    use util::{H256, Hashable};
+
+    fn vote(keypair: &KeyPair, h: usize, hash: u64) -> Vec<u8> {
+        let message = serialize(&(h, 0usize, Step::Prevote, keypair.address(), Some(H256::from(hash))), Infinite).unwrap();
+        let signature = Signature::sign(keypair.privkey(), &message.crypt_hash().into()).unwrap();
+        serialize(&(message, signature), Infinite).unwrap()
+    }
+
+    #[test]
+    fn pool() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let path = dir.as_ref().join("evidence.log");
+        let keypair = KeyPair::gen_keypair();
+        let first = Evidence::DuplicateVote(vote(&keypair, 3, 1), vote(&keypair, 3, 2));
+        let second = Evidence::DuplicateVote(vote(&keypair, 4, 1), vote(&keypair, 4, 2));
+        {
+            let mut pool = EvidencePool::open(&path).unwrap();
+            assert!(pool.add(&first));
+            assert!(!pool.add(&first));
+            assert!(!pool.add(&Evidence::DuplicateVote(vote(&keypair, 3, 1), vote(&keypair, 3, 1))));
+            assert!(pool.add(&second));
+            pool.included(&[first.to_bytes()]);
+        }
+
+        let mut pool = EvidencePool::open(&path).unwrap();
+        assert_eq!(pool.pending(5), vec![second.to_bytes()]);
+        assert!(pool.pending(4 + EVIDENCE_MAX_AGE).is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use libproto::Origin;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Counts the consensus messages each peer sent in the current window.
+/// A round takes a few from every authority, a peer far over that is flooding.
+/// Peers are told apart by origin, which network only forwards when it is
+/// bound to the address the sender proved in its handshake.
+pub struct FloodFilter {
+    window: Duration,
+    counts: HashMap<Origin, (Instant, usize)>,
+}
+
+impl FloodFilter {
+    pub fn new(window: Duration) -> Self {
+        FloodFilter {
+            window: window,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Count a message, returns how many the origin sent in this window so far.
+    pub fn count(&mut self, origin: Origin, now: Instant) -> usize {
+        let window = self.window;
+        let entry = self.counts.entry(origin).or_insert((now, 0));
+        if now.duration_since(entry.0) >= window {
+            *entry = (now, 0);
+        }
+        entry.1 += 1;
+        entry.1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FloodFilter;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn count_by_window() {
+        let mut filter = FloodFilter::new(Duration::from_secs(1));
+        let now = Instant::now();
+        assert_eq!(filter.count(1, now), 1);
+        assert_eq!(filter.count(1, now + Duration::from_millis(500)), 2);
+        assert_eq!(filter.count(2, now + Duration::from_millis(500)), 1);
+        assert_eq!(filter.count(1, now + Duration::from_secs(1)), 1);
+    }
+}
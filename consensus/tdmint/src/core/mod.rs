@@ -16,6 +16,8 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod wal;
+pub mod evidence;
+pub mod flood;
 pub mod votetime;
 pub mod tendermint;
 pub mod params;
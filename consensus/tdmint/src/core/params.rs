@@ -47,6 +47,8 @@ pub struct TendermintParams {
     /// Valid authorities
     pub authorities: Vec<Address>,
     pub authority_n: usize,
+    /// Put the evidence of equivocations in proposed blocks
+    pub include_evidence: bool,
     pub signer: Signer,
 }
 
@@ -61,6 +63,7 @@ impl From<engine_json::TendermintParams> for TendermintParams {
             duration: Duration::from_millis(p.duration.into()),
             is_test: p.is_test,
             authority_n: p.authorities.len(),
+            include_evidence: p.include_evidence.unwrap_or(false),
             authorities: p.authorities.into_iter().map(Into::into).collect::<Vec<_>>(),
             signer: Signer::from(p.signer),
             timer: TendermintTimer {
@@ -18,21 +18,27 @@
 use authority_manage::AuthorityManage;
 use bincode::{serialize, deserialize, Infinite};
 
+use core::evidence::EvidencePool;
+use core::flood::FloodFilter;
 use core::params::TendermintParams;
 use core::voteset::{VoteCollector, ProposalCollector, VoteSet, Proposal, VoteMessage, verify_tx};
 
 use core::votetime::{WaitTimer, TimeoutInfo};
-use core::wal::Wal;
+use core::wal::{Wal, LOG_TYPE_PROPOSE, LOG_TYPE_VOTE, LOG_TYPE_STATE, LOG_TYPE_PREV_HASH, LOG_TYPE_COMMITS, LOG_TYPE_VERIFIED_PROPOSE,
+                LOG_TYPE_AUTH_TXS};
 
 use crypto::{CreateKey, Signature, Sign, pubkey_to_address, SIGNATURE_BYTES_LEN};
 use engine::{EngineError, Mismatch, unix_now, AsMillis};
-use libproto::{communication, submodules, topics, MsgClass, factory, auth};
+use libproto::{submodules, topics, MsgClass, auth, Offence, Origin, Verdict};
 use libproto::blockchain::{Block, BlockWithProof, BlockTxs, RichStatus};
 use libproto::consensus::{ProposeStep, SignedProposeStep};
-use proof::TendermintProof;
-use protobuf::Message;
+use libproto::router::{ChainEvidence, ConsensusEvidence, ConsensusMsg, ConsensusProofBlock, ConsensusProposal, ConsensusVerdict, ConsensusVerifyReq, Route};
+use proof::{self, TendermintProof, Evidence};
+use protobuf::{Message, RepeatedField};
 use protobuf::core::parse_from_bytes;
+use pubsub::Done;
 use std::collections::{LinkedList, HashMap};
+use std::path::Path;
 use std::sync::mpsc::{Sender, Receiver, RecvError};
 use std::time::{SystemTime, UNIX_EPOCH, Duration, Instant};
 
@@ -42,14 +48,6 @@ use util::datapath::DataPath;
 const INIT_HEIGHT: usize = 1;
 const INIT_ROUND: usize = 0;
 
-const LOG_TYPE_PROPOSE: u8 = 1;
-const LOG_TYPE_VOTE: u8 = 2;
-const LOG_TYPE_STATE: u8 = 3;
-const LOG_TYPE_PREV_HASH: u8 = 4;
-const LOG_TYPE_COMMITS: u8 = 5;
-const LOG_TYPE_VERIFIED_PROPOSE: u8 = 6;
-const LOG_TYPE_AUTH_TXS: u8 = 7;
-
 const ID_CONSENSUS_MSG: u32 = (submodules::CONSENSUS << 16) + topics::CONSENSUS_MSG as u32;
 const ID_NEW_PROPOSAL: u32 = (submodules::CONSENSUS << 16) + topics::NEW_PROPOSAL as u32;
 //const ID_NEW_STATUS: u32 = (submodules::CHAIN << 16) + topics::NEW_STATUS as u32;
@@ -57,7 +55,10 @@ const ID_NEW_PROPOSAL: u32 = (submodules::CONSENSUS << 16) + topics::NEW_PROPOSA
 const TIMEOUT_RETRANSE_MULTIPLE: u32 = 10;
 const TIMEOUT_LOW_ROUND_MESSAGE_MULTIPLE: u32 = 20;
 
-pub type TransType = (u32, u32, MsgClass);
+// consensus messages a peer may send in a second, the rest are dropped
+const FLOOD_LIMIT: usize = 300;
+
+pub type TransType = (u32, u32, Origin, MsgClass);
 pub type PubType = (String, Vec<u8>);
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Eq, Clone, Copy, Hash)]
@@ -106,6 +107,8 @@ fn get_idx_from_reqid(reqid: u64) -> u64 {
 pub struct TenderMint {
     pub_sender: Sender<PubType>,
     pub_recver: Receiver<TransType>,
+    // one for each message of pub_recver once it is processed
+    done: Done,
 
     timer_seter: Sender<TimeoutInfo>,
     timer_notity: Receiver<TimeoutInfo>,
@@ -118,6 +121,8 @@ pub struct TenderMint {
     pre_hash: Option<H256>,
     votes: VoteCollector,
     proposals: ProposalCollector,
+    // signed proposals by height and round, to catch a second one
+    proposal_msgs: HashMap<(usize, usize), Vec<u8>>,
     proposal: Option<H256>,
     lock_round: Option<usize>,
     locked_vote: Option<VoteSet>,
@@ -133,10 +138,12 @@ pub struct TenderMint {
     unverified_msg: Vec<(usize, usize)>,
     block_txs: LinkedList<(usize, BlockTxs)>,
     block_proof: Option<(usize, BlockWithProof)>,
+    flood: FloodFilter,
+    evidence: EvidencePool,
 }
 
 impl TenderMint {
-    pub fn new(s: Sender<PubType>, r: Receiver<TransType>, ts: Sender<TimeoutInfo>, rs: Receiver<TimeoutInfo>, params: TendermintParams) -> TenderMint {
+    pub fn new(s: Sender<PubType>, r: Receiver<TransType>, done: Done, ts: Sender<TimeoutInfo>, rs: Receiver<TimeoutInfo>, params: TendermintParams) -> TenderMint {
         let proof = TendermintProof::default();
         if params.is_test {
             trace!("Run for test!");
@@ -146,6 +153,7 @@ impl TenderMint {
         TenderMint {
             pub_sender: s,
             pub_recver: r,
+            done: done,
             timer_seter: ts,
             timer_notity: rs,
 
@@ -157,6 +165,7 @@ impl TenderMint {
             pre_hash: None,
             votes: VoteCollector::new(),
             proposals: ProposalCollector::new(),
+            proposal_msgs: HashMap::new(),
             proposal: None,
             lock_round: None,
             locked_vote: None,
@@ -170,20 +179,22 @@ impl TenderMint {
             unverified_msg: Vec::new(),
             block_txs: LinkedList::new(),
             block_proof: None,
+            flood: FloodFilter::new(Duration::from_secs(1)),
+            evidence: EvidencePool::open(Path::new(&DataPath::evidence_path())).unwrap(),
         }
     }
 
     fn is_round_proposer(&self, height: usize, round: usize, address: &Address) -> Result<(), EngineError> {
         //let ref p = self.params;
         let ref p = self.auth_manage;
-        if p.authority_n == 0 {
-            info!("authority_n is {}", p.authority_n);
-            return Err(EngineError::NotAuthorized(Address::zero()));
-        }
         let proposer_nonce = height + round;
-        let proposer = p.authorities
-                        .get(proposer_nonce % p.authority_n)
-                        .expect("There are authority_n authorities; taking number modulo authority_n gives number in authority_n range; qed");
+        let proposer = match proof::proposer(&p.authorities, &p.authority_weights, proposer_nonce) {
+            Some(proposer) => proposer,
+            None => {
+                info!("authority_n is {}, without voting power", p.authority_n);
+                return Err(EngineError::NotAuthorized(Address::zero()));
+            }
+        };
         if proposer == address {
             Ok(())
         } else {
@@ -195,8 +206,7 @@ impl TenderMint {
     }
 
     pub fn pub_block(&self, block: &BlockWithProof) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::NEW_PROOF_BLOCK, communication::MsgType::BLOCK_WITH_PROOF, block.write_to_bytes().unwrap());
-        self.pub_sender.send(("consensus.blk".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ConsensusProofBlock::publish(&self.pub_sender, block);
     }
 
     pub fn pub_proposal(&self, proposal: &Proposal) -> Vec<u8> {
@@ -215,8 +225,7 @@ impl TenderMint {
         signed_propose_step.set_signature(signature.to_vec());
 
         let bmsg = signed_propose_step.write_to_bytes().unwrap();
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::NEW_PROPOSAL, communication::MsgType::MSG, bmsg.clone());
-        self.pub_sender.send(("consensus.msg".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ConsensusProposal::publish(&self.pub_sender, &bmsg);
         bmsg
     }
 
@@ -259,14 +268,15 @@ impl TenderMint {
         let vote_set = self.votes.get_voteset(height, round, Step::Prevote);
         trace!("proc_prevote vote_set {:?}", vote_set);
         if let Some(vote_set) = vote_set {
-            if self.is_above_threshold(&vote_set.count) {
+            let (power, power_by_proposal) = vote_set.power(&self.auth_manage.authorities, &self.auth_manage.authority_weights);
+            if self.is_above_threshold(power) {
                 let mut tv = self.params.timer.prevote;
-                if self.is_all_vote(&vote_set.count) {
+                if self.is_all_vote(power) {
                     tv = ::std::time::Duration::new(0, 0);
                 }
 
-                for (hash, count) in &vote_set.votes_by_proposal {
-                    if self.is_above_threshold(count) {
+                for (hash, power) in &power_by_proposal {
+                    if self.is_above_threshold(*power) {
                         //we have lock block,and now polc  then unlock
                         if self.lock_round.is_some() {
                             if self.lock_round.unwrap() < round && round <= self.round {
@@ -326,12 +336,16 @@ impl TenderMint {
         return false;
     }
 
-    fn is_above_threshold(&self, n: &usize) -> bool {
-        *n * 3 > self.auth_manage.authority_n * 2
+    fn total_power(&self) -> u64 {
+        proof::total_power(&self.auth_manage.authorities, &self.auth_manage.authority_weights)
     }
 
-    fn is_all_vote(&self, n: &usize) -> bool {
-        *n == self.auth_manage.authority_n
+    fn is_above_threshold(&self, power: u64) -> bool {
+        proof::is_above_threshold(power, self.total_power())
+    }
+
+    fn is_all_vote(&self, power: u64) -> bool {
+        power == self.total_power()
     }
 
     fn pre_proc_precommit(&mut self) -> bool {
@@ -385,16 +399,17 @@ impl TenderMint {
         let vote_set = self.votes.get_voteset(height, round, Step::Precommit);
         trace!("proc_precommit deal height {} round {} voteset {:?}", height, round, vote_set);
         if let Some(vote_set) = vote_set {
-            if self.is_above_threshold(&vote_set.count) {
+            let (power, power_by_proposal) = vote_set.power(&self.auth_manage.authorities, &self.auth_manage.authority_weights);
+            if self.is_above_threshold(power) {
                 trace!("proc_precommit is_above_threshold height {} round {}", height, round);
 
                 let mut tv = self.params.timer.precommit;
-                if self.is_all_vote(&vote_set.count) {
+                if self.is_all_vote(power) {
                     tv = ::std::time::Duration::new(0, 0);
                 }
-                for (hash, count) in vote_set.votes_by_proposal {
-                    if self.is_above_threshold(&count) {
-                        trace!("proc_precommit is_above_threshold hash {:?} {}", hash, count);
+                for (hash, power) in power_by_proposal {
+                    if self.is_above_threshold(power) {
+                        trace!("proc_precommit is_above_threshold hash {:?} {}", hash, power);
                         if hash.is_zero() {
                             tv = ::std::time::Duration::new(0, 0);
                             trace!("proc_precommit is zero");
@@ -494,19 +509,19 @@ impl TenderMint {
         let mut commits = HashMap::new();
         {
             let vote_set = self.votes.get_voteset(height, round, Step::Precommit);
-            let mut num: usize = 0;
+            let mut power = 0;
             if let Some(vote_set) = vote_set {
                 for (sender, vote) in &vote_set.votes_by_sender {
                     if vote.proposal.is_none() {
                         continue;
                     }
                     if vote.proposal.unwrap() == hash {
-                        num = num + 1;
+                        power += proof::voting_power(&self.auth_manage.authorities, &self.auth_manage.authority_weights, sender);
                         commits.insert(*sender, vote.signature.clone());
                     }
                 }
             }
-            if !self.is_above_threshold(&num) {
+            if !self.is_above_threshold(power) {
                 return None;
             }
         }
@@ -544,8 +559,9 @@ impl TenderMint {
                     self.save_wal_proof();
 
                     let mut proof_blk = BlockWithProof::new();
-                    let blk = self.locked_block.clone();
-                    proof_blk.set_blk(blk.unwrap());
+                    let blk = self.locked_block.clone().unwrap();
+                    self.evidence.included(blk.get_body().get_evidences());
+                    proof_blk.set_blk(blk);
                     proof_blk.set_proof(proof.into());
 
                     self.block_proof == Some((height, proof_blk.clone()));
@@ -564,8 +580,7 @@ impl TenderMint {
 
 
     fn pub_message(&self, message: Vec<u8>) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::CONSENSUS_MSG, communication::MsgType::MSG, message);
-        self.pub_sender.send(("consensus.msg".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+        ConsensusMsg::publish(&self.pub_sender, &message);
     }
 
     fn pub_and_broadcast_message(&mut self, height: usize, round: usize, step: Step, hash: Option<H256>) {
@@ -629,10 +644,17 @@ impl TenderMint {
             }
             let signature = Signature::from(signature);
             if let Ok(pubkey) = signature.recover(&message.crypt_hash().into()) {
-                let decoded = deserialize(&message[..]).unwrap();
+                let decoded = match deserialize(&message[..]) {
+                    Ok(decoded) => decoded,
+                    Err(_) => return Err(EngineError::UnexpectedMessage),
+                };
                 let (h, r, step, sender, hash) = decoded;
                 trace!("handle_message  parse over sender:{:?}  h:{} r:{} s:{:?} vs self {} {} {:?}", sender, h, r, step, self.height, self.round, self.step);
 
+                if pubkey_to_address(&pubkey) != sender {
+                    return Err(EngineError::InvalidSignature);
+                }
+
                 if h < self.height {
                     return Err(EngineError::UnexpectedMessage);
                 }
@@ -695,6 +717,13 @@ impl TenderMint {
                             return Ok((h, r, step));
                         }
 
+                        if let Some(vote) = self.votes.get_vote(h, r, step, &sender) {
+                            if vote.proposal != hash {
+                                let first = serialize(&(h, r, step, sender, vote.proposal), Infinite).unwrap();
+                                let first = serialize(&(first, vote.signature), Infinite).unwrap();
+                                self.report_evidence(Evidence::DuplicateVote(first, log_msg));
+                            }
+                        }
                         return Err(EngineError::DoubleVote(sender.into()));
                     }
                 }
@@ -703,14 +732,31 @@ impl TenderMint {
         Err(EngineError::UnexpectedMessage)
     }
 
+    /// The block must commit its transactions and evidences by the roots in its
+    /// header, and only carry evidence against the authorities
+    fn check_block(&self, block: &Block) -> bool {
+        let authorities = &self.auth_manage.authorities;
+        let authorities_old = &self.auth_manage.authorities_old;
+        let against_authority = |evidence: &Vec<u8>| {
+            Evidence::from_bytes(evidence)
+                .and_then(|evidence| evidence.check())
+                .map_or(false, |equivocation| authorities.contains(&equivocation.offender) || authorities_old.contains(&equivocation.offender))
+        };
+        block.check_hash() && block.get_body().get_evidences().iter().all(against_authority)
+    }
+
     fn proc_proposal(&mut self, height: usize, round: usize) -> bool {
         let proposal = self.proposals.get_proposal(height, round);
         if let Some(proposal) = proposal {
             trace!("proc proposal height {},round {} self {} {} ", height, round, self.height, self.round);
-            if !proposal.check(height, &self.auth_manage.authorities) {
+            if !proposal.check(height, &self.auth_manage.authorities, &self.auth_manage.authority_weights) {
                 trace!("proc proposal check error");
                 return false;
             }
+            if !self.check_block(&parse_from_bytes::<Block>(&proposal.block).unwrap()) {
+                warn!("proc proposal block root or evidence error height {} round {}", height, round);
+                return false;
+            }
             //height 1's block not have prehash
             if let Some(hash) = self.pre_hash {
                 //prehash : self.prehash vs  proposal's block's prehash
@@ -729,11 +775,11 @@ impl TenderMint {
                 let proof = TendermintProof::from(block_proof.clone());
                 info!(" proof is {:?}  {} {}", proof, height, round);
                 if self.auth_manage.authority_h_old == height - 1 {
-                    if !proof.check(height - 1, &self.auth_manage.authorities_old) {
+                    if !proof.check(height - 1, &self.auth_manage.authorities_old, &self.auth_manage.authority_weights_old) {
                         return false;
                     }
                 } else {
-                    if !proof.check(height - 1, &self.auth_manage.authorities) {
+                    if !proof.check(height - 1, &self.auth_manage.authorities, &self.auth_manage.authority_weights) {
                         return false;
                     }
                 }
@@ -792,8 +838,7 @@ impl TenderMint {
             let reqid = gen_reqid_from_idx(idx);
             let verify_req = block.block_verify_req(reqid);
             trace!("verify_req with {} txs with block verify request id: {} and height:{}", len, reqid, block.get_header().get_height());
-            let msg = factory::create_msg(submodules::CONSENSUS, topics::VERIFY_BLK_REQ, communication::MsgType::VERIFY_BLK_REQ, verify_req.write_to_bytes().unwrap());
-            self.pub_sender.send(("consensus.verify_req".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            ConsensusVerifyReq::publish(&self.pub_sender, &verify_req);
         }
         verify_ok
     }
@@ -833,6 +878,13 @@ impl TenderMint {
                 }
 
                 if (height == self.height && round >= self.round) || height > self.height {
+                    let now_height = self.height;
+                    self.proposal_msgs.retain(|&(h, _), _| h >= now_height);
+                    let first = self.proposal_msgs.entry((height, round)).or_insert_with(|| msg.clone()).clone();
+                    if first != msg {
+                        self.report_evidence(Evidence::DuplicateProposal(first, msg.clone()));
+                    }
+
                     if wal_flag && height == self.height {
                         self.wal_log.save(LOG_TYPE_PROPOSE, &msg).unwrap();
                     }
@@ -906,6 +958,9 @@ impl TenderMint {
                     block.set_body(blocktxs.get_body().clone());
                 }
             }
+            if self.params.include_evidence {
+                block.mut_body().set_evidences(RepeatedField::from_vec(self.evidence.pending(self.height)));
+            }
             if !flag && self.height > INIT_HEIGHT {
                 return;
             }
@@ -935,6 +990,9 @@ impl TenderMint {
             block.mut_header().set_timestamp(block_time.as_millis());
             block.mut_header().set_height(self.height as u64);
             block.mut_header().set_transactions_root(transactions_root.to_vec());
+            if let Some(evidence_root) = block.get_body().evidence_root() {
+                block.mut_header().set_evidence_root(evidence_root.to_vec());
+            }
 
             let bh = block.crypt_hash();
             info!("proposal new block: height {:?}, block hash {:?}", self.height, bh);
@@ -1021,10 +1079,35 @@ impl TenderMint {
         }
     }
 
+    /// Tell network a peer misbehaved, it is banned when that happens too often.
+    /// Network scores the address `origin` is bound to, not the origin itself.
+    fn blame(&self, origin: Origin, offence: Offence, reason: String) {
+        if Verdict::is_remote(origin) {
+            ConsensusVerdict::publish(&self.pub_sender, &Verdict::new(origin, offence, reason));
+        }
+    }
+
+    /// Keep and publish evidence of an equivocation seen for the first time
+    fn report_evidence(&mut self, evidence: Evidence) {
+        if let Some(equivocation) = evidence.check() {
+            if self.evidence.add(&evidence) {
+                warn!("{:?} equivocated at height {} round {}", equivocation.offender, equivocation.height, equivocation.round);
+                ConsensusEvidence::publish(&self.pub_sender, &evidence.to_bytes());
+            }
+        }
+    }
+
     pub fn process(&mut self, info: TransType) {
-        let (id, cmd_id, content_ext) = info;
+        let (id, cmd_id, origin, content_ext) = info;
         let from_broadcast = id == submodules::NET;
         if from_broadcast && self.consensus_power {
+            let count = self.flood.count(origin, Instant::now());
+            if count > FLOOD_LIMIT {
+                if count == FLOOD_LIMIT + 1 {
+                    self.blame(origin, Offence::Flood, format!("more than {} consensus messages in a second", FLOOD_LIMIT));
+                }
+                return;
+            }
             match cmd_id {
                 ID_CONSENSUS_MSG => {
                     //trace!("net receive_new_consensus msg");
@@ -1037,6 +1120,8 @@ impl TenderMint {
                             } else {
                                 self.proc_precommit(h, r);
                             }
+                        } else if let Err(EngineError::InvalidSignature) = res {
+                            self.blame(origin, Offence::InvalidSignature, "vote with an invalid signature".to_string());
                         }
                     }
                 }
@@ -1044,6 +1129,9 @@ impl TenderMint {
                 ID_NEW_PROPOSAL => {
                     if let MsgClass::MSG(msg) = content_ext {
                         let res = self.handle_proposal(msg, true, true);
+                        if let Err(EngineError::InvalidSignature) = res {
+                            self.blame(origin, Offence::InvalidSignature, "proposal with an invalid signature".to_string());
+                        }
                         if let Ok((h, r)) = res {
                             trace!("recive handle_proposal {:?} self height {} round {} step {:?}", (h, r), self.height, self.round, self.step);
                             if h == self.height && r == self.round && self.step < Step::Prevote {
@@ -1075,7 +1163,9 @@ impl TenderMint {
                         trace!("address[{:?}] is not consensus power !", self.params.signer.address);
                         self.consensus_power = false;
                     }
-                    self.auth_manage.receive_authorities_list(self.height, authorities);
+                    // the same weights chain checks synced proofs with, empty when nodes are equal
+                    let weights = rich_status.get_weights().to_vec();
+                    self.auth_manage.receive_weighted_authorities(self.height, authorities, weights);
                 }
 
                 MsgClass::VERIFYBLKRESP(resp) => {
@@ -1108,6 +1198,13 @@ impl TenderMint {
                     }
                 }
 
+                // evidence a committed block carried, which needn't be proposed again
+                MsgClass::MSG(evidence) => {
+                    if cmd_id == ChainEvidence::cmd_id() {
+                        self.evidence.included(&[evidence]);
+                    }
+                }
+
                 MsgClass::BLOCKTXS(block_txs) => {
                     info!("recive blocktxs height {} self height {}", block_txs.get_height(), self.height);
                     let height = block_txs.get_height() as usize;
@@ -1354,6 +1451,7 @@ impl TenderMint {
 
             if let Ok(tinfo) = ginfo {
                 self.process(tinfo);
+                self.done.handled();
             }
         }
     }
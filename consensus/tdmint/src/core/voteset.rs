@@ -21,6 +21,7 @@ use crypto::{Sign, pubkey_to_address, Signature};
 use libproto::blockchain::{Block, Transaction};
 use libproto::consensus::{VoteMessage as ProtoVoteMessage, VoteSet as ProtoVoteSet, Proposal as ProtoProposal};
 use lru_cache::LruCache;
+use proof::{voting_power, total_power, is_above_threshold};
 use protobuf::Message;
 use protobuf::core::parse_from_bytes;
 use rustc_hex::ToHex;
@@ -57,6 +58,11 @@ impl VoteCollector {
             return None;
         }
     }
+
+    /// The vote `sender` cast at the height, round and step
+    pub fn get_vote(&mut self, height: usize, round: usize, step: Step, sender: &Address) -> Option<VoteMessage> {
+        self.get_voteset(height, round, step).and_then(|vote_set| vote_set.votes_by_sender.get(sender).cloned())
+    }
 }
 
 //round -> step collector
@@ -179,7 +185,21 @@ impl VoteSet {
         }
     }
 
-    pub fn check(&self, h: usize, r: usize, step: Step, authorities: &[Address]) -> Result<Option<H256>, &str> {
+    /// Voting power of the votes, in all and for each hash, `weights` as in `proof::voting_power`.
+    /// Votes are not checked.
+    pub fn power(&self, authorities: &[Address], weights: &[u64]) -> (u64, HashMap<H256, u64>) {
+        let mut total = 0;
+        let mut by_proposal = HashMap::new();
+        for (sender, vote) in &self.votes_by_sender {
+            let power = voting_power(authorities, weights, sender);
+            total += power;
+            *by_proposal.entry(vote.proposal.unwrap_or_default()).or_insert(0) += power;
+        }
+        (total, by_proposal)
+    }
+
+    /// The hash more than two thirds of the voting power voted for, `weights` as in `proof::voting_power`
+    pub fn check(&self, h: usize, r: usize, step: Step, authorities: &[Address], weights: &[u64]) -> Result<Option<H256>, &str> {
         let mut votes_by_proposal = HashMap::new();
         for (sender, vote) in &self.votes_by_sender {
             if authorities.contains(sender) {
@@ -191,16 +211,15 @@ impl VoteSet {
                         if let Some(h) = vote.proposal {
                             hash = h;
                         }
-                        // inc the count of vote for hash
-                        if let Some(c) = votes_by_proposal.insert(hash, 1) {
-                            votes_by_proposal.insert(hash.clone(), c + 1);
-                        }
+                        // add the sender's power to the hash
+                        *votes_by_proposal.entry(hash).or_insert(0) += voting_power(authorities, weights, sender);
                     }
                 }
             }
         }
-        for (hash, count) in &votes_by_proposal {
-            if *count * 3 > authorities.len() * 2 {
+        let total = total_power(authorities, weights);
+        for (hash, power) in &votes_by_proposal {
+            if is_above_threshold(*power, total) {
                 if hash.is_zero() {
                     return Ok(None);
                 } else {
@@ -352,13 +371,13 @@ pub fn verify_tx(tx: &Transaction, height: u64) -> bool {
 }
 
 impl Proposal {
-    pub fn check(&self, h: usize, authorities: &[Address]) -> bool {
+    pub fn check(&self, h: usize, authorities: &[Address], weights: &[u64]) -> bool {
         if self.lock_round.is_none() && self.lock_votes.is_none() {
             true
         } else {
             let round = self.lock_round.unwrap();
 
-            let ret = self.lock_votes.as_ref().unwrap().check(h, round, Step::Prevote, authorities);
+            let ret = self.lock_votes.as_ref().unwrap().check(h, round, Step::Prevote, authorities, weights);
             if ret.is_err() {
                 return false;
             }
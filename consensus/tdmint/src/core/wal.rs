@@ -15,17 +15,36 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+//! The log of what the node said and saw in a height, replayed on restart.
+//! Records are `util::wal` records, `WAL_SYNC` sets when they are synced.
+
+use bincode::deserialize;
+use core::tendermint::Step;
+use libproto::blockchain::BlockTxs;
+use libproto::consensus::SignedProposeStep;
+use proof::TendermintProof;
+use protobuf::core::parse_from_bytes;
+use rustc_hex::ToHex;
 use std::ffi::OsString;
-use std::fs::{File, read_dir, OpenOptions, DirBuilder};
+use std::fs::{read_dir, DirBuilder};
 use std::io;
-use std::io::{Write, Read, Seek};
-use std::mem::transmute;
 use std::path::{PathBuf, Path};
 use std::str;
+use util::{Address, H256};
+use util::wal::{self, Log, SyncPolicy};
+
+pub const LOG_TYPE_PROPOSE: u8 = 1;
+pub const LOG_TYPE_VOTE: u8 = 2;
+pub const LOG_TYPE_STATE: u8 = 3;
+pub const LOG_TYPE_PREV_HASH: u8 = 4;
+pub const LOG_TYPE_COMMITS: u8 = 5;
+pub const LOG_TYPE_VERIFIED_PROPOSE: u8 = 6;
+pub const LOG_TYPE_AUTH_TXS: u8 = 7;
 
 pub struct Wal {
-    fs: File,
+    log: Log,
     dir: String,
+    policy: SyncPolicy,
 }
 
 impl Wal {
@@ -58,7 +77,8 @@ impl Wal {
             big_path = Path::new(&*fpath).to_path_buf();
         }
 
-        let fs = OpenOptions::new().read(true).create(true).write(true).open(big_path)?;
+        let policy = SyncPolicy::from_env();
+        let log = Log::open(&big_path, policy)?;
         let hstr = tmp.into_string().unwrap();
         let hi = hstr.parse::<u32>();
         match hi {
@@ -68,7 +88,11 @@ impl Wal {
             Ok(_) => {}
         }
 
-        Ok(Wal { fs: fs, dir: dir.to_string() })
+        Ok(Wal {
+               log: log,
+               dir: dir.to_string(),
+               policy: policy,
+           })
     }
 
     pub fn set_height(&mut self, height: usize) -> Result<(), io::Error> {
@@ -77,7 +101,7 @@ impl Wal {
 
         let pathname = self.dir.clone() + "/";
         let filename = pathname.clone() + &*name;
-        self.fs = OpenOptions::new().create(true).read(true).write(true).open(filename)?;
+        self.log = Log::open(Path::new(&filename), self.policy)?;
 
         if height > 2 {
             let mut delname = (height - 2).to_string();
@@ -89,50 +113,66 @@ impl Wal {
     }
 
     pub fn save(&mut self, mtype: u8, msg: &Vec<u8>) -> io::Result<usize> {
-        let mlen = msg.len() as u32;
-        if mlen == 0 {
+        if msg.is_empty() {
             return Ok(0);
         }
-        let len_bytes: [u8; 4] = unsafe { transmute(mlen.to_le()) };
-        let type_bytes: [u8; 1] = unsafe { transmute(mtype.to_le()) };
-        self.fs.seek(io::SeekFrom::End(0))?;
-        self.fs.write(&len_bytes[..])?;
-        self.fs.write(&type_bytes[..])?;
-        let hlen = self.fs.write(msg.as_slice())?;
-        self.fs.flush()?;
-        Ok(hlen)
+        self.log.append(mtype, msg)
     }
 
     pub fn load(&mut self) -> Vec<(u8, Vec<u8>)> {
-        let mut vec_buf: Vec<u8> = Vec::new();
-        let mut vec_out: Vec<(u8, Vec<u8>)> = Vec::new();
+        match self.log.load() {
+            Ok(records) => records,
+            Err(err) => {
+                warn!("can't load the wal: {}", err);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// One line per record of the log of `height` in `dir`
+pub fn dump(dir: &str, height: usize) -> io::Result<Vec<String>> {
+    let path = Path::new(dir).join(format!("{}.log", height));
+    let decoded = wal::read(&path)?;
+    let mut lines: Vec<String> = decoded.records.iter().map(|&(mtype, ref payload)| describe(mtype, payload)).collect();
+    if decoded.legacy {
+        lines.push("unversioned format, rewritten when opened".to_string());
+    }
+    let size = path.metadata()?.len() as usize;
+    if decoded.valid_len < size {
+        lines.push(format!("torn tail of {} bytes, dropped on load", size - decoded.valid_len));
+    }
+    Ok(lines)
+}
 
-        self.fs.seek(io::SeekFrom::Start(0)).unwrap();
-        let res_fsize = self.fs.read_to_end(&mut vec_buf);
-        if res_fsize.is_err() {
-            return vec_out;
+fn describe(mtype: u8, payload: &[u8]) -> String {
+    let detail = match mtype {
+        LOG_TYPE_PROPOSE => {
+            parse_from_bytes::<SignedProposeStep>(payload).ok().map(|signed| {
+                let step = signed.get_propose_step();
+                format!("proposal h {} r {}", step.get_height(), step.get_round())
+            })
         }
-        let fsize = res_fsize.unwrap();
-        if fsize <= 5 {
-            return vec_out;
+        LOG_TYPE_VOTE => {
+            deserialize::<(Vec<u8>, Vec<u8>)>(payload)
+                .ok()
+                .and_then(|(msg, _)| deserialize::<(usize, usize, Step, Address, Option<H256>)>(&msg).ok())
+                .map(|(h, r, step, sender, hash)| format!("vote h {} r {} {:?} from {:?} for {:?}", h, r, step, sender, hash))
         }
-        let mut index = 0;
-        loop {
-
-            if index + 5 > fsize {
-                break;
-            }
-            let hd: [u8; 4] = [vec_buf[index], vec_buf[index + 1], vec_buf[index + 2], vec_buf[index + 3]];
-            let tmp: u32 = unsafe { transmute::<[u8; 4], u32>(hd) };
-            let bodylen = tmp as usize;
-            let mtype = vec_buf[index + 4];
-            index += 5;
-            if index + bodylen > fsize {
-                break;
-            }
-            vec_out.push((mtype, vec_buf[index..index + bodylen].to_vec()));
-            index += bodylen;
+        LOG_TYPE_STATE => deserialize::<(usize, usize, Step)>(payload).ok().map(|(h, r, step)| format!("state h {} r {} {:?}", h, r, step)),
+        LOG_TYPE_PREV_HASH => Some(format!("prev hash {}", payload.to_hex())),
+        LOG_TYPE_COMMITS => {
+            deserialize::<TendermintProof>(payload)
+                .ok()
+                .map(|proof| format!("proof h {} r {} of {:?} with {} commits", proof.height, proof.round, proof.proposal, proof.commits.len()))
         }
-        vec_out
-    }
+        LOG_TYPE_VERIFIED_PROPOSE => deserialize::<(usize, usize, bool)>(payload).ok().map(|(h, r, ok)| format!("verified h {} r {}: {}", h, r, ok)),
+        LOG_TYPE_AUTH_TXS => {
+            parse_from_bytes::<BlockTxs>(payload)
+                .ok()
+                .map(|txs| format!("auth txs h {} count {}", txs.get_height(), txs.get_body().get_transactions().len()))
+        }
+        _ => None,
+    };
+    detail.unwrap_or_else(|| format!("type {} of {} bytes", mtype, payload.len()))
 }
@@ -0,0 +1,119 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#![feature(mpsc_select)]
+#[macro_use]
+extern crate serde_derive;
+extern crate libproto;
+extern crate util;
+extern crate threadpool;
+extern crate protobuf;
+#[macro_use]
+extern crate log;
+extern crate cita_crypto as crypto;
+extern crate proof;
+extern crate pubsub;
+extern crate bincode;
+extern crate engine;
+extern crate lru_cache;
+extern crate cpuprofiler;
+extern crate authority_manage;
+extern crate engine_json;
+extern crate rustc_hex;
+
+use std::sync::mpsc::channel;
+use std::thread;
+
+mod core;
+use core::spec::Spec;
+use core::tendermint::TenderMint;
+use core::votetime::WaitTimer;
+use cpuprofiler::PROFILER;
+use libproto::{parse_msg, key_to_id, trace};
+use libproto::router::{self, AuthBlockTxs, AuthVerifyBlockResp, ChainEvidence, ChainRichStatus, NetMsg, Route};
+use pubsub::{start_pubsub, sub_channel};
+use util::datapath::DataPath;
+
+
+fn profifer(flag_prof_start: u64, flag_prof_duration: u64) {
+    //start profiling
+    let start = flag_prof_start;
+    let duration = flag_prof_duration;
+    thread::spawn(move || {
+                      thread::sleep(std::time::Duration::new(start, 0));
+                      PROFILER.lock().unwrap().start("./tdmint.profiler").expect("Couldn't start");
+                      thread::sleep(std::time::Duration::new(duration, 0));
+                      PROFILER.lock().unwrap().stop().unwrap();
+                  });
+
+}
+
+/// Print the WAL records of `height`, for inspecting a node which stopped in it.
+pub fn dump_wal(height: usize) {
+    match core::wal::dump(&DataPath::wal_path(), height) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Err(err) => println!("can't read the wal of height {}: {}", height, err),
+    }
+}
+
+/// Run the tendermint service, it never returns.
+/// Profiling starts `flag_prof_start` seconds in and takes `flag_prof_duration`, zero means no profiling.
+pub fn run(config_path: &str, flag_prof_start: u64, flag_prof_duration: u64) {
+    profifer(flag_prof_start, flag_prof_duration);
+    // timer module
+    let (main2timer, timer4main) = channel();
+    let (timer2main, main4timer) = channel();
+    let timethd = thread::spawn(move || {
+                                    let wt = WaitTimer::new(timer2main, timer4main);
+                                    wt.start();
+                                });
+
+    //mq pubsub module
+    let (mq2main, main4mq) = channel();
+    let (tx_sub, rx_sub) = sub_channel();
+    let (tx_pub, rx_pub) = channel();
+    let keys = router::subscription(vec![NetMsg::key(), ChainRichStatus::key(), ChainEvidence::key(), AuthBlockTxs::key(), AuthVerifyBlockResp::key()]);
+    start_pubsub("consensus", keys, tx_sub, rx_pub);
+    // the engine tells when it is done with a message, they must reach it in order
+    let done = rx_sub.done();
+    thread::spawn(move || loop {
+                      let (key, body) = rx_sub.take().unwrap();
+                      let (cmd_id, origin, content) = parse_msg(body.as_slice());
+                      mq2main.send((key_to_id(&key), cmd_id, origin, content)).unwrap();
+                      trace::finish();
+                  });
+
+    //main tendermint loop module
+    let spec = Spec::new_test_tendermint(config_path);
+    info!("main loop start **** ");
+    let mainthd = thread::spawn(move || {
+                                    let mut engine = TenderMint::new(tx_pub, main4mq, done, main2timer, main4timer, spec.params);
+                                    engine.start();
+                                });
+
+    /*let mut log = Wal::new("./yubo").unwrap();
+    log.save("abcdefgh".to_string().into_bytes()).unwrap();
+    log.save("1234567890".to_string().into_bytes()).unwrap();
+    log.load();*/
+
+    mainthd.join().unwrap();
+    timethd.join().unwrap();
+}
@@ -20,6 +20,7 @@ use engine::{unix_now, AsMillis};
 use error::ErrorCode;
 use libproto::*;
 use libproto::blockchain::*;
+use libproto::router::{ConsensusBlock, ConsensusRpc, ConsensusTx, Route};
 use protobuf::Message;
 use protobuf::RepeatedField;
 use serde_json;
@@ -52,9 +53,8 @@ impl CandidatePool {
     }
 
     pub fn broadcast_tx(&self, tx_req: &Request) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::REQUEST, communication::MsgType::REQUEST, tx_req.write_to_bytes().unwrap());
         trace!("broadcast new tx {:?}", tx_req);
-        self.sender.send(("consensus.tx".to_string(), msg.write_to_bytes().unwrap()));
+        ConsensusTx::publish(&self.sender, tx_req);
     }
 
     pub fn add_tx(&mut self, tx_req: &Request, is_from_broadcast: bool) {
@@ -73,7 +73,7 @@ impl CandidatePool {
 
             Ok(tx) => {
                 let hash = H256::from_slice(&tx.tx_hash);
-                let success = self.pool.enqueue(tx);
+                let success = self.pool.enqueue(tx).is_ok();
                 if success {
                     let tx_response = TxResponse::new(hash.clone(), String::from("Ok"));
                     let tx_state = serde_json::to_string(&tx_response).unwrap();
@@ -88,8 +88,7 @@ impl CandidatePool {
 
         // Response RPC
         if !is_from_broadcast {
-            let msg = factory::create_msg(submodules::CONSENSUS, topics::RESPONSE, communication::MsgType::RESPONSE, response.write_to_bytes().unwrap());
-            self.sender.send(("consensus.rpc".to_string(), msg.write_to_bytes().unwrap())).unwrap();
+            ConsensusRpc::publish(&self.sender, &response);
         }
     }
 
@@ -117,9 +116,8 @@ impl CandidatePool {
     }
 
     pub fn pub_block(&self, block: &Block) {
-        let msg = factory::create_msg(submodules::CONSENSUS, topics::NEW_BLK, communication::MsgType::BLOCK, block.write_to_bytes().unwrap());
         trace!("publish block {:?}", block);
-        self.sender.send(("consensus.blk".to_string(), msg.write_to_bytes().unwrap()));
+        ConsensusBlock::publish(&self.sender, block);
     }
 
     pub fn update_txpool(&mut self, txs: &[SignedTransaction]) {
@@ -38,8 +38,9 @@ mod dispatch;
 mod cmd;
 use candidate_pool::*;
 use libproto::{key_to_id, parse_msg};
+use libproto::router::{self, ChainRichStatus, ConsensusBlock, ConsensusCmd, JsonrpcNewTx, Route};
 use log::LogLevelFilter;
-use pubsub::start_pubsub;
+use pubsub::{start_pubsub, sub_channel};
 
 use std::sync::mpsc::channel;
 use std::thread;
@@ -53,10 +54,10 @@ fn main() {
     ::std::env::set_var("RUST_BACKTRACE", "1");
     logger::init();
     info!("CITA:txpool");
-    let (tx_sub, rx_sub) = channel();
+    let (tx_sub, rx_sub) = sub_channel();
     let (tx_pub, rx_pub) = channel();
     let (tx, rx) = channel();
-    let keys = vec!["net.*", "consensus_cmd.default", "consensus.blk", "chain.richstatus", "jsonrpc.new_tx"];
+    let keys = router::subscription(vec!["net.*", ConsensusCmd::key(), ConsensusBlock::key(), ChainRichStatus::key(), JsonrpcNewTx::key()]);
     let pool = ThreadPool::new(THREAD_POOL_NUMBER);
     start_pubsub("consensus", keys, tx_sub, rx_pub);
     thread::spawn(move || loop {
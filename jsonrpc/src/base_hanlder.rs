@@ -19,6 +19,7 @@ use jsonrpc_types::Id;
 use jsonrpc_types::error::Error;
 use jsonrpc_types::request::RpcRequest;
 use jsonrpc_types::request::Version;
+use libproto::router::{JsonrpcNet, JsonrpcNewTx, JsonrpcRequest, Route};
 use serde_json;
 use std::result;
 
@@ -28,11 +29,11 @@ pub type RpcResult<T> = result::Result<T, Error>;
 pub trait BaseHandler {
     fn select_topic(method: &String) -> String {
         let topic = if method.starts_with("cita_send") {
-                        "jsonrpc.new_tx"
+                        JsonrpcNewTx::key()
                     } else if method.starts_with("cita") || method.starts_with("eth") {
-                        "jsonrpc.request"
+                        JsonrpcRequest::key()
                     } else if method.starts_with("net_") {
-                        "jsonrpc.net"
+                        JsonrpcNet::key()
                     } else {
                         "jsonrpc"
                     }
@@ -20,7 +20,7 @@ use super::{Params, Error, RpcRequest};
 use libproto::blockchain;
 use libproto::request as reqlib;
 use protobuf::core::parse_from_bytes;
-use rpctypes::{BlockNumber, CallRequest, Filter, CountOrCode, BlockParamsByHash, BlockParamsByNumber};
+use rpctypes::{BlockNumber, CallRequest, Filter, CountOrCode, BlockParamsByHash, BlockParamsByNumber, PeerEndpoint};
 use rustc_serialize::hex::FromHex;
 use serde_json;
 use util::{H256, H160, U256};
@@ -35,7 +35,15 @@ pub mod method {
     pub const CITA_GET_BLOCK_BY_NUMBER: &'static str = "cita_getBlockByNumber";
     pub const CITA_GET_TRANSACTION: &'static str = "cita_getTransaction";
     pub const CITA_SEND_TRANSACTION: &'static str = "cita_sendTransaction";
+    /// Evidence of equivocating validators. Parameter: (optional) QUANTITY, the lowest height, 0 by default
+    pub const CITA_GET_EVIDENCE: &'static str = "cita_getEvidence";
     pub const NET_PEER_COUNT: &'static str = "net_peerCount";
+    /// Details of every peer: endpoint, state, traffic by topic, reported height.
+    pub const NET_PEERS: &'static str = "net_peers";
+    /// Connect a peer till restart, refused unless network's admin_rpc is on. Parameter: {"id_card": QUANTITY, "addr": "ip:port"}
+    pub const NET_ADD_PEER: &'static str = "net_addPeer";
+    /// Disconnect a peer till restart or the config file changes, refused unless network's admin_rpc is on. Parameter: "ip:port"
+    pub const NET_REMOVE_PEER: &'static str = "net_removePeer";
     /// Executes a new message call immediately without creating a transaction on the block chain.
     /// Parameters
     /// 1. Object - The transaction call object
@@ -83,6 +91,15 @@ impl MethodHandler {
             method::NET_PEER_COUNT => {
                 self.peer_count(rpc)
             }
+            method::NET_PEERS => {
+                self.peers(rpc)
+            }
+            method::NET_ADD_PEER => {
+                self.add_peer(rpc)
+            }
+            method::NET_REMOVE_PEER => {
+                self.remove_peer(rpc)
+            }
             method::CITA_GET_BLOCK_BY_HASH => {
                 self.get_block_by_hash(rpc)
             }
@@ -92,6 +109,9 @@ impl MethodHandler {
             method::CITA_GET_TRANSACTION => {
                 self.get_transaction(rpc)
             }
+            method::CITA_GET_EVIDENCE => {
+                self.get_evidence(rpc)
+            }
             method::ETH_CALL => {
                 self.call(rpc)
             }
@@ -185,6 +205,54 @@ impl MethodHandler {
     }
 
 
+    pub fn peers(&self, req_rpc: RpcRequest) -> Result<reqlib::Request, Error> {
+        if 0 != self.params_len(&req_rpc.params)? {
+            return Err(Error::invalid_params_len());
+        }
+        drop(req_rpc);
+        let mut request = self.create_request();
+        request.set_peers(true);
+        Ok(request)
+    }
+
+
+    pub fn add_peer(&self, req_rpc: RpcRequest) -> Result<reqlib::Request, Error> {
+        if 1 != self.params_len(&req_rpc.params)? {
+            return Err(Error::invalid_params_len());
+        }
+        let (peer,): (PeerEndpoint,) = req_rpc.params.parse()?;
+        let peer = serde_json::to_string(&peer).map_err(|err| Error::invalid_params(format!("{:?}", err)))?;
+        let mut request = self.create_request();
+        request.set_add_peer(peer);
+        Ok(request)
+    }
+
+
+    pub fn remove_peer(&self, req_rpc: RpcRequest) -> Result<reqlib::Request, Error> {
+        if 1 != self.params_len(&req_rpc.params)? {
+            return Err(Error::invalid_params_len());
+        }
+        let (addr,): (String,) = req_rpc.params.parse()?;
+        let mut request = self.create_request();
+        request.set_remove_peer(addr);
+        Ok(request)
+    }
+
+
+    pub fn get_evidence(&self, req_rpc: RpcRequest) -> Result<reqlib::Request, Error> {
+        let mut request = self.create_request();
+        match self.params_len(&req_rpc.params)? {
+            0 => request.set_evidence(0),
+            1 => {
+                let (height,): (U256,) = req_rpc.params.parse()?;
+                request.set_evidence(height.into());
+            }
+            _ => return Err(Error::invalid_params_len()),
+        }
+        Ok(request)
+    }
+
+
     pub fn block_number(&self, req_rpc: RpcRequest) -> Result<reqlib::Request, Error> {
         if 0 != self.params_len(&req_rpc.params)? {
             return Err(Error::invalid_params_len());
@@ -602,4 +670,39 @@ mod tests {
         let params: Result<(Filter,), Error> = rpc_request.params.clone().parse();
         assert_eq!(serde_json::to_string(&params.unwrap().0).unwrap(), filter);
     }
+
+    #[test]
+    fn net_add_and_remove_peer() {
+        let rpc = r#"{"jsonrpc":"2.0","method":"net_addPeer","params":[{"id_card":3,"addr":"127.0.0.1:4003"}],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        let request = MethodHandler.from_req(rpc_request).unwrap();
+        let peer: PeerEndpoint = serde_json::from_str(request.get_add_peer()).unwrap();
+        assert_eq!(peer.id_card, 3);
+        assert_eq!(peer.addr, "127.0.0.1:4003");
+
+        let rpc = r#"{"jsonrpc":"2.0","method":"net_removePeer","params":["127.0.0.1:4003"],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        assert_eq!(MethodHandler.from_req(rpc_request).unwrap().get_remove_peer(), "127.0.0.1:4003");
+
+        let rpc = r#"{"jsonrpc":"2.0","method":"net_peers","params":[1],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        assert!(MethodHandler.from_req(rpc_request).is_err());
+    }
+
+    #[test]
+    fn cita_get_evidence() {
+        let rpc = r#"{"jsonrpc":"2.0","method":"cita_getEvidence","params":[],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        let request = MethodHandler.from_req(rpc_request).unwrap();
+        assert!(request.has_evidence());
+        assert_eq!(request.get_evidence(), 0);
+
+        let rpc = r#"{"jsonrpc":"2.0","method":"cita_getEvidence","params":["0x10"],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        assert_eq!(MethodHandler.from_req(rpc_request).unwrap().get_evidence(), 16);
+
+        let rpc = r#"{"jsonrpc":"2.0","method":"cita_getEvidence","params":["0x10", 1],"id":2}"#;
+        let rpc_request: RpcRequest = serde_json::from_str(rpc).unwrap();
+        assert!(MethodHandler.from_req(rpc_request).is_err());
+    }
 }
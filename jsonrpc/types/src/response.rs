@@ -21,7 +21,7 @@ use error::Error;
 use libproto::TxResponse;
 use libproto::response::{Response_oneof_data, Response};
 use request::Version;
-use rpctypes::{Receipt, Log, RpcTransaction, Block, RpcBlock};
+use rpctypes::{Receipt, Log, RpcTransaction, Block, RpcBlock, Evidence};
 use serde::{Serializer, Deserializer, Deserialize, Serialize};
 use serde::de::Error as SError;
 use serde_json;
@@ -48,6 +48,8 @@ pub enum ResultBody {
     UninstallFliter(bool),
     FilterChanges(Vec<Log>),
     FilterLog(Vec<Log>),
+    Peers(Value),
+    Evidence(Vec<Evidence>),
 }
 
 
@@ -137,6 +139,8 @@ impl Output {
                     Response_oneof_data::filter_changes(log) => success.set_result(ResultBody::FilterChanges(serde_json::from_str::<Vec<Log>>(&log).unwrap()))
                                                                        .to_out(),
                     Response_oneof_data::filter_logs(log) => success.set_result(ResultBody::FilterLog(serde_json::from_str::<Vec<Log>>(&log).unwrap())).to_out(),
+                    Response_oneof_data::peers(peers) => success.set_result(ResultBody::Peers(serde_json::from_str::<Value>(&peers).unwrap())).to_out(),
+                    Response_oneof_data::evidence(evidence) => success.set_result(ResultBody::Evidence(serde_json::from_str::<Vec<Evidence>>(&evidence).unwrap())).to_out(),
                     Response_oneof_data::error_msg(err_msg) => Output::Failure(RpcFailure::from_options(id.clone(), jsonrpc.clone(), Error::server_error(code, err_msg.as_ref()))),
                 }
             }
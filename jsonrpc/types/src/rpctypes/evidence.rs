@@ -0,0 +1,40 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use bytes::Bytes;
+use proof::Equivocation;
+use util::Address;
+
+/// Evidence of an equivocation for `cita_getEvidence`. `evidence` is
+/// `proof::Evidence::to_bytes`, what a contract needs to check it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Evidence {
+    pub height: usize,
+    pub round: usize,
+    pub offender: Address,
+    pub evidence: Bytes,
+}
+
+impl Evidence {
+    pub fn new(equivocation: Equivocation, evidence: Vec<u8>) -> Self {
+        Evidence {
+            height: equivocation.height,
+            round: equivocation.round,
+            offender: equivocation.offender,
+            evidence: Bytes::from(evidence),
+        }
+    }
+}
@@ -26,14 +26,18 @@ pub mod block;
 pub mod middle_modle;
 pub mod index;
 pub mod proof;
+pub mod peer;
+pub mod evidence;
 
 pub use self::block::*;
 pub use self::block_number::*;
 pub use self::call_request::*;
+pub use self::evidence::Evidence;
 pub use self::filter::*;
 pub use self::index::Index;
 pub use self::log::*;
 pub use self::middle_modle::*;
+pub use self::peer::PeerEndpoint;
 pub use self::proof::*;
 pub use self::receipt::*;
 pub use self::transaction::*;
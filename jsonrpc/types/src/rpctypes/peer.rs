@@ -0,0 +1,40 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Peer for `net_addPeer`, `addr` is its "ip:port".
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct PeerEndpoint {
+    pub id_card: u32,
+    pub addr: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PeerEndpoint;
+    use serde_json;
+
+    #[test]
+    fn peer_endpoint_deserialization() {
+        let s = r#"{"id_card": 3, "addr": "127.0.0.1:4003"}"#;
+        let peer: PeerEndpoint = serde_json::from_str(s).unwrap();
+        assert_eq!(peer,
+                   PeerEndpoint {
+                       id_card: 3,
+                       addr: "127.0.0.1:4003".to_string(),
+                   });
+    }
+}
@@ -17,14 +17,14 @@
 
 use bincode::deserialize;
 use libproto::blockchain::{Proof as ProtoProof, ProofType};
-use proof::{TendermintProof as TProof, AuthorityRoundProof as AProof};
+use proof::{TendermintProof as TProof, AuthorityRoundProof as AProof, RaftProof as RProof};
 use std::collections::HashMap;
 use util::{H256, Address};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub enum Proof {
     AuthorityRound(AuthorityRoundProof),
-    Raft,
+    Raft(RaftProof),
     Tendermint(TendermintProof),
 }
 
@@ -32,7 +32,7 @@ impl From<ProtoProof> for Proof {
     fn from(p: ProtoProof) -> Self {
         match p.get_field_type() {
             ProofType::AuthorityRound => Proof::AuthorityRound(AuthorityRoundProof::from(p)),
-            ProofType::Raft => Proof::Raft,
+            ProofType::Raft => Proof::Raft(RaftProof::from(p)),
             ProofType::Tendermint => Proof::Tendermint(TendermintProof::from(p)),
         }
     }
@@ -79,3 +79,30 @@ impl From<ProtoProof> for AuthorityRoundProof {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct RaftProof {
+    pub height: usize,
+    pub term: u64,
+    pub index: u64,
+    pub proposal: H256,
+    pub commits: HashMap<Address, String>,
+}
+
+impl From<ProtoProof> for RaftProof {
+    fn from(p: ProtoProof) -> Self {
+        let decoded: RProof = deserialize(&p.get_content()[..]).unwrap();
+        let mut commits: HashMap<Address, String> = HashMap::new();
+        let str_0x = "0x".to_string();
+        for (addr, sign) in decoded.commits {
+            commits.insert(addr, str_0x.clone() + &String::from(sign));
+        }
+        RaftProof {
+            height: decoded.height,
+            term: decoded.term,
+            index: decoded.index,
+            proposal: decoded.proposal,
+            commits: commits,
+        }
+    }
+}
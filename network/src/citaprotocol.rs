@@ -20,33 +20,353 @@
 use byteorder::{BigEndian, ByteOrder};
 use bytes::BufMut;
 use bytes::BytesMut;
+use crypto::PrivKey;
+use futures::{future, Future, IntoFuture};
+use futures::sync::oneshot;
+use nodes::SharedNodeList;
+use session::{FEATURE_BATCH, FEATURE_COMPRESS, Handshake, Session, HANDSHAKE_TIMEOUT, HELLO_LEN, PROOF_LEN, TAG_LEN};
+use std::collections::VecDeque;
 use std::io;
+use std::mem;
+use std::time::Duration;
+use tokio_core::reactor::{Remote, Timeout};
 use tokio_io::{AsyncRead, AsyncWrite};
 use tokio_io::codec::{Framed, Encoder, Decoder};
+use tokio_io::io::{read_exact, write_all};
 use tokio_proto::pipeline::ServerProto;
+use util::{Address, Hashable};
+use util::snappy;
 
 pub type CitaRequest = Vec<u8>;
 pub type CitaResponse = Vec<u8>;
+/// Msgs written together, in one frame when the peer takes batches
+pub type Batch = Vec<Vec<u8>>;
+/// A msg the server received, with the address the peer proved, none in plaintext
+pub type PeerRequest = (Option<Address>, CitaRequest);
 
-/// Our multiplexed line-based codec
-pub struct CitaCodec;
+const MAGIC: u32 = 0xDEADBEEF;
+/// Bumped whenever the frame layout changes
+pub const VERSION: u8 = 1;
+const FLAG_CHECKSUM: u8 = 0x01;
+const FLAG_BATCH: u8 = 0x02;
+const FLAG_COMPRESSED: u8 = 0x04;
+const HEADER_LEN: usize = 10;
+const CHECKSUM_LEN: usize = 4;
+/// Length prefix of each msg in a batch
+const BATCH_LEN_PREFIX: usize = 4;
+/// Smaller payloads are not worth compressing
+const COMPRESS_MIN_LEN: usize = 256;
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// Limits and options of the framing, both sides should agree on them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameConfig {
+    /// Largest payload accepted or sent, sealed payloads include their tag
+    pub max_frame_size: usize,
+    /// Append a checksum to sent frames. Received ones are checked whenever they carry one.
+    pub checksum: bool,
+    /// Offer compression in the handshake, it is used when the peer offers it too
+    pub compress: bool,
+    /// Gather the msgs queued within a few ms into one write, and offer batch
+    /// frames in the handshake
+    pub batch: bool,
+}
+
+impl FrameConfig {
+    /// Features offered in the handshake
+    pub fn features(&self) -> u8 {
+        (if self.batch { FEATURE_BATCH } else { 0 }) | if self.compress { FEATURE_COMPRESS } else { 0 }
+    }
+}
+
+impl Default for FrameConfig {
+    fn default() -> Self {
+        FrameConfig {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            checksum: false,
+            compress: true,
+            batch: true,
+        }
+    }
+}
+
+/// Our multiplexed line-based codec, frame payloads are sealed when it has a session
+pub struct CitaCodec {
+    frame: FrameConfig,
+    session: Option<(Session, SharedNodeList)>,
+    /// Rest of the last batch received
+    pending: VecDeque<CitaRequest>,
+}
+
+impl CitaCodec {
+    /// Codec sending payloads in plaintext, for local dev only
+    pub fn new(frame: FrameConfig) -> Self {
+        CitaCodec {
+            frame: frame,
+            session: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Codec sealing payloads with `session`, it fails once the peer drops out of `nodes`.
+    pub fn with_session(frame: FrameConfig, session: Session, nodes: SharedNodeList) -> Self {
+        CitaCodec {
+            frame: frame,
+            session: Some((session, nodes)),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Address the peer proved in the handshake, none in plaintext
+    pub fn peer(&self) -> Option<Address> {
+        self.session.as_ref().map(|&(ref session, _)| *session.peer())
+    }
+
+    /// Features both sides offered, none in plaintext
+    fn features(&self) -> u8 {
+        self.session.as_ref().map_or(0, |&(ref session, _)| session.features())
+    }
+
+    /// Largest payload before sealing
+    fn max_payload(&self) -> usize {
+        let tag_len = if self.session.is_some() { TAG_LEN } else { 0 };
+        self.frame.max_frame_size.saturating_sub(tag_len)
+    }
+
+    fn encode_frame(&mut self, mut payload: Vec<u8>, mut flags: u8, buf: &mut BytesMut) -> io::Result<()> {
+        if payload.len() > self.max_payload() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("msg of {} bytes is over the frame limit {}", payload.len(), self.frame.max_frame_size)));
+        }
+        if self.features() & FEATURE_COMPRESS != 0 && payload.len() >= COMPRESS_MIN_LEN {
+            let compressed = snappy::compress(&payload);
+            if compressed.len() < payload.len() {
+                payload = compressed;
+                flags |= FLAG_COMPRESSED;
+            }
+        }
+        if self.frame.checksum {
+            flags |= FLAG_CHECKSUM;
+        }
+        // the header is authenticated along with the payload, so its flags can't be flipped
+        let header_aad = [VERSION, flags];
+        let payload = match self.session {
+            Some((ref mut session, ref nodes)) => {
+                if !nodes.read().contains(session.peer()) {
+                    return Err(not_in_node_list(session.peer()));
+                }
+                session.seal(&payload, &header_aad)
+            }
+            None => payload,
+        };
+        trace!("encode frame {:?}", payload);
+
+        let mut header = [0u8; HEADER_LEN];
+        BigEndian::write_u32(&mut header[..4], MAGIC);
+        header[4] = VERSION;
+        header[5] = flags;
+        BigEndian::write_u32(&mut header[6..], payload.len() as u32);
+
+        opt_bytes_extend(buf, &header);
+        opt_bytes_extend(buf, &payload);
+        if self.frame.checksum {
+            opt_bytes_extend(buf, &checksum(&payload));
+        }
+
+        Ok(())
+    }
+
+    /// Encode `batch` in as few frames as the limit allows, or one frame per msg
+    /// when the peer doesn't take batches.
+    pub fn encode_batch(&mut self, batch: Batch, buf: &mut BytesMut) -> io::Result<()> {
+        if self.features() & FEATURE_BATCH == 0 {
+            for msg in batch {
+                self.encode_frame(msg, 0, buf)?;
+            }
+            return Ok(());
+        }
+        let max_payload = self.max_payload();
+        let mut chunk = Vec::new();
+        let mut chunk_len = 0;
+        for msg in batch {
+            if !chunk.is_empty() && chunk_len + BATCH_LEN_PREFIX + msg.len() > max_payload {
+                self.encode_chunk(mem::replace(&mut chunk, Vec::new()), buf)?;
+                chunk_len = 0;
+            }
+            chunk_len += BATCH_LEN_PREFIX + msg.len();
+            chunk.push(msg);
+        }
+        if !chunk.is_empty() {
+            self.encode_chunk(chunk, buf)?;
+        }
+        Ok(())
+    }
+
+    fn encode_chunk(&mut self, mut chunk: Batch, buf: &mut BytesMut) -> io::Result<()> {
+        if chunk.len() == 1 {
+            return self.encode_frame(chunk.pop().unwrap(), 0, buf);
+        }
+        let len: usize = chunk.iter().map(|msg| BATCH_LEN_PREFIX + msg.len()).sum();
+        let mut payload = Vec::with_capacity(len);
+        for msg in chunk {
+            let mut prefix = [0u8; BATCH_LEN_PREFIX];
+            BigEndian::write_u32(&mut prefix, msg.len() as u32);
+            payload.extend_from_slice(&prefix);
+            payload.extend_from_slice(&msg);
+        }
+        self.encode_frame(payload, FLAG_BATCH, buf)
+    }
+
+    /// Split a batch payload into `pending`
+    fn unbatch(&mut self, payload: &[u8]) -> io::Result<()> {
+        let mut rest = payload;
+        if rest.is_empty() {
+            return Err(invalid_frame("empty batch".to_string()));
+        }
+        while !rest.is_empty() {
+            if rest.len() < BATCH_LEN_PREFIX {
+                return Err(invalid_frame("truncated batch".to_string()));
+            }
+            let len = BigEndian::read_u32(&rest[..BATCH_LEN_PREFIX]) as usize;
+            rest = &rest[BATCH_LEN_PREFIX..];
+            if rest.len() < len {
+                return Err(invalid_frame("truncated batch".to_string()));
+            }
+            self.pending.push_back(rest[..len].to_vec());
+            rest = &rest[len..];
+        }
+        Ok(())
+    }
+}
+
+/// `CitaCodec` for the connecting side, which writes batches of queued msgs
+pub struct BatchCodec {
+    inner: CitaCodec,
+}
+
+impl BatchCodec {
+    pub fn new(inner: CitaCodec) -> Self {
+        BatchCodec { inner: inner }
+    }
+}
+
+impl Decoder for BatchCodec {
+    type Item = CitaRequest;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        self.inner.decode(buf)
+    }
+}
+
+impl Encoder for BatchCodec {
+    type Item = Batch;
+    type Error = io::Error;
+
+    fn encode(&mut self, batch: Self::Item, buf: &mut BytesMut) -> io::Result<()> {
+        self.inner.encode_batch(batch, buf)
+    }
+}
+
+/// `CitaCodec` for the listening side, which tells each msg's sender to the service
+pub struct ServerCodec {
+    inner: CitaCodec,
+}
+
+impl ServerCodec {
+    pub fn new(inner: CitaCodec) -> Self {
+        ServerCodec { inner: inner }
+    }
+}
+
+impl Decoder for ServerCodec {
+    type Item = PeerRequest;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        let peer = self.inner.peer();
+        self.inner.decode(buf).map(|msg| msg.map(|msg| (peer, msg)))
+    }
+}
+
+impl Encoder for ServerCodec {
+    type Item = CitaResponse;
+    type Error = io::Error;
+
+    fn encode(&mut self, msg: Self::Item, buf: &mut BytesMut) -> io::Result<()> {
+        self.inner.encode(msg, buf)
+    }
+}
+
+pub fn not_in_node_list(peer: &Address) -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, format!("peer {:?} is not in the node list", peer))
+}
+
+fn invalid_frame(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(&payload.crypt_hash()[..CHECKSUM_LEN]);
+    checksum
+}
 
 /// Protocol definition
-pub struct CitaProto;
+pub struct CitaProto {
+    /// Node key for the handshake, `None` means plaintext
+    signer: Option<PrivKey>,
+    nodes: SharedNodeList,
+    frame: FrameConfig,
+    /// Reactor the handshake timeouts run on, the server's own is out of reach
+    timer: Remote,
+}
 
-/// Implementation of the multiplexed line-based protocol.
+impl CitaProto {
+    pub fn new(signer: Option<PrivKey>, nodes: SharedNodeList, frame: FrameConfig, timer: Remote) -> Self {
+        CitaProto {
+            signer: signer,
+            nodes: nodes,
+            frame: frame,
+            timer: timer,
+        }
+    }
+
+    /// Resolves once `HANDSHAKE_TIMEOUT` has passed
+    fn handshake_timeout(&self) -> oneshot::Receiver<()> {
+        let (expired, timeout) = oneshot::channel();
+        self.timer.spawn(move |handle| {
+            let timer = Timeout::new(Duration::from_secs(HANDSHAKE_TIMEOUT), handle).into_future().flatten();
+            timer.then(move |_| {
+                let _ = expired.send(());
+                Ok::<(), ()>(())
+            })
+        });
+        timeout
+    }
+}
+
+/// Implementation of the multiplexed protocol.
 ///
-/// Frames begin with a 4 byte header, consisting of the numeric request ID
-/// encoded in network order, followed by the frame payload encoded as a UTF-8
-/// string and terminated with a '\n' character:
+/// Frames begin with a 10 byte header: the magic, the protocol version, flags and
+/// the payload length, all in network order. A 4 byte checksum, the head of the
+/// payload's hash, follows the payload when the checksum flag is set.
 ///
 /// # An example frame:
 ///
-/// +-- request id --+------- frame payload --------+
-/// |                |                              |
-/// | \xDEADBEEF+len | This is the frame payload    |
-/// |                |                              |
-/// +----------------+------------------------------+
+/// +-- magic --+- version -+- flags -+-- length --+-- frame payload --+-- checksum --+
+/// |           |           |         |            |                   |              |
+/// | \xDEADBEEF |   \x01    |  \x01   |  4 bytes   | length bytes      | 4 bytes      |
+/// |           |           |         |            |                   |              |
+/// +-----------+-----------+---------+------------+-------------------+--------------+
+///
+/// Flags: 0x01 checksum, 0x02 batch and 0x04 compressed. A batch payload is its msgs,
+/// each prefixed by its 4 byte length. A compressed payload is snappy compressed after
+/// batching. Both are only sent when the peer offered them in the handshake.
+///
+/// With a session, the frame payload is the sealed message followed by its 16 byte tag,
+/// and the version and flags are authenticated with it.
+/// A frame with a bad magic, an unknown version or flags, a length over the limit or
+/// a wrong checksum is an error, so the connection is closed instead of stalling.
 ///
 
 fn opt_bytes_extend(buf: &mut BytesMut, data: &[u8]) {
@@ -62,27 +382,72 @@ impl Decoder for CitaCodec {
     type Error = io::Error;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
-        let buf_len = buf.len();
-        if buf_len < 8 {
+        if let Some(msg) = self.pending.pop_front() {
+            return Ok(Some(msg));
+        }
+        if buf.len() < 4 {
             return Ok(None);
         }
-
-        // check flag and msglen
-        let request_id = BigEndian::read_u64(buf.as_ref());
-        if request_id & 0xffffffff00000000 != 0xDEADBEEF00000000 {
+        let magic = BigEndian::read_u32(&buf[..4]);
+        if magic != MAGIC {
+            return Err(invalid_frame(format!("bad magic {:#x}", magic)));
+        }
+        if buf.len() < HEADER_LEN {
             return Ok(None);
         }
-        let msg_len = request_id & 0x00000000ffffffff;
-        if (msg_len + 8) > buf_len as u64 {
+        let version = buf[4];
+        if version != VERSION {
+            return Err(invalid_frame(format!("unsupported protocol version {}", version)));
+        }
+        let flags = buf[5];
+        let features = self.features();
+        let mut known_flags = FLAG_CHECKSUM;
+        if features & FEATURE_BATCH != 0 {
+            known_flags |= FLAG_BATCH;
+        }
+        if features & FEATURE_COMPRESS != 0 {
+            known_flags |= FLAG_COMPRESSED;
+        }
+        if flags & !known_flags != 0 {
+            return Err(invalid_frame(format!("unknown flags {:#x}", flags)));
+        }
+        let msg_len = BigEndian::read_u32(&buf[6..HEADER_LEN]) as usize;
+        if msg_len > self.frame.max_frame_size {
+            return Err(invalid_frame(format!("frame of {} bytes is over the limit {}", msg_len, self.frame.max_frame_size)));
+        }
+        let checksum_len = if flags & FLAG_CHECKSUM != 0 { CHECKSUM_LEN } else { 0 };
+        let frame_len = HEADER_LEN + msg_len + checksum_len;
+        if buf.len() < frame_len {
+            let more = frame_len - buf.len();
+            buf.reserve(more);
             return Ok(None);
         }
-        // ok skip the flag
-        buf.split_to(8);
-        // get msg
-        let payload = buf.split_to(msg_len as usize).to_vec();
 
-        trace!("decode msg {:?} {:?}", request_id, payload);
+        buf.split_to(HEADER_LEN);
+        let mut payload = buf.split_to(msg_len).to_vec();
+        if checksum_len > 0 && buf.split_to(CHECKSUM_LEN)[..] != checksum(&payload)[..] {
+            return Err(invalid_frame("checksum mismatch".to_string()));
+        }
+        if let Some((ref mut session, ref nodes)) = self.session {
+            if !nodes.read().contains(session.peer()) {
+                return Err(not_in_node_list(session.peer()));
+            }
+            payload = session.open(&payload, &[version, flags])?;
+        }
+        if flags & FLAG_COMPRESSED != 0 {
+            let len = snappy::decompressed_len(&payload).map_err(|_| invalid_frame("bad compressed payload".to_string()))?;
+            if len > self.frame.max_frame_size {
+                return Err(invalid_frame(format!("frame of {} bytes decompressed is over the limit {}", len, self.frame.max_frame_size)));
+            }
+            payload = snappy::decompress(&payload).map_err(|_| invalid_frame("bad compressed payload".to_string()))?;
+        }
+
+        trace!("decode msg {:?}", payload);
 
+        if flags & FLAG_BATCH != 0 {
+            self.unbatch(&payload)?;
+            return Ok(self.pending.pop_front());
+        }
         Ok(Some(payload))
     }
 }
@@ -92,28 +457,361 @@ impl Encoder for CitaCodec {
     type Error = io::Error;
 
     fn encode(&mut self, msg: Self::Item, buf: &mut BytesMut) -> io::Result<()> {
-        let request_id = 0xDEADBEEF00000000 + msg.len();
-        trace!("encode msg {:?} {:?}", request_id, msg);
-
-        let mut encoded_request_id = [0; 8];
-        BigEndian::write_u64(&mut encoded_request_id, request_id as u64);
-
-        opt_bytes_extend(buf, &encoded_request_id);
-        opt_bytes_extend(buf, &msg);
-
-        Ok(())
+        self.encode_frame(msg, 0, buf)
     }
 }
 
 impl<T: AsyncRead + AsyncWrite + 'static> ServerProto<T> for CitaProto {
-    type Request = CitaRequest;
+    type Request = PeerRequest;
     type Response = CitaResponse;
 
-    /// `Framed<T, CitaCodec>` is the return value of `io.framed(CitaCodec)`
-    type Transport = Framed<T, CitaCodec>;
-    type BindTransport = Result<Self::Transport, io::Error>;
+    /// `Framed<T, ServerCodec>` is the return value of `io.framed(ServerCodec)`
+    type Transport = Framed<T, ServerCodec>;
+    type BindTransport = Box<Future<Item = Self::Transport, Error = io::Error>>;
 
     fn bind_transport(&self, io: T) -> Self::BindTransport {
-        Ok(io.framed(CitaCodec))
+        let frame = self.frame;
+        let signer = match self.signer {
+            Some(signer) => signer,
+            None => return Box::new(future::ok(io.framed(ServerCodec::new(CitaCodec::new(frame))))),
+        };
+        // the connecting side sends its hello first, the answer is the hello and the proof
+        let handshake = Handshake::new(frame.features());
+        let nodes = self.nodes.clone();
+        let transport = read_exact(io, vec![0u8; HELLO_LEN])
+            .and_then(move |(io, peer_hello)| {
+                let mut reply = handshake.hello();
+                reply.extend(handshake.proof(&signer, &peer_hello, false));
+                write_all(io, reply).map(move |(io, _)| (io, handshake, peer_hello))
+            })
+            .and_then(|(io, handshake, peer_hello)| read_exact(io, vec![0u8; PROOF_LEN]).map(move |(io, peer_proof)| (io, handshake, peer_hello, peer_proof)))
+            .and_then(move |(io, handshake, peer_hello, peer_proof)| {
+                let session = handshake.finish(&peer_hello, &peer_proof, false)?;
+                if !nodes.read().contains(session.peer()) {
+                    warn!("reject connection from {:?} which is not in the node list", session.peer());
+                    return Err(not_in_node_list(session.peer()));
+                }
+                info!("handshake with peer {:?} finished", session.peer());
+                Ok(io.framed(ServerCodec::new(CitaCodec::with_session(frame, session, nodes))))
+            });
+        // a client which never finishes the handshake must not hold the connection
+        let timeout = self.handshake_timeout().then(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "handshake timed out")));
+        Box::new(transport.select(timeout).map(|(transport, _)| transport).map_err(|(e, _)| e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crypto::KeyPair;
+    use libproto::{factory, submodules, topics};
+    use libproto::blockchain::{Block, Transaction};
+    use libproto::communication::MsgType;
+    use nodes::NodeList;
+    use protobuf::{Message, RepeatedField};
+    use quickcheck::{QuickCheck, TestResult, quickcheck};
+    use rustc_serialize::hex::FromHex;
+    use std::sync::Arc;
+    use test::Bencher;
+    use util::RwLock;
+
+    fn frame(checksum: bool) -> FrameConfig {
+        FrameConfig {
+            max_frame_size: 1024,
+            checksum: checksum,
+            compress: true,
+            batch: true,
+        }
+    }
+
+    /// Codecs of both ends of a connection
+    fn codecs(frame: FrameConfig, sealed: bool) -> (CitaCodec, CitaCodec) {
+        codecs_between(frame, frame, sealed)
+    }
+
+    fn codecs_between(sender: FrameConfig, receiver: FrameConfig, sealed: bool) -> (CitaCodec, CitaCodec) {
+        if !sealed {
+            return (CitaCodec::new(sender), CitaCodec::new(receiver));
+        }
+        let (a, b) = (KeyPair::gen_keypair(), KeyPair::gen_keypair());
+        let (ha, hb) = (Handshake::new(sender.features()), Handshake::new(receiver.features()));
+        let (hello_a, hello_b) = (ha.hello(), hb.hello());
+        let (proof_a, proof_b) = (ha.proof(a.privkey(), &hello_b, true), hb.proof(b.privkey(), &hello_a, false));
+        let (sa, sb) = (ha.finish(&hello_b, &proof_b, true).unwrap(), hb.finish(&hello_a, &proof_a, false).unwrap());
+        let mut list = NodeList::new();
+        list.update(0, vec![*sa.peer(), *sb.peer()].into_iter().collect());
+        let nodes = Arc::new(RwLock::new(list));
+        (CitaCodec::with_session(sender, sa, nodes.clone()), CitaCodec::with_session(receiver, sb, nodes))
+    }
+
+    fn decode_all(codec: &mut CitaCodec, buf: &mut BytesMut) -> Vec<Vec<u8>> {
+        let mut msgs = Vec::new();
+        while let Some(msg) = codec.decode(buf).unwrap() {
+            msgs.push(msg);
+        }
+        msgs
+    }
+
+    fn encode(codec: &mut CitaCodec, msg: &[u8]) -> BytesMut {
+        let mut buf = BytesMut::new();
+        codec.encode(msg.to_vec(), &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn roundtrip() {
+        fn roundtrip(msgs: Vec<Vec<u8>>, checksum: bool, sealed: bool) -> TestResult {
+            if msgs.iter().any(|msg| msg.len() + TAG_LEN > 1024) {
+                return TestResult::discard();
+            }
+            let (mut sender, mut receiver) = codecs(frame(checksum), sealed);
+            let mut buf = BytesMut::new();
+            for msg in &msgs {
+                sender.encode(msg.clone(), &mut buf).unwrap();
+            }
+            for msg in &msgs {
+                if receiver.decode(&mut buf).unwrap().as_ref() != Some(msg) {
+                    return TestResult::failed();
+                }
+            }
+            TestResult::from_bool(receiver.decode(&mut buf).unwrap().is_none() && buf.is_empty())
+        }
+        QuickCheck::new().tests(50).quickcheck(roundtrip as fn(Vec<Vec<u8>>, bool, bool) -> TestResult);
+    }
+
+    #[test]
+    fn partial_frames_wait_for_more() {
+        fn partial(msg: Vec<u8>, split: usize, checksum: bool) -> TestResult {
+            let (mut sender, mut receiver) = codecs(frame(checksum), false);
+            let frame = encode(&mut sender, &msg);
+            let split = split % frame.len();
+            let mut buf = BytesMut::from(&frame[..split]);
+            if receiver.decode(&mut buf).unwrap().is_some() {
+                return TestResult::failed();
+            }
+            buf.extend_from_slice(&frame[split..]);
+            TestResult::from_bool(receiver.decode(&mut buf).unwrap() == Some(msg))
+        }
+        quickcheck(partial as fn(Vec<u8>, usize, bool) -> TestResult);
+    }
+
+    #[test]
+    fn fuzz_random_bytes() {
+        // whatever a peer sends, decoding never panics nor returns a frame without the magic
+        fn random(data: Vec<u8>, max_frame_size: usize) -> TestResult {
+            let mut codec = CitaCodec::new(FrameConfig {
+                                               max_frame_size: max_frame_size,
+                                               checksum: false,
+                                               compress: false,
+                                               batch: false,
+                                           });
+            let mut buf = BytesMut::from(&data[..]);
+            match codec.decode(&mut buf) {
+                Ok(Some(_)) => TestResult::from_bool(data.len() >= HEADER_LEN && BigEndian::read_u32(&data[..4]) == MAGIC),
+                Ok(None) => TestResult::from_bool(data.len() < 4 || BigEndian::read_u32(&data[..4]) == MAGIC),
+                Err(_) => TestResult::passed(),
+            }
+        }
+        QuickCheck::new().tests(1000).quickcheck(random as fn(Vec<u8>, usize) -> TestResult);
+    }
+
+    #[test]
+    fn fuzz_corrupted_frames() {
+        // a corrupted frame is rejected or incomplete, it never yields a different payload
+        fn corrupted(msg: Vec<u8>, index: usize, bit: u8, sealed: bool) -> TestResult {
+            if msg.len() + TAG_LEN > 1024 {
+                return TestResult::discard();
+            }
+            let (mut sender, mut receiver) = codecs(frame(true), sealed);
+            let mut frame = encode(&mut sender, &msg).to_vec();
+            let index = index % frame.len();
+            frame[index] ^= 1 << (bit % 8);
+            let mut buf = BytesMut::from(&frame[..]);
+            match receiver.decode(&mut buf) {
+                Ok(Some(decoded)) => TestResult::from_bool(decoded == msg),
+                Ok(None) | Err(_) => TestResult::passed(),
+            }
+        }
+        QuickCheck::new().tests(500).quickcheck(corrupted as fn(Vec<u8>, usize, u8, bool) -> TestResult);
+    }
+
+    #[test]
+    fn reject_bad_header() {
+        let (mut sender, mut receiver) = codecs(frame(false), false);
+        let frame = encode(&mut sender, b"block");
+
+        let mut bad_magic = BytesMut::from(&b"GET / HTTP/1.1"[..]);
+        assert!(receiver.decode(&mut bad_magic).is_err());
+
+        let mut bad_version = frame.clone();
+        bad_version[4] = VERSION + 1;
+        assert!(receiver.decode(&mut bad_version).is_err());
+
+        let mut bad_flags = frame.clone();
+        bad_flags[5] = 0x80;
+        assert!(receiver.decode(&mut bad_flags).is_err());
+
+        // refused from the header alone, before any of the payload arrives
+        let mut oversized = BytesMut::from(&frame[..HEADER_LEN]);
+        BigEndian::write_u32(&mut oversized[6..HEADER_LEN], 1025);
+        assert!(receiver.decode(&mut oversized).is_err());
+        assert!(sender.encode(vec![0u8; 1025], &mut BytesMut::new()).is_err());
+    }
+
+    #[test]
+    fn batch_roundtrip() {
+        fn batch_roundtrip(msgs: Vec<Vec<u8>>, checksum: bool, sealed: bool) -> TestResult {
+            if msgs.iter().any(|msg| msg.len() + TAG_LEN > 1024) {
+                return TestResult::discard();
+            }
+            let (sender, mut receiver) = codecs(frame(checksum), sealed);
+            let mut sender = BatchCodec::new(sender);
+            let mut buf = BytesMut::new();
+            sender.encode(msgs.clone(), &mut buf).unwrap();
+            TestResult::from_bool(decode_all(&mut receiver, &mut buf) == msgs && buf.is_empty())
+        }
+        QuickCheck::new().tests(50).quickcheck(batch_roundtrip as fn(Vec<Vec<u8>>, bool, bool) -> TestResult);
+    }
+
+    #[test]
+    fn batch_in_one_frame() {
+        let msgs = vec![b"vote".to_vec(), Vec::new(), b"proposal".to_vec()];
+        let (sender, mut receiver) = codecs(frame(false), true);
+        let mut sender = BatchCodec::new(sender);
+        let mut buf = BytesMut::new();
+        sender.encode(msgs.clone(), &mut buf).unwrap();
+        assert_eq!(buf[5], FLAG_BATCH);
+        assert_eq!(buf.len(), HEADER_LEN + 3 * BATCH_LEN_PREFIX + 12 + TAG_LEN);
+        assert_eq!(decode_all(&mut receiver, &mut buf), msgs);
+
+        // over the limit it is split, and a peer without batches gets a frame each
+        let msgs = vec![vec![1u8; 600], vec![2u8; 600]];
+        for &sealed in &[true, false] {
+            let (sender, mut receiver) = codecs(FrameConfig { compress: false, ..frame(false) }, sealed);
+            let mut sender = BatchCodec::new(sender);
+            let mut buf = BytesMut::new();
+            sender.encode(msgs.clone(), &mut buf).unwrap();
+            assert_eq!(buf[5], 0);
+            assert_eq!(decode_all(&mut receiver, &mut buf), msgs);
+        }
+    }
+
+    #[test]
+    fn compress_when_both_offer() {
+        let msg = vec![7u8; 900];
+        let (mut sender, mut receiver) = codecs(frame(true), true);
+        let mut buf = encode(&mut sender, &msg);
+        assert_eq!(buf[5], FLAG_CHECKSUM | FLAG_COMPRESSED);
+        assert!(buf.len() < msg.len());
+        assert_eq!(receiver.decode(&mut buf).unwrap(), Some(msg.clone()));
+
+        // small msgs are left alone
+        let mut buf = encode(&mut sender, b"vote");
+        assert_eq!(buf[5], FLAG_CHECKSUM);
+        assert_eq!(receiver.decode(&mut buf).unwrap(), Some(b"vote".to_vec()));
+
+        let uncompressed = FrameConfig { compress: false, ..frame(false) };
+        for &(a, b) in &[(frame(false), uncompressed), (uncompressed, frame(false))] {
+            let (mut sender, mut receiver) = codecs_between(a, b, true);
+            let mut buf = encode(&mut sender, &msg);
+            assert_eq!(buf[5], 0);
+            assert_eq!(receiver.decode(&mut buf).unwrap(), Some(msg.clone()));
+        }
+    }
+
+    #[test]
+    fn reject_tampered_flags_and_bombs() {
+        // flags are authenticated, a frame can't be turned into a batch on the way
+        let (mut sender, mut receiver) = codecs(frame(false), true);
+        let mut buf = encode(&mut sender, &[0, 0, 0, 1, 9]);
+        buf[5] |= FLAG_BATCH;
+        assert!(receiver.decode(&mut buf).is_err());
+
+        // nor may a frame decompress to more than the limit
+        let big = FrameConfig { max_frame_size: 1 << 20, ..frame(false) };
+        let (mut sender, mut receiver) = codecs_between(big, frame(false), true);
+        let mut buf = encode(&mut sender, &vec![0u8; 16000]);
+        assert!(buf.len() < 1024);
+        assert!(receiver.decode(&mut buf).is_err());
+
+        // plaintext has neither
+        let (mut sender, mut receiver) = codecs(frame(false), false);
+        let mut buf = encode(&mut sender, &vec![0u8; 900]);
+        assert_eq!(buf[5], 0);
+        buf[5] = FLAG_COMPRESSED;
+        assert!(receiver.decode(&mut buf).is_err());
+    }
+
+    /// Msgs of the chain_performance scenario: `count` contract creations, each as auth
+    /// relays it, and a block of them all as consensus sends it.
+    fn chain_performance_msgs(count: usize) -> (Vec<Vec<u8>>, Vec<u8>) {
+        let code = "60606040523415600e57600080fd5b5b5b5b60948061001f6000396000f30060606040526000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff1680635524107714603d575b600080fd5b3415604757600080fd5b605b6004808035906020019091905050605d565b005b806000819055505b505600a165627a7a72305820c471b4376626da2540b2374e8b4110501051c426ff46814a6170ce9e219e49a80029";
+        let keypair = KeyPair::gen_keypair();
+        let mut txs = Vec::new();
+        for nonce in 0..count {
+            let mut tx = Transaction::new();
+            tx.set_data(code.from_hex().unwrap());
+            tx.set_nonce(nonce.to_string());
+            tx.set_quota(999999);
+            tx.set_valid_until_block(99999);
+            txs.push(tx.sign(*keypair.privkey()));
+        }
+        let tx_msgs = txs.iter()
+            .map(|tx| factory::create_msg(submodules::AUTH, topics::REQUEST, MsgType::MSG, tx.write_to_bytes().unwrap()).write_to_bytes().unwrap())
+            .collect();
+        let mut block = Block::new();
+        block.mut_header().set_height(1);
+        block.mut_body().set_transactions(RepeatedField::from_vec(txs));
+        let block_msg = factory::create_msg(submodules::CONSENSUS, topics::NEW_BLK, MsgType::BLOCK, block.write_to_bytes().unwrap());
+        (tx_msgs, block_msg.write_to_bytes().unwrap())
+    }
+
+    fn big_frame(compress: bool) -> FrameConfig {
+        FrameConfig { compress: compress, ..FrameConfig::default() }
+    }
+
+    /// Write and read `batches` over a sealed connection
+    fn bench_batches(b: &mut Bencher, batches: Vec<Batch>, frame: FrameConfig) {
+        let bytes: u64 = batches.iter().flat_map(|batch| batch.iter()).map(|msg| msg.len() as u64).sum();
+        let (sender, mut receiver) = codecs(frame, true);
+        let mut sender = BatchCodec::new(sender);
+        b.bytes = bytes;
+        b.iter(|| {
+                   let mut buf = BytesMut::new();
+                   for batch in batches.clone() {
+                       sender.encode(batch, &mut buf).unwrap();
+                   }
+                   decode_all(&mut receiver, &mut buf).len()
+               });
+    }
+
+    #[bench]
+    fn bench_txs_frame_each(b: &mut Bencher) {
+        let (txs, _) = chain_performance_msgs(1000);
+        bench_batches(b, txs.into_iter().map(|tx| vec![tx]).collect(), big_frame(false));
+    }
+
+    #[bench]
+    fn bench_txs_batched(b: &mut Bencher) {
+        let (txs, _) = chain_performance_msgs(1000);
+        bench_batches(b, txs.chunks(100).map(|txs| txs.to_vec()).collect(), big_frame(false));
+    }
+
+    #[bench]
+    fn bench_txs_batched_compressed(b: &mut Bencher) {
+        let (txs, _) = chain_performance_msgs(1000);
+        bench_batches(b, txs.chunks(100).map(|txs| txs.to_vec()).collect(), big_frame(true));
+    }
+
+    #[bench]
+    fn bench_block(b: &mut Bencher) {
+        let (_, block) = chain_performance_msgs(4000);
+        bench_batches(b, vec![vec![block]], big_frame(false));
+    }
+
+    #[bench]
+    fn bench_block_compressed(b: &mut Bencher) {
+        let (_, block) = chain_performance_msgs(4000);
+        bench_batches(b, vec![vec![block]], big_frame(true));
     }
 }
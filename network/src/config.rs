@@ -17,16 +17,45 @@
 
 extern crate toml;
 
+use citaprotocol::{DEFAULT_MAX_FRAME_SIZE, FrameConfig};
+use crypto::PrivKey;
+use reputation::{DEFAULT_BAN_SECS, DEFAULT_BAN_THRESHOLD, ReputationConfig};
 use std::fs::File;
 use std::io::BufReader;
 use std::io::prelude::*;
+use std::str::FromStr;
 
 #[derive(Debug, RustcDecodable)]
 pub struct NetConfig {
     pub id_card: Option<u32>,
     pub port: Option<u64>,
     pub max_peer: Option<u64>,
+    /// Seeds, always connected
     pub peers: Option<Vec<PeerConfig>>,
+    /// Nodes to discover the others from, dropped like any discovered peer once they stop answering
+    pub bootstrap: Option<Vec<PeerConfig>>,
+    /// Address other nodes reach this one at, it is not advertised when unset
+    pub ip: Option<String>,
+    /// Where discovered peers are kept across restarts
+    pub peers_file: Option<String>,
+    /// Node private key in hex, used to authenticate and encrypt connections
+    pub signer: Option<String>,
+    /// Skip the handshake and send plaintext, for local dev only
+    pub plaintext: Option<bool>,
+    /// Largest frame payload in bytes accepted from or sent to peers
+    pub max_frame_size: Option<usize>,
+    /// Append a checksum to every frame sent
+    pub checksum: Option<bool>,
+    /// Compress frames to peers which take it, on by default
+    pub compress: Option<bool>,
+    /// Write the msgs queued within a few ms at once, in batch frames, on by default
+    pub batch: Option<bool>,
+    /// Score at which a misbehaving peer gets banned, below zero
+    pub ban_threshold: Option<i64>,
+    /// How long a ban lasts, in seconds
+    pub ban_secs: Option<u64>,
+    /// Take net_addPeer and net_removePeer. Every client of jsonrpc may call them, so off by default
+    pub admin_rpc: Option<bool>,
 }
 
 #[derive(Debug, RustcDecodable)]
@@ -45,11 +74,57 @@ impl NetConfig {
         toml::decode_str(&content).unwrap()
     }
 
+    /// Key to handshake with, `None` when plaintext is allowed.
+    pub fn signer(&self) -> Option<PrivKey> {
+        if self.plaintext.unwrap_or(false) {
+            warn!("plaintext is enabled, connections are neither authenticated nor encrypted");
+            return None;
+        }
+        let signer = self.signer.as_ref().expect("signer must be set unless plaintext is enabled");
+        Some(PrivKey::from_str(signer.trim_left_matches("0x")).expect("invalid signer"))
+    }
+
+    /// Whether to discover peers. Addresses are only learned from a proved session,
+    /// so it is off in plaintext, and bootstrap nodes are refused there.
+    pub fn discovery(&self) -> bool {
+        if !self.plaintext.unwrap_or(false) {
+            return true;
+        }
+        assert!(self.bootstrap.is_none(), "bootstrap needs discovery, which is off in plaintext");
+        warn!("discovery is off in plaintext, only the configured peers are connected");
+        false
+    }
+
+    pub fn frame(&self) -> FrameConfig {
+        let max_frame_size = self.max_frame_size.unwrap_or(DEFAULT_MAX_FRAME_SIZE);
+        assert!(max_frame_size <= u32::max_value() as usize, "max_frame_size must fit in the 32 bit length of a frame");
+        FrameConfig {
+            max_frame_size: max_frame_size,
+            checksum: self.checksum.unwrap_or(false),
+            compress: self.compress.unwrap_or(true),
+            batch: self.batch.unwrap_or(true),
+        }
+    }
+
+    pub fn reputation(&self) -> ReputationConfig {
+        let ban_threshold = self.ban_threshold.unwrap_or(DEFAULT_BAN_THRESHOLD);
+        assert!(ban_threshold < 0, "ban_threshold must be below zero");
+        ReputationConfig {
+            ban_threshold: ban_threshold,
+            ban_secs: self.ban_secs.unwrap_or(DEFAULT_BAN_SECS),
+        }
+    }
+
+    pub fn admin_rpc(&self) -> bool {
+        self.admin_rpc.unwrap_or(false)
+    }
+
     pub fn test_config() -> Self {
         let toml = r#"
             id_card=0
             port = 40000
             max_peer = 1
+            plaintext = true
             [[peers]]
             id_card=0
             ip = "127.0.0.1"
@@ -65,6 +140,8 @@ impl NetConfig {
 #[cfg(test)]
 mod test {
     use super::NetConfig;
+    use citaprotocol::FrameConfig;
+    use reputation::ReputationConfig;
     extern crate toml;
     #[test]
     fn basics() {
@@ -83,4 +160,91 @@ mod test {
         println!("{:?}", value);
         assert_eq!(value.port, Some(40000));
     }
+
+    #[test]
+    fn discovery() {
+        let toml = r#"
+            port = 40000
+            ip = "10.0.0.1"
+            peers_file = "peers.json"
+            [[bootstrap]]
+            id_card = 1
+            ip = "10.0.0.2"
+            port = 40000
+        "#;
+        let value: NetConfig = toml::decode_str(toml).unwrap();
+        assert_eq!(value.ip, Some("10.0.0.1".to_string()));
+        assert_eq!(value.bootstrap.unwrap()[0].id_card, Some(1));
+        assert!(value.peers.is_none());
+    }
+
+    #[test]
+    fn frame() {
+        let toml = r#"
+            port = 40000
+            max_frame_size = 1048576
+            checksum = true
+            compress = false
+            batch = false
+        "#;
+        let value: NetConfig = toml::decode_str(toml).unwrap();
+        let frame = value.frame();
+        assert_eq!(frame.max_frame_size, 1048576);
+        assert!(frame.checksum);
+        assert!(!frame.compress);
+        assert!(!frame.batch);
+        assert_eq!(NetConfig::test_config().frame(), FrameConfig::default());
+    }
+
+    #[test]
+    fn reputation() {
+        let toml = r#"
+            port = 40000
+            ban_threshold = -50
+            ban_secs = 60
+        "#;
+        let value: NetConfig = toml::decode_str(toml).unwrap();
+        assert_eq!(value.reputation(), ReputationConfig { ban_threshold: -50, ban_secs: 60 });
+        assert_eq!(NetConfig::test_config().reputation(), ReputationConfig::default());
+    }
+
+    #[test]
+    fn signer() {
+        let toml = r#"
+            port = 40000
+            signer = "0xa100df7a048e50ed308ea696dc600215098141cb391e9527329df289f9383f65"
+        "#;
+        let value: NetConfig = toml::decode_str(toml).unwrap();
+        assert!(value.signer().is_some());
+        assert!(NetConfig::test_config().signer().is_none());
+    }
+
+    #[test]
+    fn discovery_needs_a_session() {
+        let value: NetConfig = toml::decode_str("port = 40000").unwrap();
+        assert!(value.discovery());
+        assert!(!NetConfig::test_config().discovery());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bootstrap_in_plaintext() {
+        let toml = r#"
+            port = 40000
+            plaintext = true
+            [[bootstrap]]
+            id_card = 1
+            ip = "10.0.0.2"
+            port = 40000
+        "#;
+        let value: NetConfig = toml::decode_str(toml).unwrap();
+        value.discovery();
+    }
+
+    #[test]
+    fn admin_rpc() {
+        let value: NetConfig = toml::decode_str("port = 40000\nadmin_rpc = true").unwrap();
+        assert!(value.admin_rpc());
+        assert!(!NetConfig::test_config().admin_rpc());
+    }
 }
@@ -15,190 +15,578 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use byteorder::{BigEndian, ByteOrder};
+use citaprotocol::{Batch, BatchCodec, CitaCodec, FrameConfig, not_in_node_list};
 use config;
+use crypto::PrivKey;
+use futures::{future, Async, Future, Poll, Sink, Stream};
+use futures::future::{Loop, loop_fn};
+use futures::sync::mpsc;
 use libproto::communication;
+use nodes::SharedNodeList;
 use protobuf::Message;
+use reputation::{Reputation, SharedReputation};
+use session::{Handshake, HANDSHAKE_TIMEOUT, HELLO_LEN, PROOF_LEN};
+use stats::{self, InboundStats, PeerInfo, TopicTraffic};
+use std::cmp;
 use std::convert::AsRef;
-use std::io::prelude::*;
-use std::net::TcpStream;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::net::ToSocketAddrs;
 use std::sync::Arc;
-use std::sync::mpsc::Receiver;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, channel};
 use std::thread;
 use std::time::Duration;
-use util::RwLock;
+use tokio_core::net::TcpStream;
+use tokio_core::reactor::{Core, Handle, Interval, Remote, Timeout};
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::codec::Framed;
+use tokio_io::io::{read_exact, write_all};
+use util::{Address, Mutex, RwLock};
 
+/// Seconds between keepalives
 const TIMEOUT: u64 = 15;
-//表示线程已经运行
-type ThreadRuned = Arc<RwLock<bool>>;
-//已经运行的线程是否结束
-type ThreadExit = Arc<RwLock<bool>>;
+/// Frames queued for a peer, more are dropped while it is slow
+const QUEUE_SIZE: usize = 1024;
+/// Seconds to wait before reconnecting, doubled after each failure
+const MIN_BACKOFF: u64 = 1;
+const MAX_BACKOFF: u64 = 64;
+/// Milliseconds to wait for more msgs before writing what is queued
+const BATCH_DELAY: u64 = 2;
+/// Bytes of msgs written at once without waiting
+const BATCH_SIZE: usize = 256 * 1024;
 
-pub struct Connection {
+/// Where a peer comes from, which decides who may remove it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeerSource {
+    Config,
+    Discovery,
+    Admin,
+}
+
+impl PeerSource {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            PeerSource::Config => "config",
+            PeerSource::Discovery => "discovery",
+            PeerSource::Admin => "admin",
+        }
+    }
+}
+
+struct PeerState {
+    // set while connected
+    queue: Mutex<Option<mpsc::Sender<Vec<u8>>>>,
+    closed: AtomicBool,
+    dropped: AtomicUsize,
+    attempts: AtomicUsize,
+    // proved in the last handshake
+    address: Mutex<Option<Address>>,
+    // the id_card is from the config or the admin, not from the peer itself
+    trusted: AtomicBool,
+    sent: Mutex<TopicTraffic>,
+    // write calls on the connection's socket
+    writes: AtomicUsize,
+}
+
+impl PeerState {
+    fn new() -> Self {
+        PeerState {
+            queue: Mutex::new(None),
+            closed: AtomicBool::new(false),
+            dropped: AtomicUsize::new(0),
+            attempts: AtomicUsize::new(0),
+            address: Mutex::new(None),
+            trusted: AtomicBool::new(false),
+            sent: Mutex::new(TopicTraffic::new()),
+            writes: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Outgoing side of a peer. Its task on the client reactor keeps it connected
+/// and writes what is queued, until the peer is dropped.
+pub struct Peer {
     pub id_card: u32,
-    pub peers_pair: Vec<(u32, String, Arc<RwLock<Option<TcpStream>>>, ThreadRuned, ThreadExit)>,
-    pub remove_addr: Arc<RwLock<Vec<String>>>,
+    pub addr: String,
+    pub source: PeerSource,
+    state: Arc<PeerState>,
 }
 
-impl Connection {
-    pub fn new(config: &config::NetConfig) -> Self {
-        let id_card = config.id_card.unwrap();
-        let mut peers_pair = Vec::default();
-        match config.peers.as_ref() {
-            Some(peers) => {
-                for peer in peers.iter() {
-                    let id_card: u32 = peer.id_card.unwrap();
-                    let addr = format!("{}:{}", peer.ip.clone().unwrap(), peer.port.unwrap());
-                    let addr = addr.parse::<String>().unwrap();
-                    peers_pair.push((id_card, addr, Arc::new(RwLock::new(None)), Arc::new(RwLock::new(false)), Arc::new(RwLock::new(false))));
-                }
-            }
-            None => (),
+impl Peer {
+    pub fn is_connected(&self) -> bool {
+        self.state.queue.lock().is_some()
+    }
 
+    /// Queue `msg` without waiting. Returns false when the peer is not connected or too slow.
+    pub fn send(&self, msg: Vec<u8>) -> bool {
+        let mut queue = self.state.queue.lock();
+        match queue.as_mut().map(|queue| queue.try_send(msg)) {
+            Some(Ok(())) => true,
+            Some(Err(ref e)) if e.is_full() => {
+                self.state.dropped.fetch_add(1, Ordering::Relaxed);
+                trace!("queue to {:?} is full, drop msg", self.addr);
+                false
+            }
+            _ => false,
         }
+    }
 
-        Connection {
-            id_card,
-            peers_pair,
-            remove_addr: Arc::new(RwLock::new(Vec::default())),
+    fn set_source(&mut self, source: PeerSource) {
+        self.source = source;
+        self.state.trusted.store(source != PeerSource::Discovery, Ordering::SeqCst);
+    }
+
+    /// Address proved in the last handshake, none before it or in plaintext
+    pub fn address(&self) -> Option<Address> {
+        *self.state.address.lock()
+    }
+
+    /// Write calls on the sockets of this peer's connections, each one is a syscall
+    pub fn writes(&self) -> usize {
+        self.state.writes.load(Ordering::Relaxed)
+    }
+
+    pub fn record_sent(&self, cmd_id: u32, bytes: usize) {
+        stats::record(&mut self.state.sent.lock(), cmd_id, bytes);
+    }
+
+    pub fn info(&self, inbound: &InboundStats, reputation: &Reputation) -> PeerInfo {
+        let received = inbound.get(self.id_card).cloned().unwrap_or_default();
+        let now = stats::unix_now();
+        let address = self.address();
+        PeerInfo {
+            id_card: self.id_card,
+            address: address.map(|address| address.hex()),
+            endpoint: self.addr.clone(),
+            source: self.source.as_str().to_string(),
+            state: if self.is_connected() { "connected" } else { "connecting" }.to_string(),
+            last_seen: if received.last_seen > 0 { Some(received.last_seen) } else { None },
+            reconnects: self.state.attempts.load(Ordering::Relaxed).saturating_sub(1) as u64,
+            height: received.height,
+            dropped: self.state.dropped.load(Ordering::Relaxed) as u64,
+            sent: self.state.sent.lock().clone(),
+            received: received.traffic,
+            score: address.map_or(0, |address| reputation.score(&address, now)),
+            banned_until: address.and_then(|address| reputation.banned_until(&address, now)),
         }
     }
+}
+
+impl Drop for Peer {
+    fn drop(&mut self) {
+        self.state.closed.store(true, Ordering::SeqCst);
+        // ends the queue, so the task closes the connection
+        self.state.queue.lock().take();
+    }
+}
+
+pub struct Connection {
+    pub id_card: u32,
+    pub peers: Vec<Peer>,
+    pub signer: Option<PrivKey>,
+    pub nodes: SharedNodeList,
+    pub frame: FrameConfig,
+    pub reputation: SharedReputation,
+    remote: Remote,
+}
+
+impl Connection {
+    pub fn new(config: &config::NetConfig, nodes: SharedNodeList, reputation: SharedReputation) -> Self {
+        let mut con = Connection {
+            id_card: config.id_card.unwrap(),
+            peers: Vec::new(),
+            signer: config.signer(),
+            nodes: nodes,
+            frame: config.frame(),
+            reputation: reputation,
+            remote: start_reactor("client"),
+        };
+        con.update(config);
+        con
+    }
+
+    fn push_peer(&mut self, id_card: u32, addr: String, source: PeerSource) {
+        let state = Arc::new(PeerState::new());
+        state.trusted.store(source != PeerSource::Discovery, Ordering::SeqCst);
+        let task_state = state.clone();
+        let task_addr = addr.clone();
+        let signer = self.signer;
+        let nodes = self.nodes.clone();
+        let frame = self.frame;
+        let reputation = self.reputation.clone();
+        self.remote.spawn(move |handle| run_peer(handle.clone(), task_addr, id_card, signer, nodes, frame, reputation, task_state));
+        self.peers.push(Peer {
+                            id_card: id_card,
+                            addr: addr,
+                            source: source,
+                            state: state,
+                        });
+    }
+
+    fn find(&mut self, addr: &str) -> Option<&mut Peer> {
+        self.peers.iter_mut().find(|peer| peer.addr == addr)
+    }
 
     pub fn update(&mut self, config: &config::NetConfig) {
         //添加更新的配置到self
-        match config.peers.as_ref() {
-            Some(peers) => {
-                let peers_addr: Vec<String> = self.peers_pair.clone().into_iter().map(|peers_pair| peers_pair.1).rev().collect();
-                let mut config_addr = Vec::new();
-                for peer in peers.iter() {
-                    let id_card: u32 = peer.id_card.unwrap();
-                    let addr = format!("{}:{}", peer.ip.clone().unwrap(), peer.port.unwrap());
-                    config_addr.push(addr.clone());
-                    if peers_addr.contains(&addr) {
-                        continue;
-                    }
-                    self.peers_pair
-                        .push((id_card, addr, Arc::new(RwLock::new(None)), Arc::new(RwLock::new(false)), Arc::new(RwLock::new(false))));
-                }
-                for &(_, ref addr, _, _, ref thread_exit) in &self.peers_pair {
-                    if config_addr.contains(&addr) {
-                        continue;
+        let mut config_addr = Vec::new();
+        if let Some(peers) = config.peers.as_ref() {
+            for peer in peers.iter() {
+                let id_card: u32 = peer.id_card.unwrap();
+                let addr = format!("{}:{}", peer.ip.clone().unwrap(), peer.port.unwrap());
+                config_addr.push(addr.clone());
+                let added = match self.find(&addr) {
+                    Some(peer) => {
+                        peer.set_source(PeerSource::Config);
+                        true
                     }
-                    let thread_exit = &mut *thread_exit.as_ref().write();
-                    *thread_exit = true;
+                    None => false,
+                };
+                if !added {
+                    self.push_peer(id_card, addr, PeerSource::Config);
                 }
             }
-            None => (),
         }
+        self.peers.retain(|peer| peer.source != PeerSource::Config || config_addr.contains(&peer.addr));
     }
 
-    pub fn del_peer(&mut self) {
-        let remove_addr = &mut *self.remove_addr.as_ref().write();
-        if !remove_addr.is_empty() {
-            let mut index = 0;
-            let mut len = self.peers_pair.len();
-            loop {
-                if index >= len {
-                    break;
-                }
-                if remove_addr.contains(&self.peers_pair[index].1) {
-                    self.peers_pair.remove(index);
-                    len = self.peers_pair.len();
-                } else {
-                    index += 1;
-                }
-            }
-            remove_addr.clear();
+    /// Add a peer found by discovery, unless it is connected already.
+    pub fn add_peer(&mut self, id_card: u32, addr: String) {
+        if self.find(&addr).is_none() {
+            self.push_peer(id_card, addr, PeerSource::Discovery);
         }
     }
-}
 
+    /// Stop connecting a peer added by discovery, the others are kept.
+    pub fn remove_peer(&mut self, addr: &str) {
+        self.peers.retain(|peer| peer.addr != addr || peer.source != PeerSource::Discovery);
+    }
 
-pub fn do_connect(con: &Connection) {
-    for &(_, ref addr, ref stream, ref thread_run, ref thread_exit) in &con.peers_pair {
-        let stream_lock = stream.clone();
-        let thread_run_lock = thread_run.clone();
-        let thread_exit_lock = thread_exit.clone();
-        if *thread_run.read() {
-            continue;
+    /// Add a peer by the admin rpc. It stays until removed the same way, or till restart.
+    pub fn admin_add_peer(&mut self, id_card: u32, addr: String) {
+        let added = match self.find(&addr) {
+            Some(peer) => {
+                peer.set_source(PeerSource::Admin);
+                true
+            }
+            None => false,
+        };
+        if !added {
+            self.push_peer(id_card, addr, PeerSource::Admin);
         }
+    }
 
-        let remove_addr_lock = con.remove_addr.clone();
-        let addr = addr.to_string();
-        thread::spawn(move || loop {
-                          {
-                              if *thread_exit_lock.read() {
-                                  let remove_addr = &mut *remove_addr_lock.as_ref().write();
-                                  trace!("{:?} thread exit", addr.clone());
-                                  remove_addr.push(String::from(addr.clone()));
-                                  let stream_opt = &mut *stream_lock.as_ref().write();
-                                  *stream_opt = None;
-                                  break;
-                              }
-                              {
-                                  let thread_run = &mut *thread_run_lock.as_ref().write();
-                                  *thread_run = true;
-                              }
+    /// Remove any peer by the admin rpc, a seed comes back when the config file changes.
+    /// Returns false when there is no such peer.
+    pub fn admin_remove_peer(&mut self, addr: &str) -> bool {
+        let len = self.peers.len();
+        self.peers.retain(|peer| peer.addr != addr);
+        self.peers.len() != len
+    }
 
-                              let stream_opt = &mut *stream_lock.as_ref().write();
-                              if stream_opt.is_none() {
-                                  trace!("connet {:?}", addr.clone());
-                                  let stream = TcpStream::connect(addr.clone()).ok();
-                                  *stream_opt = stream;
-                              }
+    pub fn peers_info(&self, inbound: &InboundStats) -> Vec<PeerInfo> {
+        let reputation = self.reputation.read();
+        self.peers.iter().map(|peer| peer.info(inbound, &reputation)).collect()
+    }
 
-                              let mut need_reconnect = false;
-                              if let Some(ref mut stream) = stream_opt.as_mut() {
-                                  trace!("handshake with {:?}!", addr);
-                                  let mut header = [0; 8];
-                                  BigEndian::write_u64(&mut header, 0xDEADBEEF00000000 as u64);
-                                  let res = stream.write(&header);
-                                  if res.is_err() {
-                                      warn!("handshake with {:?} error!", addr);
-                                      need_reconnect = true;
-                                  }
-                              }
+    /// Send a keepalive to every peer now. The codec fails on peers which
+    /// dropped out of the node list, so they are disconnected.
+    pub fn disconnect_unlisted(&self) {
+        for peer in &self.peers {
+            peer.send(Vec::new());
+        }
+    }
+}
+
+/// Run a reactor on its own thread, for the tasks spawned on the returned remote
+pub fn start_reactor(name: &'static str) -> Remote {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+                      info!("start {}!", name);
+                      let mut core = Core::new().unwrap();
+                      tx.send(core.remote()).unwrap();
+                      core.run(future::empty::<(), ()>()).unwrap();
+                  });
+    rx.recv().unwrap()
+}
+
+fn next_backoff(backoff: u64) -> u64 {
+    cmp::min(backoff * 2, MAX_BACKOFF)
+}
 
-                              if need_reconnect {
-                                  *stream_opt = None;
+/// Keep connecting `addr`, waiting longer after each failure, until the peer is dropped.
+/// A trusted peer's `id_card` is bound to the address each handshake proves.
+fn run_peer(handle: Handle, addr: String, id_card: u32, signer: Option<PrivKey>, nodes: SharedNodeList, frame: FrameConfig, reputation: SharedReputation, state: Arc<PeerState>) -> Box<Future<Item = (), Error = ()>> {
+    let task = loop_fn(MIN_BACKOFF, move |backoff| -> Box<Future<Item = Loop<(), u64>, Error = ()>> {
+        if state.closed.load(Ordering::SeqCst) {
+            trace!("{:?} task exit", addr);
+            return Box::new(future::ok(Loop::Break(())));
+        }
+        let session_handle = handle.clone();
+        let wait_handle = handle.clone();
+        let session_addr = addr.clone();
+        let wait_addr = addr.clone();
+        let session_state = state.clone();
+        let session_reputation = reputation.clone();
+        trace!("connect {:?}", addr);
+        state.attempts.fetch_add(1, Ordering::Relaxed);
+        let task = connect(&addr, signer, nodes.clone(), frame, state.clone(), &handle)
+            .and_then(move |(framed, address)| {
+                          if let Some(address) = address {
+                              if session_state.trusted.load(Ordering::SeqCst) {
+                                  session_reputation.write().bind(id_card, address);
                               }
                           }
+                          *session_state.address.lock() = address;
+                          serve(framed, session_state, frame.batch, &session_handle, session_addr)
+                      })
+            .then(move |res| {
+                let (wait, backoff) = match res {
+                    Ok(()) => (MIN_BACKOFF, MIN_BACKOFF),
+                    Err(e) => {
+                        warn!("connect {:?} error: {:?}, retry in {}s", wait_addr, e, backoff);
+                        (backoff, next_backoff(backoff))
+                    }
+                };
+                Timeout::new(Duration::from_secs(wait), &wait_handle)
+                    .unwrap()
+                    .then(move |_| Ok::<_, ()>(Loop::Continue(backoff)))
+            });
+        Box::new(task)
+    });
+    Box::new(task)
+}
 
-                          let ten_sec = Duration::from_millis(TIMEOUT * 1000);
-                          thread::sleep(ten_sec);
-                          trace!("after sleep retry connect {:?}!", addr);
-                      });
+/// Connect and handshake, yields the framed stream and the address the peer proved.
+fn connect(addr: &str, signer: Option<PrivKey>, nodes: SharedNodeList, frame: FrameConfig, state: Arc<PeerState>, handle: &Handle) -> Box<Future<Item = (Framed<Counted, BatchCodec>, Option<Address>), Error = io::Error>> {
+    let socket_addr = match addr.to_socket_addrs().map(|mut addrs| addrs.next()) {
+        Ok(Some(socket_addr)) => socket_addr,
+        Ok(None) => return Box::new(future::err(io::Error::new(io::ErrorKind::InvalidInput, "no address resolved"))),
+        Err(e) => return Box::new(future::err(e)),
+    };
+    let connecting = TcpStream::connect(&socket_addr, handle);
+    let signer = match signer {
+        Some(signer) => signer,
+        None => return Box::new(connecting.map(move |stream| (Counted::new(stream, state).framed(BatchCodec::new(CitaCodec::new(frame))), None))),
+    };
+
+    // the connecting side sends its hello first, and its proof once the peer proved itself
+    let handshake = Handshake::new(frame.features());
+    let hello = handshake.hello();
+    let addr = addr.to_string();
+    let listed = nodes.clone();
+    let handshaking = connecting
+        .and_then(move |stream| write_all(stream, hello))
+        .and_then(|(stream, _)| read_exact(stream, vec![0u8; HELLO_LEN + PROOF_LEN]))
+        .and_then(move |(stream, reply)| {
+                      let (peer_hello, peer_proof) = reply.split_at(HELLO_LEN);
+                      let proof = handshake.proof(&signer, peer_hello, true);
+                      let session = handshake.finish(peer_hello, peer_proof, true)?;
+                      if !listed.read().contains(session.peer()) {
+                          return Err(not_in_node_list(session.peer()));
+                      }
+                      Ok((stream, proof, session))
+                  })
+        .and_then(|(stream, proof, session)| write_all(stream, proof).map(move |(stream, _)| (stream, session)))
+        .map(move |(stream, session)| {
+                 info!("handshake with {:?} finished, peer is {:?}", addr, session.peer());
+                 let address = *session.peer();
+                 (Counted::new(stream, state).framed(BatchCodec::new(CitaCodec::with_session(frame, session, nodes))), Some(address))
+             });
+    // a peer which never answers must not hold the task
+    let timeout = match Timeout::new(Duration::from_secs(HANDSHAKE_TIMEOUT), handle) {
+        Ok(timeout) => timeout,
+        Err(e) => return Box::new(future::err(e)),
+    };
+    let timeout = timeout.and_then(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "handshake timed out")));
+    Box::new(handshaking.select(timeout).map(|(framed, _)| framed).map_err(|(e, _)| e))
+}
+
+/// Socket of a connection to a peer, counting the write calls on it
+struct Counted {
+    stream: TcpStream,
+    state: Arc<PeerState>,
+}
+
+impl Counted {
+    fn new(stream: TcpStream, state: Arc<PeerState>) -> Self {
+        Counted {
+            stream: stream,
+            state: state,
+        }
+    }
+}
+
+impl Read for Counted {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.read(buf)
+    }
+}
+
+impl Write for Counted {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.state.writes.fetch_add(1, Ordering::Relaxed);
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl AsyncRead for Counted {}
+
+impl AsyncWrite for Counted {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        AsyncWrite::shutdown(&mut self.stream)
+    }
+}
+
+/// Collects the msgs queued within `BATCH_DELAY` of the first one, so a burst of
+/// small msgs is written at once, in one frame when the peer takes batches.
+struct Batching<S> {
+    inner: S,
+    handle: Handle,
+    batch: Batch,
+    bytes: usize,
+    delay: Option<Timeout>,
+    done: bool,
+}
+
+impl<S> Batching<S> {
+    fn new(inner: S, handle: &Handle) -> Self {
+        Batching {
+            inner: inner,
+            handle: handle.clone(),
+            batch: Vec::new(),
+            bytes: 0,
+            delay: None,
+            done: false,
+        }
+    }
+
+    fn take(&mut self) -> Batch {
+        self.delay = None;
+        self.bytes = 0;
+        mem::replace(&mut self.batch, Vec::new())
     }
 }
 
+impl<S: Stream<Item = Vec<u8>, Error = io::Error>> Stream for Batching<S> {
+    type Item = Batch;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Batch>, io::Error> {
+        while !self.done {
+            match self.inner.poll()? {
+                Async::Ready(Some(msg)) => {
+                    self.bytes += msg.len();
+                    self.batch.push(msg);
+                    if self.bytes >= BATCH_SIZE {
+                        return Ok(Async::Ready(Some(self.take())));
+                    }
+                }
+                Async::Ready(None) => self.done = true,
+                Async::NotReady => break,
+            }
+        }
+        if self.batch.is_empty() {
+            return Ok(if self.done { Async::Ready(None) } else { Async::NotReady });
+        }
+        if self.done {
+            return Ok(Async::Ready(Some(self.take())));
+        }
+        if self.delay.is_none() {
+            self.delay = Some(Timeout::new(Duration::from_millis(BATCH_DELAY), &self.handle)?);
+        }
+        let expired = self.delay.as_mut().unwrap().poll()?;
+        match expired {
+            Async::Ready(()) => Ok(Async::Ready(Some(self.take()))),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// Write the queued frames and keepalives to a connected peer, until either side fails or the peer is dropped.
+/// Write errors end the connection rather than the task, so it reconnects.
+/// Without `batch` each msg is written as soon as it is queued.
+fn serve(framed: Framed<Counted, BatchCodec>, state: Arc<PeerState>, batch: bool, handle: &Handle, addr: String) -> Box<Future<Item = (), Error = io::Error>> {
+    let keepalive = match Interval::new(Duration::from_secs(TIMEOUT), handle) {
+        Ok(keepalive) => keepalive,
+        Err(e) => return Box::new(future::err(e)),
+    };
+    let (tx, rx) = mpsc::channel(QUEUE_SIZE);
+    *state.queue.lock() = Some(tx);
+
+    let running = state.clone();
+    let report = state.clone();
+    let report_addr = addr.clone();
+    let mut reported = 0;
+    let keepalive = keepalive
+        .take_while(move |_| Ok(!running.closed.load(Ordering::SeqCst)))
+        .map(move |_| {
+                 let dropped = report.dropped.load(Ordering::Relaxed);
+                 if dropped != reported {
+                     warn!("{} msgs to {:?} dropped as it is too slow", dropped, report_addr);
+                     reported = dropped;
+                 }
+                 trace!("keepalive with {:?}!", report_addr);
+                 Vec::new()
+             });
+    let outgoing = rx.map_err(|_| io::Error::new(io::ErrorKind::Other, "queue error")).select(keepalive);
+    let outgoing: Box<Stream<Item = Batch, Error = io::Error>> = if batch {
+        Box::new(Batching::new(outgoing, handle))
+    } else {
+        Box::new(outgoing.map(|msg| vec![msg]))
+    };
+
+    let (sink, incoming) = framed.split();
+    let writing = sink.send_all(outgoing).map(|_| ());
+    // the server answers every frame with an empty one, read them so its buffers do not fill up
+    let reading = incoming.for_each(|_| Ok(()));
+    let serving = writing.select(reading).then(move |res| {
+        state.queue.lock().take();
+        match res {
+            Ok(_) => info!("connection to {:?} closed", addr),
+            Err((e, _)) => warn!("connection to {:?} error: {:?}", addr, e),
+        }
+        Ok::<(), io::Error>(())
+    });
+    Box::new(serving)
+}
+
 pub fn broadcast(con: &Connection, mut msg: communication::Message) {
     let origin = msg.get_origin();
     let operate = msg.get_operate();
+    let destination = if msg.get_destination().is_empty() { None } else { Some(Address::from_slice(msg.get_destination())) };
     msg.set_origin(con.id_card);
 
     trace!("broadcast msg {:?} ", msg);
+    let cmd_id = msg.get_cmd_id();
     let msg = msg.write_to_bytes().unwrap();
-    let request_id = 0xDEADBEEF00000000 + msg.len();
-    let mut encoded_request_id = [0; 8];
-    BigEndian::write_u64(&mut encoded_request_id, request_id as u64);
-    let mut buf = Vec::new();
-    buf.extend(&encoded_request_id);
-    buf.extend(msg);
-    let send_msg = move |stream: &Arc<RwLock<Option<TcpStream>>>| {
-        let streams_lock = stream.clone();
-        let stream_opt = &mut (*streams_lock.as_ref().write());
-        if let Some(ref mut stream) = stream_opt.as_mut() {
-            let _ = stream.write(&buf);
-        }
-    };
     let mut peers = vec![];
-    for &(id_card, _, ref stream, _, _) in &con.peers_pair {
-        if is_send(id_card, origin, operate) {
-            peers.push(id_card);
-            send_msg(stream);
+    let now = stats::unix_now();
+    let reputation = con.reputation.read();
+    for peer in &con.peers {
+        let address = peer.address();
+        if address.map_or(false, |address| reputation.is_banned(&address, now)) {
+            continue;
+        }
+        let target = match destination {
+            // only a proved address is trusted, so a plaintext peer can't be addressed
+            Some(destination) if operate == communication::OperateType::SINGLE => address == Some(destination),
+            _ => is_send(peer.id_card, origin, operate),
+        };
+        if target && peer.send(msg.clone()) {
+            peer.record_sent(cmd_id, msg.len());
+            peers.push(peer.id_card);
         }
     }
 
+    if operate == communication::OperateType::SINGLE && peers.is_empty() {
+        warn!("no connected peer for msg to {:?}", destination.map_or(origin.to_string(), |address| address.hex()));
+    }
     info!("{:?} broadcast msg to nodes {:?} {:?}", con.id_card, operate, peers);
 }
 
@@ -207,20 +595,139 @@ pub fn is_send(id_card: u32, origin: u32, operate: communication::OperateType) -
 }
 
 pub fn start_client(con: Arc<RwLock<Connection>>, rx: Receiver<communication::Message>) {
-    thread::spawn(move || {
-                      info!("start client!");
-                      loop {
-                          let msg = rx.recv().unwrap();
-                          let con = &*con.as_ref().read();
-                          broadcast(&con, msg);
-                      }
+    thread::spawn(move || loop {
+                      let msg = rx.recv().unwrap();
+                      let con = &*con.as_ref().read();
+                      broadcast(&con, msg);
                   });
 }
 
 #[cfg(test)]
 mod test {
-    use super::is_send;
-    use libproto::communication;
+    extern crate toml;
+    use super::{BATCH_SIZE, Batching, Connection, MAX_BACKOFF, MIN_BACKOFF, Peer, PeerSource, PeerState, broadcast, is_send, next_backoff};
+    use config::NetConfig;
+    use futures::{Future, Stream};
+    use futures::sync::mpsc;
+    use libproto::{communication, factory, submodules, topics};
+    use nodes::NodeList;
+    use reputation::{Reputation, ReputationConfig};
+    use std::io;
+    use std::sync::Arc;
+    use std::sync::atomic::Ordering;
+    use tokio_core::reactor::Core;
+    use util::{Address, RwLock};
+
+    #[test]
+    fn drop_msgs_when_queue_is_full() {
+        let peer = Peer {
+            id_card: 1,
+            addr: "127.0.0.1:40001".to_string(),
+            source: PeerSource::Admin,
+            state: Arc::new(PeerState::new()),
+        };
+        assert!(!peer.is_connected());
+        assert!(!peer.send(vec![1]));
+
+        let (tx, _rx) = mpsc::channel(0);
+        *peer.state.queue.lock() = Some(tx);
+        assert!(peer.is_connected());
+        assert!(peer.send(vec![1]));
+        assert!(!peer.send(vec![2]));
+        assert_eq!(peer.state.dropped.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn peer_sources() {
+        let nodes = Arc::new(RwLock::new(NodeList::new()));
+        let reputation = Arc::new(RwLock::new(Reputation::new(ReputationConfig::default())));
+        let mut con = Connection::new(&NetConfig::test_config(), nodes, reputation);
+        con.add_peer(1, "127.0.0.1:40001".to_string());
+        con.admin_add_peer(2, "127.0.0.1:40002".to_string());
+        let addrs = |con: &Connection| con.peers.iter().map(|peer| (peer.addr.clone(), peer.source)).collect::<Vec<_>>();
+
+        // discovery only drops what it found, the config only what it listed
+        con.remove_peer("127.0.0.1:40000");
+        con.remove_peer("127.0.0.1:40002");
+        assert_eq!(con.peers.len(), 3);
+        let no_peers: NetConfig = toml::decode_str("id_card = 0\nport = 40000\nplaintext = true").unwrap();
+        con.update(&no_peers);
+        assert_eq!(addrs(&con),
+                   vec![("127.0.0.1:40001".to_string(), PeerSource::Discovery), ("127.0.0.1:40002".to_string(), PeerSource::Admin)]);
+
+        // the admin takes over a discovered peer, and removes any
+        con.admin_add_peer(1, "127.0.0.1:40001".to_string());
+        con.remove_peer("127.0.0.1:40001");
+        assert_eq!(con.peers.len(), 2);
+        assert!(con.admin_remove_peer("127.0.0.1:40001"));
+        assert!(!con.admin_remove_peer("127.0.0.1:40001"));
+        assert_eq!(addrs(&con), vec![("127.0.0.1:40002".to_string(), PeerSource::Admin)]);
+    }
+
+    #[test]
+    fn single_by_destination() {
+        let nodes = Arc::new(RwLock::new(NodeList::new()));
+        let reputation = Arc::new(RwLock::new(Reputation::new(ReputationConfig::default())));
+        let mut con = Connection::new(&NetConfig::test_config(), nodes, reputation);
+        con.peers.clear();
+        let mut rxs = Vec::new();
+        for id_card in 1..4 {
+            let state = Arc::new(PeerState::new());
+            let (tx, rx) = mpsc::channel(8);
+            *state.queue.lock() = Some(tx);
+            *state.address.lock() = Some(Address::from(id_card as u64));
+            rxs.push(rx);
+            con.peers.push(Peer {
+                               id_card: id_card,
+                               addr: format!("127.0.0.1:{}", 40000 + id_card),
+                               source: PeerSource::Admin,
+                               state: state,
+                           });
+        }
+        let sent_to = |con: &Connection| con.peers.iter().filter(|peer| !peer.state.sent.lock().is_empty()).map(|peer| peer.id_card).collect::<Vec<_>>();
+
+        // by id_card
+        broadcast(&con, factory::create_msg_to(submodules::CHAIN, topics::SYNC_BLK, communication::MsgType::MSG, 2, 1, vec![0; 16]));
+        assert_eq!(sent_to(&con), vec![2]);
+
+        // by address, over the id_card
+        let mut msg = factory::create_msg_to(submodules::CHAIN, topics::SYNC_BLK, communication::MsgType::MSG, 2, 2, vec![0; 16]);
+        msg.set_destination(Address::from(3).to_vec());
+        broadcast(&con, msg);
+        assert_eq!(sent_to(&con), vec![2, 3]);
+    }
+
+    #[test]
+    fn batch_queued_msgs() {
+        let mut core = Core::new().unwrap();
+        fn queue_error(_: ()) -> io::Error {
+            io::Error::new(io::ErrorKind::Other, "queue error")
+        }
+        let (mut tx, rx) = mpsc::channel(8);
+        let msgs = vec![vec![1], vec![2], vec![0; BATCH_SIZE], vec![3]];
+        for msg in msgs.clone() {
+            tx.try_send(msg).unwrap();
+        }
+        drop(tx);
+        let batches = core.run(Batching::new(rx.map_err(queue_error), &core.handle()).collect()).unwrap();
+        // a full batch is written at once, the rest when the queue ends
+        assert_eq!(batches, vec![msgs[..3].to_vec(), msgs[3..].to_vec()]);
+
+        // while the queue is open, what it holds is written after the delay
+        let (mut tx, rx) = mpsc::channel(8);
+        tx.try_send(vec![4]).unwrap();
+        let batching = Batching::new(rx.map_err(queue_error), &core.handle()).into_future().map_err(|(e, _)| e);
+        let (batch, _) = core.run(batching).unwrap();
+        assert_eq!(batch, Some(vec![vec![4]]));
+    }
+
+    #[test]
+    fn backoff() {
+        assert_eq!(next_backoff(MIN_BACKOFF), MIN_BACKOFF * 2);
+        assert_eq!(next_backoff(MAX_BACKOFF / 2 + 1), MAX_BACKOFF);
+        assert_eq!(next_backoff(MAX_BACKOFF), MAX_BACKOFF);
+    }
+
     #[test]
     fn is_seng_mag() {
         assert!(is_send(0, 0, communication::OperateType::BROADCAST));
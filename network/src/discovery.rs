@@ -0,0 +1,362 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Peer discovery.
+//!
+//! Every round each node broadcasts a ping with its own address, and the peers it
+//! has been pinged by lately. Addresses it has not heard of are dialed, and peers
+//! which stop pinging are dropped. Static `peers` are seeds and never dropped,
+//! `bootstrap` nodes and the persisted peers are only dialed to start with.
+//! Whether a peer may stay connected is still up to the node list check in the handshake.
+//! Only peers proved by the session handshake are listened to, and at most
+//! `MAX_PEERS` addresses are kept, so nobody can flood the node with addresses to dial.
+
+use config::NetConfig;
+use connection::{Connection, broadcast};
+use libproto::{MsgClass, factory, parse_msg, submodules, topics};
+use libproto::communication::{self, MsgType};
+use rustc_serialize::json;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::prelude::*;
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use util::RwLock;
+
+/// Seconds between two rounds of pings and peer exchange
+const ROUND: u64 = 15;
+/// Peers not heard of for this many rounds are dropped
+const EXPIRE_ROUNDS: u32 = 4;
+/// Addresses known at most, the ones beyond are ignored until some expire
+const MAX_PEERS: usize = 256;
+const DEFAULT_PEERS_FILE: &'static str = "discovered_peers.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable)]
+pub struct KnownPeer {
+    pub id_card: u32,
+    pub addr: String,
+}
+
+#[derive(Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum DiscoveryMsg {
+    /// Liveness ping carrying the sender's own address
+    Ping(KnownPeer),
+    /// Peers the sender was pinged by lately
+    Peers(Vec<KnownPeer>),
+}
+
+impl DiscoveryMsg {
+    pub fn to_message(&self) -> communication::Message {
+        let content = json::encode(self).unwrap().into_bytes();
+        factory::create_msg(submodules::NET, topics::DISCOVERY, MsgType::MSG, content)
+    }
+
+    pub fn from_payload(payload: &[u8]) -> Option<Self> {
+        match parse_msg(payload) {
+            (_, _, MsgClass::MSG(content)) => String::from_utf8(content).ok().and_then(|content| json::decode(&content).ok()),
+            _ => None,
+        }
+    }
+}
+
+struct Entry {
+    peer: KnownPeer,
+    learned: Instant,
+    pinged: Option<Instant>,
+}
+
+pub struct Discovery {
+    local: Option<KnownPeer>,
+    seeds: HashSet<String>,
+    peers: HashMap<String, Entry>,
+    path: String,
+    expire: Duration,
+}
+
+impl Discovery {
+    pub fn new(config: &NetConfig) -> Self {
+        let local = config.ip.as_ref().map(|ip| {
+                                               KnownPeer {
+                                                   id_card: config.id_card.unwrap(),
+                                                   addr: format!("{}:{}", ip, config.port.unwrap()),
+                                               }
+                                           });
+        if local.is_none() {
+            warn!("ip is not set, other nodes can not discover this one");
+        }
+        let seeds = config.peers
+                          .as_ref()
+                          .map(|peers| peers.iter().map(|peer| format!("{}:{}", peer.ip.clone().unwrap(), peer.port.unwrap())).collect())
+                          .unwrap_or_else(HashSet::new);
+        let path = config.peers_file.clone().unwrap_or(DEFAULT_PEERS_FILE.to_string());
+        let mut discovery = Discovery {
+            local: local,
+            seeds: seeds,
+            peers: HashMap::new(),
+            path: path,
+            expire: Duration::from_secs(ROUND * EXPIRE_ROUNDS as u64),
+        };
+
+        let mut initial = Discovery::load(&discovery.path);
+        if let Some(ref bootstrap) = config.bootstrap {
+            initial.extend(bootstrap.iter().map(|peer| {
+                                                    KnownPeer {
+                                                        id_card: peer.id_card.unwrap(),
+                                                        addr: format!("{}:{}", peer.ip.clone().unwrap(), peer.port.unwrap()),
+                                                    }
+                                                }));
+        }
+        discovery.learn(initial, Instant::now());
+        discovery
+    }
+
+    fn load(path: &str) -> Vec<KnownPeer> {
+        let mut content = String::new();
+        match File::open(path).and_then(|mut file| file.read_to_string(&mut content)) {
+            Ok(_) => {
+                json::decode(&content).unwrap_or_else(|e| {
+                                                          warn!("ignore broken peers file {:?}: {:?}", path, e);
+                                                          Vec::new()
+                                                      })
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Persist the live peers, so they can be dialed after a restart without any seed.
+    pub fn save(&self, now: Instant) {
+        let content = json::encode(&self.alive(now)).unwrap();
+        if let Err(e) = File::create(&self.path).and_then(|mut file| file.write_all(content.as_bytes())) {
+            warn!("save peers to {:?} error: {:?}", self.path, e);
+        }
+    }
+
+    fn is_local(&self, addr: &str) -> bool {
+        self.local.as_ref().map_or(false, |local| local.addr == addr)
+    }
+
+    /// Record peers heard of from others. Returns the ones which were not known yet.
+    fn learn(&mut self, peers: Vec<KnownPeer>, now: Instant) -> Vec<KnownPeer> {
+        let mut new_peers = Vec::new();
+        for peer in peers {
+            if self.is_local(&peer.addr) || self.peers.contains_key(&peer.addr) {
+                continue;
+            }
+            if self.peers.len() >= MAX_PEERS {
+                trace!("ignore peer {:?}, {} peers are known", peer, MAX_PEERS);
+                continue;
+            }
+            if !self.seeds.contains(&peer.addr) {
+                new_peers.push(peer.clone());
+            }
+            self.peers.insert(peer.addr.clone(),
+                              Entry {
+                                  peer: peer,
+                                  learned: now,
+                                  pinged: None,
+                              });
+        }
+        new_peers
+    }
+
+    /// Handle a message from another node, returns the peers to dial.
+    pub fn handle(&mut self, msg: DiscoveryMsg, now: Instant) -> Vec<KnownPeer> {
+        match msg {
+            DiscoveryMsg::Ping(peer) => {
+                let addr = peer.addr.clone();
+                let new_peers = self.learn(vec![peer.clone()], now);
+                if let Some(entry) = self.peers.get_mut(&addr) {
+                    entry.peer = peer;
+                    entry.pinged = Some(now);
+                }
+                new_peers
+            }
+            DiscoveryMsg::Peers(peers) => self.learn(peers, now),
+        }
+    }
+
+    /// Peers which pinged lately. Only those are passed on, so a dead peer
+    /// is not kept alive by nodes telling each other about it.
+    pub fn alive(&self, now: Instant) -> Vec<KnownPeer> {
+        self.peers
+            .values()
+            .filter(|entry| entry.pinged.map_or(false, |pinged| now.duration_since(pinged) < self.expire))
+            .map(|entry| entry.peer.clone())
+            .collect()
+    }
+
+    /// All the discovered peers, seeds excluded.
+    pub fn discovered(&self) -> Vec<KnownPeer> {
+        self.peers
+            .values()
+            .filter(|entry| !self.seeds.contains(&entry.peer.addr))
+            .map(|entry| entry.peer.clone())
+            .collect()
+    }
+
+    /// Forget the peers not heard of for a while, returns the addresses to disconnect.
+    pub fn expire(&mut self, now: Instant) -> Vec<String> {
+        let expire = self.expire;
+        let expired: Vec<String> = self.peers
+                                       .values()
+                                       .filter(|entry| now.duration_since(entry.pinged.unwrap_or(entry.learned)) >= expire)
+                                       .map(|entry| entry.peer.addr.clone())
+                                       .collect();
+        for addr in &expired {
+            self.peers.remove(addr);
+        }
+        expired.into_iter().filter(|addr| !self.seeds.contains(addr)).collect()
+    }
+
+    /// Messages to broadcast this round.
+    pub fn messages(&self, now: Instant) -> Vec<DiscoveryMsg> {
+        let mut msgs = Vec::new();
+        if let Some(ref local) = self.local {
+            msgs.push(DiscoveryMsg::Ping(local.clone()));
+        }
+        let alive = self.alive(now);
+        if !alive.is_empty() {
+            msgs.push(DiscoveryMsg::Peers(alive));
+        }
+        msgs
+    }
+}
+
+/// Run discovery rounds, and handle the discovery messages the server receives from `rx`.
+pub fn start_discovery(con: Arc<RwLock<Connection>>, mut discovery: Discovery, rx: Receiver<(String, Vec<u8>)>) {
+    {
+        let con = &mut *con.as_ref().write();
+        for peer in discovery.discovered() {
+            con.add_peer(peer.id_card, peer.addr);
+        }
+    }
+
+    thread::spawn(move || {
+        info!("start discovery!");
+        let round = Duration::from_secs(ROUND);
+        let mut next_round = Instant::now();
+        loop {
+            let now = Instant::now();
+            if now >= next_round {
+                next_round = now + round;
+                let con = &mut *con.as_ref().write();
+                for addr in discovery.expire(now) {
+                    info!("peer {:?} is gone", addr);
+                    con.remove_peer(&addr);
+                }
+                for msg in discovery.messages(now) {
+                    broadcast(con, msg.to_message());
+                }
+                discovery.save(now);
+            }
+
+            match rx.recv_timeout(next_round - now) {
+                Ok((_, payload)) => {
+                    if let Some(msg) = DiscoveryMsg::from_payload(&payload) {
+                        let new_peers = discovery.handle(msg, Instant::now());
+                        if !new_peers.is_empty() {
+                            let con = &mut *con.as_ref().write();
+                            for peer in new_peers {
+                                info!("discovered peer {:?}", peer);
+                                con.add_peer(peer.id_card, peer.addr);
+                            }
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::NetConfig;
+    use rustc_serialize::json;
+
+    fn peer(id_card: u32, port: u16) -> KnownPeer {
+        KnownPeer {
+            id_card: id_card,
+            addr: format!("127.0.0.1:{}", port),
+        }
+    }
+
+    fn discovery() -> Discovery {
+        let mut config = NetConfig::test_config();
+        config.ip = Some("127.0.0.1".to_string());
+        config.peers_file = Some("/nonexistent/discovered_peers.json".to_string());
+        Discovery::new(&config)
+    }
+
+    #[test]
+    fn message_roundtrip() {
+        let msg = DiscoveryMsg::Peers(vec![peer(1, 40001), peer(2, 40002)]);
+        let payload = ::protobuf::Message::write_to_bytes(&msg.to_message()).unwrap();
+        assert_eq!(DiscoveryMsg::from_payload(&payload), Some(msg));
+        assert_eq!(json::decode::<Vec<KnownPeer>>(&json::encode(&vec![peer(1, 40001)]).unwrap()).unwrap(), vec![peer(1, 40001)]);
+    }
+
+    #[test]
+    fn learn_from_ping_and_exchange() {
+        let mut discovery = discovery();
+        let now = Instant::now();
+        // the seed and the node itself are never dialed as discovered peers
+        assert!(discovery.handle(DiscoveryMsg::Ping(peer(0, 40000)), now).is_empty());
+        assert_eq!(discovery.handle(DiscoveryMsg::Ping(peer(1, 40001)), now), vec![peer(1, 40001)]);
+        assert!(discovery.handle(DiscoveryMsg::Ping(peer(1, 40001)), now).is_empty());
+        assert_eq!(discovery.handle(DiscoveryMsg::Peers(vec![peer(1, 40001), peer(2, 40002)]), now), vec![peer(2, 40002)]);
+
+        // only peers which pinged are passed on
+        let msgs = discovery.messages(now);
+        assert_eq!(msgs[0], DiscoveryMsg::Ping(peer(0, 40000)));
+        assert_eq!(msgs[1], DiscoveryMsg::Peers(vec![peer(1, 40001)]));
+    }
+
+    #[test]
+    fn known_peers_are_capped() {
+        let mut discovery = discovery();
+        let now = Instant::now();
+        let peers: Vec<KnownPeer> = (0..MAX_PEERS as u16 * 2).map(|i| peer(100 + i as u32, 41000 + i)).collect();
+        let known = discovery.peers.len();
+        assert_eq!(discovery.handle(DiscoveryMsg::Peers(peers), now).len(), MAX_PEERS - known);
+        assert_eq!(discovery.peers.len(), MAX_PEERS);
+        assert!(discovery.handle(DiscoveryMsg::Ping(peer(1, 40001)), now).is_empty());
+
+        // room is made as peers expire
+        discovery.expire(now + Duration::from_secs(ROUND * EXPIRE_ROUNDS as u64));
+        assert_eq!(discovery.handle(DiscoveryMsg::Ping(peer(1, 40001)), now), vec![peer(1, 40001)]);
+    }
+
+    #[test]
+    fn expire_silent_peers() {
+        let mut discovery = discovery();
+        let now = Instant::now();
+        discovery.handle(DiscoveryMsg::Peers(vec![peer(1, 40001), peer(2, 40002)]), now);
+        let later = now + Duration::from_secs(ROUND * 3);
+        discovery.handle(DiscoveryMsg::Ping(peer(1, 40001)), later);
+
+        let expired = discovery.expire(now + Duration::from_secs(ROUND * EXPIRE_ROUNDS as u64));
+        assert_eq!(expired, vec![peer(2, 40002).addr]);
+        assert_eq!(discovery.discovered(), vec![peer(1, 40001)]);
+        assert!(discovery.expire(later + Duration::from_secs(ROUND * EXPIRE_ROUNDS as u64)).len() == 1);
+        assert!(discovery.discovered().is_empty());
+    }
+}
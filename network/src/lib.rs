@@ -0,0 +1,164 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+#![allow(deprecated)]
+#![cfg_attr(test, feature(test))]
+#[macro_use]
+extern crate log;
+extern crate futures;
+extern crate tokio_io;
+extern crate tokio_core;
+extern crate tokio_proto;
+extern crate tokio_service;
+extern crate byteorder;
+extern crate rustc_serialize;
+extern crate libproto;
+extern crate protobuf;
+extern crate pubsub;
+extern crate bytes;
+extern crate notify;
+extern crate util;
+extern crate cita_crypto as crypto;
+extern crate cita_secp256k1;
+extern crate crypto as rcrypto;
+extern crate error;
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+extern crate test;
+
+pub mod config;
+pub mod server;
+pub mod connection;
+pub mod citaprotocol;
+pub mod msghandle;
+pub mod session;
+pub mod nodes;
+pub mod discovery;
+pub mod stats;
+pub mod reputation;
+
+
+use config::NetConfig;
+use connection::{Connection, start_client};
+use discovery::{Discovery, start_discovery};
+use libproto::trace;
+use libproto::router::{self, AuthTx, AuthVerdict, ChainBlock, ChainRichStatus, ChainStatus, ChainSync, ChainVerdict, ConsensusMsg, ConsensusVerdict, JsonrpcNet, Route};
+use msghandle::{is_need_proc, handle_rpc, handle_rich_status, handle_verdict};
+use nodes::NodeList;
+use notify::{RecommendedWatcher, Watcher, RecursiveMode};
+use pubsub::{start_pubsub, sub_channel};
+use reputation::Reputation;
+use server::MySender;
+use server::start_server;
+use stats::InboundStats;
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+use util::RwLock;
+
+
+pub fn watch_config(config_path: &str, con: Arc<RwLock<Connection>>) {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(5)).unwrap();
+    let _ = watcher.watch(config_path.clone(), RecursiveMode::Recursive).unwrap();
+    let config = String::from(config_path);
+    thread::spawn(move || loop {
+                      match rx.recv() {
+                          Ok(_) => {
+                              let config = NetConfig::new(&config.as_str());
+                              let con = &mut *con.as_ref().write();
+                              con.update(&config);
+                          }
+                          Err(e) => info!("watch error: {:?}", e),
+                      }
+                  });
+}
+
+
+/// Run the network service, it never returns.
+/// `config_path` is watched for changes to the peers, `is_test` runs the test config instead.
+pub fn run(config_path: &str, is_test: bool) {
+    let config = if is_test { NetConfig::test_config() } else { NetConfig::new(config_path) };
+
+    // init pubsub
+    // split new_tx with other msg
+    let (ctx_sub_tx, crx_sub_tx) = sub_channel();
+    let (ctx_pub_tx, crx_pub_tx) = channel();
+    start_pubsub("network_tx", router::subscription(vec![AuthTx::key()]), ctx_sub_tx, crx_pub_tx);
+
+    let (ctx_sub, crx_sub) = sub_channel();
+    let (ctx_pub, crx_pub) = channel();
+    let keys = router::subscription(vec![ConsensusMsg::key(), ChainStatus::key(), ChainBlock::key(), ChainSync::key(), ChainRichStatus::key(), JsonrpcNet::key(), ChainVerdict::key(), AuthVerdict::key(), ConsensusVerdict::key()]);
+    start_pubsub("network", keys, ctx_sub, crx_pub);
+
+    // start server
+    // This brings up our server.
+    // all server recv msg directly publish to mq
+    let mysender_tx = MySender::new(ctx_pub_tx.clone());
+    let mysender = MySender::new(ctx_pub.clone());
+    // discovery messages stay in network
+    let (ctx_discovery, crx_discovery) = channel();
+    let mysender_discovery = MySender::new(ctx_discovery);
+    let nodes = Arc::new(RwLock::new(NodeList::new()));
+    let inbound = Arc::new(RwLock::new(InboundStats::new()));
+    let reputation = Arc::new(RwLock::new(Reputation::new(config.reputation())));
+    let admin_rpc = config.admin_rpc();
+    start_server(&config, nodes.clone(), mysender, mysender_tx, mysender_discovery, inbound.clone(), reputation.clone());
+
+    // connect peers
+    let con = Connection::new(&config, nodes.clone(), reputation.clone());
+    let con_lock = Arc::new(RwLock::new(con));
+    watch_config(config_path, con_lock.clone());
+    if config.discovery() {
+        start_discovery(con_lock.clone(), Discovery::new(&config), crx_discovery);
+    }
+
+    // client for new tx
+    let (ctx_tx, crx_tx) = channel();
+    start_client(con_lock.clone(), crx_tx);
+
+    // client for other msg
+    let (ctx, crx) = channel();
+    start_client(con_lock.clone(), crx);
+
+    thread::spawn(move || {
+        loop {
+            // msg from tx mq need proc before broadcast
+            let (key, body) = crx_sub_tx.recv().unwrap();
+            trace!("handle delivery id {:?} payload {:?}", key, body);
+            if let (_, true, msg) = is_need_proc(body.as_ref(), "mq") {
+                ctx_tx.send(msg).unwrap();
+            }
+        }
+    });
+
+    loop {
+        // msg from mq need proc before broadcast
+        let (key, body) = crx_sub.recv().unwrap();
+        trace!("handle delivery id {:?} payload {:?}", key, body);
+        if let (_, true, msg) = is_need_proc(body.as_ref(), "mq") {
+            ctx.send(msg).unwrap();
+        }
+
+        handle_rpc(&con_lock, &inbound, admin_rpc, &ctx_pub, body.as_ref());
+        handle_verdict(&reputation, body.as_ref());
+        let con = &*con_lock.as_ref().read();
+        handle_rich_status(&con, &nodes, body.as_ref());
+        trace::finish();
+    }
+}
@@ -17,16 +17,25 @@
 
 use citaprotocol::CitaRequest;
 use connection::Connection;
+use discovery::KnownPeer;
+use error::ErrorCode;
 use libproto::*;
 use libproto::communication::*;
 use libproto::request::Request;
+use libproto::router::{self, ChainRpc, NetDiscovery, NetTx, Route};
+use nodes::SharedNodeList;
 use protobuf::Message;
 use protobuf::core::parse_from_bytes;
+use reputation::SharedReputation;
+use rustc_serialize::json;
 use server::MySender;
+use stats::{SharedInbound, unix_now};
 use std::io;
 use std::sync::mpsc::Sender;
+use util::{Address, RwLock};
 
-pub fn handle_rpc(con: &Connection, tx_pub: &Sender<(String, Vec<u8>)>, payload: &[u8]) {
+/// Answer jsonrpc's net requests. Adding and removing peers is refused unless `admin_rpc` is on.
+pub fn handle_rpc(con: &RwLock<Connection>, inbound: &SharedInbound, admin_rpc: bool, tx_pub: &Sender<(String, Vec<u8>)>, payload: &[u8]) {
     if let Ok(msg) = parse_from_bytes::<communication::Message>(payload) {
         let t = msg.get_field_type();
         let cid = msg.get_cmd_id();
@@ -36,53 +45,135 @@ pub fn handle_rpc(con: &Connection, tx_pub: &Sender<(String, Vec<u8>)>, payload:
             let mut response = Response::new();
             response.set_request_id(ts.take_request_id());
             if ts.has_peercount() {
-                let peercount = con.peers_pair.iter().filter(|x| x.2.as_ref().read().is_some()).count();
+                let peercount = con.read().peers.iter().filter(|peer| peer.is_connected()).count();
                 response.set_peercount(peercount as u32);
-                let ms: communication::Message = response.into();
-                tx_pub.send(("chain.rpc".to_string(), ms.write_to_bytes().unwrap())).unwrap();
+            } else if ts.has_peers() {
+                let peers = con.read().peers_info(&inbound.read());
+                response.set_peers(json::encode(&peers).unwrap());
+            } else if (ts.has_add_peer() || ts.has_remove_peer()) && !admin_rpc {
+                warn!("refuse to change peers, admin_rpc is off");
+                response.set_code(ErrorCode::query_error());
+                response.set_error_msg("admin rpc is disabled, set admin_rpc in network.toml".to_string());
+            } else if ts.has_add_peer() {
+                match json::decode::<KnownPeer>(ts.get_add_peer()) {
+                    Ok(peer) => {
+                        info!("admin add peer {:?}", peer);
+                        con.write().admin_add_peer(peer.id_card, peer.addr);
+                        response.set_none(true);
+                    }
+                    Err(e) => {
+                        response.set_code(ErrorCode::query_error());
+                        response.set_error_msg(format!("invalid peer: {:?}", e));
+                    }
+                }
+            } else if ts.has_remove_peer() {
+                info!("admin remove peer {:?}", ts.get_remove_peer());
+                if con.write().admin_remove_peer(ts.get_remove_peer()) {
+                    response.set_none(true);
+                } else {
+                    response.set_code(ErrorCode::query_error());
+                    response.set_error_msg(format!("no peer {}", ts.get_remove_peer()));
+                }
+            } else {
+                return;
             }
+            ChainRpc::publish(tx_pub, &response);
         }
     }
 }
 
-pub fn is_need_proc(payload: &[u8], source: &str) -> (String, bool, communication::Message) {
+/// Update the node list from chain's rich status, and drop the peers which were removed from it.
+pub fn handle_rich_status(con: &Connection, nodes: &SharedNodeList, payload: &[u8]) {
     if let Ok(msg) = parse_from_bytes::<communication::Message>(payload) {
-        let mut topic = String::default();
-        let mut is_proc = true;
-        let t = msg.get_field_type();
-        let cid = msg.get_cmd_id();
-        if cid == cmd_id(submodules::AUTH, topics::REQUEST) && t == MsgType::REQUEST {
-            trace!("AUTH REQUEST from {}", source);
-            topic = "net.tx".to_string();
-        } else if cid == cmd_id(submodules::CHAIN, topics::NEW_BLK) && t == MsgType::BLOCK {
-            info!("CHAIN NEW_BLK from {}", source);
-            topic = "net.blk".to_string();
-        } else if cid == cmd_id(submodules::CHAIN, topics::NEW_STATUS) && t == MsgType::STATUS {
-            info!("CHAIN NEW_STATUS from {}", source);
-            topic = "net.status".to_string();
-        } else if cid == cmd_id(submodules::CHAIN, topics::SYNC_BLK) && t == MsgType::MSG {
-            info!("CHAIN SYNC_BLK from {}", source);
-            topic = "net.sync".to_string();
-        } else if cid == cmd_id(submodules::CONSENSUS, topics::CONSENSUS_MSG) && t == MsgType::MSG {
-            info!("CONSENSUS CONSENSUS_MSG from {}", source);
-            topic = "net.msg".to_string();
-        } else if cid == cmd_id(submodules::CONSENSUS, topics::NEW_PROPOSAL) && t == MsgType::MSG {
-            info!("CONSENSUS NEW_PROPOSAL from {}", source);
-            topic = "net.msg".to_string();
-        } else {
-            is_proc = false;
+        if msg.get_field_type() != MsgType::RICH_STATUS {
+            return;
+        }
+        if let (_, _, MsgClass::RICHSTATUS(rich_status)) = parse_msg(payload) {
+            let node_list = rich_status.get_nodes().iter().map(|node| Address::from_slice(node)).collect();
+            let changed = nodes.write().update(rich_status.get_height(), node_list);
+            if changed {
+                con.disconnect_unlisted();
+            }
         }
-        return (topic, is_proc, msg);
+    }
+}
+
+/// Score the peer a verdict from chain, auth or consensus blames.
+pub fn handle_verdict(reputation: &SharedReputation, payload: &[u8]) {
+    if let Ok(msg) = parse_from_bytes::<communication::Message>(payload) {
+        if de_cmd_id(msg.get_cmd_id()).1 != topics::VERDICT {
+            return;
+        }
+        if let Some(verdict) = Verdict::from_msg(payload) {
+            reputation.write().judge(&verdict, unix_now());
+        }
+    }
+}
+
+pub fn is_need_proc(payload: &[u8], source: &str) -> (String, bool, communication::Message) {
+    if let Ok(msg) = parse_from_bytes::<communication::Message>(payload) {
+        return match router::net_route(&msg) {
+            Some(route) => {
+                if route.key == NetTx::key() || route.key == NetDiscovery::key() {
+                    trace!("{} from {}", route.name, source);
+                } else {
+                    info!("{} from {}", route.name, source);
+                }
+                (route.key.to_string(), true, msg)
+            }
+            None => (String::default(), false, msg),
+        };
     }
     ("".to_string(), false, communication::Message::new())
 }
 
-pub fn net_msg_handler(payload: CitaRequest, mysender: &MySender, mysender_tx: &MySender) -> Result<Vec<u8>, io::Error> {
+/// Forward what a peer sent to the module it is for. `peer` is the address the
+/// session proved, the origin the msg claims must be bound to it. In plaintext
+/// nothing is proved, and nobody is scored.
+pub fn net_msg_handler(payload: CitaRequest, peer: Option<Address>, mysender: &MySender, mysender_tx: &MySender, mysender_discovery: &MySender, inbound: &SharedInbound, reputation: &SharedReputation) -> Result<Vec<u8>, io::Error> {
     trace!("SERVER get msg: {:?}", payload);
     if let (topic, true, msg) = is_need_proc(payload.as_ref(), "net") {
-        trace!("recive msg from origin = {:?} with topic: {:?}", msg.get_origin(), topic);
-        if topic == "net.tx" {
+        let origin = msg.get_origin();
+        let now = unix_now();
+        trace!("recive msg from origin = {:?} with topic: {:?}", origin, topic);
+        if let Some(ref address) = peer {
+            let mut reputation = reputation.write();
+            if !reputation.authenticate(origin, address) {
+                warn!("drop {} from {:?} claiming origin {} of {:?}", topic, address, origin, reputation.address(origin));
+                return Ok(vec![]);
+            }
+            if reputation.is_banned(address, now) {
+                trace!("drop {} from banned peer {:?}", topic, address);
+                return Ok(vec![]);
+            }
+        }
+        inbound.write().record(&msg, payload.len(), now);
+        // the other modules panic on content which does not decode, so it stops here
+        match try_parse_msg(&payload) {
+            Ok((_, _, MsgClass::STATUS(status))) => {
+                inbound.write().set_height(origin, status.get_height());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let reason = format!("{}: {:?}", topic, e);
+                match peer {
+                    Some(ref address) => {
+                        reputation.write().punish(address, Offence::Undecodable, &reason, now);
+                    }
+                    None => warn!("drop undecodable {} from origin {}", reason, origin),
+                }
+                return Ok(vec![]);
+            }
+        }
+        if topic == NetTx::key() {
             mysender_tx.send((topic, payload));
+        } else if topic == NetDiscovery::key() {
+            // addresses are only learned from peers the session proved
+            if peer.is_some() {
+                mysender_discovery.send((topic, payload));
+            } else {
+                trace!("drop {} from origin {} without a session", topic, origin);
+            }
         } else {
             mysender.send((topic, payload));
         }
@@ -0,0 +1,88 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use util::{Address, RwLock};
+
+pub type SharedNodeList = Arc<RwLock<NodeList>>;
+
+/// Addresses of the nodes allowed to connect, as chain reports in `RichStatus.nodes`.
+pub struct NodeList {
+    height: u64,
+    // unknown until the first status arrives, nobody is allowed till then.
+    // chain publishes its status as it starts, so that is not long
+    nodes: Option<HashSet<Address>>,
+}
+
+impl NodeList {
+    pub fn new() -> Self {
+        NodeList { height: 0, nodes: None }
+    }
+
+    /// Replace the list with the one at `height`, statuses older than the current one are ignored.
+    /// Returns whether the list changed.
+    pub fn update(&mut self, height: u64, nodes: HashSet<Address>) -> bool {
+        if self.nodes.is_some() && height < self.height {
+            return false;
+        }
+        self.height = height;
+        if self.nodes.as_ref() == Some(&nodes) {
+            return false;
+        }
+        info!("node list changed at height {}: {:?}", height, nodes);
+        self.nodes = Some(nodes);
+        true
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        match self.nodes {
+            Some(ref nodes) => nodes.contains(address),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NodeList;
+    use std::collections::HashSet;
+    use util::Address;
+
+    #[test]
+    fn update_by_height() {
+        let a = Address::from(1);
+        let b = Address::from(2);
+        let mut list = NodeList::new();
+        assert!(!list.contains(&a));
+
+        let mut nodes = HashSet::new();
+        nodes.insert(a);
+        assert!(list.update(10, nodes.clone()));
+        assert!(!list.update(11, nodes.clone()));
+        assert!(list.contains(&a));
+        assert!(!list.contains(&b));
+
+        let mut old = HashSet::new();
+        old.insert(b);
+        assert!(!list.update(9, old.clone()));
+        assert!(!list.contains(&b));
+        assert!(list.update(12, old));
+        assert!(!list.contains(&a));
+        assert!(list.contains(&b));
+    }
+}
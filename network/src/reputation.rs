@@ -0,0 +1,270 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Peer scores, lowered by the verdicts chain, auth and consensus publish
+//! and by content network itself fails to decode. A peer whose score drops
+//! to the threshold is banned for a while: nothing it sends is forwarded
+//! and nothing is sent to it.
+//!
+//! Scores are kept by the address a peer proved in the handshake. The origin
+//! in a message is only what the sender claims, so each origin is bound to
+//! the address of the session it first came over, or to the address of the
+//! configured peer with that id_card once connecting it proved it. Messages
+//! whose origin is bound to another address are dropped, so the origin the
+//! verdicts blame is always the one of the session that sent the message.
+
+use libproto::{Offence, Origin, Verdict};
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::Arc;
+use util::{Address, RwLock};
+
+pub const DEFAULT_BAN_THRESHOLD: i64 = -100;
+pub const DEFAULT_BAN_SECS: u64 = 600;
+/// Seconds for a score to win one point back, up to 0
+const RECOVER_SECS: u64 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReputationConfig {
+    pub ban_threshold: i64,
+    pub ban_secs: u64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        ReputationConfig {
+            ban_threshold: DEFAULT_BAN_THRESHOLD,
+            ban_secs: DEFAULT_BAN_SECS,
+        }
+    }
+}
+
+/// Points an offence costs. A bad transaction may be a client's fault relayed
+/// in good faith, a bad block or consensus message can't be.
+pub fn penalty(offence: Offence) -> i64 {
+    match offence {
+        Offence::InvalidBlock => 50,
+        Offence::InvalidSignature => 25,
+        Offence::Flood => 30,
+        Offence::Undecodable => 20,
+        Offence::InvalidTx => 5,
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Score {
+    points: i64,
+    updated: u64,
+    banned_until: u64,
+}
+
+impl Score {
+    fn recovered(&self, now: u64) -> i64 {
+        let recovered = (now.saturating_sub(self.updated) / RECOVER_SECS) as i64;
+        cmp::min(0, self.points.saturating_add(recovered))
+    }
+}
+
+pub type SharedReputation = Arc<RwLock<Reputation>>;
+
+#[derive(Debug)]
+pub struct Reputation {
+    config: ReputationConfig,
+    scores: HashMap<Address, Score>,
+    addresses: HashMap<Origin, Address>,
+    origins: HashMap<Address, Origin>,
+}
+
+impl Reputation {
+    pub fn new(config: ReputationConfig) -> Self {
+        Reputation {
+            config: config,
+            scores: HashMap::new(),
+            addresses: HashMap::new(),
+            origins: HashMap::new(),
+        }
+    }
+
+    /// Bind `origin` to `address`, which connecting the configured peer of that
+    /// id_card proved. It overrides whatever either was bound to before.
+    pub fn bind(&mut self, origin: Origin, address: Address) {
+        if let Some(old) = self.addresses.insert(origin, address) {
+            if old != address {
+                warn!("origin {} moves from {:?} to {:?}", origin, old, address);
+                self.origins.remove(&old);
+            }
+        }
+        if let Some(old) = self.origins.insert(address, origin) {
+            if old != origin {
+                self.addresses.remove(&old);
+            }
+        }
+    }
+
+    /// Check the `origin` a message claims against the `address` of the session
+    /// it came over. An origin neither of them is bound to yet is bound to it.
+    /// Returns false when either is bound to another, the message is not trusted then.
+    pub fn authenticate(&mut self, origin: Origin, address: &Address) -> bool {
+        if !Verdict::is_remote(origin) {
+            return false;
+        }
+        match (self.addresses.get(&origin), self.origins.get(address)) {
+            (Some(bound), _) => return bound == address,
+            (None, Some(_)) => return false,
+            (None, None) => {}
+        }
+        self.addresses.insert(origin, *address);
+        self.origins.insert(*address, origin);
+        true
+    }
+
+    /// Address the verdicts about `origin` are scored to
+    pub fn address(&self, origin: Origin) -> Option<&Address> {
+        self.addresses.get(&origin)
+    }
+
+    /// Score the verdict to the address its origin is bound to.
+    /// Returns true if this verdict got the peer banned.
+    pub fn judge(&mut self, verdict: &Verdict, now: u64) -> bool {
+        match self.addresses.get(&verdict.origin).cloned() {
+            Some(address) => self.punish(&address, verdict.offence, &verdict.reason, now),
+            None => {
+                trace!("no peer proved origin {}, drop verdict {:?}", verdict.origin, verdict);
+                false
+            }
+        }
+    }
+
+    /// Take the offence's points from the peer, and ban it when the score drops
+    /// to the threshold. Returns true if this offence got the peer banned.
+    pub fn punish(&mut self, address: &Address, offence: Offence, reason: &str, now: u64) -> bool {
+        let config = self.config;
+        let score = self.scores.entry(*address).or_insert_with(Score::default);
+        if score.banned_until > now {
+            return false;
+        }
+        let points = score.recovered(now) - penalty(offence);
+        score.updated = now;
+        if points <= config.ban_threshold {
+            // starts over once the ban is lifted
+            score.points = 0;
+            score.banned_until = now + config.ban_secs;
+            warn!("ban peer {:?} for {}s, {:?}: {}", address, config.ban_secs, offence, reason);
+            true
+        } else {
+            score.points = points;
+            info!("peer {:?} scores {} after {:?}: {}", address, points, offence, reason);
+            false
+        }
+    }
+
+    pub fn is_banned(&self, address: &Address, now: u64) -> bool {
+        self.banned_until(address, now).is_some()
+    }
+
+    pub fn banned_until(&self, address: &Address, now: u64) -> Option<u64> {
+        self.scores.get(address).map(|score| score.banned_until).and_then(|until| if until > now { Some(until) } else { None })
+    }
+
+    pub fn score(&self, address: &Address, now: u64) -> i64 {
+        self.scores.get(address).map_or(0, |score| score.recovered(now))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use libproto::factory::ZERO_ORIGIN;
+
+    fn verdict(origin: Origin, offence: Offence) -> Verdict {
+        Verdict::new(origin, offence, "test".to_string())
+    }
+
+    /// Reputation with origins 1 and 2 bound to the addresses 1 and 2
+    fn reputation(config: ReputationConfig) -> (Reputation, Address, Address) {
+        let mut reputation = Reputation::new(config);
+        let (a, b) = (Address::from(1), Address::from(2));
+        reputation.bind(1, a);
+        reputation.bind(2, b);
+        (reputation, a, b)
+    }
+
+    #[test]
+    fn ban_below_threshold() {
+        let (mut reputation, a, b) = reputation(ReputationConfig::default());
+        assert!(!reputation.judge(&verdict(1, Offence::InvalidBlock), 100));
+        assert_eq!(reputation.score(&a, 100), -50);
+        assert!(!reputation.is_banned(&a, 100));
+
+        assert!(reputation.judge(&verdict(1, Offence::InvalidBlock), 100));
+        assert_eq!(reputation.banned_until(&a, 100), Some(100 + DEFAULT_BAN_SECS));
+        // no more verdicts count while banned
+        assert!(!reputation.judge(&verdict(1, Offence::InvalidBlock), 101));
+
+        let lifted = 100 + DEFAULT_BAN_SECS;
+        assert!(!reputation.is_banned(&a, lifted));
+        assert_eq!(reputation.score(&a, lifted), 0);
+        assert_eq!(reputation.score(&b, lifted), 0);
+    }
+
+    #[test]
+    fn recover_over_time() {
+        let (mut reputation, a, _) = reputation(ReputationConfig::default());
+        reputation.judge(&verdict(1, Offence::InvalidSignature), 0);
+        assert_eq!(reputation.score(&a, RECOVER_SECS * 5), -20);
+        assert_eq!(reputation.score(&a, RECOVER_SECS * 100), 0);
+
+        // a slow trickle of offences never adds up to a ban
+        for i in 0..100 {
+            assert!(!reputation.judge(&verdict(1, Offence::InvalidTx), 1000 + i * RECOVER_SECS * 5));
+        }
+    }
+
+    #[test]
+    fn ignore_local() {
+        let (mut reputation, a, _) = reputation(ReputationConfig { ban_threshold: 0, ban_secs: 10 });
+        assert!(!reputation.judge(&verdict(ZERO_ORIGIN, Offence::InvalidBlock), 0));
+        assert!(!reputation.authenticate(ZERO_ORIGIN, &Address::from(3)));
+        assert!(reputation.judge(&verdict(1, Offence::InvalidTx), 0));
+        assert!(reputation.is_banned(&a, 0));
+    }
+
+    #[test]
+    fn blame_the_proved_address() {
+        let (mut reputation, a, b) = reputation(ReputationConfig::default());
+        let c = Address::from(3);
+        // a peer can't claim an origin bound to another, nor a second one
+        assert!(reputation.authenticate(1, &a));
+        assert!(!reputation.authenticate(1, &c));
+        assert!(reputation.authenticate(3, &c));
+        assert!(!reputation.authenticate(4, &c));
+        reputation.judge(&verdict(3, Offence::InvalidBlock), 0);
+        assert_eq!(reputation.score(&c, 0), -50);
+        assert_eq!(reputation.score(&a, 0), 0);
+
+        // verdicts on origins nobody proved are dropped
+        assert!(!reputation.judge(&verdict(4, Offence::InvalidBlock), 0));
+        assert_eq!(reputation.address(4), None);
+
+        // connecting the configured peer overrides what was learned
+        reputation.bind(3, b);
+        assert_eq!(reputation.address(3), Some(&b));
+        assert_eq!(reputation.address(2), None);
+        assert!(!reputation.authenticate(3, &c));
+        assert!(reputation.authenticate(4, &c));
+    }
+}
@@ -15,13 +15,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use citaprotocol::{CitaProto, CitaRequest, CitaResponse};
+use citaprotocol::{CitaProto, CitaRequest, CitaResponse, PeerRequest};
 
 use config::NetConfig;
+use connection::start_reactor;
 
 use futures::{BoxFuture, Future};
 use futures::future::result;
 use msghandle::net_msg_handler;
+use nodes::SharedNodeList;
+use reputation::SharedReputation;
+use stats::SharedInbound;
 use std::{io, thread};
 use std::net::SocketAddr;
 use std::sync::mpsc::Sender;
@@ -48,29 +52,38 @@ unsafe impl Sync for MySender {}
 struct Server {
     mysender: MySender,
     mysender_tx: MySender,
+    mysender_discovery: MySender,
+    inbound: SharedInbound,
+    reputation: SharedReputation,
 }
 
 impl Service for Server {
-    type Request = CitaRequest;
+    type Request = PeerRequest;
     type Response = CitaResponse;
     type Error = io::Error;
     type Future = BoxFuture<Self::Response, io::Error>;
 
-    fn call(&self, req: Self::Request) -> Self::Future {
-        result(net_msg_handler(req, &self.mysender, &self.mysender_tx)).boxed()
+    fn call(&self, (peer, req): Self::Request) -> Self::Future {
+        result(net_msg_handler(req, peer, &self.mysender, &self.mysender_tx, &self.mysender_discovery, &self.inbound, &self.reputation)).boxed()
     }
 }
 
-pub fn start_server(config: &NetConfig, mysender: MySender, mysender_tx: MySender) {
+pub fn start_server(config: &NetConfig, nodes: SharedNodeList, mysender: MySender, mysender_tx: MySender, mysender_discovery: MySender, inbound: SharedInbound, reputation: SharedReputation) {
     let addr = format!("0.0.0.0:{}", config.port.unwrap());
     let addr = addr.parse::<SocketAddr>().unwrap();
+    let signer = config.signer();
+    let frame = config.frame();
+    let timer = start_reactor("server timer");
 
     thread::spawn(move || {
         info!("start server on {:?}!", addr);
-        TcpServer::new(CitaProto, addr).serve(move || {
+        TcpServer::new(CitaProto::new(signer, nodes, frame, timer), addr).serve(move || {
                                                   Ok(Server {
                                                          mysender: mysender.clone(),
                                                          mysender_tx: mysender_tx.clone(),
+                                                         mysender_discovery: mysender_discovery.clone(),
+                                                         inbound: inbound.clone(),
+                                                         reputation: reputation.clone(),
                                                      })
                                               });
     });
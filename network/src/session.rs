@@ -0,0 +1,294 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Authenticated and encrypted sessions between peers.
+//!
+//! Right after connecting, both sides send a hello:
+//!
+//! +-- ephemeral pubkey --+-- nonce --+-- features --+
+//! |       64 bytes       | 32 bytes  |    1 byte    |
+//! +----------------------+-----------+--------------+
+//!
+//! and then a proof, the signature by the node key of both hellos and the signer's role.
+//! It covers the fresh nonce of the peer, so a recorded proof is no good for another
+//! session, and the peer's address is recovered from it. The responder sends its proof
+//! along with its hello, the initiator only once it checked the responder's.
+//! Features are what the frames may use, the session gets those both sides offered.
+//! Each direction gets its own AES-256-GCM key derived from the ECDH secret of the
+//! ephemeral keys and both nonces. Every frame is sealed with a per-direction counter
+//! as nonce, so replayed, reordered or tampered frames fail to open.
+
+use crypto::{PrivKey, Signature, Sign, SIGNATURE_BYTES_LEN, pubkey_to_address};
+use cita_secp256k1::{KeyPair as EphemeralKeyPair, PubKey as EphemeralPubKey, PUBKEY_BYTES_LEN, ecdh};
+use rcrypto::aead::{AeadEncryptor, AeadDecryptor};
+use rcrypto::aes::KeySize;
+use rcrypto::aes_gcm::AesGcm;
+use std::io;
+use util::{H256, Address, Hashable};
+use util::crypto::CreateKey;
+
+const NONCE_LEN: usize = 32;
+const FEATURES_LEN: usize = 1;
+pub const TAG_LEN: usize = 16;
+pub const HELLO_LEN: usize = PUBKEY_BYTES_LEN + NONCE_LEN + FEATURES_LEN;
+pub const PROOF_LEN: usize = SIGNATURE_BYTES_LEN;
+/// Seconds either side waits for the other to finish the handshake
+pub const HANDSHAKE_TIMEOUT: u64 = 15;
+
+/// Several msgs in a frame
+pub const FEATURE_BATCH: u8 = 0x01;
+/// Compressed frame payloads
+pub const FEATURE_COMPRESS: u8 = 0x02;
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// One side of the handshake, holding the ephemeral key until the peer's hello arrives.
+pub struct Handshake {
+    ephemeral: EphemeralKeyPair,
+    nonce: H256,
+    features: u8,
+}
+
+impl Handshake {
+    /// Start a handshake offering `features`
+    pub fn new(features: u8) -> Self {
+        Handshake {
+            ephemeral: EphemeralKeyPair::gen_keypair(),
+            nonce: H256::random(),
+            features: features,
+        }
+    }
+
+    pub fn hello(&self) -> Vec<u8> {
+        let mut hello = Vec::with_capacity(HELLO_LEN);
+        hello.extend_from_slice(&self.ephemeral.pubkey().0);
+        hello.extend_from_slice(&self.nonce.0);
+        hello.push(self.features);
+        hello
+    }
+
+    /// Hash of both hellos and the role of the side signing it
+    fn transcript(&self, peer_hello: &[u8], initiator: bool, signed_by_initiator: bool) -> H256 {
+        let hello = self.hello();
+        let (initiator_hello, responder_hello) = if initiator { (&hello[..], peer_hello) } else { (peer_hello, &hello[..]) };
+        let mut transcript = Vec::with_capacity(HELLO_LEN * 2 + 1);
+        transcript.extend_from_slice(initiator_hello);
+        transcript.extend_from_slice(responder_hello);
+        transcript.push(if signed_by_initiator { b'i' } else { b'r' });
+        transcript.crypt_hash()
+    }
+
+    /// Sign this session with the node key, to be sent after the peer's hello arrived.
+    pub fn proof(&self, signer: &PrivKey, peer_hello: &[u8], initiator: bool) -> Vec<u8> {
+        let signature = Signature::sign(signer, &self.transcript(peer_hello, initiator, initiator)).expect("sign session with node key");
+        signature.0.to_vec()
+    }
+
+    /// Check the peer's hello and proof, and derive the session keys.
+    /// `initiator` tells whether this side opened the connection.
+    pub fn finish(self, hello: &[u8], proof: &[u8], initiator: bool) -> io::Result<Session> {
+        if hello.len() != HELLO_LEN {
+            return Err(invalid_data("bad hello length"));
+        }
+        if proof.len() != PROOF_LEN {
+            return Err(invalid_data("bad proof length"));
+        }
+        let peer_pubkey = Signature::from(proof)
+            .recover(&self.transcript(hello, initiator, !initiator))
+            .map_err(|_| invalid_data("bad session proof"))?;
+        let peer_ephemeral = EphemeralPubKey::from_slice(&hello[..PUBKEY_BYTES_LEN]);
+        let peer_nonce = H256::from_slice(&hello[PUBKEY_BYTES_LEN..PUBKEY_BYTES_LEN + NONCE_LEN]);
+        // unknown bits are from a newer peer, and not in ours
+        let peer_features = hello[PUBKEY_BYTES_LEN + NONCE_LEN];
+
+        let shared = ecdh(self.ephemeral.privkey(), &peer_ephemeral).map_err(|_| invalid_data("bad ephemeral pubkey"))?;
+        let (initiator_nonce, responder_nonce) = if initiator { (self.nonce, peer_nonce) } else { (peer_nonce, self.nonce) };
+        let derive = |label: u8| {
+            let mut material = Vec::with_capacity(32 * 3 + 1);
+            material.extend_from_slice(&shared.0);
+            material.extend_from_slice(&initiator_nonce.0);
+            material.extend_from_slice(&responder_nonce.0);
+            material.push(label);
+            material.crypt_hash()
+        };
+        let (send_key, recv_key) = if initiator { (derive(b'i'), derive(b'r')) } else { (derive(b'r'), derive(b'i')) };
+
+        Ok(Session {
+               peer: pubkey_to_address(&peer_pubkey),
+               send_key: send_key,
+               recv_key: recv_key,
+               send_seq: 0,
+               recv_seq: 0,
+               features: self.features & peer_features,
+           })
+    }
+}
+
+pub struct Session {
+    peer: Address,
+    send_key: H256,
+    recv_key: H256,
+    send_seq: u64,
+    recv_seq: u64,
+    features: u8,
+}
+
+fn frame_nonce(seq: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    for i in 0..8 {
+        nonce[4 + i] = (seq >> (8 * (7 - i))) as u8;
+    }
+    nonce
+}
+
+impl Session {
+    /// Address of the node key the peer proved it holds.
+    pub fn peer(&self) -> &Address {
+        &self.peer
+    }
+
+    /// Features both sides offered
+    pub fn features(&self) -> u8 {
+        self.features
+    }
+
+    /// Encrypt `msg`, `aad` is only authenticated
+    pub fn seal(&mut self, msg: &[u8], aad: &[u8]) -> Vec<u8> {
+        let mut cipher = AesGcm::new(KeySize::KeySize256, &self.send_key.0, &frame_nonce(self.send_seq), aad);
+        self.send_seq += 1;
+        let mut sealed = vec![0u8; msg.len() + TAG_LEN];
+        {
+            let (out, tag) = sealed.split_at_mut(msg.len());
+            cipher.encrypt(msg, out, tag);
+        }
+        sealed
+    }
+
+    pub fn open(&mut self, sealed: &[u8], aad: &[u8]) -> io::Result<Vec<u8>> {
+        if sealed.len() < TAG_LEN {
+            return Err(invalid_data("frame shorter than tag"));
+        }
+        let (data, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+        let mut cipher = AesGcm::new(KeySize::KeySize256, &self.recv_key.0, &frame_nonce(self.recv_seq), aad);
+        let mut msg = vec![0u8; data.len()];
+        if !cipher.decrypt(data, &mut msg, tag) {
+            return Err(invalid_data("frame authentication failed"));
+        }
+        self.recv_seq += 1;
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crypto::KeyPair;
+    use std::io;
+
+    fn sessions() -> (Session, Session, Address, Address) {
+        let a = KeyPair::gen_keypair();
+        let b = KeyPair::gen_keypair();
+        let ha = Handshake::new(FEATURE_BATCH | FEATURE_COMPRESS);
+        let hb = Handshake::new(FEATURE_BATCH);
+        let (hello_a, hello_b) = (ha.hello(), hb.hello());
+        let (proof_a, proof_b) = (ha.proof(a.privkey(), &hello_b, true), hb.proof(b.privkey(), &hello_a, false));
+        let sa = ha.finish(&hello_b, &proof_b, true).unwrap();
+        let sb = hb.finish(&hello_a, &proof_a, false).unwrap();
+        (sa, sb, a.address(), b.address())
+    }
+
+    #[test]
+    fn handshake_authenticates_peers() {
+        let (sa, sb, a, b) = sessions();
+        assert_eq!(*sa.peer(), b);
+        assert_eq!(*sb.peer(), a);
+    }
+
+    #[test]
+    fn seal_and_open() {
+        let (mut sa, mut sb, _, _) = sessions();
+        let first = sa.seal(b"hello", &[]);
+        let second = sa.seal(b"hello", &[]);
+        assert!(first != second);
+        assert_eq!(sb.open(&first, &[]).unwrap(), b"hello".to_vec());
+        assert_eq!(sb.open(&second, &[]).unwrap(), b"hello".to_vec());
+        assert_eq!(sa.open(&sb.seal(b"", &[]), &[]).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn tampered_or_replayed_frame_fails() {
+        let (mut sa, mut sb, _, _) = sessions();
+        let mut frame = sa.seal(b"vote", &[]);
+        frame[0] ^= 1;
+        assert!(sb.open(&frame, &[]).is_err());
+
+        let (mut sa, mut sb, _, _) = sessions();
+        let frame = sa.seal(b"vote", &[1]);
+        assert!(sb.open(&frame, &[2]).is_err());
+
+        let (mut sa, mut sb, _, _) = sessions();
+        let frame = sa.seal(b"vote", &[]);
+        assert!(sb.open(&frame, &[]).is_ok());
+        assert!(sb.open(&frame, &[]).is_err());
+    }
+
+    /// The session `responder` gets from the hello and proof of `initiator`
+    fn respond(initiator: &Handshake, hello: &[u8], signer: &KeyPair, responder: Handshake) -> io::Result<Session> {
+        let proof = initiator.proof(signer.privkey(), &responder.hello(), true);
+        responder.finish(hello, &proof, false)
+    }
+
+    #[test]
+    fn forged_hello_fails() {
+        let a = KeyPair::gen_keypair();
+        let ha = Handshake::new(FEATURE_BATCH);
+        let mut hello = ha.hello();
+        hello[0] ^= 1;
+        let session = respond(&ha, &hello, &a, Handshake::new(FEATURE_BATCH));
+        assert!(session.map(|s| *s.peer() != a.address()).unwrap_or(true));
+
+        // nor can a peer's features be changed on the way
+        let ha = Handshake::new(FEATURE_BATCH);
+        let mut hello = ha.hello();
+        hello[PUBKEY_BYTES_LEN + NONCE_LEN] |= FEATURE_COMPRESS;
+        let session = respond(&ha, &hello, &a, Handshake::new(FEATURE_BATCH | FEATURE_COMPRESS));
+        assert!(session.map(|s| *s.peer() != a.address()).unwrap_or(true));
+    }
+
+    #[test]
+    fn replayed_handshake_fails() {
+        let a = KeyPair::gen_keypair();
+        let ha = Handshake::new(FEATURE_BATCH);
+        let hb = Handshake::new(FEATURE_BATCH);
+        let (hello_a, hello_b) = (ha.hello(), hb.hello());
+        let proof_a = ha.proof(a.privkey(), &hello_b, true);
+        assert_eq!(*hb.finish(&hello_a, &proof_a, false).unwrap().peer(), a.address());
+
+        // a recorded hello and proof do not pass with another responder nonce
+        let session = Handshake::new(FEATURE_BATCH).finish(&hello_a, &proof_a, false);
+        assert!(session.map(|s| *s.peer() != a.address()).unwrap_or(true));
+    }
+
+    #[test]
+    fn negotiate_features() {
+        let (sa, sb, _, _) = sessions();
+        assert_eq!(sa.features(), FEATURE_BATCH);
+        assert_eq!(sb.features(), FEATURE_BATCH);
+    }
+}
@@ -0,0 +1,134 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per peer traffic, reported by `net_peers`.
+
+use libproto::{de_cmd_id, topic_to_string};
+use libproto::communication;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use util::RwLock;
+
+#[derive(Debug, Default, Clone, PartialEq, RustcEncodable)]
+pub struct Traffic {
+    pub msgs: u64,
+    pub bytes: u64,
+}
+
+/// Traffic by topic name
+pub type TopicTraffic = BTreeMap<String, Traffic>;
+
+pub fn record(traffic: &mut TopicTraffic, cmd_id: u32, bytes: usize) {
+    let topic = topic_to_string(de_cmd_id(cmd_id).1).to_string();
+    let entry = traffic.entry(topic).or_insert_with(Traffic::default);
+    entry.msgs += 1;
+    entry.bytes += bytes as u64;
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Received {
+    /// Unix time of the last message
+    pub last_seen: u64,
+    /// Height of the last status the peer sent
+    pub height: Option<u64>,
+    pub traffic: TopicTraffic,
+}
+
+pub type SharedInbound = Arc<RwLock<InboundStats>>;
+
+/// What the server received, by the `origin` the sender put in its messages.
+/// Incoming connections are not tied to a peer entry, so origin is how they are told apart.
+#[derive(Debug, Default)]
+pub struct InboundStats {
+    peers: HashMap<u32, Received>,
+}
+
+impl InboundStats {
+    pub fn new() -> Self {
+        InboundStats::default()
+    }
+
+    pub fn record(&mut self, msg: &communication::Message, bytes: usize, now: u64) {
+        let received = self.peers.entry(msg.get_origin()).or_insert_with(Received::default);
+        received.last_seen = now;
+        record(&mut received.traffic, msg.get_cmd_id(), bytes);
+    }
+
+    pub fn set_height(&mut self, origin: u32, height: u64) {
+        self.peers.entry(origin).or_insert_with(Received::default).height = Some(height);
+    }
+
+    pub fn get(&self, origin: u32) -> Option<&Received> {
+        self.peers.get(&origin)
+    }
+}
+
+/// One entry of `net_peers`
+#[derive(Debug, RustcEncodable)]
+pub struct PeerInfo {
+    pub id_card: u32,
+    /// Node address proved in the handshake, unknown in plaintext mode
+    pub address: Option<String>,
+    pub endpoint: String,
+    /// "config", "discovery" or "admin"
+    pub source: String,
+    /// "connected" or "connecting"
+    pub state: String,
+    pub last_seen: Option<u64>,
+    pub reconnects: u64,
+    pub height: Option<u64>,
+    /// Messages dropped as the peer was too slow
+    pub dropped: u64,
+    pub sent: TopicTraffic,
+    pub received: TopicTraffic,
+    /// Reputation, 0 unless the peer misbehaved lately
+    pub score: i64,
+    /// Unix time the peer's ban ends, while banned
+    pub banned_until: Option<u64>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use libproto::{cmd_id, factory, submodules, topics};
+    use libproto::communication::MsgType;
+
+    #[test]
+    fn record_by_origin_and_topic() {
+        let mut inbound = InboundStats::new();
+        let mut msg = factory::create_msg(submodules::CONSENSUS, topics::CONSENSUS_MSG, MsgType::MSG, vec![1, 2, 3]);
+        msg.set_origin(1);
+        inbound.record(&msg, 10, 100);
+        inbound.record(&msg, 20, 101);
+        inbound.set_height(1, 7);
+
+        let received = inbound.get(1).unwrap();
+        assert_eq!(received.last_seen, 101);
+        assert_eq!(received.height, Some(7));
+        assert_eq!(received.traffic["consensus_msg"], Traffic { msgs: 2, bytes: 30 });
+        assert!(inbound.get(2).is_none());
+
+        let mut sent = TopicTraffic::new();
+        record(&mut sent, cmd_id(submodules::CHAIN, topics::NEW_BLK), 5);
+        assert_eq!(sent["new_blk"], Traffic { msgs: 1, bytes: 5 });
+    }
+}
@@ -13,9 +13,14 @@ def main():
     net_config_name = "network.toml"
     size = int(sys.argv[3])
     dump_path = os.path.join(path, net_config_name)
+    secret_path = os.path.join(path, "privkey")
+    with open(secret_path, "r") as secret_key:
+        signer = secret_key.read().strip()
     with open(dump_path, "w") as f:
         f.write("id_card = " + str(nid) + "\n")
         f.write("port = " + port + "\n")
+        f.write("ip = \"" + ip_list[nid].split(':')[0] + "\"\n")
+        f.write("signer = \"" + signer + "\"\n")
         f.write("max_peer = " + str(size - 1) + "\n")
         ids = range(size)
         ip_list = zip(ids, ip_list)
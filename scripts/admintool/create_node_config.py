@@ -27,6 +27,9 @@ def main():
     if name == "tendermint":
         tendermint = dict(params=params)
         engine = dict(Tendermint=tendermint)
+    elif name == "raft":
+        raft = dict(params=params)
+        engine = dict(Raft=raft)
     else:
         authorityround = dict(params=params)
         engine = dict(AuthorityRound=authorityround)
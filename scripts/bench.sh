@@ -32,3 +32,5 @@ echo "performance test for call"
 time bash tests/wrk_benchmark_test/chain_performance.sh 2 10000 0 0
 echo "performance test for store"
 time bash tests/wrk_benchmark_test/chain_performance.sh 3 10000 0 0
+echo "performance test for network"
+time bash tests/wrk_benchmark_test/chain_performance.sh 4 10000 0 0
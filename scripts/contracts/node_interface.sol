@@ -10,6 +10,10 @@ interface NodeInterface {
     // list the consensus nodes that have been approved
     // which means list the node whose status is start
     function listNode() constant returns (string);
+    // set the voting power of a node
+    function setWeight(address _node, uint64 _weight) returns (bool);
+    // list the voting power of the nodes, in the order of listNode
+    function listWeight() constant returns (uint64[]);
     // get the status of the node
     // 0: close;
     // 1: ready;
@@ -19,11 +19,16 @@ contract NodeManager is NodeInterface {
 
     // consensus node list
     address[] nodes;
+    // voting power of the consensus nodes, 1 by default
+    mapping(address => uint64) weights;
+    // consensus sums the weights in 64 bits, keep each of them far below
+    uint64 constant MAX_WEIGHT = 2**32;
 
     event NewNode(address _node);
     event ApproveNode(address _node);
     event DeleteNode(address _node);
     event AddAdmin(address indexed _node, address indexed _sender);
+    event SetWeight(address indexed _node, uint64 _weight);
 
     modifier onlyAdmin {
         if (admins[msg.sender]) {
@@ -47,6 +52,7 @@ contract NodeManager is NodeInterface {
         // initialize the address to Start
         for (uint i = 0; i < _nodes.length; i++) {
             status[_nodes[i]] = NodeStatus.Start;
+            weights[_nodes[i]] = 1;
             nodes.push(_nodes[i]);
         }
         // initialize the address of admins
@@ -84,6 +90,9 @@ contract NodeManager is NodeInterface {
         }
 
         status[_node] = NodeStatus.Start;
+        if (weights[_node] == 0) {
+            weights[_node] = 1;
+        }
         block_op[block.number] = true;
         nodes.push(_node);
         ApproveNode(_node);
@@ -119,11 +128,31 @@ contract NodeManager is NodeInterface {
         return true;
     }
 
+    // set the voting power of a node
+    function setWeight(address _node, uint64 _weight) onlyAdmin oneOperate returns (bool) {
+        if (_weight == 0 || _weight > MAX_WEIGHT) {
+            return false;
+        }
+
+        weights[_node] = _weight;
+        block_op[block.number] = true;
+        SetWeight(_node, _weight);
+        return true;
+    }
+
     // list the node of the Start
     function listNode() constant returns (string) {
         return concatNodes(nodes);
     }
 
+    // list the voting power of the nodes, in the order of listNode
+    function listWeight() constant returns (uint64[] _weights) {
+        _weights = new uint64[](nodes.length);
+        for (uint i = 0; i < nodes.length; i++) {
+            _weights[i] = weights[nodes[i]];
+        }
+    }
+
     // get the status of the node
     function getStatus(address _node) constant returns (uint8) {
         return uint8(status[_node]);
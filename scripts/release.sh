@@ -31,6 +31,7 @@ cp -rf target/${type}/jsonrpc_performance  target/install/bin/
 cp -rf target/${type}/latency              target/install/bin/
 cp -rf target/${type}/benchmark_ws         target/install/bin/
 cp -rf target/${type}/monitor              target/install/bin/
+cp -rf tools/allinone/target/${type}/allinone target/install/bin/
 #strip                                     target/install/bin/*
 
 # 2) cita
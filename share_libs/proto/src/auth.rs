@@ -1214,6 +1214,10 @@ pub struct BlockTxHashes {
     pub tx_hashes: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
     pub block_gas_limit: u64,
     pub account_gas_limit: ::protobuf::SingularPtrField<super::blockchain::AccountGasLimit>,
+    pub senders: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
+    pub creators: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
+    pub check_permission: bool,
+    pub check_quota: bool,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1356,6 +1360,118 @@ impl BlockTxHashes {
     fn mut_account_gas_limit_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<super::blockchain::AccountGasLimit> {
         &mut self.account_gas_limit
     }
+
+    // repeated bytes senders = 5;
+
+    pub fn clear_senders(&mut self) {
+        self.senders.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_senders(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.senders = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_senders(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.senders
+    }
+
+    // Take field
+    pub fn take_senders(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.senders, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_senders(&self) -> &[::std::vec::Vec<u8>] {
+        &self.senders
+    }
+
+    fn get_senders_for_reflect(&self) -> &::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &self.senders
+    }
+
+    fn mut_senders_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.senders
+    }
+
+    // repeated bytes creators = 6;
+
+    pub fn clear_creators(&mut self) {
+        self.creators.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_creators(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.creators = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_creators(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.creators
+    }
+
+    // Take field
+    pub fn take_creators(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.creators, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_creators(&self) -> &[::std::vec::Vec<u8>] {
+        &self.creators
+    }
+
+    fn get_creators_for_reflect(&self) -> &::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &self.creators
+    }
+
+    fn mut_creators_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.creators
+    }
+
+    // bool check_permission = 7;
+
+    pub fn clear_check_permission(&mut self) {
+        self.check_permission = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_check_permission(&mut self, v: bool) {
+        self.check_permission = v;
+    }
+
+    pub fn get_check_permission(&self) -> bool {
+        self.check_permission
+    }
+
+    fn get_check_permission_for_reflect(&self) -> &bool {
+        &self.check_permission
+    }
+
+    fn mut_check_permission_for_reflect(&mut self) -> &mut bool {
+        &mut self.check_permission
+    }
+
+    // bool check_quota = 8;
+
+    pub fn clear_check_quota(&mut self) {
+        self.check_quota = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_check_quota(&mut self, v: bool) {
+        self.check_quota = v;
+    }
+
+    pub fn get_check_quota(&self) -> bool {
+        self.check_quota
+    }
+
+    fn get_check_quota_for_reflect(&self) -> &bool {
+        &self.check_quota
+    }
+
+    fn mut_check_quota_for_reflect(&mut self) -> &mut bool {
+        &mut self.check_quota
+    }
 }
 
 impl ::protobuf::Message for BlockTxHashes {
@@ -1392,6 +1508,26 @@ impl ::protobuf::Message for BlockTxHashes {
                 4 => {
                     ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.account_gas_limit)?;
                 },
+                5 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.senders)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.creators)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.check_permission = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.check_quota = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1417,6 +1553,18 @@ impl ::protobuf::Message for BlockTxHashes {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        for value in &self.senders {
+            my_size += ::protobuf::rt::bytes_size(5, &value);
+        };
+        for value in &self.creators {
+            my_size += ::protobuf::rt::bytes_size(6, &value);
+        };
+        if self.check_permission != false {
+            my_size += 2;
+        }
+        if self.check_quota != false {
+            my_size += 2;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1437,6 +1585,18 @@ impl ::protobuf::Message for BlockTxHashes {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        for v in &self.senders {
+            os.write_bytes(5, &v)?;
+        };
+        for v in &self.creators {
+            os.write_bytes(6, &v)?;
+        };
+        if self.check_permission != false {
+            os.write_bool(7, self.check_permission)?;
+        }
+        if self.check_quota != false {
+            os.write_bool(8, self.check_quota)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1501,6 +1661,26 @@ impl ::protobuf::MessageStatic for BlockTxHashes {
                     BlockTxHashes::get_account_gas_limit_for_reflect,
                     BlockTxHashes::mut_account_gas_limit_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "senders",
+                    BlockTxHashes::get_senders_for_reflect,
+                    BlockTxHashes::mut_senders_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "creators",
+                    BlockTxHashes::get_creators_for_reflect,
+                    BlockTxHashes::mut_creators_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "check_permission",
+                    BlockTxHashes::get_check_permission_for_reflect,
+                    BlockTxHashes::mut_check_permission_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "check_quota",
+                    BlockTxHashes::get_check_quota_for_reflect,
+                    BlockTxHashes::mut_check_quota_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<BlockTxHashes>(
                     "BlockTxHashes",
                     fields,
@@ -1517,6 +1697,10 @@ impl ::protobuf::Clear for BlockTxHashes {
         self.clear_tx_hashes();
         self.clear_block_gas_limit();
         self.clear_account_gas_limit();
+        self.clear_senders();
+        self.clear_creators();
+        self.clear_check_permission();
+        self.clear_check_quota();
         self.unknown_fields.clear();
     }
 }
@@ -1711,6 +1895,9 @@ pub enum Ret {
     BadSig = 4,
     NotReady = 5,
     Busy = 6,
+    NoTransactionPermission = 7,
+    NoContractPermission = 8,
+    AccountGasLimitReached = 9,
 }
 
 impl ::protobuf::ProtobufEnum for Ret {
@@ -1727,6 +1914,9 @@ impl ::protobuf::ProtobufEnum for Ret {
             4 => ::std::option::Option::Some(Ret::BadSig),
             5 => ::std::option::Option::Some(Ret::NotReady),
             6 => ::std::option::Option::Some(Ret::Busy),
+            7 => ::std::option::Option::Some(Ret::NoTransactionPermission),
+            8 => ::std::option::Option::Some(Ret::NoContractPermission),
+            9 => ::std::option::Option::Some(Ret::AccountGasLimitReached),
             _ => ::std::option::Option::None
         }
     }
@@ -1740,6 +1930,9 @@ impl ::protobuf::ProtobufEnum for Ret {
             Ret::BadSig,
             Ret::NotReady,
             Ret::Busy,
+            Ret::NoTransactionPermission,
+            Ret::NoContractPermission,
+            Ret::AccountGasLimitReached,
         ];
         values
     }
@@ -1785,104 +1978,128 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     VerifyBlockReq\x12\x0e\n\x02id\x18\x01\x20\x01(\x04R\x02id\x12\x20\n\x04\
     reqs\x18\x02\x20\x03(\x0b2\x0c.VerifyTxReqR\x04reqs\"9\n\x0fVerifyBlockR\
     esp\x12\x0e\n\x02id\x18\x01\x20\x01(\x04R\x02id\x12\x16\n\x03ret\x18\x02\
-    \x20\x01(\x0e2\x04.RetR\x03ret\"\xaa\x01\n\rBlockTxHashes\x12\x16\n\x06h\
+    \x20\x01(\x0e2\x04.RetR\x03ret\"\xac\x02\n\rBlockTxHashes\x12\x16\n\x06h\
     eight\x18\x01\x20\x01(\x04R\x06height\x12\x1b\n\ttx_hashes\x18\x02\x20\
     \x03(\x0cR\x08txHashes\x12&\n\x0fblock_gas_limit\x18\x03\x20\x01(\x04R\r\
     blockGasLimit\x12<\n\x11account_gas_limit\x18\x04\x20\x01(\x0b2\x10.Acco\
-    untGasLimitR\x0faccountGasLimit\"*\n\x10BlockTxHashesReq\x12\x16\n\x06he\
-    ight\x18\x01\x20\x01(\x04R\x06height*c\n\x03Ret\x12\x06\n\x02Ok\x10\0\
-    \x12\x10\n\x0cInvalidNonce\x10\x01\x12\x07\n\x03Dup\x10\x02\x12\x15\n\
-    \x11InvalidUntilBlock\x10\x03\x12\n\n\x06BadSig\x10\x04\x12\x0c\n\x08Not\
-    Ready\x10\x05\x12\x08\n\x04Busy\x10\x06J\xd6\x0e\n\x06\x12\x04\0\01\x01\
-    \n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\x07\x19\n\n\
-    \n\x02\x05\0\x12\x04\x04\0\x0c\x01\n\n\n\x03\x05\0\x01\x12\x03\x04\x05\
-    \x08\n\x0b\n\x04\x05\0\x02\0\x12\x03\x05\x04\x0b\n\x0c\n\x05\x05\0\x02\0\
-    \x01\x12\x03\x05\x04\x06\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x05\t\n\n\
-    \x0b\n\x04\x05\0\x02\x01\x12\x03\x06\x04\x15\n\x0c\n\x05\x05\0\x02\x01\
-    \x01\x12\x03\x06\x04\x10\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\x06\x13\
-    \x14\n\x0b\n\x04\x05\0\x02\x02\x12\x03\x07\x04\x0c\n\x0c\n\x05\x05\0\x02\
-    \x02\x01\x12\x03\x07\x04\x07\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\x07\n\
-    \x0b\n\x0b\n\x04\x05\0\x02\x03\x12\x03\x08\x04\x1a\n\x0c\n\x05\x05\0\x02\
-    \x03\x01\x12\x03\x08\x04\x15\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x08\
-    \x18\x19\n\x0b\n\x04\x05\0\x02\x04\x12\x03\t\x04\x0f\n\x0c\n\x05\x05\0\
-    \x02\x04\x01\x12\x03\t\x04\n\n\x0c\n\x05\x05\0\x02\x04\x02\x12\x03\t\r\
-    \x0e\n\x0b\n\x04\x05\0\x02\x05\x12\x03\n\x04\x11\n\x0c\n\x05\x05\0\x02\
-    \x05\x01\x12\x03\n\x04\x0c\n\x0c\n\x05\x05\0\x02\x05\x02\x12\x03\n\x0f\
-    \x10\n\x0b\n\x04\x05\0\x02\x06\x12\x03\x0b\x04\r\n\x0c\n\x05\x05\0\x02\
-    \x06\x01\x12\x03\x0b\x04\x08\n\x0c\n\x05\x05\0\x02\x06\x02\x12\x03\x0b\
-    \x0b\x0c\n\n\n\x02\x04\0\x12\x04\x0e\0\x16\x01\n\n\n\x03\x04\0\x01\x12\
-    \x03\x0e\x08\x13\n\x0b\n\x04\x04\0\x02\0\x12\x03\x0f\x04!\n\r\n\x05\x04\
-    \0\x02\0\x04\x12\x04\x0f\x04\x0e\x15\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\
-    \x0f\x04\n\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x0f\x0b\x1c\n\x0c\n\x05\
-    \x04\0\x02\0\x03\x12\x03\x0f\x1f\x20\n\x0b\n\x04\x04\0\x02\x01\x12\x03\
-    \x10\x04\x13\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x10\x04\x0f!\n\x0c\n\
-    \x05\x04\0\x02\x01\x05\x12\x03\x10\x04\t\n\x0c\n\x05\x04\0\x02\x01\x01\
-    \x12\x03\x10\n\x0e\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x10\x11\x12\n\
-    \x0b\n\x04\x04\0\x02\x02\x12\x03\x11\x04\x18\n\r\n\x05\x04\0\x02\x02\x04\
-    \x12\x04\x11\x04\x10\x13\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x11\x04\t\
-    \n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x11\n\x13\n\x0c\n\x05\x04\0\x02\
-    \x02\x03\x12\x03\x11\x16\x17\n\x0b\n\x04\x04\0\x02\x03\x12\x03\x12\x04\
-    \x16\n\r\n\x05\x04\0\x02\x03\x04\x12\x04\x12\x04\x11\x18\n\x0c\n\x05\x04\
-    \0\x02\x03\x06\x12\x03\x12\x04\n\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\
-    \x12\x0b\x11\n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\x12\x14\x15\n\x0b\n\
-    \x04\x04\0\x02\x04\x12\x03\x13\x04\x16\n\r\n\x05\x04\0\x02\x04\x04\x12\
-    \x04\x13\x04\x12\x16\n\x0c\n\x05\x04\0\x02\x04\x05\x12\x03\x13\x04\t\n\
-    \x0c\n\x05\x04\0\x02\x04\x01\x12\x03\x13\n\x11\n\x0c\n\x05\x04\0\x02\x04\
-    \x03\x12\x03\x13\x14\x15\n-\n\x04\x04\0\x02\x05\x12\x03\x14\x04\x15\"\
+    untGasLimitR\x0faccountGasLimit\x12\x18\n\x07senders\x18\x05\x20\x03(\
+    \x0cR\x07senders\x12\x1a\n\x08creators\x18\x06\x20\x03(\x0cR\x08creators\
+    \x12)\n\x10check_permission\x18\x07\x20\x01(\x08R\x0fcheckPermission\x12\
+    \x1f\n\x0bcheck_quota\x18\x08\x20\x01(\x08R\ncheckQuota\"*\n\x10BlockTxH\
+    ashesReq\x12\x16\n\x06height\x18\x01\x20\x01(\x04R\x06height*\xb6\x01\n\
+    \x03Ret\x12\x06\n\x02Ok\x10\0\x12\x10\n\x0cInvalidNonce\x10\x01\x12\x07\
+    \n\x03Dup\x10\x02\x12\x15\n\x11InvalidUntilBlock\x10\x03\x12\n\n\x06BadS\
+    ig\x10\x04\x12\x0c\n\x08NotReady\x10\x05\x12\x08\n\x04Busy\x10\x06\x12\
+    \x1b\n\x17NoTransactionPermission\x10\x07\x12\x18\n\x14NoContractPermiss\
+    ion\x10\x08\x12\x1a\n\x16AccountGasLimitReached\x10\tJ\xe7\x11\n\x06\x12\
+    \x04\0\08\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\
+    \x02\x07\x19\n\n\n\x02\x05\0\x12\x04\x04\0\x0f\x01\n\n\n\x03\x05\0\x01\
+    \x12\x03\x04\x05\x08\n\x0b\n\x04\x05\0\x02\0\x12\x03\x05\x04\x0b\n\x0c\n\
+    \x05\x05\0\x02\0\x01\x12\x03\x05\x04\x06\n\x0c\n\x05\x05\0\x02\0\x02\x12\
+    \x03\x05\t\n\n\x0b\n\x04\x05\0\x02\x01\x12\x03\x06\x04\x15\n\x0c\n\x05\
+    \x05\0\x02\x01\x01\x12\x03\x06\x04\x10\n\x0c\n\x05\x05\0\x02\x01\x02\x12\
+    \x03\x06\x13\x14\n\x0b\n\x04\x05\0\x02\x02\x12\x03\x07\x04\x0c\n\x0c\n\
+    \x05\x05\0\x02\x02\x01\x12\x03\x07\x04\x07\n\x0c\n\x05\x05\0\x02\x02\x02\
+    \x12\x03\x07\n\x0b\n\x0b\n\x04\x05\0\x02\x03\x12\x03\x08\x04\x1a\n\x0c\n\
+    \x05\x05\0\x02\x03\x01\x12\x03\x08\x04\x15\n\x0c\n\x05\x05\0\x02\x03\x02\
+    \x12\x03\x08\x18\x19\n\x0b\n\x04\x05\0\x02\x04\x12\x03\t\x04\x0f\n\x0c\n\
+    \x05\x05\0\x02\x04\x01\x12\x03\t\x04\n\n\x0c\n\x05\x05\0\x02\x04\x02\x12\
+    \x03\t\r\x0e\n\x0b\n\x04\x05\0\x02\x05\x12\x03\n\x04\x11\n\x0c\n\x05\x05\
+    \0\x02\x05\x01\x12\x03\n\x04\x0c\n\x0c\n\x05\x05\0\x02\x05\x02\x12\x03\n\
+    \x0f\x10\n\x0b\n\x04\x05\0\x02\x06\x12\x03\x0b\x04\r\n\x0c\n\x05\x05\0\
+    \x02\x06\x01\x12\x03\x0b\x04\x08\n\x0c\n\x05\x05\0\x02\x06\x02\x12\x03\
+    \x0b\x0b\x0c\n\x0b\n\x04\x05\0\x02\x07\x12\x03\x0c\x04\x20\n\x0c\n\x05\
+    \x05\0\x02\x07\x01\x12\x03\x0c\x04\x1b\n\x0c\n\x05\x05\0\x02\x07\x02\x12\
+    \x03\x0c\x1e\x1f\n\x0b\n\x04\x05\0\x02\x08\x12\x03\r\x04\x1d\n\x0c\n\x05\
+    \x05\0\x02\x08\x01\x12\x03\r\x04\x18\n\x0c\n\x05\x05\0\x02\x08\x02\x12\
+    \x03\r\x1b\x1c\n\x0b\n\x04\x05\0\x02\t\x12\x03\x0e\x04\x1f\n\x0c\n\x05\
+    \x05\0\x02\t\x01\x12\x03\x0e\x04\x1a\n\x0c\n\x05\x05\0\x02\t\x02\x12\x03\
+    \x0e\x1d\x1e\n\n\n\x02\x04\0\x12\x04\x11\0\x19\x01\n\n\n\x03\x04\0\x01\
+    \x12\x03\x11\x08\x13\n\x0b\n\x04\x04\0\x02\0\x12\x03\x12\x04!\n\r\n\x05\
+    \x04\0\x02\0\x04\x12\x04\x12\x04\x11\x15\n\x0c\n\x05\x04\0\x02\0\x05\x12\
+    \x03\x12\x04\n\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x12\x0b\x1c\n\x0c\n\
+    \x05\x04\0\x02\0\x03\x12\x03\x12\x1f\x20\n\x0b\n\x04\x04\0\x02\x01\x12\
+    \x03\x13\x04\x13\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x13\x04\x12!\n\x0c\
+    \n\x05\x04\0\x02\x01\x05\x12\x03\x13\x04\t\n\x0c\n\x05\x04\0\x02\x01\x01\
+    \x12\x03\x13\n\x0e\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x13\x11\x12\n\
+    \x0b\n\x04\x04\0\x02\x02\x12\x03\x14\x04\x18\n\r\n\x05\x04\0\x02\x02\x04\
+    \x12\x04\x14\x04\x13\x13\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x14\x04\t\
+    \n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x14\n\x13\n\x0c\n\x05\x04\0\x02\
+    \x02\x03\x12\x03\x14\x16\x17\n\x0b\n\x04\x04\0\x02\x03\x12\x03\x15\x04\
+    \x16\n\r\n\x05\x04\0\x02\x03\x04\x12\x04\x15\x04\x14\x18\n\x0c\n\x05\x04\
+    \0\x02\x03\x06\x12\x03\x15\x04\n\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\
+    \x15\x0b\x11\n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\x15\x14\x15\n\x0b\n\
+    \x04\x04\0\x02\x04\x12\x03\x16\x04\x16\n\r\n\x05\x04\0\x02\x04\x04\x12\
+    \x04\x16\x04\x15\x16\n\x0c\n\x05\x04\0\x02\x04\x05\x12\x03\x16\x04\t\n\
+    \x0c\n\x05\x04\0\x02\x04\x01\x12\x03\x16\n\x11\n\x0c\n\x05\x04\0\x02\x04\
+    \x03\x12\x03\x16\x14\x15\n-\n\x04\x04\0\x02\x05\x12\x03\x17\x04\x15\"\
     \x20public\x20key\x20only\x20set\x20in\x20BlockReq\n\n\r\n\x05\x04\0\x02\
-    \x05\x04\x12\x04\x14\x04\x13\x16\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\
-    \x14\x04\t\n\x0c\n\x05\x04\0\x02\x05\x01\x12\x03\x14\n\x10\n\x0c\n\x05\
-    \x04\0\x02\x05\x03\x12\x03\x14\x13\x14\n\x0b\n\x04\x04\0\x02\x06\x12\x03\
-    \x15\x04\x15\n\r\n\x05\x04\0\x02\x06\x04\x12\x04\x15\x04\x14\x15\n\x0c\n\
-    \x05\x04\0\x02\x06\x05\x12\x03\x15\x04\n\n\x0c\n\x05\x04\0\x02\x06\x01\
-    \x12\x03\x15\x0b\x10\n\x0c\n\x05\x04\0\x02\x06\x03\x12\x03\x15\x13\x14\n\
-    \n\n\x02\x04\x01\x12\x04\x18\0\x1c\x01\n\n\n\x03\x04\x01\x01\x12\x03\x18\
-    \x08\x14\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x19\x04\x16\n\r\n\x05\x04\x01\
-    \x02\0\x04\x12\x04\x19\x04\x18\x16\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\
-    \x19\x04\t\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x19\n\x11\n\x0c\n\x05\
-    \x04\x01\x02\0\x03\x12\x03\x19\x14\x15\n\x0b\n\x04\x04\x01\x02\x01\x12\
-    \x03\x1a\x04\x10\n\r\n\x05\x04\x01\x02\x01\x04\x12\x04\x1a\x04\x19\x16\n\
-    \x0c\n\x05\x04\x01\x02\x01\x06\x12\x03\x1a\x04\x07\n\x0c\n\x05\x04\x01\
-    \x02\x01\x01\x12\x03\x1a\x08\x0b\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\
-    \x1a\x0e\x0f\n+\n\x04\x04\x01\x02\x02\x12\x03\x1b\x04\x15\"\x1epublic\
+    \x05\x04\x12\x04\x17\x04\x16\x16\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\
+    \x17\x04\t\n\x0c\n\x05\x04\0\x02\x05\x01\x12\x03\x17\n\x10\n\x0c\n\x05\
+    \x04\0\x02\x05\x03\x12\x03\x17\x13\x14\n\x0b\n\x04\x04\0\x02\x06\x12\x03\
+    \x18\x04\x15\n\r\n\x05\x04\0\x02\x06\x04\x12\x04\x18\x04\x17\x15\n\x0c\n\
+    \x05\x04\0\x02\x06\x05\x12\x03\x18\x04\n\n\x0c\n\x05\x04\0\x02\x06\x01\
+    \x12\x03\x18\x0b\x10\n\x0c\n\x05\x04\0\x02\x06\x03\x12\x03\x18\x13\x14\n\
+    \n\n\x02\x04\x01\x12\x04\x1b\0\x1f\x01\n\n\n\x03\x04\x01\x01\x12\x03\x1b\
+    \x08\x14\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x1c\x04\x16\n\r\n\x05\x04\x01\
+    \x02\0\x04\x12\x04\x1c\x04\x1b\x16\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\
+    \x1c\x04\t\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x1c\n\x11\n\x0c\n\x05\
+    \x04\x01\x02\0\x03\x12\x03\x1c\x14\x15\n\x0b\n\x04\x04\x01\x02\x01\x12\
+    \x03\x1d\x04\x10\n\r\n\x05\x04\x01\x02\x01\x04\x12\x04\x1d\x04\x1c\x16\n\
+    \x0c\n\x05\x04\x01\x02\x01\x06\x12\x03\x1d\x04\x07\n\x0c\n\x05\x04\x01\
+    \x02\x01\x01\x12\x03\x1d\x08\x0b\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\
+    \x1d\x0e\x0f\n+\n\x04\x04\x01\x02\x02\x12\x03\x1e\x04\x15\"\x1epublic\
     \x20key\x20only\x20set\x20in\x20TxResp\n\n\r\n\x05\x04\x01\x02\x02\x04\
-    \x12\x04\x1b\x04\x1a\x10\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x1b\x04\
-    \t\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x1b\n\x10\n\x0c\n\x05\x04\x01\
-    \x02\x02\x03\x12\x03\x1b\x13\x14\n\n\n\x02\x04\x02\x12\x04\x1e\0!\x01\n\
-    \n\n\x03\x04\x02\x01\x12\x03\x1e\x08\x16\n\x0b\n\x04\x04\x02\x02\0\x12\
-    \x03\x1f\x04\x12\n\r\n\x05\x04\x02\x02\0\x04\x12\x04\x1f\x04\x1e\x18\n\
-    \x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x1f\x04\n\n\x0c\n\x05\x04\x02\x02\0\
-    \x01\x12\x03\x1f\x0b\r\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x1f\x10\x11\
-    \n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x20\x04\"\n\x0c\n\x05\x04\x02\x02\
-    \x01\x04\x12\x03\x20\x04\x0c\n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x03\x20\
-    \r\x18\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x20\x19\x1d\n\x0c\n\x05\
-    \x04\x02\x02\x01\x03\x12\x03\x20\x20!\n\n\n\x02\x04\x03\x12\x04#\0&\x01\
-    \n\n\n\x03\x04\x03\x01\x12\x03#\x08\x17\n\x0b\n\x04\x04\x03\x02\0\x12\
-    \x03$\x04\x12\n\r\n\x05\x04\x03\x02\0\x04\x12\x04$\x04#\x19\n\x0c\n\x05\
-    \x04\x03\x02\0\x05\x12\x03$\x04\n\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03$\
-    \x0b\r\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03$\x10\x11\n\x0b\n\x04\x04\
-    \x03\x02\x01\x12\x03%\x04\x10\n\r\n\x05\x04\x03\x02\x01\x04\x12\x04%\x04\
-    $\x12\n\x0c\n\x05\x04\x03\x02\x01\x06\x12\x03%\x04\x07\n\x0c\n\x05\x04\
-    \x03\x02\x01\x01\x12\x03%\x08\x0b\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\
-    \x03%\x0e\x0f\n\n\n\x02\x04\x04\x12\x04(\0-\x01\n\n\n\x03\x04\x04\x01\
-    \x12\x03(\x08\x15\n\x0b\n\x04\x04\x04\x02\0\x12\x03)\x04\x16\n\r\n\x05\
-    \x04\x04\x02\0\x04\x12\x04)\x04(\x17\n\x0c\n\x05\x04\x04\x02\0\x05\x12\
-    \x03)\x04\n\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03)\x0b\x11\n\x0c\n\x05\
-    \x04\x04\x02\0\x03\x12\x03)\x14\x15\n\x0b\n\x04\x04\x04\x02\x01\x12\x03*\
-    \x04!\n\x0c\n\x05\x04\x04\x02\x01\x04\x12\x03*\x04\x0c\n\x0c\n\x05\x04\
-    \x04\x02\x01\x05\x12\x03*\r\x12\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x03*\
-    \x13\x1c\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03*\x1f\x20\n\x0b\n\x04\
-    \x04\x04\x02\x02\x12\x03+\x04\x1f\n\r\n\x05\x04\x04\x02\x02\x04\x12\x04+\
-    \x04*!\n\x0c\n\x05\x04\x04\x02\x02\x05\x12\x03+\x04\n\n\x0c\n\x05\x04\
-    \x04\x02\x02\x01\x12\x03+\x0b\x1a\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\
-    \x03+\x1d\x1e\n\x0b\n\x04\x04\x04\x02\x03\x12\x03,\x04*\n\r\n\x05\x04\
-    \x04\x02\x03\x04\x12\x04,\x04+\x1f\n\x0c\n\x05\x04\x04\x02\x03\x06\x12\
-    \x03,\x04\x13\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x03,\x14%\n\x0c\n\x05\
-    \x04\x04\x02\x03\x03\x12\x03,()\n\n\n\x02\x04\x05\x12\x04/\01\x01\n\n\n\
-    \x03\x04\x05\x01\x12\x03/\x08\x18\n\x0b\n\x04\x04\x05\x02\0\x12\x030\x04\
-    \x16\n\r\n\x05\x04\x05\x02\0\x04\x12\x040\x04/\x1a\n\x0c\n\x05\x04\x05\
-    \x02\0\x05\x12\x030\x04\n\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x030\x0b\x11\
-    \n\x0c\n\x05\x04\x05\x02\0\x03\x12\x030\x14\x15b\x06proto3\
+    \x12\x04\x1e\x04\x1d\x10\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x1e\x04\
+    \t\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x1e\n\x10\n\x0c\n\x05\x04\x01\
+    \x02\x02\x03\x12\x03\x1e\x13\x14\n\n\n\x02\x04\x02\x12\x04!\0$\x01\n\n\n\
+    \x03\x04\x02\x01\x12\x03!\x08\x16\n\x0b\n\x04\x04\x02\x02\0\x12\x03\"\
+    \x04\x12\n\r\n\x05\x04\x02\x02\0\x04\x12\x04\"\x04!\x18\n\x0c\n\x05\x04\
+    \x02\x02\0\x05\x12\x03\"\x04\n\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\"\
+    \x0b\r\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\"\x10\x11\n\x0b\n\x04\x04\
+    \x02\x02\x01\x12\x03#\x04\"\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x03#\x04\
+    \x0c\n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x03#\r\x18\n\x0c\n\x05\x04\x02\
+    \x02\x01\x01\x12\x03#\x19\x1d\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03#\
+    \x20!\n\n\n\x02\x04\x03\x12\x04&\0)\x01\n\n\n\x03\x04\x03\x01\x12\x03&\
+    \x08\x17\n\x0b\n\x04\x04\x03\x02\0\x12\x03'\x04\x12\n\r\n\x05\x04\x03\
+    \x02\0\x04\x12\x04'\x04&\x19\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03'\x04\
+    \n\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03'\x0b\r\n\x0c\n\x05\x04\x03\x02\
+    \0\x03\x12\x03'\x10\x11\n\x0b\n\x04\x04\x03\x02\x01\x12\x03(\x04\x10\n\r\
+    \n\x05\x04\x03\x02\x01\x04\x12\x04(\x04'\x12\n\x0c\n\x05\x04\x03\x02\x01\
+    \x06\x12\x03(\x04\x07\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03(\x08\x0b\n\
+    \x0c\n\x05\x04\x03\x02\x01\x03\x12\x03(\x0e\x0f\n\n\n\x02\x04\x04\x12\
+    \x04+\04\x01\n\n\n\x03\x04\x04\x01\x12\x03+\x08\x15\n\x0b\n\x04\x04\x04\
+    \x02\0\x12\x03,\x04\x16\n\r\n\x05\x04\x04\x02\0\x04\x12\x04,\x04+\x17\n\
+    \x0c\n\x05\x04\x04\x02\0\x05\x12\x03,\x04\n\n\x0c\n\x05\x04\x04\x02\0\
+    \x01\x12\x03,\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03,\x14\x15\n\
+    \x0b\n\x04\x04\x04\x02\x01\x12\x03-\x04!\n\x0c\n\x05\x04\x04\x02\x01\x04\
+    \x12\x03-\x04\x0c\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03-\r\x12\n\x0c\n\
+    \x05\x04\x04\x02\x01\x01\x12\x03-\x13\x1c\n\x0c\n\x05\x04\x04\x02\x01\
+    \x03\x12\x03-\x1f\x20\n\x0b\n\x04\x04\x04\x02\x02\x12\x03.\x04\x1f\n\r\n\
+    \x05\x04\x04\x02\x02\x04\x12\x04.\x04-!\n\x0c\n\x05\x04\x04\x02\x02\x05\
+    \x12\x03.\x04\n\n\x0c\n\x05\x04\x04\x02\x02\x01\x12\x03.\x0b\x1a\n\x0c\n\
+    \x05\x04\x04\x02\x02\x03\x12\x03.\x1d\x1e\n\x0b\n\x04\x04\x04\x02\x03\
+    \x12\x03/\x04*\n\r\n\x05\x04\x04\x02\x03\x04\x12\x04/\x04.\x1f\n\x0c\n\
+    \x05\x04\x04\x02\x03\x06\x12\x03/\x04\x13\n\x0c\n\x05\x04\x04\x02\x03\
+    \x01\x12\x03/\x14%\n\x0c\n\x05\x04\x04\x02\x03\x03\x12\x03/()\n\x0b\n\
+    \x04\x04\x04\x02\x04\x12\x030\x04\x1f\n\x0c\n\x05\x04\x04\x02\x04\x04\
+    \x12\x030\x04\x0c\n\x0c\n\x05\x04\x04\x02\x04\x05\x12\x030\r\x12\n\x0c\n\
+    \x05\x04\x04\x02\x04\x01\x12\x030\x13\x1a\n\x0c\n\x05\x04\x04\x02\x04\
+    \x03\x12\x030\x1d\x1e\n\x0b\n\x04\x04\x04\x02\x05\x12\x031\x04\x20\n\x0c\
+    \n\x05\x04\x04\x02\x05\x04\x12\x031\x04\x0c\n\x0c\n\x05\x04\x04\x02\x05\
+    \x05\x12\x031\r\x12\n\x0c\n\x05\x04\x04\x02\x05\x01\x12\x031\x13\x1b\n\
+    \x0c\n\x05\x04\x04\x02\x05\x03\x12\x031\x1e\x1f\n\x0b\n\x04\x04\x04\x02\
+    \x06\x12\x032\x04\x1e\n\r\n\x05\x04\x04\x02\x06\x04\x12\x042\x041\x20\n\
+    \x0c\n\x05\x04\x04\x02\x06\x05\x12\x032\x04\x08\n\x0c\n\x05\x04\x04\x02\
+    \x06\x01\x12\x032\t\x19\n\x0c\n\x05\x04\x04\x02\x06\x03\x12\x032\x1c\x1d\
+    \n\x0b\n\x04\x04\x04\x02\x07\x12\x033\x04\x19\n\r\n\x05\x04\x04\x02\x07\
+    \x04\x12\x043\x042\x1e\n\x0c\n\x05\x04\x04\x02\x07\x05\x12\x033\x04\x08\
+    \n\x0c\n\x05\x04\x04\x02\x07\x01\x12\x033\t\x14\n\x0c\n\x05\x04\x04\x02\
+    \x07\x03\x12\x033\x17\x18\n\n\n\x02\x04\x05\x12\x046\08\x01\n\n\n\x03\
+    \x04\x05\x01\x12\x036\x08\x18\n\x0b\n\x04\x04\x05\x02\0\x12\x037\x04\x16\
+    \n\r\n\x05\x04\x05\x02\0\x04\x12\x047\x046\x1a\n\x0c\n\x05\x04\x05\x02\0\
+    \x05\x12\x037\x04\n\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x037\x0b\x11\n\x0c\
+    \n\x05\x04\x05\x02\0\x03\x12\x037\x14\x15b\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -269,6 +269,7 @@ pub struct BlockHeader {
     pub gas_used: u64,
     pub gas_limit: u64,
     pub proof: ::protobuf::SingularPtrField<Proof>,
+    pub evidence_root: ::std::vec::Vec<u8>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -560,6 +561,40 @@ impl BlockHeader {
     fn mut_proof_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<Proof> {
         &mut self.proof
     }
+
+    // bytes evidence_root = 10;
+
+    pub fn clear_evidence_root(&mut self) {
+        self.evidence_root.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_evidence_root(&mut self, v: ::std::vec::Vec<u8>) {
+        self.evidence_root = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_evidence_root(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.evidence_root
+    }
+
+    // Take field
+    pub fn take_evidence_root(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.evidence_root, ::std::vec::Vec::new())
+    }
+
+    pub fn get_evidence_root(&self) -> &[u8] {
+        &self.evidence_root
+    }
+
+    fn get_evidence_root_for_reflect(&self) -> &::std::vec::Vec<u8> {
+        &self.evidence_root
+    }
+
+    fn mut_evidence_root_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.evidence_root
+    }
 }
 
 impl ::protobuf::Message for BlockHeader {
@@ -619,6 +654,9 @@ impl ::protobuf::Message for BlockHeader {
                 9 => {
                     ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.proof)?;
                 },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.evidence_root)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -659,6 +697,9 @@ impl ::protobuf::Message for BlockHeader {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        if !self.evidence_root.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(10, &self.evidence_root);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -694,6 +735,9 @@ impl ::protobuf::Message for BlockHeader {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        if !self.evidence_root.is_empty() {
+            os.write_bytes(10, &self.evidence_root)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -783,6 +827,11 @@ impl ::protobuf::MessageStatic for BlockHeader {
                     BlockHeader::get_proof_for_reflect,
                     BlockHeader::mut_proof_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "evidence_root",
+                    BlockHeader::get_evidence_root_for_reflect,
+                    BlockHeader::mut_evidence_root_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<BlockHeader>(
                     "BlockHeader",
                     fields,
@@ -804,6 +853,7 @@ impl ::protobuf::Clear for BlockHeader {
         self.clear_gas_used();
         self.clear_gas_limit();
         self.clear_proof();
+        self.clear_evidence_root();
         self.unknown_fields.clear();
     }
 }
@@ -1259,6 +1309,7 @@ pub struct RichStatus {
     pub hash: ::std::vec::Vec<u8>,
     pub height: u64,
     pub nodes: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
+    pub weights: ::std::vec::Vec<u64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1371,6 +1422,39 @@ impl RichStatus {
     fn mut_nodes_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
         &mut self.nodes
     }
+
+    // repeated uint64 weights = 4;
+
+    pub fn clear_weights(&mut self) {
+        self.weights.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_weights(&mut self, v: ::std::vec::Vec<u64>) {
+        self.weights = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_weights(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.weights
+    }
+
+    // Take field
+    pub fn take_weights(&mut self) -> ::std::vec::Vec<u64> {
+        ::std::mem::replace(&mut self.weights, ::std::vec::Vec::new())
+    }
+
+    pub fn get_weights(&self) -> &[u64] {
+        &self.weights
+    }
+
+    fn get_weights_for_reflect(&self) -> &::std::vec::Vec<u64> {
+        &self.weights
+    }
+
+    fn mut_weights_for_reflect(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.weights
+    }
 }
 
 impl ::protobuf::Message for RichStatus {
@@ -1395,6 +1479,9 @@ impl ::protobuf::Message for RichStatus {
                 3 => {
                     ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.nodes)?;
                 },
+                4 => {
+                    ::protobuf::rt::read_repeated_uint64_into(wire_type, is, &mut self.weights)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1416,6 +1503,9 @@ impl ::protobuf::Message for RichStatus {
         for value in &self.nodes {
             my_size += ::protobuf::rt::bytes_size(3, &value);
         };
+        if !self.weights.is_empty() {
+            my_size += ::protobuf::rt::vec_packed_varint_size(4, &self.weights);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1431,6 +1521,14 @@ impl ::protobuf::Message for RichStatus {
         for v in &self.nodes {
             os.write_bytes(3, &v)?;
         };
+        if !self.weights.is_empty() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            // TODO: Data size is computed again, it should be cached
+            os.write_raw_varint32(::protobuf::rt::vec_packed_varint_data_size(&self.weights))?;
+            for v in &self.weights {
+                os.write_uint64_no_tag(*v)?;
+            };
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1490,6 +1588,11 @@ impl ::protobuf::MessageStatic for RichStatus {
                     RichStatus::get_nodes_for_reflect,
                     RichStatus::mut_nodes_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "weights",
+                    RichStatus::get_weights_for_reflect,
+                    RichStatus::mut_weights_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<RichStatus>(
                     "RichStatus",
                     fields,
@@ -1505,6 +1608,7 @@ impl ::protobuf::Clear for RichStatus {
         self.clear_hash();
         self.clear_height();
         self.clear_nodes();
+        self.clear_weights();
         self.unknown_fields.clear();
     }
 }
@@ -2462,6 +2566,7 @@ impl ::protobuf::reflect::ProtobufValue for SignedTransaction {
 pub struct BlockBody {
     // message fields
     pub transactions: ::protobuf::RepeatedField<SignedTransaction>,
+    pub evidences: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -2517,6 +2622,39 @@ impl BlockBody {
     fn mut_transactions_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<SignedTransaction> {
         &mut self.transactions
     }
+
+    // repeated bytes evidences = 2;
+
+    pub fn clear_evidences(&mut self) {
+        self.evidences.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_evidences(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.evidences = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_evidences(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.evidences
+    }
+
+    // Take field
+    pub fn take_evidences(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.evidences, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_evidences(&self) -> &[::std::vec::Vec<u8>] {
+        &self.evidences
+    }
+
+    fn get_evidences_for_reflect(&self) -> &::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &self.evidences
+    }
+
+    fn mut_evidences_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.evidences
+    }
 }
 
 impl ::protobuf::Message for BlockBody {
@@ -2536,6 +2674,9 @@ impl ::protobuf::Message for BlockBody {
                 1 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.transactions)?;
                 },
+                2 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.evidences)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -2552,6 +2693,9 @@ impl ::protobuf::Message for BlockBody {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        for value in &self.evidences {
+            my_size += ::protobuf::rt::bytes_size(2, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -2563,6 +2707,9 @@ impl ::protobuf::Message for BlockBody {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
+        for v in &self.evidences {
+            os.write_bytes(2, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -2612,6 +2759,11 @@ impl ::protobuf::MessageStatic for BlockBody {
                     BlockBody::get_transactions_for_reflect,
                     BlockBody::mut_transactions_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "evidences",
+                    BlockBody::get_evidences_for_reflect,
+                    BlockBody::mut_evidences_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<BlockBody>(
                     "BlockBody",
                     fields,
@@ -2625,6 +2777,7 @@ impl ::protobuf::MessageStatic for BlockBody {
 impl ::protobuf::Clear for BlockBody {
     fn clear(&mut self) {
         self.clear_transactions();
+        self.clear_evidences();
         self.unknown_fields.clear();
     }
 }
@@ -3546,195 +3699,210 @@ impl ::protobuf::reflect::ProtobufValue for Crypto {
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x10blockchain.proto\"A\n\x05Proof\x12\x18\n\x07content\x18\x01\x20\
     \x01(\x0cR\x07content\x12\x1e\n\x04type\x18\x02\x20\x01(\x0e2\n.ProofTyp\
-    eR\x04type\"\xa6\x02\n\x0bBlockHeader\x12\x1a\n\x08prevhash\x18\x01\x20\
+    eR\x04type\"\xcb\x02\n\x0bBlockHeader\x12\x1a\n\x08prevhash\x18\x01\x20\
     \x01(\x0cR\x08prevhash\x12\x1c\n\ttimestamp\x18\x02\x20\x01(\x04R\ttimes\
     tamp\x12\x16\n\x06height\x18\x03\x20\x01(\x04R\x06height\x12\x1d\n\nstat\
     e_root\x18\x04\x20\x01(\x0cR\tstateRoot\x12+\n\x11transactions_root\x18\
     \x05\x20\x01(\x0cR\x10transactionsRoot\x12#\n\rreceipts_root\x18\x06\x20\
     \x01(\x0cR\x0creceiptsRoot\x12\x19\n\x08gas_used\x18\x07\x20\x01(\x04R\
     \x07gasUsed\x12\x1b\n\tgas_limit\x18\x08\x20\x01(\x04R\x08gasLimit\x12\
-    \x1c\n\x05proof\x18\t\x20\x01(\x0b2\x06.ProofR\x05proof\"4\n\x06Status\
-    \x12\x12\n\x04hash\x18\x01\x20\x01(\x0cR\x04hash\x12\x16\n\x06height\x18\
-    \x02\x20\x01(\x04R\x06height\"\xd6\x01\n\x0fAccountGasLimit\x12(\n\x10co\
-    mmon_gas_limit\x18\x01\x20\x01(\x04R\x0ecommonGasLimit\x12T\n\x12specifi\
-    c_gas_limit\x18\x02\x20\x03(\x0b2&.AccountGasLimit.SpecificGasLimitEntry\
-    R\x10specificGasLimit\x1aC\n\x15SpecificGasLimitEntry\x12\x10\n\x03key\
-    \x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\x04R\x05\
-    value:\x028\x01\"N\n\nRichStatus\x12\x12\n\x04hash\x18\x01\x20\x01(\x0cR\
-    \x04hash\x12\x16\n\x06height\x18\x02\x20\x01(\x04R\x06height\x12\x14\n\
-    \x05nodes\x18\x03\x20\x03(\x0cR\x05nodes\"\x89\x01\n\x0bTransaction\x12\
-    \x0e\n\x02to\x18\x01\x20\x01(\tR\x02to\x12\x14\n\x05nonce\x18\x02\x20\
-    \x01(\tR\x05nonce\x12\x14\n\x05quota\x18\x03\x20\x01(\x04R\x05quota\x12*\
-    \n\x11valid_until_block\x18\x04\x20\x01(\x04R\x0fvalidUntilBlock\x12\x12\
-    \n\x04data\x18\x05\x20\x01(\x0cR\x04data\"\x86\x01\n\x15UnverifiedTransa\
-    ction\x12.\n\x0btransaction\x18\x01\x20\x01(\x0b2\x0c.TransactionR\x0btr\
-    ansaction\x12\x1c\n\tsignature\x18\x02\x20\x01(\x0cR\tsignature\x12\x1f\
-    \n\x06crypto\x18\x03\x20\x01(\x0e2\x07.CryptoR\x06crypto\"\x8e\x01\n\x11\
-    SignedTransaction\x12H\n\x14transaction_with_sig\x18\x01\x20\x01(\x0b2\
-    \x16.UnverifiedTransactionR\x12transactionWithSig\x12\x17\n\x07tx_hash\
-    \x18\x02\x20\x01(\x0cR\x06txHash\x12\x16\n\x06signer\x18\x03\x20\x01(\
-    \x0cR\x06signer\"C\n\tBlockBody\x126\n\x0ctransactions\x18\x01\x20\x03(\
-    \x0b2\x12.SignedTransactionR\x0ctransactions\"g\n\x05Block\x12\x18\n\x07\
-    version\x18\x01\x20\x01(\rR\x07version\x12$\n\x06header\x18\x02\x20\x01(\
-    \x0b2\x0c.BlockHeaderR\x06header\x12\x1e\n\x04body\x18\x03\x20\x01(\x0b2\
-    \n.BlockBodyR\x04body\"H\n\x0eBlockWithProof\x12\x18\n\x03blk\x18\x01\
-    \x20\x01(\x0b2\x06.BlockR\x03blk\x12\x1c\n\x05proof\x18\x02\x20\x01(\x0b\
-    2\x06.ProofR\x05proof\"B\n\x08BlockTxs\x12\x16\n\x06height\x18\x01\x20\
-    \x01(\x04R\x06height\x12\x1e\n\x04body\x18\x03\x20\x01(\x0b2\n.BlockBody\
-    R\x04body*9\n\tProofType\x12\x12\n\x0eAuthorityRound\x10\0\x12\x08\n\x04\
-    Raft\x10\x01\x12\x0e\n\nTendermint\x10\x02*\x1b\n\x06Crypto\x12\x08\n\
-    \x04SECP\x10\0\x12\x07\n\x03SM2\x10\x01J\x89\x19\n\x06\x12\x04\0\0V\x01\
-    \n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\x02\x05\0\x12\x04\x02\0\x06\x01\n\
-    \n\n\x03\x05\0\x01\x12\x03\x02\x05\x0e\n\x0b\n\x04\x05\0\x02\0\x12\x03\
-    \x03\x04\x17\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x03\x04\x12\n\x0c\n\x05\
-    \x05\0\x02\0\x02\x12\x03\x03\x15\x16\n\x0b\n\x04\x05\0\x02\x01\x12\x03\
-    \x04\x04\r\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\x04\x04\x08\n\x0c\n\x05\
-    \x05\0\x02\x01\x02\x12\x03\x04\x0b\x0c\n\x0b\n\x04\x05\0\x02\x02\x12\x03\
-    \x05\x04\x13\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03\x05\x04\x0e\n\x0c\n\
-    \x05\x05\0\x02\x02\x02\x12\x03\x05\x11\x12\n\n\n\x02\x04\0\x12\x04\x08\0\
-    \x0b\x01\n\n\n\x03\x04\0\x01\x12\x03\x08\x08\r\n\x0b\n\x04\x04\0\x02\0\
-    \x12\x03\t\x04\x16\n\r\n\x05\x04\0\x02\0\x04\x12\x04\t\x04\x08\x0f\n\x0c\
-    \n\x05\x04\0\x02\0\x05\x12\x03\t\x04\t\n\x0c\n\x05\x04\0\x02\0\x01\x12\
-    \x03\t\n\x11\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\t\x14\x15\n\x0b\n\x04\
-    \x04\0\x02\x01\x12\x03\n\x04\x17\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\n\
-    \x04\t\x16\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\n\x04\r\n\x0c\n\x05\x04\
-    \0\x02\x01\x01\x12\x03\n\x0e\x12\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\n\
-    \x15\x16\n\n\n\x02\x04\x01\x12\x04\r\0\x17\x01\n\n\n\x03\x04\x01\x01\x12\
-    \x03\r\x08\x13\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x0e\x04\x17\n\r\n\x05\
-    \x04\x01\x02\0\x04\x12\x04\x0e\x04\r\x15\n\x0c\n\x05\x04\x01\x02\0\x05\
-    \x12\x03\x0e\x04\t\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x0e\n\x12\n\x0c\
-    \n\x05\x04\x01\x02\0\x03\x12\x03\x0e\x15\x16\n\x0b\n\x04\x04\x01\x02\x01\
-    \x12\x03\x0f\x04\x19\n\r\n\x05\x04\x01\x02\x01\x04\x12\x04\x0f\x04\x0e\
-    \x17\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x0f\x04\n\n\x0c\n\x05\x04\
-    \x01\x02\x01\x01\x12\x03\x0f\x0b\x14\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\
-    \x03\x0f\x17\x18\n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x10\x04\x16\n\r\n\
-    \x05\x04\x01\x02\x02\x04\x12\x04\x10\x04\x0f\x19\n\x0c\n\x05\x04\x01\x02\
-    \x02\x05\x12\x03\x10\x04\n\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x10\
-    \x0b\x11\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x10\x14\x15\n\x0b\n\x04\
-    \x04\x01\x02\x03\x12\x03\x11\x04\x19\n\r\n\x05\x04\x01\x02\x03\x04\x12\
-    \x04\x11\x04\x10\x16\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\x11\x04\t\n\
-    \x0c\n\x05\x04\x01\x02\x03\x01\x12\x03\x11\n\x14\n\x0c\n\x05\x04\x01\x02\
-    \x03\x03\x12\x03\x11\x17\x18\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x12\x04\
-    \x20\n\r\n\x05\x04\x01\x02\x04\x04\x12\x04\x12\x04\x11\x19\n\x0c\n\x05\
-    \x04\x01\x02\x04\x05\x12\x03\x12\x04\t\n\x0c\n\x05\x04\x01\x02\x04\x01\
-    \x12\x03\x12\n\x1b\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x12\x1e\x1f\n\
-    \x0b\n\x04\x04\x01\x02\x05\x12\x03\x13\x04\x1c\n\r\n\x05\x04\x01\x02\x05\
-    \x04\x12\x04\x13\x04\x12\x20\n\x0c\n\x05\x04\x01\x02\x05\x05\x12\x03\x13\
-    \x04\t\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x13\n\x17\n\x0c\n\x05\x04\
-    \x01\x02\x05\x03\x12\x03\x13\x1a\x1b\n\x0b\n\x04\x04\x01\x02\x06\x12\x03\
-    \x14\x04\x18\n\r\n\x05\x04\x01\x02\x06\x04\x12\x04\x14\x04\x13\x1c\n\x0c\
-    \n\x05\x04\x01\x02\x06\x05\x12\x03\x14\x04\n\n\x0c\n\x05\x04\x01\x02\x06\
-    \x01\x12\x03\x14\x0b\x13\n\x0c\n\x05\x04\x01\x02\x06\x03\x12\x03\x14\x16\
-    \x17\n\x0b\n\x04\x04\x01\x02\x07\x12\x03\x15\x04\x19\n\r\n\x05\x04\x01\
-    \x02\x07\x04\x12\x04\x15\x04\x14\x18\n\x0c\n\x05\x04\x01\x02\x07\x05\x12\
-    \x03\x15\x04\n\n\x0c\n\x05\x04\x01\x02\x07\x01\x12\x03\x15\x0b\x14\n\x0c\
-    \n\x05\x04\x01\x02\x07\x03\x12\x03\x15\x17\x18\n\x0b\n\x04\x04\x01\x02\
-    \x08\x12\x03\x16\x04\x14\n\r\n\x05\x04\x01\x02\x08\x04\x12\x04\x16\x04\
-    \x15\x19\n\x0c\n\x05\x04\x01\x02\x08\x06\x12\x03\x16\x04\t\n\x0c\n\x05\
-    \x04\x01\x02\x08\x01\x12\x03\x16\n\x0f\n\x0c\n\x05\x04\x01\x02\x08\x03\
-    \x12\x03\x16\x12\x13\n\n\n\x02\x04\x02\x12\x04\x19\0\x1c\x01\n\n\n\x03\
-    \x04\x02\x01\x12\x03\x19\x08\x0e\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x1a\
-    \x04\x13\n\r\n\x05\x04\x02\x02\0\x04\x12\x04\x1a\x04\x19\x10\n\x0c\n\x05\
-    \x04\x02\x02\0\x05\x12\x03\x1a\x04\t\n\x0c\n\x05\x04\x02\x02\0\x01\x12\
-    \x03\x1a\n\x0e\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x1a\x11\x12\n\x0b\n\
-    \x04\x04\x02\x02\x01\x12\x03\x1b\x04\x16\n\r\n\x05\x04\x02\x02\x01\x04\
-    \x12\x04\x1b\x04\x1a\x13\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\x03\x1b\x04\
-    \n\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x1b\x0b\x11\n\x0c\n\x05\x04\
-    \x02\x02\x01\x03\x12\x03\x1b\x14\x15\n\n\n\x02\x04\x03\x12\x04\x1e\0!\
-    \x01\n\n\n\x03\x04\x03\x01\x12\x03\x1e\x08\x17\n\x0b\n\x04\x04\x03\x02\0\
-    \x12\x03\x1f\x04\x20\n\r\n\x05\x04\x03\x02\0\x04\x12\x04\x1f\x04\x1e\x19\
-    \n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03\x1f\x04\n\n\x0c\n\x05\x04\x03\x02\
-    \0\x01\x12\x03\x1f\x0b\x1b\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03\x1f\x1e\
-    \x1f\n\x0b\n\x04\x04\x03\x02\x01\x12\x03\x20\x04.\n\r\n\x05\x04\x03\x02\
-    \x01\x04\x12\x04\x20\x04\x1f\x20\n\x0c\n\x05\x04\x03\x02\x01\x06\x12\x03\
-    \x20\x04\x16\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03\x20\x17)\n\x0c\n\
-    \x05\x04\x03\x02\x01\x03\x12\x03\x20,-\n\n\n\x02\x04\x04\x12\x04#\0'\x01\
-    \n\n\n\x03\x04\x04\x01\x12\x03#\x08\x12\n\x0b\n\x04\x04\x04\x02\0\x12\
-    \x03$\x04\x13\n\r\n\x05\x04\x04\x02\0\x04\x12\x04$\x04#\x14\n\x0c\n\x05\
-    \x04\x04\x02\0\x05\x12\x03$\x04\t\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03$\
-    \n\x0e\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03$\x11\x12\n\x0b\n\x04\x04\
-    \x04\x02\x01\x12\x03%\x04\x16\n\r\n\x05\x04\x04\x02\x01\x04\x12\x04%\x04\
-    $\x13\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03%\x04\n\n\x0c\n\x05\x04\x04\
-    \x02\x01\x01\x12\x03%\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03%\
-    \x14\x15\n\x0b\n\x04\x04\x04\x02\x02\x12\x03&\x04\x1d\n\x0c\n\x05\x04\
-    \x04\x02\x02\x04\x12\x03&\x04\x0c\n\x0c\n\x05\x04\x04\x02\x02\x05\x12\
-    \x03&\r\x12\n\x0c\n\x05\x04\x04\x02\x02\x01\x12\x03&\x13\x18\n\x0c\n\x05\
-    \x04\x04\x02\x02\x03\x12\x03&\x1b\x1c\n\n\n\x02\x05\x01\x12\x04)\0,\x01\
-    \n\n\n\x03\x05\x01\x01\x12\x03)\x05\x0b\n\x0b\n\x04\x05\x01\x02\0\x12\
-    \x03*\x04\r\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03*\x04\x08\n\x0c\n\x05\
-    \x05\x01\x02\0\x02\x12\x03*\x0b\x0c\n\x0b\n\x04\x05\x01\x02\x01\x12\x03+\
-    \x04\x0c\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03+\x04\x07\n\x0c\n\x05\
-    \x05\x01\x02\x01\x02\x12\x03+\n\x0b\n\n\n\x02\x04\x05\x12\x04.\04\x01\n\
-    \n\n\x03\x04\x05\x01\x12\x03.\x08\x13\n\x0b\n\x04\x04\x05\x02\0\x12\x03/\
-    \x04\x12\n\r\n\x05\x04\x05\x02\0\x04\x12\x04/\x04.\x15\n\x0c\n\x05\x04\
-    \x05\x02\0\x05\x12\x03/\x04\n\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03/\x0b\
-    \r\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03/\x10\x11\n\x0b\n\x04\x04\x05\
-    \x02\x01\x12\x030\x04\x15\n\r\n\x05\x04\x05\x02\x01\x04\x12\x040\x04/\
-    \x12\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x030\x04\n\n\x0c\n\x05\x04\x05\
-    \x02\x01\x01\x12\x030\x0b\x10\n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x030\
-    \x13\x14\n\x0b\n\x04\x04\x05\x02\x02\x12\x031\x04\x15\n\r\n\x05\x04\x05\
-    \x02\x02\x04\x12\x041\x040\x15\n\x0c\n\x05\x04\x05\x02\x02\x05\x12\x031\
-    \x04\n\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\x031\x0b\x10\n\x0c\n\x05\x04\
-    \x05\x02\x02\x03\x12\x031\x13\x14\n\x0b\n\x04\x04\x05\x02\x03\x12\x032\
-    \x04!\n\r\n\x05\x04\x05\x02\x03\x04\x12\x042\x041\x15\n\x0c\n\x05\x04\
-    \x05\x02\x03\x05\x12\x032\x04\n\n\x0c\n\x05\x04\x05\x02\x03\x01\x12\x032\
-    \x0b\x1c\n\x0c\n\x05\x04\x05\x02\x03\x03\x12\x032\x1f\x20\n\x0b\n\x04\
-    \x04\x05\x02\x04\x12\x033\x04\x13\n\r\n\x05\x04\x05\x02\x04\x04\x12\x043\
-    \x042!\n\x0c\n\x05\x04\x05\x02\x04\x05\x12\x033\x04\t\n\x0c\n\x05\x04\
-    \x05\x02\x04\x01\x12\x033\n\x0e\n\x0c\n\x05\x04\x05\x02\x04\x03\x12\x033\
-    \x11\x12\n\n\n\x02\x04\x06\x12\x046\0:\x01\n\n\n\x03\x04\x06\x01\x12\x03\
-    6\x08\x1d\n\x0b\n\x04\x04\x06\x02\0\x12\x037\x04\x20\n\r\n\x05\x04\x06\
-    \x02\0\x04\x12\x047\x046\x1f\n\x0c\n\x05\x04\x06\x02\0\x06\x12\x037\x04\
-    \x0f\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x037\x10\x1b\n\x0c\n\x05\x04\x06\
-    \x02\0\x03\x12\x037\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x01\x12\x038\x04\x18\
-    \n\r\n\x05\x04\x06\x02\x01\x04\x12\x048\x047\x20\n\x0c\n\x05\x04\x06\x02\
-    \x01\x05\x12\x038\x04\t\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x038\n\x13\n\
-    \x0c\n\x05\x04\x06\x02\x01\x03\x12\x038\x16\x17\n\x0b\n\x04\x04\x06\x02\
-    \x02\x12\x039\x04\x16\n\r\n\x05\x04\x06\x02\x02\x04\x12\x049\x048\x18\n\
-    \x0c\n\x05\x04\x06\x02\x02\x06\x12\x039\x04\n\n\x0c\n\x05\x04\x06\x02\
-    \x02\x01\x12\x039\x0b\x11\n\x0c\n\x05\x04\x06\x02\x02\x03\x12\x039\x14\
-    \x15\n\n\n\x02\x04\x07\x12\x04<\0@\x01\n\n\n\x03\x04\x07\x01\x12\x03<\
-    \x08\x19\n\x0b\n\x04\x04\x07\x02\0\x12\x03=\x043\n\r\n\x05\x04\x07\x02\0\
-    \x04\x12\x04=\x04<\x1b\n\x0c\n\x05\x04\x07\x02\0\x06\x12\x03=\x04\x19\n\
-    \x0c\n\x05\x04\x07\x02\0\x01\x12\x03=\x1a.\n\x0c\n\x05\x04\x07\x02\0\x03\
-    \x12\x03=12\n%\n\x04\x04\x07\x02\x01\x12\x03>\x04\x16\"\x18\x20SignedTra\
-    nsaction\x20hash\n\n\r\n\x05\x04\x07\x02\x01\x04\x12\x04>\x04=3\n\x0c\n\
-    \x05\x04\x07\x02\x01\x05\x12\x03>\x04\t\n\x0c\n\x05\x04\x07\x02\x01\x01\
-    \x12\x03>\n\x11\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\x03>\x14\x15\n\x18\n\
-    \x04\x04\x07\x02\x02\x12\x03?\x04\x15\"\x0bpublic\x20key\n\n\r\n\x05\x04\
-    \x07\x02\x02\x04\x12\x04?\x04>\x16\n\x0c\n\x05\x04\x07\x02\x02\x05\x12\
-    \x03?\x04\t\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03?\n\x10\n\x0c\n\x05\
-    \x04\x07\x02\x02\x03\x12\x03?\x13\x14\n!\n\x02\x04\x08\x12\x04D\0F\x012\
-    \x15\x20data\x20precompile\x20API\n\n\n\n\x03\x04\x08\x01\x12\x03D\x08\
-    \x11\n\x0b\n\x04\x04\x08\x02\0\x12\x03E\x040\n\x0c\n\x05\x04\x08\x02\0\
-    \x04\x12\x03E\x04\x0c\n\x0c\n\x05\x04\x08\x02\0\x06\x12\x03E\r\x1e\n\x0c\
-    \n\x05\x04\x08\x02\0\x01\x12\x03E\x1f+\n\x0c\n\x05\x04\x08\x02\0\x03\x12\
-    \x03E./\n\n\n\x02\x04\t\x12\x04H\0L\x01\n\n\n\x03\x04\t\x01\x12\x03H\x08\
-    \r\n\x0b\n\x04\x04\t\x02\0\x12\x03I\x04\x17\n\r\n\x05\x04\t\x02\0\x04\
-    \x12\x04I\x04H\x0f\n\x0c\n\x05\x04\t\x02\0\x05\x12\x03I\x04\n\n\x0c\n\
-    \x05\x04\t\x02\0\x01\x12\x03I\x0b\x12\n\x0c\n\x05\x04\t\x02\0\x03\x12\
-    \x03I\x15\x16\n\x0b\n\x04\x04\t\x02\x01\x12\x03J\x04\x1b\n\r\n\x05\x04\t\
-    \x02\x01\x04\x12\x04J\x04I\x17\n\x0c\n\x05\x04\t\x02\x01\x06\x12\x03J\
-    \x04\x0f\n\x0c\n\x05\x04\t\x02\x01\x01\x12\x03J\x10\x16\n\x0c\n\x05\x04\
-    \t\x02\x01\x03\x12\x03J\x19\x1a\n\x0b\n\x04\x04\t\x02\x02\x12\x03K\x04\
-    \x17\n\r\n\x05\x04\t\x02\x02\x04\x12\x04K\x04J\x1b\n\x0c\n\x05\x04\t\x02\
-    \x02\x06\x12\x03K\x04\r\n\x0c\n\x05\x04\t\x02\x02\x01\x12\x03K\x0e\x12\n\
-    \x0c\n\x05\x04\t\x02\x02\x03\x12\x03K\x15\x16\n\n\n\x02\x04\n\x12\x04N\0\
-    Q\x01\n\n\n\x03\x04\n\x01\x12\x03N\x08\x16\n\x0b\n\x04\x04\n\x02\0\x12\
-    \x03O\x04\x12\n\r\n\x05\x04\n\x02\0\x04\x12\x04O\x04N\x18\n\x0c\n\x05\
-    \x04\n\x02\0\x06\x12\x03O\x04\t\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03O\n\r\
-    \n\x0c\n\x05\x04\n\x02\0\x03\x12\x03O\x10\x11\n\x0b\n\x04\x04\n\x02\x01\
-    \x12\x03P\x04\x14\n\r\n\x05\x04\n\x02\x01\x04\x12\x04P\x04O\x12\n\x0c\n\
-    \x05\x04\n\x02\x01\x06\x12\x03P\x04\t\n\x0c\n\x05\x04\n\x02\x01\x01\x12\
-    \x03P\n\x0f\n\x0c\n\x05\x04\n\x02\x01\x03\x12\x03P\x12\x13\n\n\n\x02\x04\
-    \x0b\x12\x04S\0V\x01\n\n\n\x03\x04\x0b\x01\x12\x03S\x08\x10\n\x0b\n\x04\
-    \x04\x0b\x02\0\x12\x03T\x04\x16\n\r\n\x05\x04\x0b\x02\0\x04\x12\x04T\x04\
-    S\x12\n\x0c\n\x05\x04\x0b\x02\0\x05\x12\x03T\x04\n\n\x0c\n\x05\x04\x0b\
-    \x02\0\x01\x12\x03T\x0b\x11\n\x0c\n\x05\x04\x0b\x02\0\x03\x12\x03T\x14\
-    \x15\n\x0b\n\x04\x04\x0b\x02\x01\x12\x03U\x04\x17\n\r\n\x05\x04\x0b\x02\
-    \x01\x04\x12\x04U\x04T\x16\n\x0c\n\x05\x04\x0b\x02\x01\x06\x12\x03U\x04\
-    \r\n\x0c\n\x05\x04\x0b\x02\x01\x01\x12\x03U\x0e\x12\n\x0c\n\x05\x04\x0b\
-    \x02\x01\x03\x12\x03U\x15\x16b\x06proto3\
+    \x1c\n\x05proof\x18\t\x20\x01(\x0b2\x06.ProofR\x05proof\x12#\n\revidence\
+    _root\x18\n\x20\x01(\x0cR\x0cevidenceRoot\"4\n\x06Status\x12\x12\n\x04ha\
+    sh\x18\x01\x20\x01(\x0cR\x04hash\x12\x16\n\x06height\x18\x02\x20\x01(\
+    \x04R\x06height\"\xd6\x01\n\x0fAccountGasLimit\x12(\n\x10common_gas_limi\
+    t\x18\x01\x20\x01(\x04R\x0ecommonGasLimit\x12T\n\x12specific_gas_limit\
+    \x18\x02\x20\x03(\x0b2&.AccountGasLimit.SpecificGasLimitEntryR\x10specif\
+    icGasLimit\x1aC\n\x15SpecificGasLimitEntry\x12\x10\n\x03key\x18\x01\x20\
+    \x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\x04R\x05value:\x028\
+    \x01\"h\n\nRichStatus\x12\x12\n\x04hash\x18\x01\x20\x01(\x0cR\x04hash\
+    \x12\x16\n\x06height\x18\x02\x20\x01(\x04R\x06height\x12\x14\n\x05nodes\
+    \x18\x03\x20\x03(\x0cR\x05nodes\x12\x18\n\x07weights\x18\x04\x20\x03(\
+    \x04R\x07weights\"\x89\x01\n\x0bTransaction\x12\x0e\n\x02to\x18\x01\x20\
+    \x01(\tR\x02to\x12\x14\n\x05nonce\x18\x02\x20\x01(\tR\x05nonce\x12\x14\n\
+    \x05quota\x18\x03\x20\x01(\x04R\x05quota\x12*\n\x11valid_until_block\x18\
+    \x04\x20\x01(\x04R\x0fvalidUntilBlock\x12\x12\n\x04data\x18\x05\x20\x01(\
+    \x0cR\x04data\"\x86\x01\n\x15UnverifiedTransaction\x12.\n\x0btransaction\
+    \x18\x01\x20\x01(\x0b2\x0c.TransactionR\x0btransaction\x12\x1c\n\tsignat\
+    ure\x18\x02\x20\x01(\x0cR\tsignature\x12\x1f\n\x06crypto\x18\x03\x20\x01\
+    (\x0e2\x07.CryptoR\x06crypto\"\x8e\x01\n\x11SignedTransaction\x12H\n\x14\
+    transaction_with_sig\x18\x01\x20\x01(\x0b2\x16.UnverifiedTransactionR\
+    \x12transactionWithSig\x12\x17\n\x07tx_hash\x18\x02\x20\x01(\x0cR\x06txH\
+    ash\x12\x16\n\x06signer\x18\x03\x20\x01(\x0cR\x06signer\"a\n\tBlockBody\
+    \x126\n\x0ctransactions\x18\x01\x20\x03(\x0b2\x12.SignedTransactionR\x0c\
+    transactions\x12\x1c\n\tevidences\x18\x02\x20\x03(\x0cR\tevidences\"g\n\
+    \x05Block\x12\x18\n\x07version\x18\x01\x20\x01(\rR\x07version\x12$\n\x06\
+    header\x18\x02\x20\x01(\x0b2\x0c.BlockHeaderR\x06header\x12\x1e\n\x04bod\
+    y\x18\x03\x20\x01(\x0b2\n.BlockBodyR\x04body\"H\n\x0eBlockWithProof\x12\
+    \x18\n\x03blk\x18\x01\x20\x01(\x0b2\x06.BlockR\x03blk\x12\x1c\n\x05proof\
+    \x18\x02\x20\x01(\x0b2\x06.ProofR\x05proof\"B\n\x08BlockTxs\x12\x16\n\
+    \x06height\x18\x01\x20\x01(\x04R\x06height\x12\x1e\n\x04body\x18\x03\x20\
+    \x01(\x0b2\n.BlockBodyR\x04body*9\n\tProofType\x12\x12\n\x0eAuthorityRou\
+    nd\x10\0\x12\x08\n\x04Raft\x10\x01\x12\x0e\n\nTendermint\x10\x02*\x1b\n\
+    \x06Crypto\x12\x08\n\x04SECP\x10\0\x12\x07\n\x03SM2\x10\x01J\xd3\x1b\n\
+    \x06\x12\x04\0\0[\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\x02\x05\0\
+    \x12\x04\x02\0\x06\x01\n\n\n\x03\x05\0\x01\x12\x03\x02\x05\x0e\n\x0b\n\
+    \x04\x05\0\x02\0\x12\x03\x03\x04\x17\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\
+    \x03\x04\x12\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x03\x15\x16\n\x0b\n\x04\
+    \x05\0\x02\x01\x12\x03\x04\x04\r\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\
+    \x04\x04\x08\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\x04\x0b\x0c\n\x0b\n\
+    \x04\x05\0\x02\x02\x12\x03\x05\x04\x13\n\x0c\n\x05\x05\0\x02\x02\x01\x12\
+    \x03\x05\x04\x0e\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\x05\x11\x12\n\n\n\
+    \x02\x04\0\x12\x04\x08\0\x0b\x01\n\n\n\x03\x04\0\x01\x12\x03\x08\x08\r\n\
+    \x0b\n\x04\x04\0\x02\0\x12\x03\t\x04\x16\n\r\n\x05\x04\0\x02\0\x04\x12\
+    \x04\t\x04\x08\x0f\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\t\x04\t\n\x0c\n\
+    \x05\x04\0\x02\0\x01\x12\x03\t\n\x11\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\
+    \t\x14\x15\n\x0b\n\x04\x04\0\x02\x01\x12\x03\n\x04\x17\n\r\n\x05\x04\0\
+    \x02\x01\x04\x12\x04\n\x04\t\x16\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\n\
+    \x04\r\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\n\x0e\x12\n\x0c\n\x05\x04\0\
+    \x02\x01\x03\x12\x03\n\x15\x16\n\n\n\x02\x04\x01\x12\x04\r\0\x19\x01\n\n\
+    \n\x03\x04\x01\x01\x12\x03\r\x08\x13\n\x0b\n\x04\x04\x01\x02\0\x12\x03\
+    \x0e\x04\x17\n\r\n\x05\x04\x01\x02\0\x04\x12\x04\x0e\x04\r\x15\n\x0c\n\
+    \x05\x04\x01\x02\0\x05\x12\x03\x0e\x04\t\n\x0c\n\x05\x04\x01\x02\0\x01\
+    \x12\x03\x0e\n\x12\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x0e\x15\x16\n\
+    \x0b\n\x04\x04\x01\x02\x01\x12\x03\x0f\x04\x19\n\r\n\x05\x04\x01\x02\x01\
+    \x04\x12\x04\x0f\x04\x0e\x17\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x0f\
+    \x04\n\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x0f\x0b\x14\n\x0c\n\x05\
+    \x04\x01\x02\x01\x03\x12\x03\x0f\x17\x18\n\x0b\n\x04\x04\x01\x02\x02\x12\
+    \x03\x10\x04\x16\n\r\n\x05\x04\x01\x02\x02\x04\x12\x04\x10\x04\x0f\x19\n\
+    \x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x10\x04\n\n\x0c\n\x05\x04\x01\x02\
+    \x02\x01\x12\x03\x10\x0b\x11\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x10\
+    \x14\x15\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x11\x04\x19\n\r\n\x05\x04\
+    \x01\x02\x03\x04\x12\x04\x11\x04\x10\x16\n\x0c\n\x05\x04\x01\x02\x03\x05\
+    \x12\x03\x11\x04\t\n\x0c\n\x05\x04\x01\x02\x03\x01\x12\x03\x11\n\x14\n\
+    \x0c\n\x05\x04\x01\x02\x03\x03\x12\x03\x11\x17\x18\n\x0b\n\x04\x04\x01\
+    \x02\x04\x12\x03\x12\x04\x20\n\r\n\x05\x04\x01\x02\x04\x04\x12\x04\x12\
+    \x04\x11\x19\n\x0c\n\x05\x04\x01\x02\x04\x05\x12\x03\x12\x04\t\n\x0c\n\
+    \x05\x04\x01\x02\x04\x01\x12\x03\x12\n\x1b\n\x0c\n\x05\x04\x01\x02\x04\
+    \x03\x12\x03\x12\x1e\x1f\n\x0b\n\x04\x04\x01\x02\x05\x12\x03\x13\x04\x1c\
+    \n\r\n\x05\x04\x01\x02\x05\x04\x12\x04\x13\x04\x12\x20\n\x0c\n\x05\x04\
+    \x01\x02\x05\x05\x12\x03\x13\x04\t\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\
+    \x03\x13\n\x17\n\x0c\n\x05\x04\x01\x02\x05\x03\x12\x03\x13\x1a\x1b\n\x0b\
+    \n\x04\x04\x01\x02\x06\x12\x03\x14\x04\x18\n\r\n\x05\x04\x01\x02\x06\x04\
+    \x12\x04\x14\x04\x13\x1c\n\x0c\n\x05\x04\x01\x02\x06\x05\x12\x03\x14\x04\
+    \n\n\x0c\n\x05\x04\x01\x02\x06\x01\x12\x03\x14\x0b\x13\n\x0c\n\x05\x04\
+    \x01\x02\x06\x03\x12\x03\x14\x16\x17\n\x0b\n\x04\x04\x01\x02\x07\x12\x03\
+    \x15\x04\x19\n\r\n\x05\x04\x01\x02\x07\x04\x12\x04\x15\x04\x14\x18\n\x0c\
+    \n\x05\x04\x01\x02\x07\x05\x12\x03\x15\x04\n\n\x0c\n\x05\x04\x01\x02\x07\
+    \x01\x12\x03\x15\x0b\x14\n\x0c\n\x05\x04\x01\x02\x07\x03\x12\x03\x15\x17\
+    \x18\n\x0b\n\x04\x04\x01\x02\x08\x12\x03\x16\x04\x14\n\r\n\x05\x04\x01\
+    \x02\x08\x04\x12\x04\x16\x04\x15\x19\n\x0c\n\x05\x04\x01\x02\x08\x06\x12\
+    \x03\x16\x04\t\n\x0c\n\x05\x04\x01\x02\x08\x01\x12\x03\x16\n\x0f\n\x0c\n\
+    \x05\x04\x01\x02\x08\x03\x12\x03\x16\x12\x13\nN\n\x04\x04\x01\x02\t\x12\
+    \x03\x18\x04\x1d\x1aA\x20merkle\x20root\x20of\x20the\x20body's\x20eviden\
+    ces,\x20empty\x20when\x20it\x20carries\x20none\n\n\r\n\x05\x04\x01\x02\t\
+    \x04\x12\x04\x18\x04\x16\x14\n\x0c\n\x05\x04\x01\x02\t\x05\x12\x03\x18\
+    \x04\t\n\x0c\n\x05\x04\x01\x02\t\x01\x12\x03\x18\n\x17\n\x0c\n\x05\x04\
+    \x01\x02\t\x03\x12\x03\x18\x1a\x1c\n\n\n\x02\x04\x02\x12\x04\x1b\0\x1e\
+    \x01\n\n\n\x03\x04\x02\x01\x12\x03\x1b\x08\x0e\n\x0b\n\x04\x04\x02\x02\0\
+    \x12\x03\x1c\x04\x13\n\r\n\x05\x04\x02\x02\0\x04\x12\x04\x1c\x04\x1b\x10\
+    \n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x1c\x04\t\n\x0c\n\x05\x04\x02\x02\
+    \0\x01\x12\x03\x1c\n\x0e\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x1c\x11\
+    \x12\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x1d\x04\x16\n\r\n\x05\x04\x02\
+    \x02\x01\x04\x12\x04\x1d\x04\x1c\x13\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\
+    \x03\x1d\x04\n\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x1d\x0b\x11\n\x0c\
+    \n\x05\x04\x02\x02\x01\x03\x12\x03\x1d\x14\x15\n\n\n\x02\x04\x03\x12\x04\
+    \x20\0#\x01\n\n\n\x03\x04\x03\x01\x12\x03\x20\x08\x17\n\x0b\n\x04\x04\
+    \x03\x02\0\x12\x03!\x04\x20\n\r\n\x05\x04\x03\x02\0\x04\x12\x04!\x04\x20\
+    \x19\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03!\x04\n\n\x0c\n\x05\x04\x03\
+    \x02\0\x01\x12\x03!\x0b\x1b\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03!\x1e\
+    \x1f\n\x0b\n\x04\x04\x03\x02\x01\x12\x03\"\x04.\n\r\n\x05\x04\x03\x02\
+    \x01\x04\x12\x04\"\x04!\x20\n\x0c\n\x05\x04\x03\x02\x01\x06\x12\x03\"\
+    \x04\x16\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03\"\x17)\n\x0c\n\x05\x04\
+    \x03\x02\x01\x03\x12\x03\",-\n\n\n\x02\x04\x04\x12\x04%\0+\x01\n\n\n\x03\
+    \x04\x04\x01\x12\x03%\x08\x12\n\x0b\n\x04\x04\x04\x02\0\x12\x03&\x04\x13\
+    \n\r\n\x05\x04\x04\x02\0\x04\x12\x04&\x04%\x14\n\x0c\n\x05\x04\x04\x02\0\
+    \x05\x12\x03&\x04\t\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03&\n\x0e\n\x0c\n\
+    \x05\x04\x04\x02\0\x03\x12\x03&\x11\x12\n\x0b\n\x04\x04\x04\x02\x01\x12\
+    \x03'\x04\x16\n\r\n\x05\x04\x04\x02\x01\x04\x12\x04'\x04&\x13\n\x0c\n\
+    \x05\x04\x04\x02\x01\x05\x12\x03'\x04\n\n\x0c\n\x05\x04\x04\x02\x01\x01\
+    \x12\x03'\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03'\x14\x15\n\x0b\
+    \n\x04\x04\x04\x02\x02\x12\x03(\x04\x1d\n\x0c\n\x05\x04\x04\x02\x02\x04\
+    \x12\x03(\x04\x0c\n\x0c\n\x05\x04\x04\x02\x02\x05\x12\x03(\r\x12\n\x0c\n\
+    \x05\x04\x04\x02\x02\x01\x12\x03(\x13\x18\n\x0c\n\x05\x04\x04\x02\x02\
+    \x03\x12\x03(\x1b\x1c\nB\n\x04\x04\x04\x02\x03\x12\x03*\x04\x20\x1a5\x20\
+    voting\x20power\x20of\x20nodes[i],\x20empty\x20when\x20they\x20are\x20eq\
+    ual\n\n\x0c\n\x05\x04\x04\x02\x03\x04\x12\x03*\x04\x0c\n\x0c\n\x05\x04\
+    \x04\x02\x03\x05\x12\x03*\r\x13\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x03*\
+    \x14\x1b\n\x0c\n\x05\x04\x04\x02\x03\x03\x12\x03*\x1e\x1f\n\n\n\x02\x05\
+    \x01\x12\x04-\00\x01\n\n\n\x03\x05\x01\x01\x12\x03-\x05\x0b\n\x0b\n\x04\
+    \x05\x01\x02\0\x12\x03.\x04\r\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03.\x04\
+    \x08\n\x0c\n\x05\x05\x01\x02\0\x02\x12\x03.\x0b\x0c\n\x0b\n\x04\x05\x01\
+    \x02\x01\x12\x03/\x04\x0c\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03/\x04\
+    \x07\n\x0c\n\x05\x05\x01\x02\x01\x02\x12\x03/\n\x0b\n\n\n\x02\x04\x05\
+    \x12\x042\08\x01\n\n\n\x03\x04\x05\x01\x12\x032\x08\x13\n\x0b\n\x04\x04\
+    \x05\x02\0\x12\x033\x04\x12\n\r\n\x05\x04\x05\x02\0\x04\x12\x043\x042\
+    \x15\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x033\x04\n\n\x0c\n\x05\x04\x05\
+    \x02\0\x01\x12\x033\x0b\r\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x033\x10\x11\
+    \n\x0b\n\x04\x04\x05\x02\x01\x12\x034\x04\x15\n\r\n\x05\x04\x05\x02\x01\
+    \x04\x12\x044\x043\x12\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x034\x04\n\n\
+    \x0c\n\x05\x04\x05\x02\x01\x01\x12\x034\x0b\x10\n\x0c\n\x05\x04\x05\x02\
+    \x01\x03\x12\x034\x13\x14\n\x0b\n\x04\x04\x05\x02\x02\x12\x035\x04\x15\n\
+    \r\n\x05\x04\x05\x02\x02\x04\x12\x045\x044\x15\n\x0c\n\x05\x04\x05\x02\
+    \x02\x05\x12\x035\x04\n\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\x035\x0b\x10\
+    \n\x0c\n\x05\x04\x05\x02\x02\x03\x12\x035\x13\x14\n\x0b\n\x04\x04\x05\
+    \x02\x03\x12\x036\x04!\n\r\n\x05\x04\x05\x02\x03\x04\x12\x046\x045\x15\n\
+    \x0c\n\x05\x04\x05\x02\x03\x05\x12\x036\x04\n\n\x0c\n\x05\x04\x05\x02\
+    \x03\x01\x12\x036\x0b\x1c\n\x0c\n\x05\x04\x05\x02\x03\x03\x12\x036\x1f\
+    \x20\n\x0b\n\x04\x04\x05\x02\x04\x12\x037\x04\x13\n\r\n\x05\x04\x05\x02\
+    \x04\x04\x12\x047\x046!\n\x0c\n\x05\x04\x05\x02\x04\x05\x12\x037\x04\t\n\
+    \x0c\n\x05\x04\x05\x02\x04\x01\x12\x037\n\x0e\n\x0c\n\x05\x04\x05\x02\
+    \x04\x03\x12\x037\x11\x12\n\n\n\x02\x04\x06\x12\x04:\0>\x01\n\n\n\x03\
+    \x04\x06\x01\x12\x03:\x08\x1d\n\x0b\n\x04\x04\x06\x02\0\x12\x03;\x04\x20\
+    \n\r\n\x05\x04\x06\x02\0\x04\x12\x04;\x04:\x1f\n\x0c\n\x05\x04\x06\x02\0\
+    \x06\x12\x03;\x04\x0f\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03;\x10\x1b\n\
+    \x0c\n\x05\x04\x06\x02\0\x03\x12\x03;\x1e\x1f\n\x0b\n\x04\x04\x06\x02\
+    \x01\x12\x03<\x04\x18\n\r\n\x05\x04\x06\x02\x01\x04\x12\x04<\x04;\x20\n\
+    \x0c\n\x05\x04\x06\x02\x01\x05\x12\x03<\x04\t\n\x0c\n\x05\x04\x06\x02\
+    \x01\x01\x12\x03<\n\x13\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x03<\x16\x17\
+    \n\x0b\n\x04\x04\x06\x02\x02\x12\x03=\x04\x16\n\r\n\x05\x04\x06\x02\x02\
+    \x04\x12\x04=\x04<\x18\n\x0c\n\x05\x04\x06\x02\x02\x06\x12\x03=\x04\n\n\
+    \x0c\n\x05\x04\x06\x02\x02\x01\x12\x03=\x0b\x11\n\x0c\n\x05\x04\x06\x02\
+    \x02\x03\x12\x03=\x14\x15\n\n\n\x02\x04\x07\x12\x04@\0D\x01\n\n\n\x03\
+    \x04\x07\x01\x12\x03@\x08\x19\n\x0b\n\x04\x04\x07\x02\0\x12\x03A\x043\n\
+    \r\n\x05\x04\x07\x02\0\x04\x12\x04A\x04@\x1b\n\x0c\n\x05\x04\x07\x02\0\
+    \x06\x12\x03A\x04\x19\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03A\x1a.\n\x0c\
+    \n\x05\x04\x07\x02\0\x03\x12\x03A12\n%\n\x04\x04\x07\x02\x01\x12\x03B\
+    \x04\x16\"\x18\x20SignedTransaction\x20hash\n\n\r\n\x05\x04\x07\x02\x01\
+    \x04\x12\x04B\x04A3\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x03B\x04\t\n\x0c\
+    \n\x05\x04\x07\x02\x01\x01\x12\x03B\n\x11\n\x0c\n\x05\x04\x07\x02\x01\
+    \x03\x12\x03B\x14\x15\n\x18\n\x04\x04\x07\x02\x02\x12\x03C\x04\x15\"\x0b\
+    public\x20key\n\n\r\n\x05\x04\x07\x02\x02\x04\x12\x04C\x04B\x16\n\x0c\n\
+    \x05\x04\x07\x02\x02\x05\x12\x03C\x04\t\n\x0c\n\x05\x04\x07\x02\x02\x01\
+    \x12\x03C\n\x10\n\x0c\n\x05\x04\x07\x02\x02\x03\x12\x03C\x13\x14\n!\n\
+    \x02\x04\x08\x12\x04H\0K\x012\x15\x20data\x20precompile\x20API\n\n\n\n\
+    \x03\x04\x08\x01\x12\x03H\x08\x11\n\x0b\n\x04\x04\x08\x02\0\x12\x03I\x04\
+    0\n\x0c\n\x05\x04\x08\x02\0\x04\x12\x03I\x04\x0c\n\x0c\n\x05\x04\x08\x02\
+    \0\x06\x12\x03I\r\x1e\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03I\x1f+\n\x0c\
+    \n\x05\x04\x08\x02\0\x03\x12\x03I./\n\x0b\n\x04\x04\x08\x02\x01\x12\x03J\
+    \x04!\n\x0c\n\x05\x04\x08\x02\x01\x04\x12\x03J\x04\x0c\n\x0c\n\x05\x04\
+    \x08\x02\x01\x05\x12\x03J\r\x12\n\x0c\n\x05\x04\x08\x02\x01\x01\x12\x03J\
+    \x13\x1c\n\x0c\n\x05\x04\x08\x02\x01\x03\x12\x03J\x1f\x20\n\n\n\x02\x04\
+    \t\x12\x04M\0Q\x01\n\n\n\x03\x04\t\x01\x12\x03M\x08\r\n\x0b\n\x04\x04\t\
+    \x02\0\x12\x03N\x04\x17\n\r\n\x05\x04\t\x02\0\x04\x12\x04N\x04M\x0f\n\
+    \x0c\n\x05\x04\t\x02\0\x05\x12\x03N\x04\n\n\x0c\n\x05\x04\t\x02\0\x01\
+    \x12\x03N\x0b\x12\n\x0c\n\x05\x04\t\x02\0\x03\x12\x03N\x15\x16\n\x0b\n\
+    \x04\x04\t\x02\x01\x12\x03O\x04\x1b\n\r\n\x05\x04\t\x02\x01\x04\x12\x04O\
+    \x04N\x17\n\x0c\n\x05\x04\t\x02\x01\x06\x12\x03O\x04\x0f\n\x0c\n\x05\x04\
+    \t\x02\x01\x01\x12\x03O\x10\x16\n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03O\
+    \x19\x1a\n\x0b\n\x04\x04\t\x02\x02\x12\x03P\x04\x17\n\r\n\x05\x04\t\x02\
+    \x02\x04\x12\x04P\x04O\x1b\n\x0c\n\x05\x04\t\x02\x02\x06\x12\x03P\x04\r\
+    \n\x0c\n\x05\x04\t\x02\x02\x01\x12\x03P\x0e\x12\n\x0c\n\x05\x04\t\x02\
+    \x02\x03\x12\x03P\x15\x16\n\n\n\x02\x04\n\x12\x04S\0V\x01\n\n\n\x03\x04\
+    \n\x01\x12\x03S\x08\x16\n\x0b\n\x04\x04\n\x02\0\x12\x03T\x04\x12\n\r\n\
+    \x05\x04\n\x02\0\x04\x12\x04T\x04S\x18\n\x0c\n\x05\x04\n\x02\0\x06\x12\
+    \x03T\x04\t\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03T\n\r\n\x0c\n\x05\x04\n\
+    \x02\0\x03\x12\x03T\x10\x11\n\x0b\n\x04\x04\n\x02\x01\x12\x03U\x04\x14\n\
+    \r\n\x05\x04\n\x02\x01\x04\x12\x04U\x04T\x12\n\x0c\n\x05\x04\n\x02\x01\
+    \x06\x12\x03U\x04\t\n\x0c\n\x05\x04\n\x02\x01\x01\x12\x03U\n\x0f\n\x0c\n\
+    \x05\x04\n\x02\x01\x03\x12\x03U\x12\x13\n\n\n\x02\x04\x0b\x12\x04X\0[\
+    \x01\n\n\n\x03\x04\x0b\x01\x12\x03X\x08\x10\n\x0b\n\x04\x04\x0b\x02\0\
+    \x12\x03Y\x04\x16\n\r\n\x05\x04\x0b\x02\0\x04\x12\x04Y\x04X\x12\n\x0c\n\
+    \x05\x04\x0b\x02\0\x05\x12\x03Y\x04\n\n\x0c\n\x05\x04\x0b\x02\0\x01\x12\
+    \x03Y\x0b\x11\n\x0c\n\x05\x04\x0b\x02\0\x03\x12\x03Y\x14\x15\n\x0b\n\x04\
+    \x04\x0b\x02\x01\x12\x03Z\x04\x17\n\r\n\x05\x04\x0b\x02\x01\x04\x12\x04Z\
+    \x04Y\x16\n\x0c\n\x05\x04\x0b\x02\x01\x06\x12\x03Z\x04\r\n\x0c\n\x05\x04\
+    \x0b\x02\x01\x01\x12\x03Z\x0e\x12\n\x0c\n\x05\x04\x0b\x02\x01\x03\x12\
+    \x03Z\x15\x16b\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
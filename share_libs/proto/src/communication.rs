@@ -46,6 +46,10 @@ pub struct Message {
     pub origin: u32,
     pub operate: OperateType,
     pub content: ::std::vec::Vec<u8>,
+    pub request_id: u64,
+    pub destination: ::std::vec::Vec<u8>,
+    pub trace_id: ::std::vec::Vec<u8>,
+    pub span_id: u64,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -194,6 +198,120 @@ impl Message {
     fn mut_content_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
         &mut self.content
     }
+
+    // uint64 request_id = 6;
+
+    pub fn clear_request_id(&mut self) {
+        self.request_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: u64) {
+        self.request_id = v;
+    }
+
+    pub fn get_request_id(&self) -> u64 {
+        self.request_id
+    }
+
+    fn get_request_id_for_reflect(&self) -> &u64 {
+        &self.request_id
+    }
+
+    fn mut_request_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.request_id
+    }
+
+    // bytes destination = 7;
+
+    pub fn clear_destination(&mut self) {
+        self.destination.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_destination(&mut self, v: ::std::vec::Vec<u8>) {
+        self.destination = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_destination(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.destination
+    }
+
+    // Take field
+    pub fn take_destination(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.destination, ::std::vec::Vec::new())
+    }
+
+    pub fn get_destination(&self) -> &[u8] {
+        &self.destination
+    }
+
+    fn get_destination_for_reflect(&self) -> &::std::vec::Vec<u8> {
+        &self.destination
+    }
+
+    fn mut_destination_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.destination
+    }
+
+    // bytes trace_id = 8;
+
+    pub fn clear_trace_id(&mut self) {
+        self.trace_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_trace_id(&mut self, v: ::std::vec::Vec<u8>) {
+        self.trace_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_trace_id(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.trace_id
+    }
+
+    // Take field
+    pub fn take_trace_id(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.trace_id, ::std::vec::Vec::new())
+    }
+
+    pub fn get_trace_id(&self) -> &[u8] {
+        &self.trace_id
+    }
+
+    fn get_trace_id_for_reflect(&self) -> &::std::vec::Vec<u8> {
+        &self.trace_id
+    }
+
+    fn mut_trace_id_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.trace_id
+    }
+
+    // uint64 span_id = 9;
+
+    pub fn clear_span_id(&mut self) {
+        self.span_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_span_id(&mut self, v: u64) {
+        self.span_id = v;
+    }
+
+    pub fn get_span_id(&self) -> u64 {
+        self.span_id
+    }
+
+    fn get_span_id_for_reflect(&self) -> &u64 {
+        &self.span_id
+    }
+
+    fn mut_span_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.span_id
+    }
 }
 
 impl ::protobuf::Message for Message {
@@ -236,6 +354,26 @@ impl ::protobuf::Message for Message {
                 5 => {
                     ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.content)?;
                 },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.request_id = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.destination)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.trace_id)?;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.span_id = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -263,6 +401,18 @@ impl ::protobuf::Message for Message {
         if !self.content.is_empty() {
             my_size += ::protobuf::rt::bytes_size(5, &self.content);
         }
+        if self.request_id != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.request_id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.destination.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(7, &self.destination);
+        }
+        if !self.trace_id.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(8, &self.trace_id);
+        }
+        if self.span_id != 0 {
+            my_size += ::protobuf::rt::value_size(9, self.span_id, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -284,6 +434,18 @@ impl ::protobuf::Message for Message {
         if !self.content.is_empty() {
             os.write_bytes(5, &self.content)?;
         }
+        if self.request_id != 0 {
+            os.write_uint64(6, self.request_id)?;
+        }
+        if !self.destination.is_empty() {
+            os.write_bytes(7, &self.destination)?;
+        }
+        if !self.trace_id.is_empty() {
+            os.write_bytes(8, &self.trace_id)?;
+        }
+        if self.span_id != 0 {
+            os.write_uint64(9, self.span_id)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -353,6 +515,26 @@ impl ::protobuf::MessageStatic for Message {
                     Message::get_content_for_reflect,
                     Message::mut_content_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "request_id",
+                    Message::get_request_id_for_reflect,
+                    Message::mut_request_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "destination",
+                    Message::get_destination_for_reflect,
+                    Message::mut_destination_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "trace_id",
+                    Message::get_trace_id_for_reflect,
+                    Message::mut_trace_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "span_id",
+                    Message::get_span_id_for_reflect,
+                    Message::mut_span_id_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Message>(
                     "Message",
                     fields,
@@ -370,6 +552,10 @@ impl ::protobuf::Clear for Message {
         self.clear_origin();
         self.clear_operate();
         self.clear_content();
+        self.clear_request_id();
+        self.clear_destination();
+        self.clear_trace_id();
+        self.clear_span_id();
         self.unknown_fields.clear();
     }
 }
@@ -539,77 +725,101 @@ impl ::protobuf::reflect::ProtobufValue for OperateType {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x13communication.proto\"\x98\x01\n\x07Message\x12\x15\n\x06cmd_id\x18\
+    \n\x13communication.proto\"\x8d\x02\n\x07Message\x12\x15\n\x06cmd_id\x18\
     \x01\x20\x01(\rR\x05cmdId\x12\x1c\n\x04type\x18\x02\x20\x01(\x0e2\x08.Ms\
     gTypeR\x04type\x12\x16\n\x06origin\x18\x03\x20\x01(\rR\x06origin\x12&\n\
     \x07operate\x18\x04\x20\x01(\x0e2\x0c.OperateTypeR\x07operate\x12\x18\n\
-    \x07content\x18\x05\x20\x01(\x0cR\x07content*\x82\x02\n\x07MsgType\x12\
-    \x0b\n\x07REQUEST\x10\0\x12\n\n\x06HEADER\x10\x01\x12\t\n\x05BLOCK\x10\
-    \x02\x12\n\n\x06STATUS\x10\x03\x12\x07\n\x03MSG\x10\x04\x12\x0c\n\x08RES\
-    PONSE\x10\x05\x12\x11\n\rVERIFY_TX_REQ\x10\x06\x12\x12\n\x0eVERIFY_TX_RE\
-    SP\x10\x07\x12\x12\n\x0eVERIFY_BLK_REQ\x10\x08\x12\x13\n\x0fVERIFY_BLK_R\
-    ESP\x10\t\x12\x12\n\x0eBLOCK_TXHASHES\x10\n\x12\x16\n\x12BLOCK_TXHASHES_\
-    REQ\x10\x0b\x12\x14\n\x10BLOCK_WITH_PROOF\x10\x0c\x12\r\n\tBLOCK_TXS\x10\
-    \r\x12\x0f\n\x0bRICH_STATUS\x10\x0e*6\n\x0bOperateType\x12\r\n\tBROADCAS\
-    T\x10\0\x12\n\n\x06SINGLE\x10\x01\x12\x0c\n\x08SUBTRACT\x10\x02J\x9a\t\n\
-    \x06\x12\x04\0\0\x20\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\x02\x05\0\
-    \x12\x04\x02\0\x12\x01\n\n\n\x03\x05\0\x01\x12\x03\x02\x05\x0c\n\x0b\n\
-    \x04\x05\0\x02\0\x12\x03\x03\x04\x10\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\
-    \x03\x04\x0b\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x03\x0e\x0f\n\x0b\n\x04\
-    \x05\0\x02\x01\x12\x03\x04\x04\x0f\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\
-    \x04\x04\n\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\x04\r\x0e\n\x0b\n\x04\
-    \x05\0\x02\x02\x12\x03\x05\x04\x0e\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03\
-    \x05\x04\t\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\x05\x0c\r\n\x0b\n\x04\
-    \x05\0\x02\x03\x12\x03\x06\x04\x0f\n\x0c\n\x05\x05\0\x02\x03\x01\x12\x03\
-    \x06\x04\n\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x06\r\x0e\n\x0b\n\x04\
-    \x05\0\x02\x04\x12\x03\x07\x04\x0c\n\x0c\n\x05\x05\0\x02\x04\x01\x12\x03\
-    \x07\x04\x07\n\x0c\n\x05\x05\0\x02\x04\x02\x12\x03\x07\n\x0b\n\x0b\n\x04\
-    \x05\0\x02\x05\x12\x03\x08\x04\x11\n\x0c\n\x05\x05\0\x02\x05\x01\x12\x03\
-    \x08\x04\x0c\n\x0c\n\x05\x05\0\x02\x05\x02\x12\x03\x08\x0f\x10\n\x0b\n\
-    \x04\x05\0\x02\x06\x12\x03\t\x04\x16\n\x0c\n\x05\x05\0\x02\x06\x01\x12\
-    \x03\t\x04\x11\n\x0c\n\x05\x05\0\x02\x06\x02\x12\x03\t\x14\x15\n\x0b\n\
-    \x04\x05\0\x02\x07\x12\x03\n\x04\x17\n\x0c\n\x05\x05\0\x02\x07\x01\x12\
-    \x03\n\x04\x12\n\x0c\n\x05\x05\0\x02\x07\x02\x12\x03\n\x15\x16\n\x0b\n\
-    \x04\x05\0\x02\x08\x12\x03\x0b\x04\x17\n\x0c\n\x05\x05\0\x02\x08\x01\x12\
-    \x03\x0b\x04\x12\n\x0c\n\x05\x05\0\x02\x08\x02\x12\x03\x0b\x15\x16\n\x0b\
-    \n\x04\x05\0\x02\t\x12\x03\x0c\x04\x18\n\x0c\n\x05\x05\0\x02\t\x01\x12\
-    \x03\x0c\x04\x13\n\x0c\n\x05\x05\0\x02\t\x02\x12\x03\x0c\x16\x17\n\x0b\n\
-    \x04\x05\0\x02\n\x12\x03\r\x04\x18\n\x0c\n\x05\x05\0\x02\n\x01\x12\x03\r\
-    \x04\x12\n\x0c\n\x05\x05\0\x02\n\x02\x12\x03\r\x15\x17\n\x0b\n\x04\x05\0\
-    \x02\x0b\x12\x03\x0e\x04\x1c\n\x0c\n\x05\x05\0\x02\x0b\x01\x12\x03\x0e\
-    \x04\x16\n\x0c\n\x05\x05\0\x02\x0b\x02\x12\x03\x0e\x19\x1b\n\x0b\n\x04\
-    \x05\0\x02\x0c\x12\x03\x0f\x04\x1a\n\x0c\n\x05\x05\0\x02\x0c\x01\x12\x03\
-    \x0f\x04\x14\n\x0c\n\x05\x05\0\x02\x0c\x02\x12\x03\x0f\x17\x19\n\x0b\n\
-    \x04\x05\0\x02\r\x12\x03\x10\x04\x13\n\x0c\n\x05\x05\0\x02\r\x01\x12\x03\
-    \x10\x04\r\n\x0c\n\x05\x05\0\x02\r\x02\x12\x03\x10\x10\x12\n\x0b\n\x04\
-    \x05\0\x02\x0e\x12\x03\x11\x04\x15\n\x0c\n\x05\x05\0\x02\x0e\x01\x12\x03\
-    \x11\x04\x0f\n\x0c\n\x05\x05\0\x02\x0e\x02\x12\x03\x11\x12\x14\n\n\n\x02\
-    \x05\x01\x12\x04\x14\0\x18\x01\n\n\n\x03\x05\x01\x01\x12\x03\x14\x05\x10\
-    \n\x0b\n\x04\x05\x01\x02\0\x12\x03\x15\x04\x12\n\x0c\n\x05\x05\x01\x02\0\
-    \x01\x12\x03\x15\x04\r\n\x0c\n\x05\x05\x01\x02\0\x02\x12\x03\x15\x10\x11\
-    \n\x0b\n\x04\x05\x01\x02\x01\x12\x03\x16\x04\x0f\n\x0c\n\x05\x05\x01\x02\
-    \x01\x01\x12\x03\x16\x04\n\n\x0c\n\x05\x05\x01\x02\x01\x02\x12\x03\x16\r\
-    \x0e\n\x0b\n\x04\x05\x01\x02\x02\x12\x03\x17\x04\x11\n\x0c\n\x05\x05\x01\
-    \x02\x02\x01\x12\x03\x17\x04\x0c\n\x0c\n\x05\x05\x01\x02\x02\x02\x12\x03\
-    \x17\x0f\x10\n\n\n\x02\x04\0\x12\x04\x1a\0\x20\x01\n\n\n\x03\x04\0\x01\
-    \x12\x03\x1a\x08\x0f\n\x0b\n\x04\x04\0\x02\0\x12\x03\x1b\x04\x16\n\r\n\
-    \x05\x04\0\x02\0\x04\x12\x04\x1b\x04\x1a\x11\n\x0c\n\x05\x04\0\x02\0\x05\
-    \x12\x03\x1b\x04\n\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x1b\x0b\x11\n\x0c\
-    \n\x05\x04\0\x02\0\x03\x12\x03\x1b\x14\x15\n\x0b\n\x04\x04\0\x02\x01\x12\
-    \x03\x1c\x04\x15\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x1c\x04\x1b\x16\n\
-    \x0c\n\x05\x04\0\x02\x01\x06\x12\x03\x1c\x04\x0b\n\x0c\n\x05\x04\0\x02\
-    \x01\x01\x12\x03\x1c\x0c\x10\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x1c\
-    \x13\x14\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x1d\x04\x16\n\r\n\x05\x04\0\
-    \x02\x02\x04\x12\x04\x1d\x04\x1c\x15\n\x0c\n\x05\x04\0\x02\x02\x05\x12\
-    \x03\x1d\x04\n\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x1d\x0b\x11\n\x0c\n\
-    \x05\x04\0\x02\x02\x03\x12\x03\x1d\x14\x15\n\x0b\n\x04\x04\0\x02\x03\x12\
-    \x03\x1e\x04\x1c\n\r\n\x05\x04\0\x02\x03\x04\x12\x04\x1e\x04\x1d\x16\n\
-    \x0c\n\x05\x04\0\x02\x03\x06\x12\x03\x1e\x04\x0f\n\x0c\n\x05\x04\0\x02\
-    \x03\x01\x12\x03\x1e\x10\x17\n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\x1e\
-    \x1a\x1b\n\x0b\n\x04\x04\0\x02\x04\x12\x03\x1f\x04\x16\n\r\n\x05\x04\0\
-    \x02\x04\x04\x12\x04\x1f\x04\x1e\x1c\n\x0c\n\x05\x04\0\x02\x04\x05\x12\
-    \x03\x1f\x04\t\n\x0c\n\x05\x04\0\x02\x04\x01\x12\x03\x1f\n\x11\n\x0c\n\
-    \x05\x04\0\x02\x04\x03\x12\x03\x1f\x14\x15b\x06proto3\
+    \x07content\x18\x05\x20\x01(\x0cR\x07content\x12\x1d\n\nrequest_id\x18\
+    \x06\x20\x01(\x04R\trequestId\x12\x20\n\x0bdestination\x18\x07\x20\x01(\
+    \x0cR\x0bdestination\x12\x19\n\x08trace_id\x18\x08\x20\x01(\x0cR\x07trac\
+    eId\x12\x17\n\x07span_id\x18\t\x20\x01(\x04R\x06spanId*\x82\x02\n\x07Msg\
+    Type\x12\x0b\n\x07REQUEST\x10\0\x12\n\n\x06HEADER\x10\x01\x12\t\n\x05BLO\
+    CK\x10\x02\x12\n\n\x06STATUS\x10\x03\x12\x07\n\x03MSG\x10\x04\x12\x0c\n\
+    \x08RESPONSE\x10\x05\x12\x11\n\rVERIFY_TX_REQ\x10\x06\x12\x12\n\x0eVERIF\
+    Y_TX_RESP\x10\x07\x12\x12\n\x0eVERIFY_BLK_REQ\x10\x08\x12\x13\n\x0fVERIF\
+    Y_BLK_RESP\x10\t\x12\x12\n\x0eBLOCK_TXHASHES\x10\n\x12\x16\n\x12BLOCK_TX\
+    HASHES_REQ\x10\x0b\x12\x14\n\x10BLOCK_WITH_PROOF\x10\x0c\x12\r\n\tBLOCK_\
+    TXS\x10\r\x12\x0f\n\x0bRICH_STATUS\x10\x0e*6\n\x0bOperateType\x12\r\n\tB\
+    ROADCAST\x10\0\x12\n\n\x06SINGLE\x10\x01\x12\x0c\n\x08SUBTRACT\x10\x02J\
+    \xfa\r\n\x06\x12\x04\0\0)\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\x02\
+    \x05\0\x12\x04\x02\0\x12\x01\n\n\n\x03\x05\0\x01\x12\x03\x02\x05\x0c\n\
+    \x0b\n\x04\x05\0\x02\0\x12\x03\x03\x04\x10\n\x0c\n\x05\x05\0\x02\0\x01\
+    \x12\x03\x03\x04\x0b\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x03\x0e\x0f\n\
+    \x0b\n\x04\x05\0\x02\x01\x12\x03\x04\x04\x0f\n\x0c\n\x05\x05\0\x02\x01\
+    \x01\x12\x03\x04\x04\n\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\x04\r\x0e\n\
+    \x0b\n\x04\x05\0\x02\x02\x12\x03\x05\x04\x0e\n\x0c\n\x05\x05\0\x02\x02\
+    \x01\x12\x03\x05\x04\t\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\x05\x0c\r\n\
+    \x0b\n\x04\x05\0\x02\x03\x12\x03\x06\x04\x0f\n\x0c\n\x05\x05\0\x02\x03\
+    \x01\x12\x03\x06\x04\n\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x06\r\x0e\n\
+    \x0b\n\x04\x05\0\x02\x04\x12\x03\x07\x04\x0c\n\x0c\n\x05\x05\0\x02\x04\
+    \x01\x12\x03\x07\x04\x07\n\x0c\n\x05\x05\0\x02\x04\x02\x12\x03\x07\n\x0b\
+    \n\x0b\n\x04\x05\0\x02\x05\x12\x03\x08\x04\x11\n\x0c\n\x05\x05\0\x02\x05\
+    \x01\x12\x03\x08\x04\x0c\n\x0c\n\x05\x05\0\x02\x05\x02\x12\x03\x08\x0f\
+    \x10\n\x0b\n\x04\x05\0\x02\x06\x12\x03\t\x04\x16\n\x0c\n\x05\x05\0\x02\
+    \x06\x01\x12\x03\t\x04\x11\n\x0c\n\x05\x05\0\x02\x06\x02\x12\x03\t\x14\
+    \x15\n\x0b\n\x04\x05\0\x02\x07\x12\x03\n\x04\x17\n\x0c\n\x05\x05\0\x02\
+    \x07\x01\x12\x03\n\x04\x12\n\x0c\n\x05\x05\0\x02\x07\x02\x12\x03\n\x15\
+    \x16\n\x0b\n\x04\x05\0\x02\x08\x12\x03\x0b\x04\x17\n\x0c\n\x05\x05\0\x02\
+    \x08\x01\x12\x03\x0b\x04\x12\n\x0c\n\x05\x05\0\x02\x08\x02\x12\x03\x0b\
+    \x15\x16\n\x0b\n\x04\x05\0\x02\t\x12\x03\x0c\x04\x18\n\x0c\n\x05\x05\0\
+    \x02\t\x01\x12\x03\x0c\x04\x13\n\x0c\n\x05\x05\0\x02\t\x02\x12\x03\x0c\
+    \x16\x17\n\x0b\n\x04\x05\0\x02\n\x12\x03\r\x04\x18\n\x0c\n\x05\x05\0\x02\
+    \n\x01\x12\x03\r\x04\x12\n\x0c\n\x05\x05\0\x02\n\x02\x12\x03\r\x15\x17\n\
+    \x0b\n\x04\x05\0\x02\x0b\x12\x03\x0e\x04\x1c\n\x0c\n\x05\x05\0\x02\x0b\
+    \x01\x12\x03\x0e\x04\x16\n\x0c\n\x05\x05\0\x02\x0b\x02\x12\x03\x0e\x19\
+    \x1b\n\x0b\n\x04\x05\0\x02\x0c\x12\x03\x0f\x04\x1a\n\x0c\n\x05\x05\0\x02\
+    \x0c\x01\x12\x03\x0f\x04\x14\n\x0c\n\x05\x05\0\x02\x0c\x02\x12\x03\x0f\
+    \x17\x19\n\x0b\n\x04\x05\0\x02\r\x12\x03\x10\x04\x13\n\x0c\n\x05\x05\0\
+    \x02\r\x01\x12\x03\x10\x04\r\n\x0c\n\x05\x05\0\x02\r\x02\x12\x03\x10\x10\
+    \x12\n\x0b\n\x04\x05\0\x02\x0e\x12\x03\x11\x04\x15\n\x0c\n\x05\x05\0\x02\
+    \x0e\x01\x12\x03\x11\x04\x0f\n\x0c\n\x05\x05\0\x02\x0e\x02\x12\x03\x11\
+    \x12\x14\n\n\n\x02\x05\x01\x12\x04\x14\0\x18\x01\n\n\n\x03\x05\x01\x01\
+    \x12\x03\x14\x05\x10\n\x0b\n\x04\x05\x01\x02\0\x12\x03\x15\x04\x12\n\x0c\
+    \n\x05\x05\x01\x02\0\x01\x12\x03\x15\x04\r\n\x0c\n\x05\x05\x01\x02\0\x02\
+    \x12\x03\x15\x10\x11\n\x0b\n\x04\x05\x01\x02\x01\x12\x03\x16\x04\x0f\n\
+    \x0c\n\x05\x05\x01\x02\x01\x01\x12\x03\x16\x04\n\n\x0c\n\x05\x05\x01\x02\
+    \x01\x02\x12\x03\x16\r\x0e\n\x0b\n\x04\x05\x01\x02\x02\x12\x03\x17\x04\
+    \x11\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03\x17\x04\x0c\n\x0c\n\x05\x05\
+    \x01\x02\x02\x02\x12\x03\x17\x0f\x10\n\n\n\x02\x04\0\x12\x04\x1a\0)\x01\
+    \n\n\n\x03\x04\0\x01\x12\x03\x1a\x08\x0f\n\x0b\n\x04\x04\0\x02\0\x12\x03\
+    \x1b\x04\x16\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x1b\x04\x1a\x11\n\x0c\n\
+    \x05\x04\0\x02\0\x05\x12\x03\x1b\x04\n\n\x0c\n\x05\x04\0\x02\0\x01\x12\
+    \x03\x1b\x0b\x11\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x1b\x14\x15\n\x0b\n\
+    \x04\x04\0\x02\x01\x12\x03\x1c\x04\x15\n\r\n\x05\x04\0\x02\x01\x04\x12\
+    \x04\x1c\x04\x1b\x16\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\x1c\x04\x0b\n\
+    \x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x1c\x0c\x10\n\x0c\n\x05\x04\0\x02\
+    \x01\x03\x12\x03\x1c\x13\x14\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x1d\x04\
+    \x16\n\r\n\x05\x04\0\x02\x02\x04\x12\x04\x1d\x04\x1c\x15\n\x0c\n\x05\x04\
+    \0\x02\x02\x05\x12\x03\x1d\x04\n\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\
+    \x1d\x0b\x11\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x1d\x14\x15\n\x0b\n\
+    \x04\x04\0\x02\x03\x12\x03\x1e\x04\x1c\n\r\n\x05\x04\0\x02\x03\x04\x12\
+    \x04\x1e\x04\x1d\x16\n\x0c\n\x05\x04\0\x02\x03\x06\x12\x03\x1e\x04\x0f\n\
+    \x0c\n\x05\x04\0\x02\x03\x01\x12\x03\x1e\x10\x17\n\x0c\n\x05\x04\0\x02\
+    \x03\x03\x12\x03\x1e\x1a\x1b\n\x0b\n\x04\x04\0\x02\x04\x12\x03\x1f\x04\
+    \x16\n\r\n\x05\x04\0\x02\x04\x04\x12\x04\x1f\x04\x1e\x1c\n\x0c\n\x05\x04\
+    \0\x02\x04\x05\x12\x03\x1f\x04\t\n\x0c\n\x05\x04\0\x02\x04\x01\x12\x03\
+    \x1f\n\x11\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x1f\x14\x15\nW\n\x04\
+    \x04\0\x02\x05\x12\x03!\x04\x1a\x1aJ\x20set\x20by\x20a\x20requester\x20a\
+    nd\x20copied\x20into\x20the\x20responses,\x200\x20outside\x20of\x20a\x20\
+    request\n\n\r\n\x05\x04\0\x02\x05\x04\x12\x04!\x04\x1f\x16\n\x0c\n\x05\
+    \x04\0\x02\x05\x05\x12\x03!\x04\n\n\x0c\n\x05\x04\0\x02\x05\x01\x12\x03!\
+    \x0b\x15\n\x0c\n\x05\x04\0\x02\x05\x03\x12\x03!\x18\x19\n|\n\x04\x04\0\
+    \x02\x06\x12\x03$\x04\x1a\x1ao\x20address\x20of\x20the\x20only\x20node\
+    \x20to\x20deliver\x20a\x20SINGLE\x20message\x20to,\x20the\x20node\x20who\
+    se\n\x20id_card\x20is\x20origin\x20gets\x20it\x20when\x20empty\n\n\r\n\
+    \x05\x04\0\x02\x06\x04\x12\x04$\x04!\x1a\n\x0c\n\x05\x04\0\x02\x06\x05\
+    \x12\x03$\x04\t\n\x0c\n\x05\x04\0\x02\x06\x01\x12\x03$\n\x15\n\x0c\n\x05\
+    \x04\0\x02\x06\x03\x12\x03$\x18\x19\nQ\n\x04\x04\0\x02\x07\x12\x03&\x04\
+    \x17\x1aD\x20trace\x20of\x20the\x20request\x20this\x20message\x20is\x20s\
+    ent\x20for,\x20empty\x20when\x20untraced\n\n\r\n\x05\x04\0\x02\x07\x04\
+    \x12\x04&\x04$\x1a\n\x0c\n\x05\x04\0\x02\x07\x05\x12\x03&\x04\t\n\x0c\n\
+    \x05\x04\0\x02\x07\x01\x12\x03&\n\x12\n\x0c\n\x05\x04\0\x02\x07\x03\x12\
+    \x03&\x15\x16\nP\n\x04\x04\0\x02\x08\x12\x03(\x04\x17\x1aC\x20span\x20of\
+    \x20the\x20sender,\x20parent\x20of\x20the\x20span\x20which\x20handles\
+    \x20this\x20message\n\n\r\n\x05\x04\0\x02\x08\x04\x12\x04(\x04&\x17\n\
+    \x0c\n\x05\x04\0\x02\x08\x05\x12\x03(\x04\n\n\x0c\n\x05\x04\0\x02\x08\
+    \x01\x12\x03(\x0b\x12\n\x0c\n\x05\x04\0\x02\x08\x03\x12\x03(\x15\x16b\
+    \x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -24,7 +24,11 @@ use topics;
 
 impl Into<communication::Message> for Request {
     fn into(self) -> communication::Message {
-        let msg = factory::create_msg(submodules::JSON_RPC, topics::REQUEST, communication::MsgType::REQUEST, self.write_to_bytes().unwrap());
+        let mut msg = factory::create_msg(submodules::JSON_RPC, topics::REQUEST, communication::MsgType::REQUEST, self.write_to_bytes().unwrap());
+        // a request starts a trace, unless it is sent while handling another one
+        if msg.get_trace_id().is_empty() {
+            msg.set_trace_id(self.get_request_id().to_vec());
+        }
         msg
     }
 }
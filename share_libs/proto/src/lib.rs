@@ -21,9 +21,13 @@ extern crate rustc_serialize;
 extern crate rlp;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 extern crate cita_crypto as crypto;
 #[macro_use]
 extern crate log as rlog;
+#[macro_use]
+extern crate lazy_static;
+extern crate pubsub_topic;
 
 pub mod blockchain;
 pub mod communication;
@@ -32,18 +36,21 @@ pub mod into;
 pub mod auth;
 pub mod response;
 pub mod consensus;
+pub mod router;
+pub mod trace;
 
 pub use auth::*;
 use blockchain::*;
 use communication::*;
 pub use consensus::*;
 use crypto::{PrivKey, PubKey, Signature, KeyPair, SIGNATURE_BYTES_LEN, Message as SignMessage, CreateKey, Sign};
-use protobuf::{Message, RepeatedField};
+use protobuf::{Message, ProtobufResult, RepeatedField};
 use protobuf::core::parse_from_bytes;
 pub use request::*;
 pub use response::*;
 use rlp::*;
 use rustc_serialize::hex::ToHex;
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::result::Result::Err;
 use util::{H256, Hashable, merklehash};
@@ -95,6 +102,9 @@ pub mod topics {
     pub const NEW_PROOF_BLOCK: u16 = 14;
     pub const BLOCK_TXS: u16 = 15;
     pub const RICH_STATUS: u16 = 16;
+    pub const DISCOVERY: u16 = 17;
+    pub const VERDICT: u16 = 18;
+    pub const EVIDENCE: u16 = 19;
 }
 
 #[derive(Debug)]
@@ -116,27 +126,13 @@ pub enum MsgClass {
     RICHSTATUS(RichStatus),
 }
 
+lazy_static! {
+    static ref TOPIC_NAMES: HashMap<u16, String> = router::ROUTES.iter().map(|route| (route.topic, route.topic_name.to_lowercase())).collect();
+}
+
+/// Name of a topic some route publishes, "" for the others
 pub fn topic_to_string(top: u16) -> &'static str {
-    match top {
-        topics::DEFAULT => "default",
-        topics::REQUEST => "request",
-        topics::NEW_BLK => "new_blk",
-        topics::NEW_STATUS => "new_status",
-        topics::SYNC_BLK => "sync_blk",
-        topics::RESPONSE => "response",
-        topics::CONSENSUS_MSG => "consensus_msg",
-        topics::NEW_PROPOSAL => "new_proposal",
-        topics::VERIFY_TX_REQ => "verify_tx_req",
-        topics::VERIFY_TX_RESP => "verify_tx_resp",
-        topics::VERIFY_BLK_REQ => "verify_blk_req",
-        topics::VERIFY_BLK_RESP => "verify_blk_resp",
-        topics::BLOCK_TXHASHES => "block_txhashes",
-        topics::BLOCK_TXHASHES_REQ => "block_txhashes_req",
-        topics::NEW_PROOF_BLOCK => "new_proof_blk",
-        topics::BLOCK_TXS => "block_txs",
-        topics::RICH_STATUS => "rich_status",
-        _ => "",
-    }
+    TOPIC_NAMES.get(&top).map_or("", |name| name.as_str())
 }
 
 pub fn id_to_key(id: u32) -> &'static str {
@@ -151,22 +147,9 @@ pub fn id_to_key(id: u32) -> &'static str {
     }
 }
 
+/// The module publishing on routing `key`, 0 for a key of no route
 pub fn key_to_id(key: &str) -> u32 {
-    if key.starts_with("jsonrpc") {
-        submodules::JSON_RPC
-    } else if key.starts_with("net") {
-        submodules::NET
-    } else if key.starts_with("chain") {
-        submodules::CHAIN
-    } else if key.starts_with("consensus_cmd") {
-        submodules::CONSENSUS_CMD
-    } else if key.starts_with("consensus") {
-        submodules::CONSENSUS
-    } else if key.starts_with("auth") {
-        submodules::AUTH
-    } else {
-        0
-    }
+    router::ROUTES.iter().find(|route| route.key == key).map_or(0, |route| route.publisher)
 }
 
 pub fn de_cmd_id(cmd_id: u32) -> (u32, u16) {
@@ -198,6 +181,7 @@ pub mod factory {
         msg.set_origin(ZERO_ORIGIN);
         //compress data
         msg.set_content(snappy::cita_compresse(content));
+        trace::inject(&mut msg);
         msg
     }
 
@@ -208,41 +192,108 @@ pub mod factory {
         msg.set_operate(operate);
         msg
     }
+
+    /// For the node whose id_card is `to` alone. A request takes a new `request_id`
+    /// and each response to it the same one, anything else 0.
+    pub fn create_msg_to(sub: u32, top: u16, msg_type: MsgType, to: u32, request_id: u64, content: Vec<u8>) -> communication::Message {
+        let mut msg = factory::create_msg_ex(sub, top, msg_type, communication::OperateType::SINGLE, to, content);
+        msg.set_request_id(request_id);
+        msg
+    }
 }
 
 type CmdId = u32;
 pub type Origin = u32;
 
 pub fn parse_msg(msg: &[u8]) -> (CmdId, Origin, MsgClass) {
-    let mut msg = parse_from_bytes::<communication::Message>(msg.as_ref()).unwrap();
+    try_parse_msg(msg).unwrap()
+}
+
+/// Like `parse_msg`, but gives an error instead of panicking on bytes which do not decode.
+pub fn try_parse_msg(msg: &[u8]) -> ProtobufResult<(CmdId, Origin, MsgClass)> {
+    let mut msg = parse_from_bytes::<communication::Message>(msg.as_ref())?;
+    let (sub, top) = display_cmd(msg.get_cmd_id());
+    trace::enter(&msg, &format!("{}.{}", sub, top));
     let content_msg = msg.take_content();
     let content_msg = snappy::cita_decompress(content_msg);
     let msg_class = match msg.get_field_type() {
-        MsgType::REQUEST => MsgClass::REQUEST(parse_from_bytes::<Request>(&content_msg).unwrap()),
+        MsgType::REQUEST => MsgClass::REQUEST(parse_from_bytes::<Request>(&content_msg)?),
         MsgType::RESPONSE => {
-            MsgClass::RESPONSE(parse_from_bytes::<Response>(&content_msg).unwrap())
+            MsgClass::RESPONSE(parse_from_bytes::<Response>(&content_msg)?)
         }
-        MsgType::HEADER => MsgClass::HEADER(parse_from_bytes::<BlockHeader>(&content_msg).unwrap()),
-        MsgType::BLOCK => MsgClass::BLOCK(parse_from_bytes::<Block>(&content_msg).unwrap()),
-        MsgType::STATUS => MsgClass::STATUS(parse_from_bytes::<Status>(&content_msg).unwrap()),
-        MsgType::VERIFY_TX_REQ => MsgClass::VERIFYTXREQ(parse_from_bytes::<VerifyTxReq>(&content_msg).unwrap()),
-        MsgType::VERIFY_TX_RESP => MsgClass::VERIFYTXRESP(parse_from_bytes::<VerifyTxResp>(&content_msg).unwrap()),
-        MsgType::VERIFY_BLK_REQ => MsgClass::VERIFYBLKREQ(parse_from_bytes::<VerifyBlockReq>(&content_msg).unwrap()),
-        MsgType::VERIFY_BLK_RESP => MsgClass::VERIFYBLKRESP(parse_from_bytes::<VerifyBlockResp>(&content_msg).unwrap()),
-        MsgType::BLOCK_TXHASHES => MsgClass::BLOCKTXHASHES(parse_from_bytes::<BlockTxHashes>(&content_msg).unwrap()),
-        MsgType::BLOCK_TXHASHES_REQ => MsgClass::BLOCKTXHASHESREQ(parse_from_bytes::<BlockTxHashesReq>(&content_msg).unwrap()),
-        MsgType::BLOCK_WITH_PROOF => MsgClass::BLOCKWITHPROOF(parse_from_bytes::<BlockWithProof>(&content_msg).unwrap()),
-        MsgType::BLOCK_TXS => MsgClass::BLOCKTXS(parse_from_bytes::<BlockTxs>(&content_msg).unwrap()),
+        MsgType::HEADER => MsgClass::HEADER(parse_from_bytes::<BlockHeader>(&content_msg)?),
+        MsgType::BLOCK => MsgClass::BLOCK(parse_from_bytes::<Block>(&content_msg)?),
+        MsgType::STATUS => MsgClass::STATUS(parse_from_bytes::<Status>(&content_msg)?),
+        MsgType::VERIFY_TX_REQ => MsgClass::VERIFYTXREQ(parse_from_bytes::<VerifyTxReq>(&content_msg)?),
+        MsgType::VERIFY_TX_RESP => MsgClass::VERIFYTXRESP(parse_from_bytes::<VerifyTxResp>(&content_msg)?),
+        MsgType::VERIFY_BLK_REQ => MsgClass::VERIFYBLKREQ(parse_from_bytes::<VerifyBlockReq>(&content_msg)?),
+        MsgType::VERIFY_BLK_RESP => MsgClass::VERIFYBLKRESP(parse_from_bytes::<VerifyBlockResp>(&content_msg)?),
+        MsgType::BLOCK_TXHASHES => MsgClass::BLOCKTXHASHES(parse_from_bytes::<BlockTxHashes>(&content_msg)?),
+        MsgType::BLOCK_TXHASHES_REQ => MsgClass::BLOCKTXHASHESREQ(parse_from_bytes::<BlockTxHashesReq>(&content_msg)?),
+        MsgType::BLOCK_WITH_PROOF => MsgClass::BLOCKWITHPROOF(parse_from_bytes::<BlockWithProof>(&content_msg)?),
+        MsgType::BLOCK_TXS => MsgClass::BLOCKTXS(parse_from_bytes::<BlockTxs>(&content_msg)?),
         MsgType::MSG => {
             let mut content = Vec::new();
             content.extend_from_slice(&content_msg);
             MsgClass::MSG(content)
         }
-        MsgType::RICH_STATUS => MsgClass::RICHSTATUS(parse_from_bytes::<RichStatus>(&content_msg).unwrap()),
+        MsgType::RICH_STATUS => MsgClass::RICHSTATUS(parse_from_bytes::<RichStatus>(&content_msg)?),
 
     };
 
-    (msg.get_cmd_id(), msg.get_origin(), msg_class)
+    Ok((msg.get_cmd_id(), msg.get_origin(), msg_class))
+}
+
+/// What a peer did wrong, network decides how much each one costs it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offence {
+    /// A block with a wrong transactions root or proof
+    InvalidBlock,
+    /// Content which does not decode
+    Undecodable,
+    /// A transaction with a bad signature
+    InvalidTx,
+    /// A consensus message with a bad signature, or signed by a non authority
+    InvalidSignature,
+    /// Far more consensus messages than a round needs
+    Flood,
+}
+
+/// Blames the peer a message came from. Chain, auth and consensus publish it
+/// as `<module>.verdict` and network scores the peer by it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Verdict {
+    pub origin: Origin,
+    pub offence: Offence,
+    pub reason: String,
+}
+
+impl Verdict {
+    pub fn new(origin: Origin, offence: Offence, reason: String) -> Self {
+        Verdict {
+            origin: origin,
+            offence: offence,
+            reason: reason,
+        }
+    }
+
+    /// Messages from the local node carry `ZERO_ORIGIN`, there is nobody to blame for them.
+    pub fn is_remote(origin: Origin) -> bool {
+        origin != factory::ZERO_ORIGIN
+    }
+
+    pub fn to_msg(&self, sub: u32) -> communication::Message {
+        factory::create_msg(sub, topics::VERDICT, MsgType::MSG, serde_json::to_vec(self).unwrap())
+    }
+
+    pub fn from_msg(msg: &[u8]) -> Option<Verdict> {
+        match try_parse_msg(msg) {
+            Ok((cmd_id, _, MsgClass::MSG(content))) => {
+                if de_cmd_id(cmd_id).1 == topics::VERDICT { serde_json::from_slice(&content).ok() } else { None }
+            }
+            _ => None,
+        }
+    }
 }
 
 impl blockchain::Transaction {
@@ -375,7 +426,11 @@ impl Block {
     }
 
     pub fn check_hash(&self) -> bool {
-        self.get_body().transactions_root().0 == *self.get_header().get_transactions_root()
+        let body = self.get_body();
+        let header = self.get_header();
+        let evidence_root = header.get_evidence_root();
+        body.transactions_root().0 == *header.get_transactions_root() &&
+            body.evidence_root().map_or(evidence_root.is_empty(), |root| root.0 == *evidence_root)
     }
 
     pub fn block_verify_req(&self, request_id: u64) -> VerifyBlockReq {
@@ -415,6 +470,16 @@ impl BlockBody {
     pub fn transactions_root(&self) -> H256 {
         merklehash::complete_merkle_root_raw(self.transaction_hashes().clone())
     }
+
+    /// Root the header commits the evidences by, None when the body carries none
+    /// so that blocks without evidence keep their old hash.
+    pub fn evidence_root(&self) -> Option<H256> {
+        if self.get_evidences().is_empty() {
+            None
+        } else {
+            Some(merklehash::complete_merkle_root(self.get_evidences().to_vec()))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -427,6 +492,53 @@ mod tests {
         assert_eq!(cmd_id(submodules::CHAIN, topics::RESPONSE), 0x30005);
     }
 
+    #[test]
+    fn msg_to() {
+        let msg = factory::create_msg_to(submodules::CHAIN, topics::SYNC_BLK, MsgType::MSG, 2, 7, vec![0; 16]);
+        let msg = parse_from_bytes::<communication::Message>(&msg.write_to_bytes().unwrap()).unwrap();
+        assert_eq!(msg.get_operate(), OperateType::SINGLE);
+        assert_eq!(msg.get_origin(), 2);
+        assert_eq!(msg.get_request_id(), 7);
+        assert!(msg.get_destination().is_empty());
+    }
+
+    #[test]
+    fn check_hash_covers_evidences() {
+        let mut block = Block::new();
+        let root = block.get_body().transactions_root();
+        block.mut_header().set_transactions_root(root.to_vec());
+        assert!(block.check_hash());
+
+        block.mut_body().mut_evidences().push(vec![1, 2, 3]);
+        assert!(!block.check_hash());
+        let root = block.get_body().evidence_root().unwrap();
+        block.mut_header().set_evidence_root(root.to_vec());
+        assert!(block.check_hash());
+
+        block.mut_body().mut_evidences()[0] = vec![3, 2, 1];
+        assert!(!block.check_hash());
+        block.mut_body().clear_evidences();
+        assert!(!block.check_hash());
+    }
+
+    #[test]
+    fn verdict_roundtrip() {
+        let verdict = Verdict::new(3, Offence::InvalidBlock, "bad proof at 10".to_string());
+        let msg = verdict.to_msg(submodules::CHAIN).write_to_bytes().unwrap();
+        assert_eq!(Verdict::from_msg(&msg), Some(verdict));
+        assert!(!Verdict::is_remote(factory::ZERO_ORIGIN));
+
+        let other = factory::create_msg(submodules::CHAIN, topics::SYNC_BLK, MsgType::MSG, vec![0; 8]);
+        assert_eq!(Verdict::from_msg(&other.write_to_bytes().unwrap()), None);
+    }
+
+    #[test]
+    fn try_parse_undecodable() {
+        let msg = factory::create_msg(submodules::CHAIN, topics::NEW_STATUS, MsgType::STATUS, vec![0xff; 8]);
+        assert!(try_parse_msg(&msg.write_to_bytes().unwrap()).is_err());
+        assert!(try_parse_msg(&[0xff; 8]).is_err());
+    }
+
     #[test]
     fn create_tx() {
         let keypair = KeyPair::gen_keypair();
@@ -398,6 +398,10 @@ pub enum Request_oneof_req {
     filter_logs(u64),
     un_tx(super::blockchain::UnverifiedTransaction),
     batch_req(BatchRequest),
+    peers(bool),
+    add_peer(::std::string::String),
+    remove_peer(::std::string::String),
+    evidence(u64),
 }
 
 impl Request {
@@ -1162,6 +1166,154 @@ impl Request {
             _ => BatchRequest::default_instance(),
         }
     }
+
+    // bool peers = 20;
+
+    pub fn clear_peers(&mut self) {
+        self.req = ::std::option::Option::None;
+    }
+
+    pub fn has_peers(&self) -> bool {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::peers(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_peers(&mut self, v: bool) {
+        self.req = ::std::option::Option::Some(Request_oneof_req::peers(v))
+    }
+
+    pub fn get_peers(&self) -> bool {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::peers(v)) => v,
+            _ => false,
+        }
+    }
+
+    // string add_peer = 21;
+
+    pub fn clear_add_peer(&mut self) {
+        self.req = ::std::option::Option::None;
+    }
+
+    pub fn has_add_peer(&self) -> bool {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::add_peer(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_add_peer(&mut self, v: ::std::string::String) {
+        self.req = ::std::option::Option::Some(Request_oneof_req::add_peer(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_add_peer(&mut self) -> &mut ::std::string::String {
+        if let ::std::option::Option::Some(Request_oneof_req::add_peer(_)) = self.req {
+        } else {
+            self.req = ::std::option::Option::Some(Request_oneof_req::add_peer(::std::string::String::new()));
+        }
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::add_peer(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_add_peer(&mut self) -> ::std::string::String {
+        if self.has_add_peer() {
+            match self.req.take() {
+                ::std::option::Option::Some(Request_oneof_req::add_peer(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            ::std::string::String::new()
+        }
+    }
+
+    pub fn get_add_peer(&self) -> &str {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::add_peer(ref v)) => v,
+            _ => "",
+        }
+    }
+
+    // string remove_peer = 22;
+
+    pub fn clear_remove_peer(&mut self) {
+        self.req = ::std::option::Option::None;
+    }
+
+    pub fn has_remove_peer(&self) -> bool {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::remove_peer(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_remove_peer(&mut self, v: ::std::string::String) {
+        self.req = ::std::option::Option::Some(Request_oneof_req::remove_peer(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_remove_peer(&mut self) -> &mut ::std::string::String {
+        if let ::std::option::Option::Some(Request_oneof_req::remove_peer(_)) = self.req {
+        } else {
+            self.req = ::std::option::Option::Some(Request_oneof_req::remove_peer(::std::string::String::new()));
+        }
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::remove_peer(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_remove_peer(&mut self) -> ::std::string::String {
+        if self.has_remove_peer() {
+            match self.req.take() {
+                ::std::option::Option::Some(Request_oneof_req::remove_peer(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            ::std::string::String::new()
+        }
+    }
+
+    pub fn get_remove_peer(&self) -> &str {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::remove_peer(ref v)) => v,
+            _ => "",
+        }
+    }
+
+    // uint64 evidence = 23;
+
+    pub fn clear_evidence(&mut self) {
+        self.req = ::std::option::Option::None;
+    }
+
+    pub fn has_evidence(&self) -> bool {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::evidence(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_evidence(&mut self, v: u64) {
+        self.req = ::std::option::Option::Some(Request_oneof_req::evidence(v))
+    }
+
+    pub fn get_evidence(&self) -> u64 {
+        match self.req {
+            ::std::option::Option::Some(Request_oneof_req::evidence(v)) => v,
+            _ => 0,
+        }
+    }
 }
 
 impl ::protobuf::Message for Request {
@@ -1299,6 +1451,30 @@ impl ::protobuf::Message for Request {
                     }
                     self.req = ::std::option::Option::Some(Request_oneof_req::batch_req(is.read_message()?));
                 },
+                20 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.req = ::std::option::Option::Some(Request_oneof_req::peers(is.read_bool()?));
+                },
+                21 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.req = ::std::option::Option::Some(Request_oneof_req::add_peer(is.read_string()?));
+                },
+                22 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.req = ::std::option::Option::Some(Request_oneof_req::remove_peer(is.read_string()?));
+                },
+                23 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.req = ::std::option::Option::Some(Request_oneof_req::evidence(is.read_uint64()?));
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1373,6 +1549,18 @@ impl ::protobuf::Message for Request {
                     let len = v.compute_size();
                     my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
                 },
+                &Request_oneof_req::peers(v) => {
+                    my_size += 3;
+                },
+                &Request_oneof_req::add_peer(ref v) => {
+                    my_size += ::protobuf::rt::string_size(21, &v);
+                },
+                &Request_oneof_req::remove_peer(ref v) => {
+                    my_size += ::protobuf::rt::string_size(22, &v);
+                },
+                &Request_oneof_req::evidence(v) => {
+                    my_size += ::protobuf::rt::value_size(23, v, ::protobuf::wire_format::WireTypeVarint);
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
@@ -1446,6 +1634,18 @@ impl ::protobuf::Message for Request {
                     os.write_raw_varint32(v.get_cached_size())?;
                     v.write_to_with_cached_sizes(os)?;
                 },
+                &Request_oneof_req::peers(v) => {
+                    os.write_bool(20, v)?;
+                },
+                &Request_oneof_req::add_peer(ref v) => {
+                    os.write_string(21, v)?;
+                },
+                &Request_oneof_req::remove_peer(ref v) => {
+                    os.write_string(22, v)?;
+                },
+                &Request_oneof_req::evidence(v) => {
+                    os.write_uint64(23, v)?;
+                },
             };
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -1587,6 +1787,26 @@ impl ::protobuf::MessageStatic for Request {
                     Request::has_batch_req,
                     Request::get_batch_req,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor::<_>(
+                    "peers",
+                    Request::has_peers,
+                    Request::get_peers,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor::<_>(
+                    "add_peer",
+                    Request::has_add_peer,
+                    Request::get_add_peer,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor::<_>(
+                    "remove_peer",
+                    Request::has_remove_peer,
+                    Request::get_remove_peer,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor::<_>(
+                    "evidence",
+                    Request::has_evidence,
+                    Request::get_evidence,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Request>(
                     "Request",
                     fields,
@@ -1618,6 +1838,10 @@ impl ::protobuf::Clear for Request {
         self.clear_filter_logs();
         self.clear_un_tx();
         self.clear_batch_req();
+        self.clear_peers();
+        self.clear_add_peer();
+        self.clear_remove_peer();
+        self.clear_evidence();
         self.unknown_fields.clear();
     }
 }
@@ -1876,7 +2100,7 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \n\rrequest.proto\x1a\x10blockchain.proto\"V\n\x04Call\x12\x12\n\x04from\
     \x18\x01\x20\x01(\x0cR\x04from\x12\x0e\n\x02to\x18\x02\x20\x01(\x0cR\x02\
     to\x12\x12\n\x04data\x18\x03\x20\x01(\x0cR\x04data\x12\x16\n\x06height\
-    \x18\x04\x20\x01(\tR\x06height\"\xd4\x05\n\x07Request\x12\x1d\n\nrequest\
+    \x18\x04\x20\x01(\tR\x06height\"\xca\x06\n\x07Request\x12\x1d\n\nrequest\
     _id\x18\x01\x20\x01(\x0cR\trequestId\x12#\n\x0cblock_number\x18\x02\x20\
     \x01(\x08H\0R\x0bblockNumber\x12$\n\rblock_by_hash\x18\x03\x20\x01(\tH\0\
     R\x0bblockByHash\x12(\n\x0fblock_by_height\x18\x04\x20\x01(\tH\0R\rblock\
@@ -1893,75 +2117,78 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x18\x10\x20\x01(\x04H\0R\rfilterChanges\x12!\n\x0bfilter_logs\x18\x11\
     \x20\x01(\x04H\0R\nfilterLogs\x12-\n\x05un_tx\x18\x12\x20\x01(\x0b2\x16.\
     UnverifiedTransactionH\0R\x04unTx\x12,\n\tbatch_req\x18\x13\x20\x01(\x0b\
-    2\r.BatchRequestH\0R\x08batchReqB\x05\n\x03req\"@\n\x0cBatchRequest\x120\
-    \n\x0fnew_tx_requests\x18\x01\x20\x03(\x0b2\x08.RequestR\rnewTxRequests*\
-    $\n\x08BlockTag\x12\n\n\x06Latest\x10\0\x12\x0c\n\x08Earliest\x10\x01J\
-    \x9f\r\n\x06\x12\x04\0\0*\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\
-    \x03\0\x12\x03\x02\x07\x19\n\n\n\x02\x05\0\x12\x04\x04\0\x07\x01\n\n\n\
-    \x03\x05\0\x01\x12\x03\x04\x05\r\n\x0b\n\x04\x05\0\x02\0\x12\x03\x05\x04\
-    \x0f\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x05\x04\n\n\x0c\n\x05\x05\0\x02\
-    \0\x02\x12\x03\x05\r\x0e\n\x0b\n\x04\x05\0\x02\x01\x12\x03\x06\x04\x11\n\
-    \x0c\n\x05\x05\0\x02\x01\x01\x12\x03\x06\x04\x0c\n\x0c\n\x05\x05\0\x02\
-    \x01\x02\x12\x03\x06\x0f\x10\n\n\n\x02\x04\0\x12\x04\t\0\x0e\x01\n\n\n\
-    \x03\x04\0\x01\x12\x03\t\x08\x0c\n\x0b\n\x04\x04\0\x02\0\x12\x03\n\x04\
-    \x13\n\r\n\x05\x04\0\x02\0\x04\x12\x04\n\x04\t\x0e\n\x0c\n\x05\x04\0\x02\
-    \0\x05\x12\x03\n\x04\t\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\n\n\x0e\n\x0c\
-    \n\x05\x04\0\x02\0\x03\x12\x03\n\x11\x12\n\x0b\n\x04\x04\0\x02\x01\x12\
-    \x03\x0b\x04\x11\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x0b\x04\n\x13\n\x0c\
-    \n\x05\x04\0\x02\x01\x05\x12\x03\x0b\x04\t\n\x0c\n\x05\x04\0\x02\x01\x01\
-    \x12\x03\x0b\n\x0c\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x0b\x0f\x10\n\
-    \x0b\n\x04\x04\0\x02\x02\x12\x03\x0c\x04\x13\n\r\n\x05\x04\0\x02\x02\x04\
-    \x12\x04\x0c\x04\x0b\x11\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x0c\x04\t\
-    \n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x0c\n\x0e\n\x0c\n\x05\x04\0\x02\
-    \x02\x03\x12\x03\x0c\x11\x12\n\x0b\n\x04\x04\0\x02\x03\x12\x03\r\x04\x16\
-    \n\r\n\x05\x04\0\x02\x03\x04\x12\x04\r\x04\x0c\x13\n\x0c\n\x05\x04\0\x02\
-    \x03\x05\x12\x03\r\x04\n\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\r\x0b\x11\
-    \n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\r\x14\x15\n\n\n\x02\x04\x01\x12\
-    \x04\x10\0&\x01\n\n\n\x03\x04\x01\x01\x12\x03\x10\x08\x0f\n\x0b\n\x04\
-    \x04\x01\x02\0\x12\x03\x11\x04\x19\n\r\n\x05\x04\x01\x02\0\x04\x12\x04\
-    \x11\x04\x10\x11\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x11\x04\t\n\x0c\n\
-    \x05\x04\x01\x02\0\x01\x12\x03\x11\n\x14\n\x0c\n\x05\x04\x01\x02\0\x03\
-    \x12\x03\x11\x17\x18\n\x0c\n\x04\x04\x01\x08\0\x12\x04\x12\x04%\x05\n\
-    \x0c\n\x05\x04\x01\x08\0\x01\x12\x03\x12\n\r\n\x0b\n\x04\x04\x01\x02\x01\
-    \x12\x03\x13\x08\x1e\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x13\x08\x0c\
-    \n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x13\r\x19\n\x0c\n\x05\x04\x01\
-    \x02\x01\x03\x12\x03\x13\x1c\x1d\n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x14\
-    \x08!\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x14\x08\x0e\n\x0c\n\x05\
-    \x04\x01\x02\x02\x01\x12\x03\x14\x0f\x1c\n\x0c\n\x05\x04\x01\x02\x02\x03\
-    \x12\x03\x14\x1f\x20\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x15\x08#\n\x0c\
-    \n\x05\x04\x01\x02\x03\x05\x12\x03\x15\x08\x0e\n\x0c\n\x05\x04\x01\x02\
-    \x03\x01\x12\x03\x15\x0f\x1e\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\x03\x15\
-    !\"\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x16\x08\x1e\n\x0c\n\x05\x04\x01\
-    \x02\x04\x05\x12\x03\x16\x08\r\n\x0c\n\x05\x04\x01\x02\x04\x01\x12\x03\
-    \x16\x0e\x19\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x16\x1c\x1d\n\x0b\n\
-    \x04\x04\x01\x02\x05\x12\x03\x17\x08\x1a\n\x0c\n\x05\x04\x01\x02\x05\x05\
-    \x12\x03\x17\x08\x0e\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x17\x0f\x15\
-    \n\x0c\n\x05\x04\x01\x02\x05\x03\x12\x03\x17\x18\x19\n\x0b\n\x04\x04\x01\
-    \x02\x06\x12\x03\x18\x08\x1b\n\x0c\n\x05\x04\x01\x02\x06\x05\x12\x03\x18\
-    \x08\x0c\n\x0c\n\x05\x04\x01\x02\x06\x01\x12\x03\x18\r\x16\n\x0c\n\x05\
-    \x04\x01\x02\x06\x03\x12\x03\x18\x19\x1a\n\x0b\n\x04\x04\x01\x02\x07\x12\
-    \x03\x19\x08\x16\n\x0c\n\x05\x04\x01\x02\x07\x06\x12\x03\x19\x08\x0c\n\
-    \x0c\n\x05\x04\x01\x02\x07\x01\x12\x03\x19\r\x11\n\x0c\n\x05\x04\x01\x02\
-    \x07\x03\x12\x03\x19\x14\x15\n\x0b\n\x04\x04\x01\x02\x08\x12\x03\x1a\x08\
-    \x1a\n\x0c\n\x05\x04\x01\x02\x08\x05\x12\x03\x1a\x08\x0e\n\x0c\n\x05\x04\
-    \x01\x02\x08\x01\x12\x03\x1a\x0f\x15\n\x0c\n\x05\x04\x01\x02\x08\x03\x12\
-    \x03\x1a\x18\x19\n\x0b\n\x04\x04\x01\x02\t\x12\x03\x1b\x08'\n\x0c\n\x05\
-    \x04\x01\x02\t\x05\x12\x03\x1b\x08\r\n\x0c\n\x05\x04\x01\x02\t\x01\x12\
-    \x03\x1b\x0e!\n\x0c\n\x05\x04\x01\x02\t\x03\x12\x03\x1b$&\n\x0b\n\x04\
-    \x04\x01\x02\n\x12\x03\x1c\x08&\n\x0c\n\x05\x04\x01\x02\n\x05\x12\x03\
-    \x1c\x08\x0e\n\x0c\n\x05\x04\x01\x02\n\x01\x12\x03\x1c\x0f\x20\n\x0c\n\
-    \x05\x04\x01\x02\n\x03\x12\x03\x1c#%\n\x0b\n\x04\x04\x01\x02\x0b\x12\x03\
-    \x1d\x08\x19\n\x0c\n\x05\x04\x01\x02\x0b\x05\x12\x03\x1d\x08\x0e\n\x0c\n\
-    \x05\x04\x01\x02\x0b\x01\x12\x03\x1d\x0f\x13\n\x0c\n\x05\x04\x01\x02\x0b\
-    \x03\x12\x03\x1d\x16\x18\n\x0b\n\x04\x04\x01\x02\x0c\x12\x03\x1e\x08\x1f\
-    \n\x0c\n\x05\x04\x01\x02\x0c\x05\x12\x03\x1e\x08\x0e\n\x0c\n\x05\x04\x01\
-    \x02\x0c\x01\x12\x03\x1e\x0f\x19\n\x0c\n\x05\x04\x01\x02\x0c\x03\x12\x03\
-    \x1e\x1c\x1e\n\x0b\n\x04\x04\x01\x02\r\x12\x03\x1f\x08#\n\x0c\n\x05\x04\
-    \x01\x02\r\x05\x12\x03\x1f\x08\x0c\n\x0c\n\x05\x04\x01\x02\r\x01\x12\x03\
-    \x1f\r\x1d\n\x0c\n\x05\x04\x01\x02\r\x03\x12\x03\x1f\x20\"\n\x0b\n\x04\
-    \x04\x01\x02\x0e\x12\x03\x20\x08%\n\x0c\n\x05\x04\x01\x02\x0e\x05\x12\
-    \x03\x20\x08\x0e\n\x0c\n\x05\x04\x01\x02\x0e\x01\x12\x03\x20\x0f\x1f\n\
-    \x0c\n\x05\x04\x01\x02\x0e\x03\x12\x03\x20\"$\n\x0b\n\x04\x04\x01\x02\
+    2\r.BatchRequestH\0R\x08batchReq\x12\x16\n\x05peers\x18\x14\x20\x01(\x08\
+    H\0R\x05peers\x12\x1b\n\x08add_peer\x18\x15\x20\x01(\tH\0R\x07addPeer\
+    \x12!\n\x0bremove_peer\x18\x16\x20\x01(\tH\0R\nremovePeer\x12\x1c\n\x08e\
+    vidence\x18\x17\x20\x01(\x04H\0R\x08evidenceB\x05\n\x03req\"@\n\x0cBatch\
+    Request\x120\n\x0fnew_tx_requests\x18\x01\x20\x03(\x0b2\x08.RequestR\rne\
+    wTxRequests*$\n\x08BlockTag\x12\n\n\x06Latest\x10\0\x12\x0c\n\x08Earlies\
+    t\x10\x01J\xfb\x0e\n\x06\x12\x04\0\0.\x01\n\x08\n\x01\x0c\x12\x03\0\0\
+    \x12\n\t\n\x02\x03\0\x12\x03\x02\x07\x19\n\n\n\x02\x05\0\x12\x04\x04\0\
+    \x07\x01\n\n\n\x03\x05\0\x01\x12\x03\x04\x05\r\n\x0b\n\x04\x05\0\x02\0\
+    \x12\x03\x05\x04\x0f\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x05\x04\n\n\x0c\
+    \n\x05\x05\0\x02\0\x02\x12\x03\x05\r\x0e\n\x0b\n\x04\x05\0\x02\x01\x12\
+    \x03\x06\x04\x11\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\x06\x04\x0c\n\x0c\
+    \n\x05\x05\0\x02\x01\x02\x12\x03\x06\x0f\x10\n\n\n\x02\x04\0\x12\x04\t\0\
+    \x0e\x01\n\n\n\x03\x04\0\x01\x12\x03\t\x08\x0c\n\x0b\n\x04\x04\0\x02\0\
+    \x12\x03\n\x04\x13\n\r\n\x05\x04\0\x02\0\x04\x12\x04\n\x04\t\x0e\n\x0c\n\
+    \x05\x04\0\x02\0\x05\x12\x03\n\x04\t\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\
+    \n\n\x0e\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\n\x11\x12\n\x0b\n\x04\x04\0\
+    \x02\x01\x12\x03\x0b\x04\x11\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x0b\x04\
+    \n\x13\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x0b\x04\t\n\x0c\n\x05\x04\0\
+    \x02\x01\x01\x12\x03\x0b\n\x0c\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x0b\
+    \x0f\x10\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x0c\x04\x13\n\r\n\x05\x04\0\
+    \x02\x02\x04\x12\x04\x0c\x04\x0b\x11\n\x0c\n\x05\x04\0\x02\x02\x05\x12\
+    \x03\x0c\x04\t\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x0c\n\x0e\n\x0c\n\
+    \x05\x04\0\x02\x02\x03\x12\x03\x0c\x11\x12\n\x0b\n\x04\x04\0\x02\x03\x12\
+    \x03\r\x04\x16\n\r\n\x05\x04\0\x02\x03\x04\x12\x04\r\x04\x0c\x13\n\x0c\n\
+    \x05\x04\0\x02\x03\x05\x12\x03\r\x04\n\n\x0c\n\x05\x04\0\x02\x03\x01\x12\
+    \x03\r\x0b\x11\n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\r\x14\x15\n\n\n\x02\
+    \x04\x01\x12\x04\x10\0*\x01\n\n\n\x03\x04\x01\x01\x12\x03\x10\x08\x0f\n\
+    \x0b\n\x04\x04\x01\x02\0\x12\x03\x11\x04\x19\n\r\n\x05\x04\x01\x02\0\x04\
+    \x12\x04\x11\x04\x10\x11\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x11\x04\t\
+    \n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x11\n\x14\n\x0c\n\x05\x04\x01\x02\
+    \0\x03\x12\x03\x11\x17\x18\n\x0c\n\x04\x04\x01\x08\0\x12\x04\x12\x04)\
+    \x05\n\x0c\n\x05\x04\x01\x08\0\x01\x12\x03\x12\n\r\n\x0b\n\x04\x04\x01\
+    \x02\x01\x12\x03\x13\x08\x1e\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x13\
+    \x08\x0c\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x13\r\x19\n\x0c\n\x05\
+    \x04\x01\x02\x01\x03\x12\x03\x13\x1c\x1d\n\x0b\n\x04\x04\x01\x02\x02\x12\
+    \x03\x14\x08!\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x14\x08\x0e\n\x0c\
+    \n\x05\x04\x01\x02\x02\x01\x12\x03\x14\x0f\x1c\n\x0c\n\x05\x04\x01\x02\
+    \x02\x03\x12\x03\x14\x1f\x20\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x15\x08\
+    #\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\x15\x08\x0e\n\x0c\n\x05\x04\
+    \x01\x02\x03\x01\x12\x03\x15\x0f\x1e\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\
+    \x03\x15!\"\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x16\x08\x1e\n\x0c\n\x05\
+    \x04\x01\x02\x04\x05\x12\x03\x16\x08\r\n\x0c\n\x05\x04\x01\x02\x04\x01\
+    \x12\x03\x16\x0e\x19\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x16\x1c\x1d\
+    \n\x0b\n\x04\x04\x01\x02\x05\x12\x03\x17\x08\x1a\n\x0c\n\x05\x04\x01\x02\
+    \x05\x05\x12\x03\x17\x08\x0e\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x17\
+    \x0f\x15\n\x0c\n\x05\x04\x01\x02\x05\x03\x12\x03\x17\x18\x19\n\x0b\n\x04\
+    \x04\x01\x02\x06\x12\x03\x18\x08\x1b\n\x0c\n\x05\x04\x01\x02\x06\x05\x12\
+    \x03\x18\x08\x0c\n\x0c\n\x05\x04\x01\x02\x06\x01\x12\x03\x18\r\x16\n\x0c\
+    \n\x05\x04\x01\x02\x06\x03\x12\x03\x18\x19\x1a\n\x0b\n\x04\x04\x01\x02\
+    \x07\x12\x03\x19\x08\x16\n\x0c\n\x05\x04\x01\x02\x07\x06\x12\x03\x19\x08\
+    \x0c\n\x0c\n\x05\x04\x01\x02\x07\x01\x12\x03\x19\r\x11\n\x0c\n\x05\x04\
+    \x01\x02\x07\x03\x12\x03\x19\x14\x15\n\x0b\n\x04\x04\x01\x02\x08\x12\x03\
+    \x1a\x08\x1a\n\x0c\n\x05\x04\x01\x02\x08\x05\x12\x03\x1a\x08\x0e\n\x0c\n\
+    \x05\x04\x01\x02\x08\x01\x12\x03\x1a\x0f\x15\n\x0c\n\x05\x04\x01\x02\x08\
+    \x03\x12\x03\x1a\x18\x19\n\x0b\n\x04\x04\x01\x02\t\x12\x03\x1b\x08'\n\
+    \x0c\n\x05\x04\x01\x02\t\x05\x12\x03\x1b\x08\r\n\x0c\n\x05\x04\x01\x02\t\
+    \x01\x12\x03\x1b\x0e!\n\x0c\n\x05\x04\x01\x02\t\x03\x12\x03\x1b$&\n\x0b\
+    \n\x04\x04\x01\x02\n\x12\x03\x1c\x08&\n\x0c\n\x05\x04\x01\x02\n\x05\x12\
+    \x03\x1c\x08\x0e\n\x0c\n\x05\x04\x01\x02\n\x01\x12\x03\x1c\x0f\x20\n\x0c\
+    \n\x05\x04\x01\x02\n\x03\x12\x03\x1c#%\n\x0b\n\x04\x04\x01\x02\x0b\x12\
+    \x03\x1d\x08\x19\n\x0c\n\x05\x04\x01\x02\x0b\x05\x12\x03\x1d\x08\x0e\n\
+    \x0c\n\x05\x04\x01\x02\x0b\x01\x12\x03\x1d\x0f\x13\n\x0c\n\x05\x04\x01\
+    \x02\x0b\x03\x12\x03\x1d\x16\x18\n\x0b\n\x04\x04\x01\x02\x0c\x12\x03\x1e\
+    \x08\x1f\n\x0c\n\x05\x04\x01\x02\x0c\x05\x12\x03\x1e\x08\x0e\n\x0c\n\x05\
+    \x04\x01\x02\x0c\x01\x12\x03\x1e\x0f\x19\n\x0c\n\x05\x04\x01\x02\x0c\x03\
+    \x12\x03\x1e\x1c\x1e\n\x0b\n\x04\x04\x01\x02\r\x12\x03\x1f\x08#\n\x0c\n\
+    \x05\x04\x01\x02\r\x05\x12\x03\x1f\x08\x0c\n\x0c\n\x05\x04\x01\x02\r\x01\
+    \x12\x03\x1f\r\x1d\n\x0c\n\x05\x04\x01\x02\r\x03\x12\x03\x1f\x20\"\n\x0b\
+    \n\x04\x04\x01\x02\x0e\x12\x03\x20\x08%\n\x0c\n\x05\x04\x01\x02\x0e\x05\
+    \x12\x03\x20\x08\x0e\n\x0c\n\x05\x04\x01\x02\x0e\x01\x12\x03\x20\x0f\x1f\
+    \n\x0c\n\x05\x04\x01\x02\x0e\x03\x12\x03\x20\"$\n\x0b\n\x04\x04\x01\x02\
     \x0f\x12\x03!\x08#\n\x0c\n\x05\x04\x01\x02\x0f\x05\x12\x03!\x08\x0e\n\
     \x0c\n\x05\x04\x01\x02\x0f\x01\x12\x03!\x0f\x1d\n\x0c\n\x05\x04\x01\x02\
     \x0f\x03\x12\x03!\x20\"\n\x0b\n\x04\x04\x01\x02\x10\x12\x03\"\x08\x20\n\
@@ -1974,12 +2201,22 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x1d\n\x0c\n\x05\x04\x01\x02\x11\x01\x12\x03#\x1e#\n\x0c\n\x05\x04\x01\
     \x02\x11\x03\x12\x03#&(\n\x0b\n\x04\x04\x01\x02\x12\x12\x03$\x08$\n\x0c\
     \n\x05\x04\x01\x02\x12\x06\x12\x03$\x08\x14\n\x0c\n\x05\x04\x01\x02\x12\
-    \x01\x12\x03$\x15\x1e\n\x0c\n\x05\x04\x01\x02\x12\x03\x12\x03$!#\n\n\n\
-    \x02\x04\x02\x12\x04(\0*\x01\n\n\n\x03\x04\x02\x01\x12\x03(\x08\x14\n\
-    \x0b\n\x04\x04\x02\x02\0\x12\x03)\x04)\n\x0c\n\x05\x04\x02\x02\0\x04\x12\
-    \x03)\x04\x0c\n\x0c\n\x05\x04\x02\x02\0\x06\x12\x03)\r\x14\n\x0c\n\x05\
-    \x04\x02\x02\0\x01\x12\x03)\x15$\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03)'\
-    (b\x06proto3\
+    \x01\x12\x03$\x15\x1e\n\x0c\n\x05\x04\x01\x02\x12\x03\x12\x03$!#\n\x0b\n\
+    \x04\x04\x01\x02\x13\x12\x03%\x08\x18\n\x0c\n\x05\x04\x01\x02\x13\x05\
+    \x12\x03%\x08\x0c\n\x0c\n\x05\x04\x01\x02\x13\x01\x12\x03%\r\x12\n\x0c\n\
+    \x05\x04\x01\x02\x13\x03\x12\x03%\x15\x17\n\x0b\n\x04\x04\x01\x02\x14\
+    \x12\x03&\x08\x1d\n\x0c\n\x05\x04\x01\x02\x14\x05\x12\x03&\x08\x0e\n\x0c\
+    \n\x05\x04\x01\x02\x14\x01\x12\x03&\x0f\x17\n\x0c\n\x05\x04\x01\x02\x14\
+    \x03\x12\x03&\x1a\x1c\n\x0b\n\x04\x04\x01\x02\x15\x12\x03'\x08\x20\n\x0c\
+    \n\x05\x04\x01\x02\x15\x05\x12\x03'\x08\x0e\n\x0c\n\x05\x04\x01\x02\x15\
+    \x01\x12\x03'\x0f\x1a\n\x0c\n\x05\x04\x01\x02\x15\x03\x12\x03'\x1d\x1f\n\
+    \x0b\n\x04\x04\x01\x02\x16\x12\x03(\x08\x1d\n\x0c\n\x05\x04\x01\x02\x16\
+    \x05\x12\x03(\x08\x0e\n\x0c\n\x05\x04\x01\x02\x16\x01\x12\x03(\x0f\x17\n\
+    \x0c\n\x05\x04\x01\x02\x16\x03\x12\x03(\x1a\x1c\n\n\n\x02\x04\x02\x12\
+    \x04,\0.\x01\n\n\n\x03\x04\x02\x01\x12\x03,\x08\x14\n\x0b\n\x04\x04\x02\
+    \x02\0\x12\x03-\x04)\n\x0c\n\x05\x04\x02\x02\0\x04\x12\x03-\x04\x0c\n\
+    \x0c\n\x05\x04\x02\x02\0\x06\x12\x03-\r\x14\n\x0c\n\x05\x04\x02\x02\0\
+    \x01\x12\x03-\x15$\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03-'(b\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
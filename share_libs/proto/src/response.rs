@@ -398,6 +398,8 @@ pub enum Response_oneof_data {
     filter_changes(::std::string::String),
     filter_logs(::std::string::String),
     none(bool),
+    peers(::std::string::String),
+    evidence(::std::string::String),
 }
 
 impl Response {
@@ -1111,6 +1113,104 @@ impl Response {
             _ => false,
         }
     }
+
+    // string peers = 19;
+
+    pub fn clear_peers(&mut self) {
+        self.data = ::std::option::Option::None;
+    }
+
+    pub fn has_peers(&self) -> bool {
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::peers(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_peers(&mut self, v: ::std::string::String) {
+        self.data = ::std::option::Option::Some(Response_oneof_data::peers(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_peers(&mut self) -> &mut ::std::string::String {
+        if let ::std::option::Option::Some(Response_oneof_data::peers(_)) = self.data {
+        } else {
+            self.data = ::std::option::Option::Some(Response_oneof_data::peers(::std::string::String::new()));
+        }
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::peers(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_peers(&mut self) -> ::std::string::String {
+        if self.has_peers() {
+            match self.data.take() {
+                ::std::option::Option::Some(Response_oneof_data::peers(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            ::std::string::String::new()
+        }
+    }
+
+    pub fn get_peers(&self) -> &str {
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::peers(ref v)) => v,
+            _ => "",
+        }
+    }
+
+    // string evidence = 20;
+
+    pub fn clear_evidence(&mut self) {
+        self.data = ::std::option::Option::None;
+    }
+
+    pub fn has_evidence(&self) -> bool {
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::evidence(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_evidence(&mut self, v: ::std::string::String) {
+        self.data = ::std::option::Option::Some(Response_oneof_data::evidence(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_evidence(&mut self) -> &mut ::std::string::String {
+        if let ::std::option::Option::Some(Response_oneof_data::evidence(_)) = self.data {
+        } else {
+            self.data = ::std::option::Option::Some(Response_oneof_data::evidence(::std::string::String::new()));
+        }
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::evidence(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_evidence(&mut self) -> ::std::string::String {
+        if self.has_evidence() {
+            match self.data.take() {
+                ::std::option::Option::Some(Response_oneof_data::evidence(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            ::std::string::String::new()
+        }
+    }
+
+    pub fn get_evidence(&self) -> &str {
+        match self.data {
+            ::std::option::Option::Some(Response_oneof_data::evidence(ref v)) => v,
+            _ => "",
+        }
+    }
 }
 
 impl ::protobuf::Message for Response {
@@ -1233,6 +1333,18 @@ impl ::protobuf::Message for Response {
                     }
                     self.data = ::std::option::Option::Some(Response_oneof_data::none(is.read_bool()?));
                 },
+                19 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.data = ::std::option::Option::Some(Response_oneof_data::peers(is.read_string()?));
+                },
+                20 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.data = ::std::option::Option::Some(Response_oneof_data::evidence(is.read_string()?));
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1302,6 +1414,12 @@ impl ::protobuf::Message for Response {
                 &Response_oneof_data::none(v) => {
                     my_size += 3;
                 },
+                &Response_oneof_data::peers(ref v) => {
+                    my_size += ::protobuf::rt::string_size(19, &v);
+                },
+                &Response_oneof_data::evidence(ref v) => {
+                    my_size += ::protobuf::rt::string_size(20, &v);
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
@@ -1368,6 +1486,12 @@ impl ::protobuf::Message for Response {
                 &Response_oneof_data::none(v) => {
                     os.write_bool(18, v)?;
                 },
+                &Response_oneof_data::peers(ref v) => {
+                    os.write_string(19, v)?;
+                },
+                &Response_oneof_data::evidence(ref v) => {
+                    os.write_string(20, v)?;
+                },
             };
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -1504,6 +1628,16 @@ impl ::protobuf::MessageStatic for Response {
                     Response::has_none,
                     Response::get_none,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor::<_>(
+                    "peers",
+                    Response::has_peers,
+                    Response::get_peers,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor::<_>(
+                    "evidence",
+                    Response::has_evidence,
+                    Response::get_evidence,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Response>(
                     "Response",
                     fields,
@@ -1534,6 +1668,8 @@ impl ::protobuf::Clear for Response {
         self.clear_filter_changes();
         self.clear_filter_logs();
         self.clear_none();
+        self.clear_peers();
+        self.clear_evidence();
         self.unknown_fields.clear();
     }
 }
@@ -1555,7 +1691,7 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     ion\x124\n\x0btransaction\x18\x01\x20\x01(\x0b2\x12.SignedTransactionR\
     \x0btransaction\x12!\n\x0cblock_number\x18\x02\x20\x01(\x04R\x0bblockNum\
     ber\x12\x1d\n\nblock_hash\x18\x03\x20\x01(\x0cR\tblockHash\x12\x14\n\x05\
-    index\x18\x04\x20\x01(\rR\x05index\"\xdb\x04\n\x08Response\x12\x1d\n\nre\
+    index\x18\x04\x20\x01(\rR\x05index\"\x91\x05\n\x08Response\x12\x1d\n\nre\
     quest_id\x18\x01\x20\x01(\x0cR\trequestId\x12\x12\n\x04code\x18\x02\x20\
     \x01(\x03R\x04code\x12\x1d\n\terror_msg\x18\x03\x20\x01(\tH\0R\x08errorM\
     sg\x12\x1b\n\x08tx_state\x18\x04\x20\x01(\tH\0R\x07txState\x12#\n\x0cblo\
@@ -1570,78 +1706,85 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x12+\n\x10uninstall_filter\x18\x0f\x20\x01(\x08H\0R\x0funinstallFilter\
     \x12'\n\x0efilter_changes\x18\x10\x20\x01(\tH\0R\rfilterChanges\x12!\n\
     \x0bfilter_logs\x18\x11\x20\x01(\tH\0R\nfilterLogs\x12\x14\n\x04none\x18\
-    \x12\x20\x01(\x08H\0R\x04noneB\x06\n\x04dataJ\xfd\n\n\x06\x12\x04\0\0!\
-    \x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\x07\x19\
-    \n\n\n\x02\x04\0\x12\x04\x04\0\t\x01\n\n\n\x03\x04\0\x01\x12\x03\x04\x08\
-    \x17\n\x0b\n\x04\x04\0\x02\0\x12\x03\x05\x04&\n\r\n\x05\x04\0\x02\0\x04\
-    \x12\x04\x05\x04\x04\x19\n\x0c\n\x05\x04\0\x02\0\x06\x12\x03\x05\x04\x15\
-    \n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x05\x16!\n\x0c\n\x05\x04\0\x02\0\
-    \x03\x12\x03\x05$%\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x06\x04\x1c\n\r\n\
-    \x05\x04\0\x02\x01\x04\x12\x04\x06\x04\x05&\n\x0c\n\x05\x04\0\x02\x01\
-    \x05\x12\x03\x06\x04\n\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x06\x0b\x17\
-    \n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x06\x1a\x1b\n\x0b\n\x04\x04\0\x02\
-    \x02\x12\x03\x07\x04\x19\n\r\n\x05\x04\0\x02\x02\x04\x12\x04\x07\x04\x06\
-    \x1c\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x07\x04\t\n\x0c\n\x05\x04\0\
-    \x02\x02\x01\x12\x03\x07\n\x14\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x07\
-    \x17\x18\n\x0b\n\x04\x04\0\x02\x03\x12\x03\x08\x04\x15\n\r\n\x05\x04\0\
-    \x02\x03\x04\x12\x04\x08\x04\x07\x19\n\x0c\n\x05\x04\0\x02\x03\x05\x12\
-    \x03\x08\x04\n\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\x08\x0b\x10\n\x0c\n\
-    \x05\x04\0\x02\x03\x03\x12\x03\x08\x13\x14\n\n\n\x02\x04\x01\x12\x04\x0c\
-    \0!\x01\n\n\n\x03\x04\x01\x01\x12\x03\x0c\x08\x10\n\x0b\n\x04\x04\x01\
-    \x02\0\x12\x03\r\x04\x19\n\r\n\x05\x04\x01\x02\0\x04\x12\x04\r\x04\x0c\
-    \x12\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\r\x04\t\n\x0c\n\x05\x04\x01\
-    \x02\0\x01\x12\x03\r\n\x14\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\r\x17\
-    \x18\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x0e\x04\x13\n\r\n\x05\x04\x01\
-    \x02\x01\x04\x12\x04\x0e\x04\r\x19\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\
-    \x03\x0e\x04\t\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x0e\n\x0e\n\x0c\n\
-    \x05\x04\x01\x02\x01\x03\x12\x03\x0e\x11\x12\n\x0c\n\x04\x04\x01\x08\0\
-    \x12\x04\x0f\x04\x20\x05\n\x0c\n\x05\x04\x01\x08\0\x01\x12\x03\x0f\n\x0e\
-    \n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x10\x08\x1d\n\x0c\n\x05\x04\x01\x02\
-    \x02\x05\x12\x03\x10\x08\x0e\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x10\
-    \x0f\x18\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x10\x1b\x1c\n\x0b\n\x04\
-    \x04\x01\x02\x03\x12\x03\x11\x08\x1c\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\
-    \x03\x11\x08\x0e\n\x0c\n\x05\x04\x01\x02\x03\x01\x12\x03\x11\x0f\x17\n\
-    \x0c\n\x05\x04\x01\x02\x03\x03\x12\x03\x11\x1a\x1b\n\x0b\n\x04\x04\x01\
-    \x02\x04\x12\x03\x12\x08\x20\n\x0c\n\x05\x04\x01\x02\x04\x05\x12\x03\x12\
-    \x08\x0e\n\x0c\n\x05\x04\x01\x02\x04\x01\x12\x03\x12\x0f\x1b\n\x0c\n\x05\
-    \x04\x01\x02\x04\x03\x12\x03\x12\x1e\x1f\n\x0b\n\x04\x04\x01\x02\x05\x12\
-    \x03\x13\x08\x19\n\x0c\n\x05\x04\x01\x02\x05\x05\x12\x03\x13\x08\x0e\n\
-    \x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x13\x0f\x14\n\x0c\n\x05\x04\x01\
-    \x02\x05\x03\x12\x03\x13\x17\x18\n\x0b\n\x04\x04\x01\x02\x06\x12\x03\x14\
-    \x08\x1f\n\x0c\n\x05\x04\x01\x02\x06\x06\x12\x03\x14\x08\x17\n\x0c\n\x05\
-    \x04\x01\x02\x06\x01\x12\x03\x14\x18\x1a\n\x0c\n\x05\x04\x01\x02\x06\x03\
-    \x12\x03\x14\x1d\x1e\n\x0b\n\x04\x04\x01\x02\x07\x12\x03\x15\x08\x1d\n\
-    \x0c\n\x05\x04\x01\x02\x07\x05\x12\x03\x15\x08\x0e\n\x0c\n\x05\x04\x01\
-    \x02\x07\x01\x12\x03\x15\x0f\x18\n\x0c\n\x05\x04\x01\x02\x07\x03\x12\x03\
-    \x15\x1b\x1c\n\x0b\n\x04\x04\x01\x02\x08\x12\x03\x16\x08\x1e\n\x0c\n\x05\
-    \x04\x01\x02\x08\x05\x12\x03\x16\x08\r\n\x0c\n\x05\x04\x01\x02\x08\x01\
-    \x12\x03\x16\x0e\x19\n\x0c\n\x05\x04\x01\x02\x08\x03\x12\x03\x16\x1c\x1d\
-    \n\x0b\n\x04\x04\x01\x02\t\x12\x03\x17\x08\x19\n\x0c\n\x05\x04\x01\x02\t\
-    \x05\x12\x03\x17\x08\x0e\n\x0c\n\x05\x04\x01\x02\t\x01\x12\x03\x17\x0f\
-    \x13\n\x0c\n\x05\x04\x01\x02\t\x03\x12\x03\x17\x16\x18\n\x0b\n\x04\x04\
-    \x01\x02\n\x12\x03\x18\x08\x1c\n\x0c\n\x05\x04\x01\x02\n\x05\x12\x03\x18\
-    \x08\x0e\n\x0c\n\x05\x04\x01\x02\n\x01\x12\x03\x18\x0f\x16\n\x0c\n\x05\
-    \x04\x01\x02\n\x03\x12\x03\x18\x19\x1b\n\x0b\n\x04\x04\x01\x02\x0b\x12\
-    \x03\x19\x08&\n\x0c\n\x05\x04\x01\x02\x0b\x05\x12\x03\x19\x08\x0e\n\x0c\
-    \n\x05\x04\x01\x02\x0b\x01\x12\x03\x19\x0f\x20\n\x0c\n\x05\x04\x01\x02\
-    \x0b\x03\x12\x03\x19#%\n\x0b\n\x04\x04\x01\x02\x0c\x12\x03\x1a\x08!\n\
-    \x0c\n\x05\x04\x01\x02\x0c\x05\x12\x03\x1a\x08\r\n\x0c\n\x05\x04\x01\x02\
-    \x0c\x01\x12\x03\x1a\x0e\x1b\n\x0c\n\x05\x04\x01\x02\x0c\x03\x12\x03\x1a\
-    \x1e\x20\n\x0b\n\x04\x04\x01\x02\r\x12\x03\x1b\x08\x1e\n\x0c\n\x05\x04\
-    \x01\x02\r\x05\x12\x03\x1b\x08\x0e\n\x0c\n\x05\x04\x01\x02\r\x01\x12\x03\
-    \x1b\x0f\x18\n\x0c\n\x05\x04\x01\x02\r\x03\x12\x03\x1b\x1b\x1d\n\x0b\n\
-    \x04\x04\x01\x02\x0e\x12\x03\x1c\x08#\n\x0c\n\x05\x04\x01\x02\x0e\x05\
-    \x12\x03\x1c\x08\x0c\n\x0c\n\x05\x04\x01\x02\x0e\x01\x12\x03\x1c\r\x1d\n\
-    \x0c\n\x05\x04\x01\x02\x0e\x03\x12\x03\x1c\x20\"\n\x0b\n\x04\x04\x01\x02\
-    \x0f\x12\x03\x1d\x08#\n\x0c\n\x05\x04\x01\x02\x0f\x05\x12\x03\x1d\x08\
-    \x0e\n\x0c\n\x05\x04\x01\x02\x0f\x01\x12\x03\x1d\x0f\x1d\n\x0c\n\x05\x04\
-    \x01\x02\x0f\x03\x12\x03\x1d\x20\"\n\x0b\n\x04\x04\x01\x02\x10\x12\x03\
-    \x1e\x08\x20\n\x0c\n\x05\x04\x01\x02\x10\x05\x12\x03\x1e\x08\x0e\n\x0c\n\
-    \x05\x04\x01\x02\x10\x01\x12\x03\x1e\x0f\x1a\n\x0c\n\x05\x04\x01\x02\x10\
-    \x03\x12\x03\x1e\x1d\x1f\n\x0b\n\x04\x04\x01\x02\x11\x12\x03\x1f\x08\x17\
-    \n\x0c\n\x05\x04\x01\x02\x11\x05\x12\x03\x1f\x08\x0c\n\x0c\n\x05\x04\x01\
-    \x02\x11\x01\x12\x03\x1f\r\x11\n\x0c\n\x05\x04\x01\x02\x11\x03\x12\x03\
-    \x1f\x14\x16b\x06proto3\
+    \x12\x20\x01(\x08H\0R\x04none\x12\x16\n\x05peers\x18\x13\x20\x01(\tH\0R\
+    \x05peers\x12\x1c\n\x08evidence\x18\x14\x20\x01(\tH\0R\x08evidenceB\x06\
+    \n\x04dataJ\xeb\x0b\n\x06\x12\x04\0\0#\x01\n\x08\n\x01\x0c\x12\x03\0\0\
+    \x12\n\t\n\x02\x03\0\x12\x03\x02\x07\x19\n\n\n\x02\x04\0\x12\x04\x04\0\t\
+    \x01\n\n\n\x03\x04\0\x01\x12\x03\x04\x08\x17\n\x0b\n\x04\x04\0\x02\0\x12\
+    \x03\x05\x04&\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x05\x04\x04\x19\n\x0c\n\
+    \x05\x04\0\x02\0\x06\x12\x03\x05\x04\x15\n\x0c\n\x05\x04\0\x02\0\x01\x12\
+    \x03\x05\x16!\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x05$%\n\x0b\n\x04\x04\
+    \0\x02\x01\x12\x03\x06\x04\x1c\n\r\n\x05\x04\0\x02\x01\x04\x12\x04\x06\
+    \x04\x05&\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x06\x04\n\n\x0c\n\x05\
+    \x04\0\x02\x01\x01\x12\x03\x06\x0b\x17\n\x0c\n\x05\x04\0\x02\x01\x03\x12\
+    \x03\x06\x1a\x1b\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x07\x04\x19\n\r\n\x05\
+    \x04\0\x02\x02\x04\x12\x04\x07\x04\x06\x1c\n\x0c\n\x05\x04\0\x02\x02\x05\
+    \x12\x03\x07\x04\t\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x07\n\x14\n\x0c\
+    \n\x05\x04\0\x02\x02\x03\x12\x03\x07\x17\x18\n\x0b\n\x04\x04\0\x02\x03\
+    \x12\x03\x08\x04\x15\n\r\n\x05\x04\0\x02\x03\x04\x12\x04\x08\x04\x07\x19\
+    \n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\x08\x04\n\n\x0c\n\x05\x04\0\x02\
+    \x03\x01\x12\x03\x08\x0b\x10\n\x0c\n\x05\x04\0\x02\x03\x03\x12\x03\x08\
+    \x13\x14\n\n\n\x02\x04\x01\x12\x04\x0c\0#\x01\n\n\n\x03\x04\x01\x01\x12\
+    \x03\x0c\x08\x10\n\x0b\n\x04\x04\x01\x02\0\x12\x03\r\x04\x19\n\r\n\x05\
+    \x04\x01\x02\0\x04\x12\x04\r\x04\x0c\x12\n\x0c\n\x05\x04\x01\x02\0\x05\
+    \x12\x03\r\x04\t\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\r\n\x14\n\x0c\n\
+    \x05\x04\x01\x02\0\x03\x12\x03\r\x17\x18\n\x0b\n\x04\x04\x01\x02\x01\x12\
+    \x03\x0e\x04\x13\n\r\n\x05\x04\x01\x02\x01\x04\x12\x04\x0e\x04\r\x19\n\
+    \x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x0e\x04\t\n\x0c\n\x05\x04\x01\x02\
+    \x01\x01\x12\x03\x0e\n\x0e\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x0e\
+    \x11\x12\n\x0c\n\x04\x04\x01\x08\0\x12\x04\x0f\x04\"\x05\n\x0c\n\x05\x04\
+    \x01\x08\0\x01\x12\x03\x0f\n\x0e\n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x10\
+    \x08\x1d\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x10\x08\x0e\n\x0c\n\x05\
+    \x04\x01\x02\x02\x01\x12\x03\x10\x0f\x18\n\x0c\n\x05\x04\x01\x02\x02\x03\
+    \x12\x03\x10\x1b\x1c\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x11\x08\x1c\n\
+    \x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\x11\x08\x0e\n\x0c\n\x05\x04\x01\
+    \x02\x03\x01\x12\x03\x11\x0f\x17\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\x03\
+    \x11\x1a\x1b\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x12\x08\x20\n\x0c\n\x05\
+    \x04\x01\x02\x04\x05\x12\x03\x12\x08\x0e\n\x0c\n\x05\x04\x01\x02\x04\x01\
+    \x12\x03\x12\x0f\x1b\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x12\x1e\x1f\
+    \n\x0b\n\x04\x04\x01\x02\x05\x12\x03\x13\x08\x19\n\x0c\n\x05\x04\x01\x02\
+    \x05\x05\x12\x03\x13\x08\x0e\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x13\
+    \x0f\x14\n\x0c\n\x05\x04\x01\x02\x05\x03\x12\x03\x13\x17\x18\n\x0b\n\x04\
+    \x04\x01\x02\x06\x12\x03\x14\x08\x1f\n\x0c\n\x05\x04\x01\x02\x06\x06\x12\
+    \x03\x14\x08\x17\n\x0c\n\x05\x04\x01\x02\x06\x01\x12\x03\x14\x18\x1a\n\
+    \x0c\n\x05\x04\x01\x02\x06\x03\x12\x03\x14\x1d\x1e\n\x0b\n\x04\x04\x01\
+    \x02\x07\x12\x03\x15\x08\x1d\n\x0c\n\x05\x04\x01\x02\x07\x05\x12\x03\x15\
+    \x08\x0e\n\x0c\n\x05\x04\x01\x02\x07\x01\x12\x03\x15\x0f\x18\n\x0c\n\x05\
+    \x04\x01\x02\x07\x03\x12\x03\x15\x1b\x1c\n\x0b\n\x04\x04\x01\x02\x08\x12\
+    \x03\x16\x08\x1e\n\x0c\n\x05\x04\x01\x02\x08\x05\x12\x03\x16\x08\r\n\x0c\
+    \n\x05\x04\x01\x02\x08\x01\x12\x03\x16\x0e\x19\n\x0c\n\x05\x04\x01\x02\
+    \x08\x03\x12\x03\x16\x1c\x1d\n\x0b\n\x04\x04\x01\x02\t\x12\x03\x17\x08\
+    \x19\n\x0c\n\x05\x04\x01\x02\t\x05\x12\x03\x17\x08\x0e\n\x0c\n\x05\x04\
+    \x01\x02\t\x01\x12\x03\x17\x0f\x13\n\x0c\n\x05\x04\x01\x02\t\x03\x12\x03\
+    \x17\x16\x18\n\x0b\n\x04\x04\x01\x02\n\x12\x03\x18\x08\x1c\n\x0c\n\x05\
+    \x04\x01\x02\n\x05\x12\x03\x18\x08\x0e\n\x0c\n\x05\x04\x01\x02\n\x01\x12\
+    \x03\x18\x0f\x16\n\x0c\n\x05\x04\x01\x02\n\x03\x12\x03\x18\x19\x1b\n\x0b\
+    \n\x04\x04\x01\x02\x0b\x12\x03\x19\x08&\n\x0c\n\x05\x04\x01\x02\x0b\x05\
+    \x12\x03\x19\x08\x0e\n\x0c\n\x05\x04\x01\x02\x0b\x01\x12\x03\x19\x0f\x20\
+    \n\x0c\n\x05\x04\x01\x02\x0b\x03\x12\x03\x19#%\n\x0b\n\x04\x04\x01\x02\
+    \x0c\x12\x03\x1a\x08!\n\x0c\n\x05\x04\x01\x02\x0c\x05\x12\x03\x1a\x08\r\
+    \n\x0c\n\x05\x04\x01\x02\x0c\x01\x12\x03\x1a\x0e\x1b\n\x0c\n\x05\x04\x01\
+    \x02\x0c\x03\x12\x03\x1a\x1e\x20\n\x0b\n\x04\x04\x01\x02\r\x12\x03\x1b\
+    \x08\x1e\n\x0c\n\x05\x04\x01\x02\r\x05\x12\x03\x1b\x08\x0e\n\x0c\n\x05\
+    \x04\x01\x02\r\x01\x12\x03\x1b\x0f\x18\n\x0c\n\x05\x04\x01\x02\r\x03\x12\
+    \x03\x1b\x1b\x1d\n\x0b\n\x04\x04\x01\x02\x0e\x12\x03\x1c\x08#\n\x0c\n\
+    \x05\x04\x01\x02\x0e\x05\x12\x03\x1c\x08\x0c\n\x0c\n\x05\x04\x01\x02\x0e\
+    \x01\x12\x03\x1c\r\x1d\n\x0c\n\x05\x04\x01\x02\x0e\x03\x12\x03\x1c\x20\"\
+    \n\x0b\n\x04\x04\x01\x02\x0f\x12\x03\x1d\x08#\n\x0c\n\x05\x04\x01\x02\
+    \x0f\x05\x12\x03\x1d\x08\x0e\n\x0c\n\x05\x04\x01\x02\x0f\x01\x12\x03\x1d\
+    \x0f\x1d\n\x0c\n\x05\x04\x01\x02\x0f\x03\x12\x03\x1d\x20\"\n\x0b\n\x04\
+    \x04\x01\x02\x10\x12\x03\x1e\x08\x20\n\x0c\n\x05\x04\x01\x02\x10\x05\x12\
+    \x03\x1e\x08\x0e\n\x0c\n\x05\x04\x01\x02\x10\x01\x12\x03\x1e\x0f\x1a\n\
+    \x0c\n\x05\x04\x01\x02\x10\x03\x12\x03\x1e\x1d\x1f\n\x0b\n\x04\x04\x01\
+    \x02\x11\x12\x03\x1f\x08\x17\n\x0c\n\x05\x04\x01\x02\x11\x05\x12\x03\x1f\
+    \x08\x0c\n\x0c\n\x05\x04\x01\x02\x11\x01\x12\x03\x1f\r\x11\n\x0c\n\x05\
+    \x04\x01\x02\x11\x03\x12\x03\x1f\x14\x16\n\x0b\n\x04\x04\x01\x02\x12\x12\
+    \x03\x20\x08\x1a\n\x0c\n\x05\x04\x01\x02\x12\x05\x12\x03\x20\x08\x0e\n\
+    \x0c\n\x05\x04\x01\x02\x12\x01\x12\x03\x20\x0f\x14\n\x0c\n\x05\x04\x01\
+    \x02\x12\x03\x12\x03\x20\x17\x19\n\x0b\n\x04\x04\x01\x02\x13\x12\x03!\
+    \x08\x1d\n\x0c\n\x05\x04\x01\x02\x13\x05\x12\x03!\x08\x0e\n\x0c\n\x05\
+    \x04\x01\x02\x13\x01\x12\x03!\x0f\x17\n\x0c\n\x05\x04\x01\x02\x13\x03\
+    \x12\x03!\x1a\x1cb\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -0,0 +1,366 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Every message the services exchange over MQ, in one table.
+//!
+//! A route is a routing key and the envelope (`cmd_id` and `MsgType`) of what is
+//! published under it. The payload type decides the `MsgType`, so a route can only
+//! publish and parse its own payload. Several routes may share a key, and routes
+//! whose key is `net.*` are what network takes from peers and republishes locally.
+//! Routes are grouped by the module publishing them, which is what `key_to_id` tells,
+//! and `topic_to_string` names the topics of the table.
+
+pub use pubsub_topic::matches;
+use super::*;
+use std::sync::mpsc::Sender;
+
+/// Content of a `communication::Message`
+pub trait Payload: Sized {
+    fn msg_type() -> MsgType;
+    fn to_content(&self) -> Vec<u8>;
+    fn from_class(class: MsgClass) -> Option<Self>;
+}
+
+macro_rules! payloads {
+    ($($payload:ty => $msg_type:ident, $class:ident;)*) => {
+        $(
+            impl Payload for $payload {
+                fn msg_type() -> MsgType {
+                    MsgType::$msg_type
+                }
+
+                fn to_content(&self) -> Vec<u8> {
+                    self.write_to_bytes().unwrap()
+                }
+
+                fn from_class(class: MsgClass) -> Option<Self> {
+                    match class {
+                        MsgClass::$class(payload) => Some(payload),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    }
+}
+
+payloads! {
+    Request => REQUEST, REQUEST;
+    Response => RESPONSE, RESPONSE;
+    Block => BLOCK, BLOCK;
+    Status => STATUS, STATUS;
+    RichStatus => RICH_STATUS, RICHSTATUS;
+    VerifyBlockReq => VERIFY_BLK_REQ, VERIFYBLKREQ;
+    VerifyBlockResp => VERIFY_BLK_RESP, VERIFYBLKRESP;
+    BlockTxHashes => BLOCK_TXHASHES, BLOCKTXHASHES;
+    BlockTxHashesReq => BLOCK_TXHASHES_REQ, BLOCKTXHASHESREQ;
+    BlockWithProof => BLOCK_WITH_PROOF, BLOCKWITHPROOF;
+    BlockTxs => BLOCK_TXS, BLOCKTXS;
+}
+
+/// Bytes only the receiving module understands
+impl Payload for Vec<u8> {
+    fn msg_type() -> MsgType {
+        MsgType::MSG
+    }
+
+    fn to_content(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn from_class(class: MsgClass) -> Option<Self> {
+        match class {
+            MsgClass::MSG(content) => Some(content),
+            _ => None,
+        }
+    }
+}
+
+impl Payload for Verdict {
+    fn msg_type() -> MsgType {
+        MsgType::MSG
+    }
+
+    fn to_content(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    fn from_class(class: MsgClass) -> Option<Self> {
+        match class {
+            MsgClass::MSG(content) => serde_json::from_slice(&content).ok(),
+            _ => None,
+        }
+    }
+}
+
+pub trait Route {
+    type Payload: Payload;
+
+    fn key() -> &'static str;
+    fn submodule() -> u32;
+    fn topic() -> u16;
+
+    fn cmd_id() -> u32 {
+        cmd_id(Self::submodule(), Self::topic())
+    }
+
+    /// Whether `msg` has this route's envelope
+    fn accepts(msg: &communication::Message) -> bool {
+        msg.get_cmd_id() == Self::cmd_id() && msg.get_field_type() == Self::Payload::msg_type()
+    }
+
+    fn msg(payload: &Self::Payload) -> communication::Message {
+        factory::create_msg(Self::submodule(), Self::topic(), Self::Payload::msg_type(), payload.to_content())
+    }
+
+    /// See `factory::create_msg_to`
+    fn msg_to(to: u32, request_id: u64, payload: &Self::Payload) -> communication::Message {
+        factory::create_msg_to(Self::submodule(), Self::topic(), Self::Payload::msg_type(), to, request_id, payload.to_content())
+    }
+
+    fn publish(tx: &Sender<(String, Vec<u8>)>, payload: &Self::Payload) {
+        Self::send(tx, Self::msg(payload));
+    }
+
+    /// Publish a message built by `msg` or `msg_to`
+    fn send(tx: &Sender<(String, Vec<u8>)>, msg: communication::Message) {
+        assert!(Self::accepts(&msg), "{} does not route {:?}", Self::key(), display_cmd(msg.get_cmd_id()));
+        tx.send((Self::key().to_string(), msg.write_to_bytes().unwrap())).unwrap();
+    }
+
+    /// The payload, when `msg` decodes and has this route's envelope
+    fn parse(msg: &[u8]) -> Option<Self::Payload> {
+        match parse_from_bytes::<communication::Message>(msg) {
+            Ok(ref envelope) if Self::accepts(envelope) => {}
+            _ => return None,
+        }
+        try_parse_msg(msg).ok().and_then(|(_, _, class)| Self::Payload::from_class(class))
+    }
+}
+
+/// One row of `ROUTES`
+pub struct RouteInfo {
+    pub name: &'static str,
+    pub key: &'static str,
+    /// Module publishing on the key
+    pub publisher: u32,
+    pub submodule: u32,
+    pub topic: u16,
+    pub topic_name: &'static str,
+    pub msg_type: fn() -> MsgType,
+}
+
+impl RouteInfo {
+    pub fn cmd_id(&self) -> u32 {
+        cmd_id(self.submodule, self.topic)
+    }
+
+    pub fn accepts(&self, msg: &communication::Message) -> bool {
+        msg.get_cmd_id() == self.cmd_id() && msg.get_field_type() == (self.msg_type)()
+    }
+}
+
+macro_rules! routes {
+    ($($publisher:ident {
+        $($(#[$doc:meta])* $name:ident: $key:expr => $sub:ident, $topic:ident, $payload:ty;)*
+    })*) => {
+        $($(
+            $(#[$doc])*
+            pub struct $name;
+
+            impl Route for $name {
+                type Payload = $payload;
+
+                fn key() -> &'static str {
+                    $key
+                }
+
+                fn submodule() -> u32 {
+                    submodules::$sub
+                }
+
+                fn topic() -> u16 {
+                    topics::$topic
+                }
+            }
+        )*)*
+
+        pub static ROUTES: &'static [RouteInfo] = &[
+            $($(
+                RouteInfo {
+                    name: stringify!($name),
+                    key: $key,
+                    publisher: submodules::$publisher,
+                    submodule: submodules::$sub,
+                    topic: topics::$topic,
+                    topic_name: stringify!($topic),
+                    msg_type: <$payload as Payload>::msg_type,
+                },
+            )*)*
+        ];
+    }
+}
+
+routes! {
+    JSON_RPC {
+        /// A transaction sent to the node's jsonrpc
+        JsonrpcNewTx: "jsonrpc.new_tx" => JSON_RPC, REQUEST, Request;
+        /// Transactions jsonrpc buffered for auth
+        JsonrpcNewTxBatch: "jsonrpc.new_tx_batch" => JSON_RPC, REQUEST, Request;
+        /// A query for chain
+        JsonrpcRequest: "jsonrpc.request" => JSON_RPC, REQUEST, Request;
+        /// A query for network
+        JsonrpcNet: "jsonrpc.net" => JSON_RPC, REQUEST, Request;
+    }
+
+    AUTH {
+        /// A transaction auth accepted, for the other nodes
+        AuthTx: "auth.tx" => AUTH, REQUEST, Request;
+        AuthRpc: "auth.rpc" => AUTH, RESPONSE, Response;
+        /// Transactions of the next proposal
+        AuthBlockTxs: "auth.block_txs" => AUTH, BLOCK_TXS, BlockTxs;
+        AuthBlockTxHashesReq: "auth.blk_tx_hashs_req" => AUTH, BLOCK_TXHASHES_REQ, BlockTxHashesReq;
+        /// Result of verifying the transactions of a proposal
+        AuthVerifyBlockResp: "verify_blk_consensus" => AUTH, VERIFY_BLK_RESP, VerifyBlockResp;
+        AuthVerdict: "auth.verdict" => AUTH, VERDICT, Verdict;
+    }
+
+    CHAIN {
+        ChainStatus: "chain.status" => CHAIN, NEW_STATUS, Status;
+        ChainRichStatus: "chain.richstatus" => CHAIN, RICH_STATUS, RichStatus;
+        /// Blocks a peer asked for, with `msg_to`
+        ChainBlock: "chain.blk" => CHAIN, NEW_BLK, Block;
+        /// A block range to sync from one peer, with `msg_to`
+        ChainSync: "chain.sync" => CHAIN, SYNC_BLK, Vec<u8>;
+        ChainTxHashes: "chain.txhashes" => CHAIN, BLOCK_TXHASHES, BlockTxHashes;
+        /// Responses for jsonrpc, network answers on this key too
+        ChainRpc: "chain.rpc" => CHAIN, RESPONSE, Response;
+        ChainVerdict: "chain.verdict" => CHAIN, VERDICT, Verdict;
+        /// Evidence a committed block carried, `proof::Evidence::to_bytes`
+        ChainEvidence: "chain.evidence" => CHAIN, EVIDENCE, Vec<u8>;
+    }
+
+    CONSENSUS {
+        ConsensusMsg: "consensus.msg" => CONSENSUS, CONSENSUS_MSG, Vec<u8>;
+        ConsensusProposal: "consensus.msg" => CONSENSUS, NEW_PROPOSAL, Vec<u8>;
+        ConsensusBlock: "consensus.blk" => CONSENSUS, NEW_BLK, Block;
+        ConsensusProofBlock: "consensus.blk" => CONSENSUS, NEW_PROOF_BLOCK, BlockWithProof;
+        ConsensusVerifyReq: "consensus.verify_req" => CONSENSUS, VERIFY_BLK_REQ, VerifyBlockReq;
+        ConsensusTx: "consensus.tx" => CONSENSUS, REQUEST, Request;
+        ConsensusRpc: "consensus.rpc" => CONSENSUS, RESPONSE, Response;
+        ConsensusDefault: "consensus.default" => CONSENSUS, DEFAULT, Vec<u8>;
+        ConsensusVerdict: "consensus.verdict" => CONSENSUS, VERDICT, Verdict;
+        /// A validator signed two conflicting votes or proposals, `proof::Evidence::to_bytes`
+        ConsensusEvidence: "consensus.evidence" => CONSENSUS, EVIDENCE, Vec<u8>;
+    }
+
+    CONSENSUS_CMD {
+        /// Commands of the raft consensus
+        ConsensusCmd: "consensus_cmd.default" => CONSENSUS_CMD, DEFAULT, Vec<u8>;
+    }
+
+    NET {
+        /// Published by network for what peers sent
+        NetTx: "net.tx" => AUTH, REQUEST, Request;
+        NetStatus: "net.status" => CHAIN, NEW_STATUS, Status;
+        NetBlock: "net.blk" => CHAIN, NEW_BLK, Block;
+        NetSync: "net.sync" => CHAIN, SYNC_BLK, Vec<u8>;
+        NetMsg: "net.msg" => CONSENSUS, CONSENSUS_MSG, Vec<u8>;
+        NetProposal: "net.msg" => CONSENSUS, NEW_PROPOSAL, Vec<u8>;
+        NetDiscovery: "net.discovery" => NET, DISCOVERY, Vec<u8>;
+    }
+}
+
+/// The `net.*` route a message from a peer is republished on, None for what
+/// does not cross the network
+pub fn net_route(msg: &communication::Message) -> Option<&'static RouteInfo> {
+    ROUTES.iter().find(|route| route.key.starts_with("net.") && route.accepts(msg))
+}
+
+/// Check the keys a service subscribes, each must match a route. Every service
+/// passes its keys through this on start, so a typo stops it at once.
+pub fn subscription(keys: Vec<&'static str>) -> Vec<&'static str> {
+    for key in &keys {
+        if !ROUTES.iter().any(|route| matches(key, route.key)) {
+            panic!("no route for subscription {}", key);
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn routes_are_unambiguous() {
+        let mut envelopes = HashMap::new();
+        for route in ROUTES {
+            let envelope = (route.key, route.cmd_id(), (route.msg_type)());
+            if let Some(other) = envelopes.insert(envelope, route.name) {
+                panic!("{} and {} route the same", other, route.name);
+            }
+        }
+        // a key is told apart by its publisher
+        for route in ROUTES {
+            assert_eq!(key_to_id(route.key), route.publisher, "{}", route.name);
+        }
+    }
+
+    #[test]
+    fn names_from_routes() {
+        assert_eq!(key_to_id(NetTx::key()), submodules::NET);
+        assert_eq!(key_to_id(ConsensusCmd::key()), submodules::CONSENSUS_CMD);
+        assert_eq!(key_to_id("chain.unknown"), 0);
+        assert_eq!(topic_to_string(topics::NEW_PROOF_BLOCK), "new_proof_block");
+        assert_eq!(topic_to_string(topics::VERIFY_TX_REQ), "");
+        assert_eq!(display_cmd(ChainEvidence::cmd_id()), ("chain", "evidence"));
+    }
+
+    #[test]
+    fn typed_roundtrip() {
+        let (tx, rx) = channel();
+        let mut status = Status::new();
+        status.set_height(7);
+        ChainStatus::publish(&tx, &status);
+
+        let (key, msg) = rx.recv().unwrap();
+        assert_eq!(key, "chain.status");
+        assert_eq!(ChainStatus::parse(&msg).unwrap().get_height(), 7);
+        assert!(ChainRichStatus::parse(&msg).is_none());
+        // network republishes it for the other nodes
+        let envelope = parse_from_bytes::<communication::Message>(&msg).unwrap();
+        assert_eq!(net_route(&envelope).unwrap().key, NetStatus::key());
+
+        let verdict = Verdict::new(3, Offence::Flood, "votes".to_string());
+        AuthVerdict::publish(&tx, &verdict);
+        let (key, msg) = rx.recv().unwrap();
+        assert_eq!(key, "auth.verdict");
+        assert_eq!(AuthVerdict::parse(&msg), Some(verdict));
+        assert!(ChainVerdict::parse(&msg).is_none());
+        let envelope = parse_from_bytes::<communication::Message>(&msg).unwrap();
+        assert!(net_route(&envelope).is_none());
+    }
+
+    #[test]
+    fn subscriptions() {
+        assert_eq!(subscription(vec!["net.*", "chain.richstatus"]), vec!["net.*", "chain.richstatus"]);
+        assert!(::std::panic::catch_unwind(|| subscription(vec!["chain.richstatu"])).is_err());
+    }
+}
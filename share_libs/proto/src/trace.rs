@@ -0,0 +1,245 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Follows a request through the services.
+//!
+//! A thread handling a traced message is in a span of that message's trace,
+//! `try_parse_msg` enters it and `factory::create_msg` stamps the trace and the
+//! span on whatever the thread sends, so the receivers' spans become its children.
+//! jsonrpc starts a trace per request, with `Request.request_id` as its id.
+//!
+//! Spans are logged, and written to the file `CITA_TRACE_FILE` names when it is
+//! set, in the Chrome trace event format (chrome://tracing, Perfetto).
+
+use communication::Message;
+use rustc_serialize::hex::ToHex;
+use std::cell::RefCell;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::time::{SystemTime, UNIX_EPOCH};
+use util::{H64, Hashable};
+
+pub const CITA_TRACE_FILE: &'static str = "CITA_TRACE_FILE";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub trace_id: Vec<u8>,
+    pub span_id: u64,
+    /// 0 for the root of a trace
+    pub parent_id: u64,
+    pub name: String,
+    /// microseconds since the unix epoch
+    pub start: u64,
+}
+
+impl Span {
+    /// One complete ("X") event of the Chrome trace event format
+    pub fn to_event(&self, end: u64, pid: u64, tid: usize) -> String {
+        format!("{{\"name\":\"{}\",\"cat\":\"cita\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{},\"tid\":{},\
+                 \"args\":{{\"trace_id\":\"{}\",\"span_id\":\"{:016x}\",\"parent_id\":\"{:016x}\"}}}}",
+                self.name,
+                self.start,
+                end.saturating_sub(self.start),
+                pid,
+                tid,
+                self.trace_id.to_hex(),
+                self.span_id,
+                self.parent_id)
+    }
+}
+
+static NEXT_TID: AtomicUsize = ATOMIC_USIZE_INIT;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Span>> = RefCell::new(None);
+    static TID: usize = NEXT_TID.fetch_add(1, Ordering::Relaxed);
+}
+
+struct Exporter {
+    file: File,
+    pid: u64,
+}
+
+lazy_static! {
+    static ref EXPORTER: Mutex<Option<Exporter>> = Mutex::new(open_exporter());
+}
+
+fn open_exporter() -> Option<Exporter> {
+    let path = match env::var(CITA_TRACE_FILE) {
+        Ok(path) => path,
+        Err(_) => return None,
+    };
+    let mut file = match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => file,
+        Err(err) => {
+            warn!("can't open trace file {}: {}", path, err);
+            return None;
+        }
+    };
+    // the format allows leaving the array unclosed, so services can share a file
+    if file.metadata().map(|meta| meta.len() == 0).unwrap_or(false) {
+        let _ = file.write_all(b"[\n");
+    }
+    let name = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    // spans of one service share a row
+    let pid = H64::from(name.as_bytes().crypt_hash()).low_u64() >> 12;
+    let _ = writeln!(file, "{{\"name\":\"process_name\",\"ph\":\"M\",\"pid\":{},\"args\":{{\"name\":\"{}\"}}}},", pid, name);
+    Some(Exporter { file: file, pid: pid })
+}
+
+fn now() -> u64 {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    elapsed.as_secs() * 1_000_000 + (elapsed.subsec_nanos() / 1_000) as u64
+}
+
+fn new_span_id() -> u64 {
+    loop {
+        let id = H64::random().low_u64();
+        if id != 0 {
+            return id;
+        }
+    }
+}
+
+/// The span this thread is in
+pub fn current() -> Option<Span> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Begin a new trace, ending the span this thread was in
+pub fn start(trace_id: Vec<u8>, name: &str) {
+    begin(trace_id, 0, name);
+}
+
+/// Begin the span which handles `msg`, a child of its sender's. Untraced
+/// messages end the span this thread was in.
+pub fn enter(msg: &Message, name: &str) {
+    if msg.get_trace_id().is_empty() {
+        finish();
+        return;
+    }
+    let same = CURRENT.with(|current| match *current.borrow() {
+        Some(ref span) => span.trace_id == msg.get_trace_id() && span.parent_id == msg.get_span_id(),
+        None => false,
+    });
+    // parsing the same message twice does not make another span
+    if !same {
+        begin(msg.get_trace_id().to_vec(), msg.get_span_id(), name);
+    }
+}
+
+fn begin(trace_id: Vec<u8>, parent_id: u64, name: &str) {
+    finish();
+    let span = Span {
+        trace_id: trace_id,
+        span_id: new_span_id(),
+        parent_id: parent_id,
+        name: name.to_string(),
+        start: now(),
+    };
+    debug!("trace {} span {:016x} of {:016x} begins {}", span.trace_id.to_hex(), span.span_id, span.parent_id, span.name);
+    CURRENT.with(|current| *current.borrow_mut() = Some(span));
+}
+
+/// End the span this thread is in, if any
+pub fn finish() {
+    if let Some(span) = CURRENT.with(|current| current.borrow_mut().take()) {
+        let end = now();
+        debug!("trace {} span {:016x} ends {} after {} us", span.trace_id.to_hex(), span.span_id, span.name, end.saturating_sub(span.start));
+        if let Some(ref mut exporter) = *EXPORTER.lock().unwrap() {
+            let event = span.to_event(end, exporter.pid, TID.with(|tid| *tid));
+            if let Err(err) = writeln!(exporter.file, "{},", event) {
+                warn!("can't write trace file: {}", err);
+            }
+        }
+    }
+}
+
+/// Stamp `msg` with the trace and span this thread is in
+pub fn inject(msg: &mut Message) {
+    CURRENT.with(|current| if let Some(ref span) = *current.borrow() {
+        msg.set_trace_id(span.trace_id.clone());
+        msg.set_span_id(span.span_id);
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn propagate() {
+        let mut msg = Message::new();
+        inject(&mut msg);
+        assert!(msg.get_trace_id().is_empty());
+
+        start(vec![1, 2], "jsonrpc.request");
+        let root = current().unwrap();
+        assert_eq!(root.parent_id, 0);
+        inject(&mut msg);
+        assert_eq!(msg.get_trace_id(), &[1, 2]);
+        assert_eq!(msg.get_span_id(), root.span_id);
+
+        // the receiver
+        finish();
+        enter(&msg, "chain.request");
+        let child = current().unwrap();
+        assert_eq!(child.trace_id, vec![1, 2]);
+        assert_eq!(child.parent_id, root.span_id);
+        assert!(child.span_id != root.span_id);
+        enter(&msg, "chain.request");
+        assert_eq!(current().unwrap(), child);
+
+        enter(&Message::new(), "chain.status");
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn request_starts_trace() {
+        let mut req = ::Request::new();
+        req.set_request_id(vec![9, 9]);
+        let msg: Message = req.clone().into();
+        assert_eq!(msg.get_trace_id(), &[9, 9]);
+        assert_eq!(msg.get_span_id(), 0);
+
+        // sent while handling another trace
+        start(vec![1], "chain.request");
+        let msg: Message = req.into();
+        assert_eq!(msg.get_trace_id(), &[1]);
+        finish();
+    }
+
+    #[test]
+    fn chrome_event() {
+        let span = Span {
+            trace_id: vec![0xab, 0xcd],
+            span_id: 1,
+            parent_id: 0,
+            name: "auth.request".to_string(),
+            start: 10,
+        };
+        assert_eq!(span.to_event(25, 7, 3),
+                   "{\"name\":\"auth.request\",\"cat\":\"cita\",\"ph\":\"X\",\"ts\":10,\"dur\":15,\"pid\":7,\"tid\":3,\
+                    \"args\":{\"trace_id\":\"abcd\",\"span_id\":\"0000000000000001\",\"parent_id\":\"0000000000000000\"}}");
+    }
+}
@@ -20,38 +20,128 @@ extern crate pubsub_kafka;
 extern crate pubsub_rabbitmq;
 #[cfg(feature = "zeromq")]
 extern crate pubsub_zeromq;
+#[cfg(feature = "inprocess")]
+extern crate pubsub_inprocess;
 extern crate dotenv;
 use dotenv::dotenv;
 
 
 
-#[cfg(feature = "kafka")]
+#[cfg(all(feature = "kafka", not(feature = "inprocess")))]
 use pubsub_kafka::start_kafka;
 
-#[cfg(feature = "rabbitmq")]
+#[cfg(all(feature = "rabbitmq", not(feature = "inprocess")))]
 use pubsub_rabbitmq::start_rabbitmq;
 
-#[cfg(feature = "zeromq")]
+#[cfg(all(feature = "zeromq", not(feature = "inprocess")))]
 use pubsub_zeromq::start_zeromq;
-use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
 
-#[cfg(feature = "rabbitmq")]
-pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+#[cfg(feature = "inprocess")]
+use pubsub_inprocess::start_inprocess;
+#[cfg(feature = "inprocess")]
+pub use pubsub_inprocess::wait_bound;
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, RecvError, SyncSender, sync_channel};
+
+/// Capacity of the channel a service receives its subscriptions from. With
+/// rabbitmq as many messages stay unacked, it must be below 65525 there.
+pub const PUBSUB_CAPACITY: &'static str = "PUBSUB_CAPACITY";
+const DEFAULT_CAPACITY: usize = 10240;
+
+pub fn capacity() -> usize {
     dotenv().ok();
-    start_rabbitmq(name, keys, tx, rx);
+    std::env::var(PUBSUB_CAPACITY).ok().and_then(|capacity| capacity.parse().ok()).unwrap_or(DEFAULT_CAPACITY)
 }
 
-#[cfg(feature = "zeromq")]
-pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+/// Sending half of `sub_channel`, for `start_pubsub`
+pub struct SubSender {
+    pub tx: SyncSender<(String, Vec<u8>)>,
+    /// number of messages the service is done with, so a backend can ack them
+    pub done: Arc<AtomicUsize>,
+}
+
+/// Receiving half of `sub_channel`.
+///
+/// A service handling each message before taking the next one uses `recv`.
+/// A service handing them on to another thread uses `take` instead, and calls
+/// `Done::handled` once per message, in order, when it is through with it.
+pub struct Subscription {
+    rx: Receiver<(String, Vec<u8>)>,
+    taken: Cell<usize>,
+    done: Arc<AtomicUsize>,
+}
+
+impl Subscription {
+    /// Take the next message, the service is done with the ones taken before
+    pub fn recv(&self) -> Result<(String, Vec<u8>), RecvError> {
+        self.done.store(self.taken.get(), Ordering::Release);
+        let msg = self.rx.recv()?;
+        self.taken.set(self.taken.get() + 1);
+        Ok(msg)
+    }
+
+    /// Take the next message, leaving it to a `Done` to tell when it is handled
+    pub fn take(&self) -> Result<(String, Vec<u8>), RecvError> {
+        self.rx.recv()
+    }
+
+    pub fn done(&self) -> Done {
+        Done(self.done.clone())
+    }
+}
+
+/// Tells the backend a message from `Subscription::take` is handled
+#[derive(Clone)]
+pub struct Done(Arc<AtomicUsize>);
+
+impl Done {
+    pub fn handled(&self) {
+        self.0.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// Bounded channel to pass to `start_pubsub` as `tx`, so a slow service
+/// pushes back on the backend instead of queueing without limit
+pub fn sub_channel() -> (SubSender, Subscription) {
+    let (tx, rx) = sync_channel(capacity());
+    let done = Arc::new(AtomicUsize::new(0));
+    (
+        SubSender {
+            tx: tx,
+            done: done.clone(),
+        },
+        Subscription {
+            rx: rx,
+            taken: Cell::new(0),
+            done: done,
+        },
+    )
+}
+
+#[cfg(all(feature = "rabbitmq", not(feature = "inprocess")))]
+pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: SubSender, rx: Receiver<(String, Vec<u8>)>) {
     dotenv().ok();
-    start_zeromq(name, keys, tx, rx);
+    start_rabbitmq(name, keys, capacity(), tx.tx, tx.done, rx);
 }
-#[cfg(feature = "kafka")]
-pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+
+#[cfg(all(feature = "zeromq", not(feature = "inprocess")))]
+pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: SubSender, rx: Receiver<(String, Vec<u8>)>) {
+    dotenv().ok();
+    start_zeromq(name, keys, tx.tx, rx);
+}
+#[cfg(all(feature = "kafka", not(feature = "inprocess")))]
+pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: SubSender, rx: Receiver<(String, Vec<u8>)>) {
     dotenv().ok();
     let keys = keys.iter().map(|elem| elem.to_string()).collect::<Vec<_>>();
-    start_kafka(name, keys, tx, rx);
+    start_kafka(name, keys, tx.tx, rx);
+}
+
+#[cfg(feature = "inprocess")]
+pub fn start_pubsub(name: &str, keys: Vec<&str>, tx: SubSender, rx: Receiver<(String, Vec<u8>)>) {
+    dotenv().ok();
+    start_inprocess(name, keys, tx.tx, rx);
 }
 
 #[cfg(test)]
@@ -62,11 +152,11 @@ mod test {
     #[test]
     fn basics() {
 
-        let (ntx_sub, nrx_sub) = channel();
+        let (ntx_sub, nrx_sub) = sub_channel();
         let (ntx_pub, nrx_pub) = channel();
         start_pubsub("network", vec!["chain.newtx", "chain.newblk"], ntx_sub, nrx_pub);
 
-        let (ctx_sub, crx_sub) = channel();
+        let (ctx_sub, crx_sub) = sub_channel();
         let (ctx_pub, crx_pub) = channel();
         start_pubsub("chain", vec!["network.newtx", "network.newblk"], ctx_sub, crx_pub);
 
@@ -97,4 +187,25 @@ mod test {
 
 
     }
+
+    #[test]
+    fn count_what_the_service_is_done_with() {
+        let (tx, rx) = sub_channel();
+        for i in 0..3 {
+            tx.tx.send(("key".to_string(), vec![i])).unwrap();
+        }
+        rx.recv().unwrap();
+        assert_eq!(tx.done.load(Ordering::Acquire), 0);
+        rx.recv().unwrap();
+        assert_eq!(tx.done.load(Ordering::Acquire), 1);
+
+
+        let (tx, rx) = sub_channel();
+        tx.tx.send(("key".to_string(), vec![0])).unwrap();
+        let done = rx.done();
+        rx.take().unwrap();
+        assert_eq!(tx.done.load(Ordering::Acquire), 0);
+        done.handled();
+        assert_eq!(tx.done.load(Ordering::Acquire), 1);
+    }
 }
@@ -0,0 +1,170 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A broker in memory, for services started as threads of one process.
+//! It routes like the RabbitMQ topic exchange: each name is a queue bound to
+//! its keys, where `*` stands for one word and `#` for any number of them.
+//! Every queue bound to a routing key gets a copy of the msg, consumers of the
+//! same queue take turns. A msg no queue is bound to yet is dropped.
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate log;
+extern crate pubsub_topic;
+pub use pubsub_topic::matches;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{ATOMIC_USIZE_INIT, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Delivery = (String, Vec<u8>);
+type Consumer = (usize, SyncSender<Delivery>);
+
+static NEXT_CONSUMER: AtomicUsize = ATOMIC_USIZE_INIT;
+
+#[derive(Default)]
+struct Queue {
+    keys: Vec<String>,
+    consumers: Vec<Consumer>,
+    next: usize,
+}
+
+impl Queue {
+    /// The consumers to try for a msg, starting from the one whose turn it is
+    fn turn(&mut self) -> Vec<Consumer> {
+        let start = if self.consumers.is_empty() { 0 } else { self.next % self.consumers.len() };
+        self.next = start + 1;
+        self.consumers[start..].iter().chain(self.consumers[..start].iter()).cloned().collect()
+    }
+}
+
+lazy_static! {
+    static ref QUEUES: Mutex<HashMap<String, Queue>> = Mutex::new(HashMap::new());
+}
+
+pub fn publish(routing_key: &str, msg: Vec<u8>) {
+    // a send blocks while the consumer is full, so take the consumers out of the lock first
+    let turns: Vec<(String, Vec<Consumer>)> = {
+        let mut queues = QUEUES.lock().unwrap();
+        let turns = queues
+            .iter_mut()
+            .filter(|&(_, ref queue)| queue.keys.iter().any(|key| matches(key, routing_key)))
+            .map(|(name, queue)| (name.clone(), queue.turn()))
+            .collect();
+        turns
+    };
+    if turns.is_empty() {
+        trace!("no queue is bound to {}", routing_key);
+    }
+    for (name, consumers) in turns {
+        let mut gone = Vec::new();
+        for (id, consumer) in consumers {
+            if consumer.send((routing_key.to_string(), msg.clone())).is_ok() {
+                break;
+            }
+            gone.push(id);
+        }
+        if !gone.is_empty() {
+            if let Some(queue) = QUEUES.lock().unwrap().get_mut(&name) {
+                queue.consumers.retain(|&(id, _)| !gone.contains(&id));
+            }
+        }
+    }
+}
+
+/// Whether a consumer of queue `name` started
+pub fn is_bound(name: &str) -> bool {
+    QUEUES.lock().unwrap().get(name).map_or(false, |queue| !queue.consumers.is_empty())
+}
+
+/// Wait until all `names` are bound, returns false on timeout
+pub fn wait_bound(names: &[&str], timeout: Duration) -> bool {
+    let start = Instant::now();
+    while !names.iter().all(|name| is_bound(name)) {
+        if start.elapsed() > timeout {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    true
+}
+
+pub fn start_inprocess(name: &str, keys: Vec<&str>, tx: SyncSender<Delivery>, rx: Receiver<Delivery>) {
+    {
+        let mut queues = QUEUES.lock().unwrap();
+        let queue = queues.entry(name.to_string()).or_insert_with(Queue::default);
+        for key in keys {
+            if !queue.keys.iter().any(|bound| bound == key) {
+                queue.keys.push(key.to_string());
+            }
+        }
+        queue.consumers.push((NEXT_CONSUMER.fetch_add(1, Ordering::SeqCst), tx));
+    }
+
+    let _ = thread::Builder::new().name("publisher".to_string()).spawn(move || loop {
+        match rx.recv() {
+            Ok((routing_key, msg)) => publish(&routing_key, msg),
+            Err(_) => break,
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc::{channel, sync_channel};
+
+    #[test]
+    fn copies_and_turns() {
+        let (tx1, rx1) = sync_channel(16);
+        let (_, rx) = channel();
+        start_inprocess("test_copies_a", vec!["test_copies.*"], tx1, rx);
+        let (tx2, rx2) = sync_channel(16);
+        let (_, rx) = channel();
+        start_inprocess("test_copies_b", vec!["test_copies.blk"], tx2, rx);
+        // a second consumer of queue a
+        let (tx3, rx3) = sync_channel(16);
+        let (pub_tx, pub_rx) = channel();
+        start_inprocess("test_copies_a", vec![], tx3, pub_rx);
+        assert!(wait_bound(&["test_copies_a", "test_copies_b"], Duration::from_secs(1)));
+
+        pub_tx.send(("test_copies.blk".to_string(), vec![1])).unwrap();
+        pub_tx.send(("test_copies.tx".to_string(), vec![2])).unwrap();
+        assert_eq!(rx2.recv().unwrap(), ("test_copies.blk".to_string(), vec![1]));
+        assert_eq!(rx1.recv().unwrap(), ("test_copies.blk".to_string(), vec![1]));
+        assert_eq!(rx3.recv().unwrap(), ("test_copies.tx".to_string(), vec![2]));
+    }
+
+    #[test]
+    fn full_consumer_keeps_broker_free() {
+        let (tx, rx) = sync_channel(1);
+        let (pub_tx, pub_rx) = channel();
+        start_inprocess("test_full", vec!["test_full.*"], tx, pub_rx);
+        assert!(wait_bound(&["test_full"], Duration::from_secs(1)));
+
+        pub_tx.send(("test_full.a".to_string(), vec![1])).unwrap();
+        // blocks the publisher thread until the consumer makes room
+        pub_tx.send(("test_full.b".to_string(), vec![2])).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert!(is_bound("test_full"));
+        assert_eq!(rx.recv().unwrap(), ("test_full.a".to_string(), vec![1]));
+        assert_eq!(rx.recv().unwrap(), ("test_full.b".to_string(), vec![2]));
+    }
+}
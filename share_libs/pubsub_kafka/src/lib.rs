@@ -16,7 +16,7 @@ use rdkafka::error::KafkaResult;
 use rdkafka::producer::FutureProducer;
 use rdkafka::types;
 use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::SyncSender;
 use std::thread;
 
 // The Context can be used to change the behavior of producers and consumers by adding callbacks
@@ -43,7 +43,7 @@ impl ConsumerContext for ConsumerContextExample {
 
 pub const KAFKA_URL: &'static str = "KAFKA_URL";
 
-pub fn start_kafka(name: &str, keys: Vec<String>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+pub fn start_kafka(name: &str, keys: Vec<String>, tx: SyncSender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
     let brokers = std::env::var(KAFKA_URL).expect(format!("{} must be set", KAFKA_URL).as_str());
     let consumer_brokers = brokers.clone();
     let _ = thread::Builder::new().name("publisher".to_string()).spawn(move || {
@@ -14,86 +14,358 @@
 
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-
 extern crate amqp;
+#[macro_use]
+extern crate log;
 use amqp::{Basic, Session, Consumer, Channel, Table, protocol};
-use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender, TrySendError};
 use std::thread;
+use std::time::{Duration, Instant};
+
+pub const AMQP_URL: &'static str = "AMQP_URL";
+/// `block` (default) or `drop`, see `Overflow`
+pub const AMQP_OVERFLOW: &'static str = "AMQP_OVERFLOW";
+
+const EXCHANGE: &'static str = "cita";
+/// Deliveries in flight beyond the unacked ones the service channel holds
+const PREFETCH: u16 = 10;
+/// messages outlive a broker restart
+const PERSISTENT: u8 = 2;
+const RECONNECT_MIN: u64 = 100;
+const RECONNECT_MAX: u64 = 10_000;
+/// How long the queue stays quiet before the acks still owed are flushed
+const FLUSH_IDLE: u64 = 100;
+
+static DROPPED: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Number of messages dropped because the service channel was full
+pub fn dropped() -> usize {
+    DROPPED.load(Ordering::Relaxed)
+}
+
+/// What to do with a delivery when the service channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wait for the service. Unacked messages stay in the durable queue,
+    /// so the broker stops delivering once the prefetch limit is in flight.
+    Block,
+    /// Reject the message without requeue and count it in `dropped`.
+    Drop,
+}
+
+impl Overflow {
+    pub fn from_env() -> Self {
+        match std::env::var(AMQP_OVERFLOW) {
+            Ok(ref policy) if policy == "drop" => Overflow::Drop,
+            Ok(ref policy) if policy != "block" => {
+                warn!("unknown {} {}, use block", AMQP_OVERFLOW, policy);
+                Overflow::Block
+            }
+            _ => Overflow::Block,
+        }
+    }
+}
+
+/// Delivery tags of the messages put in the service channel, by the index
+/// the service counts them with. Message `i` is acked once the service is done
+/// with `i + 1` messages, so anything it never handled is redelivered.
+#[derive(Debug)]
+struct Unacked {
+    tags: VecDeque<(usize, u64)>,
+}
+
+impl Unacked {
+    fn new() -> Self {
+        Unacked { tags: VecDeque::new() }
+    }
+
+    fn push(&mut self, index: usize, tag: u64) {
+        self.tags.push_back((index, tag));
+    }
+
+    /// The tags of the messages below `done`
+    fn done(&mut self, done: usize) -> Vec<u64> {
+        let count = self.tags.iter().take_while(|&&(index, _)| index < done).count();
+        self.tags.drain(..count).map(|(_, tag)| tag).collect()
+    }
+}
+
+/// What the consumer and the publisher thread know of the service
+#[derive(Debug)]
+struct Progress {
+    /// messages the service is done with, from `pubsub::Subscription`
+    done: Arc<AtomicUsize>,
+    /// messages put in the service channel
+    sent: AtomicUsize,
+    /// index of the oldest message not acked yet
+    waiting: AtomicUsize,
+    deliveries: AtomicUsize,
+    /// a flush marker is in the queue
+    flushing: AtomicBool,
+}
+
+impl Progress {
+    fn new(done: Arc<AtomicUsize>) -> Self {
+        Progress {
+            done: done,
+            sent: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+            deliveries: AtomicUsize::new(0),
+            flushing: AtomicBool::new(false),
+        }
+    }
+
+    /// The service is done with messages not acked yet
+    fn behind(&self) -> bool {
+        self.done.load(Ordering::Acquire) > self.waiting.load(Ordering::Acquire)
+    }
+}
 
 pub struct Handler {
-    tx: Sender<(String, Vec<u8>)>,
+    tx: SyncSender<(String, Vec<u8>)>,
+    overflow: Overflow,
+    unacked: Unacked,
+    progress: Arc<Progress>,
 }
 
 impl Handler {
-    pub fn new(tx: Sender<(String, Vec<u8>)>) -> Self {
-        Handler { tx: tx }
+    fn new(tx: SyncSender<(String, Vec<u8>)>, overflow: Overflow, progress: Arc<Progress>) -> Self {
+        // a marker published on the old connection is gone with it
+        progress.flushing.store(false, Ordering::Release);
+        progress.waiting.store(progress.sent.load(Ordering::Acquire), Ordering::Release);
+        Handler {
+            tx: tx,
+            overflow: overflow,
+            unacked: Unacked::new(),
+            progress: progress,
+        }
+    }
+
+    fn ack_done(&mut self, channel: &mut Channel) {
+        for tag in self.unacked.done(self.progress.done.load(Ordering::Acquire)) {
+            let _ = channel.basic_ack(tag, false);
+        }
+        let waiting = self.unacked.tags.front().map_or(self.progress.sent.load(Ordering::Acquire), |&(index, _)| index);
+        self.progress.waiting.store(waiting, Ordering::Release);
     }
 }
 
 impl Consumer for Handler {
     fn handle_delivery(&mut self, channel: &mut Channel, deliver: protocol::basic::Deliver, _: protocol::basic::BasicProperties, body: Vec<u8>) {
-        let _ = self.tx.send((deliver.routing_key, body));
-        let _ = channel.basic_ack(deliver.delivery_tag, false);
+        let tag = deliver.delivery_tag;
+        self.progress.deliveries.fetch_add(1, Ordering::Relaxed);
+        // only the publisher thread sends to the default exchange, see `flush`
+        if deliver.exchange.is_empty() {
+            let _ = channel.basic_ack(tag, false);
+            self.progress.flushing.store(false, Ordering::Release);
+            self.ack_done(channel);
+            return;
+        }
+        let msg = (deliver.routing_key, body);
+        let accepted = match self.overflow {
+            Overflow::Block => self.tx.send(msg).is_ok(),
+            Overflow::Drop => match self.tx.try_send(msg) {
+                Ok(_) => true,
+                Err(TrySendError::Full((routing_key, _))) => {
+                    let dropped = DROPPED.fetch_add(1, Ordering::Relaxed) + 1;
+                    warn!("service is busy, drop {} ({} dropped)", routing_key, dropped);
+                    let _ = channel.basic_reject(tag, false);
+                    return;
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+        };
+        if accepted {
+            let index = self.progress.sent.fetch_add(1, Ordering::AcqRel);
+            self.unacked.push(index, tag);
+            self.ack_done(channel);
+        } else {
+            // the service is gone, leave what it didn't handle to the next consumer
+            for tag in self.unacked.tags.drain(..).map(|(_, tag)| tag).chain(Some(tag)) {
+                let _ = channel.basic_nack(tag, false, true);
+            }
+        }
     }
 }
 
-pub const AMQP_URL: &'static str = "AMQP_URL";
-
-pub fn start_rabbitmq(name: &str, keys: Vec<&str>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
-    let amqp_url = std::env::var(AMQP_URL).expect(format!("{} must be set", AMQP_URL).as_str());
-    let mut session = match Session::open_url(&amqp_url) {
-        Ok(session) => session,
-        Err(error) => panic!("failed to open url {} : {:?}", amqp_url, error),
-    };
+fn open(amqp_url: &str) -> Result<(Session, Channel), String> {
+    let mut session = Session::open_url(amqp_url).map_err(|err| format!("failed to open url {} : {:?}", amqp_url, err))?;
+    let mut channel = session.open_channel(1).map_err(|err| format!("can't open channel: {:?}", err))?;
+    //exchange: &str, exchange_type: &str, passive: bool, durable: bool, auto_delete: bool, internal: bool, nowait: bool, arguments: Table
+    channel.exchange_declare(EXCHANGE, "topic", false, true, false, false, false, Table::new())
+           .map_err(|err| format!("can't declare exchange: {:?}", err))?;
+    Ok((session, channel))
+}
 
-    let mut channel = session.open_channel(1).ok().expect("Can't open channel");
-    let _ = channel.basic_prefetch(10);
-    channel.exchange_declare("cita", "topic", false, true, false, false, false, Table::new()).unwrap();
+/// Call `f` until it succeeds, backing off between attempts
+fn retry<T, F>(what: &str, mut f: F) -> T
+where
+    F: FnMut() -> Result<T, String>,
+{
+    let mut delay = RECONNECT_MIN;
+    loop {
+        match f() {
+            Ok(t) => return t,
+            Err(err) => {
+                warn!("{} failed: {}, retry in {} ms", what, err, delay);
+                thread::sleep(Duration::from_millis(delay));
+                delay = std::cmp::min(delay * 2, RECONNECT_MAX);
+            }
+        }
+    }
+}
 
+fn subscribe(amqp_url: &str, name: &str, keys: &[String], capacity: usize, overflow: Overflow, tx: &SyncSender<(String, Vec<u8>)>, progress: &Arc<Progress>) -> Result<(Session, Channel), String> {
+    let (session, mut channel) = open(amqp_url)?;
+    // the channel's messages stay unacked, the broker must deliver past them
+    let _ = channel.basic_prefetch(capacity as u16 + 1 + PREFETCH);
     //queue: &str, passive: bool, durable: bool, exclusive: bool, auto_delete: bool, nowait: bool, arguments: Table
-    channel.queue_declare(name.clone(), false, true, false, false, false, Table::new()).unwrap();
-
+    channel.queue_declare(name, false, true, false, false, false, Table::new())
+           .map_err(|err| format!("can't declare queue {}: {:?}", name, err))?;
     for key in keys {
-        channel.queue_bind(name.clone(), "cita", key, false, Table::new()).unwrap();
+        channel.queue_bind(name, EXCHANGE, key.as_str(), false, Table::new())
+               .map_err(|err| format!("can't bind {} to {}: {:?}", name, key, err))?;
     }
-    let callback = Handler::new(tx);
     //queue: &str, consumer_tag: &str, no_local: bool, no_ack: bool, exclusive: bool, nowait: bool, arguments: Table
-    channel.basic_consume(callback, name.clone(), "", false, false, false, false, Table::new()).unwrap();
+    channel.basic_consume(Handler::new(tx.clone(), overflow, progress.clone()), name, "", false, false, false, false, Table::new())
+           .map_err(|err| format!("can't consume {}: {:?}", name, err))?;
+    Ok((session, channel))
+}
 
-    // thread recv msg from mq
-    let _ = thread::Builder::new().name("subscriber".to_string()).spawn(move || {
-                                                                            channel.start_consuming();
-                                                                            let _ = channel.close(200, "Bye");
-                                                                        });
+/// Ask the consumer to ack what the service is done with, when the queue has
+/// been quiet for `FLUSH_IDLE` and no delivery would do it
+fn flush(channel: &mut Channel, name: &str, progress: &Progress) {
+    if !progress.behind() || progress.flushing.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    // the default exchange routes to the queue of the same name
+    if let Err(err) = channel.basic_publish("", name, false, false, protocol::basic::BasicProperties::default(), vec![]) {
+        warn!("failed to flush acks of {}: {:?}", name, err);
+        progress.flushing.store(false, Ordering::Release);
+    }
+}
 
+/// `capacity` is the one `tx` was created with, a message is acked once the
+/// service counts it in `done`, see `Unacked`
+pub fn start_rabbitmq(name: &str, keys: Vec<&str>, capacity: usize, tx: SyncSender<(String, Vec<u8>)>, done: Arc<AtomicUsize>, rx: Receiver<(String, Vec<u8>)>) {
+    assert!(capacity + 1 + (PREFETCH as usize) <= u16::max_value() as usize, "a capacity of {} leaves too many messages unacked", capacity);
+    let amqp_url = std::env::var(AMQP_URL).expect(format!("{} must be set", AMQP_URL).as_str());
+    let overflow = Overflow::from_env();
+    let name = name.to_string();
+    let keys: Vec<String> = keys.into_iter().map(|key| key.to_string()).collect();
+    let progress = Arc::new(Progress::new(done));
 
-    let mut session = match Session::open_url(&amqp_url) {
-        Ok(session) => session,
-        Err(error) => panic!("failed to open url {} : {:?}", amqp_url, error),
+    // the first connection fails fast, as before
+    let (session, channel) = match subscribe(&amqp_url, &name, &keys, capacity, overflow, &tx, &progress) {
+        Ok(conn) => conn,
+        Err(err) => panic!("{}", err),
     };
-    let mut channel = session.open_channel(1).ok().expect("Can't open channel");
-    let _ = channel.basic_prefetch(10);
-    channel.exchange_declare("cita", "topic", false, true, false, false, false, Table::new()).unwrap();
 
-    // thread send msg to mq
+    // thread recv msg from mq, reconnect when the broker connection drops
+    let url = amqp_url.clone();
+    let queue = name.clone();
+    let consumed = progress.clone();
+    let _ = thread::Builder::new().name("subscriber".to_string()).spawn(move || {
+        let mut conn = (session, channel);
+        loop {
+            conn.1.start_consuming();
+            warn!("lost connection of queue {}, reconnecting", name);
+            let _ = conn.1.close(200, "Bye");
+            let _ = conn.0.close(200, "Bye");
+            conn = retry("subscribe", || subscribe(&url, &name, &keys, capacity, overflow, &tx, &consumed));
+        }
+    });
+
+    let (session, channel) = match open(&amqp_url) {
+        Ok(conn) => conn,
+        Err(err) => panic!("{}", err),
+    };
+
+    // thread send msg to mq, a message is retried on a new connection until the broker takes it
     let _ = thread::Builder::new().name("publisher".to_string()).spawn(move || {
+        let mut conn = (session, channel);
+        let idle = Duration::from_millis(FLUSH_IDLE);
+        let mut checked = Instant::now();
+        let mut deliveries = 0;
         loop {
-            let ret = rx.recv();
-            if ret.is_err() {
-                break;
+            let ret = rx.recv_timeout(idle);
+            if checked.elapsed() >= idle {
+                let seen = progress.deliveries.load(Ordering::Relaxed);
+                if seen == deliveries {
+                    flush(&mut conn.1, &queue, &progress);
+                }
+                deliveries = seen;
+                checked = Instant::now();
+            }
+            let (routing_key, msg) = match ret {
+                Ok(msg) => msg,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            while let Err(err) = conn.1.basic_publish(EXCHANGE,
+                                                      &routing_key,
+                                                      false,
+                                                      false,
+                                                      protocol::basic::BasicProperties {
+                                                          content_type: Some("text".to_string()),
+                                                          delivery_mode: Some(PERSISTENT),
+                                                          ..Default::default()
+                                                      },
+                                                      msg.clone())
+            {
+                warn!("failed to publish {}: {:?}, reconnecting", routing_key, err);
+                conn = retry("publish", || open(&amqp_url));
             }
-            let (routing_key, msg) = ret.unwrap();
-            let _ = channel.basic_publish("cita",
-                                          &routing_key,
-                                          false,
-                                          false,
-                                          protocol::basic::BasicProperties {
-                                              content_type: Some("text".to_string()),
-                                              ..Default::default()
-                                          },
-                                          msg);
         }
-        let _ = channel.close(200, "Bye");
+        let _ = conn.1.close(200, "Bye");
+        let _ = conn.0.close(200, "Bye");
     });
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn ack_what_the_service_is_done_with() {
+        let mut unacked = Unacked::new();
+        unacked.push(0, 1);
+        unacked.push(1, 2);
+        unacked.push(2, 3);
+        assert!(unacked.done(0).is_empty());
+        assert_eq!(unacked.done(2), vec![1, 2]);
+        assert!(unacked.done(2).is_empty());
+        // the tags of a new channel go on from where the service counts
+        unacked.push(5, 1);
+        assert_eq!(unacked.done(6), vec![3, 1]);
+        assert!(unacked.tags.is_empty());
+    }
+
+    #[test]
+    fn behind_the_service() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let progress = Progress::new(done.clone());
+        assert!(!progress.behind());
+        done.store(2, Ordering::Release);
+        assert!(progress.behind());
+        progress.waiting.store(2, Ordering::Release);
+        assert!(!progress.behind());
+    }
+
+    #[test]
+    fn overflow_from_env() {
+        env::remove_var(AMQP_OVERFLOW);
+        assert_eq!(Overflow::from_env(), Overflow::Block);
+        env::set_var(AMQP_OVERFLOW, "drop");
+        assert_eq!(Overflow::from_env(), Overflow::Drop);
+        env::set_var(AMQP_OVERFLOW, "block");
+        assert_eq!(Overflow::from_env(), Overflow::Block);
+        env::set_var(AMQP_OVERFLOW, "whatever");
+        assert_eq!(Overflow::from_env(), Overflow::Block);
+        env::remove_var(AMQP_OVERFLOW);
+    }
+}
@@ -0,0 +1,55 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Routing keys as bound on the RabbitMQ topic exchange, which every pubsub
+//! backend and the route table of libproto follow: words are separated by `.`,
+//! in a binding `*` stands for one word and `#` for any number of them.
+
+/// Whether a binding `pattern` matches `routing_key`
+pub fn matches(pattern: &str, routing_key: &str) -> bool {
+    fn words_match(pattern: &[&str], key: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => key.is_empty(),
+            Some((&"#", rest)) => (0..key.len() + 1).any(|skip| words_match(rest, &key[skip..])),
+            Some((&"*", rest)) => !key.is_empty() && words_match(rest, &key[1..]),
+            Some((word, rest)) => key.first() == Some(word) && words_match(rest, &key[1..]),
+        }
+    }
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    let key: Vec<&str> = routing_key.split('.').collect();
+    words_match(&pattern, &key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::matches;
+
+    #[test]
+    fn topic_matching() {
+        assert!(matches("chain.status", "chain.status"));
+        assert!(!matches("chain.status", "chain.richstatus"));
+        assert!(!matches("net.msg", "net.msgs"));
+        assert!(matches("chain.*", "chain.status"));
+        assert!(!matches("chain.*", "chain"));
+        assert!(!matches("chain.*", "net.status"));
+        assert!(matches("*.verdict", "auth.verdict"));
+        assert!(matches("#", "verify_blk_consensus"));
+        assert!(matches("net.#", "net"));
+        assert!(matches("net.#.tx", "net.a.b.tx"));
+        assert!(!matches("net.#.tx", "net.a.b"));
+    }
+}
@@ -15,108 +15,165 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+//! Pubsub over ZeroMQ, without a broker. Every service binds a PUB socket at its
+//! own endpoint, and one SUB socket connects to the endpoints of all services.
+//! Keys are bound like on the RabbitMQ topic exchange, `*` stands for one word
+//! and `#` for any number of them.
+//!
+//! Endpoints default to ports 5563 and up on localhost. `ZMQ_ENDPOINTS` overrides
+//! them, or adds services, as comma separated `name=endpoint` pairs, e.g.
+//! `chain=ipc:///var/run/cita/chain.ipc,auth=tcp://10.0.0.2:5567`. An endpoint is
+//! bound by its service and connected to by the others, so all services of a node
+//! must share the same settings.
+
 extern crate zmq;
 #[macro_use]
 extern crate log;
+extern crate pubsub_topic;
+pub use pubsub_topic::matches;
+use std::collections::BTreeMap;
 use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::SyncSender;
 use std::thread;
-//use util::log::error;
-pub fn start_zeromq(name: &str, keys: Vec<&str>, tx: Sender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
-    let context = zmq::Context::new();
-    //pub
-    let publisher = context.socket(zmq::PUB).unwrap();
-    match name {
-        "network" => assert!(publisher.bind("tcp://*:5563").is_ok()),
-        "chain" => assert!(publisher.bind("tcp://*:5564").is_ok()),
-        "jsonrpc" => assert!(publisher.bind("tcp://*:5565").is_ok()),
-        "consensus" => assert!(publisher.bind("tcp://*:5566").is_ok()),
-        _ => error!("not hava {} module !", name),
+
+pub const ZMQ_ENDPOINTS: &'static str = "ZMQ_ENDPOINTS";
+
+const DEFAULT_ENDPOINTS: &'static [(&'static str, &'static str)] = &[
+    ("network", "tcp://127.0.0.1:5563"),
+    ("chain", "tcp://127.0.0.1:5564"),
+    ("jsonrpc", "tcp://127.0.0.1:5565"),
+    ("consensus", "tcp://127.0.0.1:5566"),
+    ("auth", "tcp://127.0.0.1:5567"),
+    ("network_tx", "tcp://127.0.0.1:5568"),
+    ("consensus_cmd", "tcp://127.0.0.1:5569"),
+];
+
+/// Endpoint of each service
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoints(BTreeMap<String, String>);
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints(DEFAULT_ENDPOINTS.iter().map(|&(name, endpoint)| (name.to_string(), endpoint.to_string())).collect())
     }
+}
 
-    let _ = thread::Builder::new().name("publisher".to_string()).spawn(move || loop {
-                                                                           let ret = rx.recv();
+impl Endpoints {
+    pub fn new() -> Self {
+        Endpoints(BTreeMap::new())
+    }
 
-                                                                           if ret.is_err() {
-                                                                               break;
-                                                                           }
-                                                                           let (topic, msg) = ret.unwrap();
-                                                                           publisher.send_multipart(&[&(topic.into_bytes())], zmq::SNDMORE).unwrap();
-                                                                           publisher.send(&msg, 0).unwrap();
-                                                                       });
+    /// The defaults, overridden by `ZMQ_ENDPOINTS` when set
+    pub fn from_env() -> Self {
+        let mut endpoints = Endpoints::default();
+        if let Ok(value) = std::env::var(ZMQ_ENDPOINTS) {
+            endpoints.extend(&value).expect(format!("invalid {}", ZMQ_ENDPOINTS).as_str());
+        }
+        endpoints
+    }
 
+    /// Add or replace endpoints from `name=endpoint` pairs separated by commas
+    pub fn extend(&mut self, pairs: &str) -> Result<(), String> {
+        for pair in pairs.split(',').map(|pair| pair.trim()).filter(|pair| !pair.is_empty()) {
+            let mut parts = pair.splitn(2, '=');
+            match (parts.next().map(|name| name.trim()), parts.next().map(|endpoint| endpoint.trim())) {
+                (Some(name), Some(endpoint)) if !name.is_empty() && endpoint.contains("://") => {
+                    self.insert(name, endpoint);
+                }
+                _ => return Err(format!("bad endpoint {:?}, expected name=transport://address", pair)),
+            }
+        }
+        Ok(())
+    }
 
-    //sub
+    pub fn insert(&mut self, name: &str, endpoint: &str) {
+        self.0.insert(name.to_string(), endpoint.to_string());
+    }
 
-    let network_subscriber = context.socket(zmq::SUB).unwrap();
-    assert!(network_subscriber.connect("tcp://localhost:5563").is_ok());
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(|endpoint| endpoint.as_str())
+    }
+}
 
-    let chain_subscriber = context.socket(zmq::SUB).unwrap();
-    assert!(chain_subscriber.connect("tcp://localhost:5564").is_ok());
+/// ZeroMQ filters by prefix, so subscribe to what comes before any wildcard
+/// and match the rest on receipt.
+fn subscription(key: &str) -> &str {
+    let prefix = key.find(|c| c == '*' || c == '#').map_or(key, |wildcard| &key[..wildcard]);
+    prefix.trim_right_matches('.')
+}
 
-    let jsonrpc_subscriber = context.socket(zmq::SUB).unwrap();
-    assert!(jsonrpc_subscriber.connect("tcp://localhost:5565").is_ok());
+pub fn start_zeromq(name: &str, keys: Vec<&str>, tx: SyncSender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+    start_zeromq_with(&Endpoints::from_env(), name, keys, tx, rx);
+}
 
-    let consensus_subscriber = context.socket(zmq::SUB).unwrap();
-    assert!(network_subscriber.connect("tcp://localhost:5566").is_ok());
+pub fn start_zeromq_with(endpoints: &Endpoints, name: &str, keys: Vec<&str>, tx: SyncSender<(String, Vec<u8>)>, rx: Receiver<(String, Vec<u8>)>) {
+    let endpoint = endpoints.get(name).expect(format!("no zeromq endpoint for {}, add it to {}", name, ZMQ_ENDPOINTS).as_str());
+    let context = zmq::Context::new();
 
-    let mut flag = 400;
-    for topic in keys {
-        flag = match name {
-            "network" => {
-                network_subscriber.set_subscribe(&topic.to_string().into_bytes()).unwrap();
-                0
-            }
-            "chain" => {
-                chain_subscriber.set_subscribe(&topic.to_string().into_bytes()).unwrap();
-                1
-            }
-            "jsonrpc" => {
-                jsonrpc_subscriber.set_subscribe(&topic.to_string().into_bytes()).unwrap();
-                2
-            }
-            "consensus" => {
-                consensus_subscriber.set_subscribe(&topic.to_string().into_bytes()).unwrap();
-                3
-            }
-            _ => {
-                error!("invalid  flag!");
-                -1
-            }
+    //pub
+    let publisher = context.socket(zmq::PUB).unwrap();
+    publisher.bind(endpoint).expect(format!("bind {} at {}", name, endpoint).as_str());
+    let _ = thread::Builder::new().name("publisher".to_string()).spawn(move || loop {
+        let (topic, msg) = match rx.recv() {
+            Ok(published) => published,
+            Err(_) => break,
+        };
+        if let Err(e) = publisher.send_multipart(&[topic.as_bytes(), &msg[..]], 0) {
+            error!("publish {} error: {:?}", topic, e);
         }
-    }
+    });
 
+    //sub
+    let subscriber = context.socket(zmq::SUB).unwrap();
+    for endpoint in endpoints.0.values() {
+        subscriber.connect(endpoint).expect(format!("connect {}", endpoint).as_str());
+    }
+    let keys: Vec<String> = keys.into_iter().map(|key| key.to_string()).collect();
+    for key in &keys {
+        subscriber.set_subscribe(subscription(key).as_bytes()).unwrap();
+    }
     let _ = thread::Builder::new().name("subscriber".to_string()).spawn(move || loop {
+        let mut parts = match subscriber.recv_multipart(0) {
+            Ok(parts) => parts,
+            Err(e) => {
+                error!("subscriber error: {:?}", e);
+                break;
+            }
+        };
+        if parts.len() != 2 {
+            warn!("drop msg of {} parts", parts.len());
+            continue;
+        }
+        let msg = parts.pop().unwrap();
+        let topic = match String::from_utf8(parts.pop().unwrap()) {
+            Ok(topic) => topic,
+            Err(_) => continue,
+        };
+        if keys.iter().any(|key| matches(key, &topic)) && tx.send((topic, msg)).is_err() {
+            break;
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn endpoints() {
+        let mut endpoints = Endpoints::default();
+        assert_eq!(endpoints.get("auth"), Some("tcp://127.0.0.1:5567"));
+        endpoints.extend("auth=ipc:///tmp/auth.ipc, executor=tcp://10.0.0.2:6000,").unwrap();
+        assert_eq!(endpoints.get("auth"), Some("ipc:///tmp/auth.ipc"));
+        assert_eq!(endpoints.get("executor"), Some("tcp://10.0.0.2:6000"));
+        assert!(endpoints.extend("auth").is_err());
+        assert!(endpoints.extend("auth=5567").is_err());
+    }
 
-                                                                            match flag {
-                                                                                0 => {
-                                                                                    let topic = network_subscriber.recv_string(0).unwrap().unwrap();
-                                                                                    let msg = network_subscriber.recv_bytes(0).unwrap();
-                                                                                    let _ = tx.send((topic, msg));
-                                                                                }
-
-                                                                                1 => {
-                                                                                    let topic = chain_subscriber.recv_string(0).unwrap().unwrap();
-                                                                                    let msg = chain_subscriber.recv_bytes(0).unwrap();
-                                                                                    let _ = tx.send((topic, msg));
-                                                                                }
-
-                                                                                2 => {
-                                                                                    let topic = jsonrpc_subscriber.recv_string(0).unwrap().unwrap();
-                                                                                    let msg = jsonrpc_subscriber.recv_bytes(0).unwrap();
-                                                                                    let _ = tx.send((topic, msg));
-                                                                                }
-
-                                                                                3 => {
-                                                                                    let topic = consensus_subscriber.recv_string(0).unwrap().unwrap();
-                                                                                    let msg = consensus_subscriber.recv_bytes(0).unwrap();
-                                                                                    let _ = tx.send((topic, msg));
-                                                                                }
-
-                                                                                _ => {
-                                                                                    break;
-                                                                                }
-                                                                            }
-
-                                                                        });
+    #[test]
+    fn topics() {
+        assert_eq!(subscription("net.msg"), "net.msg");
+        assert_eq!(subscription("chain.*"), "chain");
+        assert_eq!(subscription("#"), "");
+    }
 }
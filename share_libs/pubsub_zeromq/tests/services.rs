@@ -0,0 +1,100 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Five services of a node over ipc endpoints, each bound to the keys its
+//! binary subscribes to. Every routing key is published and must reach exactly
+//! the services bound to it.
+
+extern crate pubsub_zeromq;
+
+use pubsub_zeromq::{Endpoints, matches, start_zeromq_with};
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::sync::mpsc::{Receiver, Sender, channel, sync_channel};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const SERVICES: &'static [(&'static str, &'static [&'static str])] = &[
+    ("auth", &["consensus.verify_req", "chain.txhashes", "jsonrpc.new_tx_batch", "net.tx"]),
+    ("chain", &["net.blk", "net.status", "net.sync", "consensus.blk", "jsonrpc.request", "auth.blk_tx_hashs_req", "consensus.msg"]),
+    ("consensus", &["net.msg", "chain.richstatus", "chain.evidence", "auth.block_txs", "verify_blk_consensus"]),
+    ("jsonrpc", &["auth.rpc", "chain.rpc"]),
+    ("network", &["consensus.msg", "chain.status", "chain.blk", "chain.sync", "chain.richstatus", "jsonrpc.net", "chain.verdict", "auth.verdict", "consensus.verdict", "auth.tx"]),
+];
+
+/// The service publishing a routing key, by its first word
+fn publisher(routing_key: &str) -> &'static str {
+    match routing_key.split('.').next().unwrap() {
+        "net" => "network",
+        "chain" => "chain",
+        "consensus" => "consensus",
+        "jsonrpc" => "jsonrpc",
+        _ => "auth",
+    }
+}
+
+struct Service {
+    pub_tx: Sender<(String, Vec<u8>)>,
+    sub_rx: Receiver<(String, Vec<u8>)>,
+    received: BTreeSet<String>,
+}
+
+#[test]
+fn all_topics_reach_their_subscribers() {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+    let dir = env::temp_dir();
+    let mut endpoints = Endpoints::new();
+    for &(name, _) in SERVICES {
+        endpoints.insert(name, &format!("ipc://{}/cita-zmq-{}-{}.ipc", dir.display(), nanos, name));
+    }
+
+    let mut services = HashMap::new();
+    for &(name, keys) in SERVICES {
+        let (sub_tx, sub_rx) = sync_channel(1024);
+        let (pub_tx, pub_rx) = channel();
+        start_zeromq_with(&endpoints, name, keys.to_vec(), sub_tx, pub_rx);
+        services.insert(name,
+                        Service {
+                            pub_tx: pub_tx,
+                            sub_rx: sub_rx,
+                            received: BTreeSet::new(),
+                        });
+    }
+
+    let routing_keys: BTreeSet<&str> = SERVICES.iter().flat_map(|&(_, keys)| keys.iter().cloned()).collect();
+    let expected = |name: &str| -> BTreeSet<String> {
+        let keys = SERVICES.iter().find(|&&(service, _)| service == name).unwrap().1;
+        routing_keys.iter().filter(|routing_key| keys.iter().any(|key| matches(key, routing_key))).map(|key| key.to_string()).collect()
+    };
+
+    // subscriptions take a while to reach the publishers, so publish until everything arrived
+    let start = Instant::now();
+    while services.iter().any(|(name, service)| service.received != expected(name)) {
+        assert!(start.elapsed() < Duration::from_secs(10), "topics still missing");
+        for routing_key in &routing_keys {
+            services[publisher(routing_key)].pub_tx.send((routing_key.to_string(), routing_key.as_bytes().to_vec())).unwrap();
+        }
+        thread::sleep(Duration::from_millis(50));
+        for (name, service) in services.iter_mut() {
+            while let Ok((topic, msg)) = service.sub_rx.try_recv() {
+                assert_eq!(msg, topic.as_bytes().to_vec());
+                assert!(expected(name).contains(&topic), "{} got {} it is not bound to", name, topic);
+                service.received.insert(topic);
+            }
+        }
+    }
+}
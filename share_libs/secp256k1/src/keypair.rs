@@ -18,9 +18,10 @@
 use super::{PrivKey, PubKey, Address, SECP256K1, Error};
 use rand::thread_rng;
 use rustc_serialize::hex::ToHex;
-use secp256k1::key;
+use secp256k1::{ecdh, key};
 use std::fmt;
 use util::H160 as Hash160;
+use util::H256;
 use util::Hashable;
 use util::crypto::CreateKey;
 
@@ -28,6 +29,17 @@ pub fn pubkey_to_address(pubkey: &PubKey) -> Address {
     Address::from(Hash160::from(pubkey.crypt_hash()))
 }
 
+/// Diffie-Hellman key agreement, returns the shared secret of `privkey` and `pubkey`
+pub fn ecdh(privkey: &PrivKey, pubkey: &PubKey) -> Result<H256, Error> {
+    let context = &SECP256K1;
+    let s: key::SecretKey = key::SecretKey::from_slice(context, &privkey.0[..])?;
+    let mut serialized = [4u8; 65];
+    serialized[1..65].copy_from_slice(&pubkey.0[..]);
+    let p = key::PublicKey::from_slice(context, &serialized[..])?;
+    let shared = ecdh::SharedSecret::new(context, &p, &s);
+    Ok(H256::from_slice(&shared[..]))
+}
+
 /// key pair
 #[derive(Default)]
 pub struct KeyPair {
@@ -89,7 +101,7 @@ impl CreateKey for KeyPair {
 
 #[cfg(test)]
 mod tests {
-    use super::{KeyPair, PrivKey};
+    use super::{KeyPair, PrivKey, ecdh};
     use std::str::FromStr;
     use util::H256 as Hash256;
     use util::crypto::CreateKey;
@@ -99,4 +111,13 @@ mod tests {
         let privkey = PrivKey::from(Hash256::from_str("a100df7a048e50ed308ea696dc600215098141cb391e9527329df289f9383f65").unwrap());
         let _ = KeyPair::from_privkey(privkey).unwrap();
     }
+
+    #[test]
+    fn ecdh_agreement() {
+        let a = KeyPair::gen_keypair();
+        let b = KeyPair::gen_keypair();
+        let ab = ecdh(a.privkey(), b.pubkey()).unwrap();
+        let ba = ecdh(b.privkey(), a.pubkey()).unwrap();
+        assert_eq!(ab, ba);
+    }
 }
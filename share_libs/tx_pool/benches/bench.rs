@@ -25,11 +25,38 @@ extern crate cita_crypto as crypto;
 
 use crypto::{KeyPair, CreateKey};
 use libproto::blockchain::AccountGasLimit;
-use libproto::blockchain::Transaction;
-use std::collections::HashMap;
+use libproto::blockchain::{SignedTransaction, Transaction};
+use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
 use test::Bencher;
 use tx_pool::pool::*;
+
+const LARGE_POOL_SIZE: usize = 100000;
+
+fn generate_txs(n: usize, quota: u64) -> Vec<SignedTransaction> {
+    let mut tx = Transaction::new();
+    let keypair = KeyPair::gen_keypair();
+    let pv = keypair.privkey();
+    (0..n)
+        .map(|i| {
+                 tx.set_data(format!("{}", i).as_bytes().to_vec());
+                 tx.set_to("1234567".to_string());
+                 tx.set_nonce(format!("{}", i));
+                 tx.set_valid_until_block(99);
+                 tx.set_quota(quota);
+                 tx.sign(*pv)
+             })
+        .collect()
+}
+
+fn large_pool(quota: u64) -> Pool {
+    let mut p = Pool::new(1000);
+    for tx in generate_txs(LARGE_POOL_SIZE, quota) {
+        p.enqueue(tx);
+    }
+    p
+}
+
 #[bench]
 fn bench_base(b: &mut Bencher) {
     let start = SystemTime::now();
@@ -54,7 +81,7 @@ fn bench_enqueue(b: &mut Bencher) {
     for i in 0..10000 {
         tx.set_data(format!("{}", i).as_bytes().to_vec());
         tx.set_to("1234567".to_string());
-        tx.set_nonce("0".to_string());
+        tx.set_nonce(format!("{}", i));
         tx.set_valid_until_block(99);
         // 2000*10000 <= account_gas_limit <= block_gas_limit
         tx.set_quota(2000);
@@ -77,7 +104,7 @@ fn bench_package(b: &mut Bencher) {
     for i in 0..10000 {
         tx.set_data(format!("{}", i).as_bytes().to_vec());
         tx.set_to("1234567".to_string());
-        tx.set_nonce("0".to_string());
+        tx.set_nonce(format!("{}", i));
         tx.set_valid_until_block(99);
         // 6000*10000 <= account_gas_limit <= block_gas_limit
         tx.set_quota(6000);
@@ -111,7 +138,7 @@ fn bench_update(b: &mut Bencher) {
     for i in 0..10000 {
         tx.set_data(format!("{}", i).as_bytes().to_vec());
         tx.set_to("1234567".to_string());
-        tx.set_nonce("0".to_string());
+        tx.set_nonce(format!("{}", i));
         tx.set_valid_until_block(99);
         // 6000*10000 <= account_gas_limit <= block_gas_limit
         tx.set_quota(6000);
@@ -134,3 +161,58 @@ fn bench_update(b: &mut Bencher) {
     println!("test {:20} ... bench: {}.{} s/iter", "bench_update", diff.as_secs(), diff.subsec_nanos());
     b.iter(|| {});
 }
+
+#[bench]
+fn bench_enqueue_large_pool(b: &mut Bencher) {
+    let mut p = large_pool(2000);
+    // signed by a different key than the pooled txs, so no nonce is replaced
+    let txs = generate_txs(10000, 2000);
+    let start = SystemTime::now();
+    for tx in txs {
+        p.enqueue(tx);
+    }
+    let sys_time = SystemTime::now();
+    let diff = sys_time.duration_since(start).expect("SystemTime::duration_since failed");
+    println!("pass");
+    println!("test {:20} ... bench: {}.{} s/iter", "bench_enqueue_large_pool", diff.as_secs(), diff.subsec_nanos());
+    b.iter(|| {});
+}
+
+#[bench]
+fn bench_package_large_pool(b: &mut Bencher) {
+    let mut p = large_pool(6000);
+    let mut account_gas_limit = AccountGasLimit::new();
+    let block_gas_limit = 61415926;
+    let height = 0;
+    account_gas_limit.set_common_gas_limit(block_gas_limit);
+    account_gas_limit.set_specific_gas_limit(HashMap::new());
+
+    let start = SystemTime::now();
+    p.package(height, block_gas_limit, account_gas_limit.clone());
+    let sys_time = SystemTime::now();
+    let diff = sys_time.duration_since(start).expect("SystemTime::duration_since failed");
+    println!("pass");
+    println!("test {:20} ... bench: {}.{} s/iter", "bench_package_large_pool", diff.as_secs(), diff.subsec_nanos());
+    b.iter(|| {});
+}
+
+#[bench]
+fn bench_update_large_pool(b: &mut Bencher) {
+    let mut p = large_pool(6000);
+    let mut account_gas_limit = AccountGasLimit::new();
+    let block_gas_limit = 61415926;
+    let height = 0;
+    account_gas_limit.set_common_gas_limit(block_gas_limit);
+    account_gas_limit.set_specific_gas_limit(HashMap::new());
+    let txs = p.package(height, block_gas_limit, account_gas_limit.clone());
+    let hashes: HashSet<_> = txs.iter().map(|tx| tx.crypt_hash()).collect();
+
+    // one block worth of txs removed from a pool of LARGE_POOL_SIZE
+    let start = SystemTime::now();
+    p.update_with_hash(&hashes);
+    let sys_time = SystemTime::now();
+    let diff = sys_time.duration_since(start).expect("SystemTime::duration_since failed");
+    println!("pass");
+    println!("test {:20} ... bench: {}.{} s/iter", "bench_update_large_pool", diff.as_secs(), diff.subsec_nanos());
+    b.iter(|| {});
+}
@@ -18,7 +18,7 @@
 use crypto::{pubkey_to_address, PubKey};
 use libproto::blockchain::{SignedTransaction, AccountGasLimit};
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet, BTreeSet};
+use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
 use util::{H256, Address, BLOCKLIMIT};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -58,32 +58,77 @@ impl Ord for TxOrder {
     }
 }
 
+// A pooled tx together with its key in `order_set`,
+// so it can be removed from the set without a scan.
+#[derive(Debug)]
+struct PoolTx {
+    order: u64,
+    signer: Address,
+    tx: SignedTransaction,
+}
+
+/// Outcome of `Pool::enqueue`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EnqueueResult {
+    /// The transaction was added to the pool.
+    Added,
+    /// The transaction was added and the oldest entry, given here, was evicted to make room.
+    Evicted(H256),
+    /// The transaction replaced the pending one, given here, with the same signer and nonce.
+    Replaced(H256),
+    /// The transaction is already in the pool.
+    Dup,
+    /// The signer already has the maximum number of pending transactions.
+    SenderLimit,
+}
+
+impl EnqueueResult {
+    pub fn is_ok(&self) -> bool {
+        match *self {
+            EnqueueResult::Added | EnqueueResult::Evicted(_) | EnqueueResult::Replaced(_) => true,
+            EnqueueResult::Dup | EnqueueResult::SenderLimit => false,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Pool {
     package_limit: usize,
+    // 0 means no limit
+    pool_limit: usize,
+    // 0 means no limit
+    sender_limit: usize,
     order_set: BTreeSet<TxOrder>,
-    txs: HashMap<H256, SignedTransaction>,
+    txs: HashMap<H256, PoolTx>,
+    // signer -> nonce -> tx hash
+    senders: HashMap<Address, HashMap<String, H256>>,
+    // valid_until_block -> tx hashes
+    expiry: BTreeMap<u64, HashSet<H256>>,
     strategy: Strategy,
     order: u64,
 }
 
 impl Pool {
     pub fn new(package_limit: usize) -> Self {
-        Pool {
-            package_limit: package_limit,
-            order_set: BTreeSet::new(),
-            txs: HashMap::new(),
-            strategy: Strategy::FIFO,
-            order: 0,
-        }
+        Pool::new_with_limit(package_limit, 0, 0)
     }
 
     pub fn new_with_strategy(package_limit: usize, strategy: Strategy) -> Self {
+        let mut pool = Pool::new(package_limit);
+        pool.strategy = strategy;
+        pool
+    }
+
+    pub fn new_with_limit(package_limit: usize, pool_limit: usize, sender_limit: usize) -> Self {
         Pool {
             package_limit: package_limit,
+            pool_limit: pool_limit,
+            sender_limit: sender_limit,
             order_set: BTreeSet::new(),
             txs: HashMap::new(),
-            strategy: strategy,
+            senders: HashMap::new(),
+            expiry: BTreeMap::new(),
+            strategy: Strategy::FIFO,
             order: 0,
         }
     }
@@ -105,42 +150,108 @@ impl Pool {
         return self.get_order();
     }
 
-    pub fn enqueue(&mut self, tx: SignedTransaction) -> bool {
+    fn signer(tx: &SignedTransaction) -> Address {
+        pubkey_to_address(&PubKey::from(tx.get_signer()))
+    }
+
+    pub fn enqueue(&mut self, tx: SignedTransaction) -> EnqueueResult {
         let hash = H256::from_slice(tx.get_tx_hash());
+        if self.txs.contains_key(&hash) {
+            return EnqueueResult::Dup;
+        }
 
-        let is_ok = !self.txs.contains_key(&hash);
-        if is_ok {
-            let order = match self.strategy {
-                Strategy::FIFO => self.get_order(),
-                Strategy::PRIORITY => self.get_order_by_priority(&tx),
-                Strategy::VIP => self.get_order_by_vip(&tx),
-            };
-            let tx_order = TxOrder::new(hash, order);
-            self.order_set.insert(tx_order);
-            self.txs.insert(hash, tx);
+        let signer = Pool::signer(&tx);
+        let nonce = tx.get_transaction_with_sig().get_transaction().get_nonce().to_string();
+        let (replaced, pending) = match self.senders.get(&signer) {
+            Some(nonces) => (nonces.get(&nonce).cloned(), nonces.len()),
+            None => (None, 0),
+        };
+
+        let mut result = EnqueueResult::Added;
+        if let Some(old_hash) = replaced {
+            self.remove(&old_hash);
+            result = EnqueueResult::Replaced(old_hash);
+        } else if self.sender_limit != 0 && pending >= self.sender_limit {
+            return EnqueueResult::SenderLimit;
+        } else if self.pool_limit != 0 && self.txs.len() >= self.pool_limit {
+            if let Some(oldest) = self.order_set.iter().next().map(|order| order.hash) {
+                self.remove(&oldest);
+                result = EnqueueResult::Evicted(oldest);
+            }
         }
-        is_ok
+
+        let order = match self.strategy {
+            Strategy::FIFO => self.get_order(),
+            Strategy::PRIORITY => self.get_order_by_priority(&tx),
+            Strategy::VIP => self.get_order_by_vip(&tx),
+        };
+        let valid_until_block = tx.get_transaction_with_sig().get_transaction().get_valid_until_block();
+        self.order_set.insert(TxOrder::new(hash, order));
+        self.senders.entry(signer).or_insert_with(HashMap::new).insert(nonce, hash);
+        self.expiry.entry(valid_until_block).or_insert_with(HashSet::new).insert(hash);
+        self.txs.insert(hash, PoolTx { order: order, signer: signer, tx: tx });
+        result
     }
 
-    fn update_order_set(&mut self, hash_list: &HashSet<H256>) {
-        self.order_set = self.order_set.iter().cloned().filter(|order| !hash_list.contains(&order.hash)).collect();
+    // Remove a tx from every index in O(log n).
+    fn remove(&mut self, hash: &H256) -> Option<SignedTransaction> {
+        let entry = self.txs.remove(hash);
+        if let Some(PoolTx { order, signer, ref tx }) = entry {
+            self.order_set.remove(&TxOrder::new(*hash, order));
+
+            let transaction = tx.get_transaction_with_sig().get_transaction();
+            let empty = match self.senders.get_mut(&signer) {
+                Some(nonces) => {
+                    nonces.remove(transaction.get_nonce());
+                    nonces.is_empty()
+                }
+                None => false,
+            };
+            if empty {
+                self.senders.remove(&signer);
+            }
+
+            let valid_until_block = transaction.get_valid_until_block();
+            let empty = match self.expiry.get_mut(&valid_until_block) {
+                Some(hashes) => {
+                    hashes.remove(hash);
+                    hashes.is_empty()
+                }
+                None => false,
+            };
+            if empty {
+                self.expiry.remove(&valid_until_block);
+            }
+        }
+        entry.map(|entry| entry.tx)
     }
 
     pub fn update(&mut self, txs: &[SignedTransaction]) {
-        let mut hash_list = HashSet::with_capacity(txs.len());
         for tx in txs {
-            let hash = tx.crypt_hash();
-            self.txs.remove(&hash);
-            hash_list.insert(hash);
+            self.remove(&tx.crypt_hash());
         }
-        self.update_order_set(&hash_list);
     }
 
     pub fn update_with_hash(&mut self, txs: &HashSet<H256>) {
         for tx in txs {
-            self.txs.remove(&tx);
+            self.remove(tx);
         }
-        self.update_order_set(txs);
+    }
+
+    /// Drop every tx whose `valid_until_block` is not above `height`,
+    /// and return the hashes of the dropped txs.
+    pub fn remove_expired(&mut self, height: u64) -> Vec<H256> {
+        // valid_until_block 0 means the tx never expires
+        let expired: Vec<H256> = self.expiry.range(1..height.saturating_add(1)).flat_map(|(_, hashes)| hashes.iter().cloned()).collect();
+        for hash in &expired {
+            self.remove(hash);
+        }
+        expired
+    }
+
+    /// Number of pending txs sent by `signer`.
+    pub fn pending_of(&self, signer: &Address) -> usize {
+        self.senders.get(signer).map_or(0, |nonces| nonces.len())
     }
 
     pub fn package(&mut self, height: u64, block_gas_limit: u64, account_gas_limit: AccountGasLimit) -> Vec<SignedTransaction> {
@@ -150,6 +261,7 @@ impl Pool {
         let mut gas_limit = account_gas_limit.get_common_gas_limit();
         let mut specific_gas_limit = account_gas_limit.get_specific_gas_limit().clone();
         let mut account_gas_used: HashMap<Address, u64> = HashMap::new();
+        self.remove_expired(height);
         {
             let mut iter = self.order_set.iter();
             loop {
@@ -158,7 +270,7 @@ impl Pool {
                     break;
                 }
                 let hash = order.unwrap().hash;
-                let tx = self.txs.get(&hash);
+                let tx = self.txs.get(&hash).map(|entry| &entry.tx);
                 let tx_is_valid = |signed_tx: &SignedTransaction, height: u64| {
                     let valid_until_block = signed_tx.get_transaction().get_valid_until_block();
                     (valid_until_block == 0) || (height < valid_until_block && valid_until_block <= (height + BLOCKLIMIT))
@@ -166,7 +278,7 @@ impl Pool {
                 if let Some(tx) = tx {
                     if tx_is_valid(tx, height) {
                         let quota = tx.get_transaction_with_sig().get_transaction().quota;
-                        let signer = pubkey_to_address(&PubKey::from(tx.get_signer()));
+                        let signer = Pool::signer(tx);
                         if n <= quota {
                             if tx_list.is_empty() {
                                 tx_list.push(tx.clone());
@@ -220,7 +332,7 @@ impl Pool {
                     break;
                 }
                 let hash = order.unwrap().hash;
-                let tx = self.txs.get(&hash);
+                let tx = self.txs.get(&hash).map(|entry| &entry.tx);
                 if let Some(tx) = tx {
                     if tx.get_transaction_with_sig().get_transaction().valid_until_block >= height && tx.get_transaction_with_sig().get_transaction().valid_until_block < (height + BLOCKLIMIT) {
                         tx_list.push(tx.clone());
@@ -253,11 +365,11 @@ mod tests {
     use crypto::{KeyPair, PrivKey, CreateKey};
     use libproto::blockchain::{SignedTransaction, Transaction, AccountGasLimit};
 
-    pub fn generate_tx(data: Vec<u8>, valid_until_block: u64, privkey: &PrivKey) -> SignedTransaction {
+    pub fn generate_tx(data: Vec<u8>, nonce: &str, valid_until_block: u64, privkey: &PrivKey) -> SignedTransaction {
         let mut tx = Transaction::new();
         tx.set_data(data);
         tx.set_to("1234567".to_string());
-        tx.set_nonce("0".to_string());
+        tx.set_nonce(nonce.to_string());
         tx.set_valid_until_block(valid_until_block);
         tx.set_quota(184467440737095);
 
@@ -270,19 +382,19 @@ mod tests {
         let keypair = KeyPair::gen_keypair();
         let privkey = keypair.privkey();
 
-        let tx1 = generate_tx(vec![1], 99, privkey);
-        let tx2 = generate_tx(vec![1], 99, privkey);
-        let tx3 = generate_tx(vec![2], 99, privkey);
-        let tx4 = generate_tx(vec![3], 5, privkey);
+        let tx1 = generate_tx(vec![1], "1", 99, privkey);
+        let tx2 = generate_tx(vec![1], "1", 99, privkey);
+        let tx3 = generate_tx(vec![2], "2", 99, privkey);
+        let tx4 = generate_tx(vec![3], "3", 5, privkey);
 
         let mut account_gas_limit = AccountGasLimit::new();
         account_gas_limit.set_common_gas_limit(10000);
         account_gas_limit.set_specific_gas_limit(HashMap::new());
 
-        assert_eq!(p.enqueue(tx1.clone()), true);
-        assert_eq!(p.enqueue(tx2.clone()), false);
-        assert_eq!(p.enqueue(tx3.clone()), true);
-        assert_eq!(p.enqueue(tx4.clone()), true);
+        assert_eq!(p.enqueue(tx1.clone()), EnqueueResult::Added);
+        assert_eq!(p.enqueue(tx2.clone()), EnqueueResult::Dup);
+        assert_eq!(p.enqueue(tx3.clone()), EnqueueResult::Added);
+        assert_eq!(p.enqueue(tx4.clone()), EnqueueResult::Added);
         assert_eq!(p.len(), 3);
         p.update(&vec![tx1.clone()]);
         assert_eq!(p.len(), 2);
@@ -293,4 +405,94 @@ mod tests {
         assert_eq!(p.package(5, 30, account_gas_limit.clone()), vec![]);
         assert_eq!(p.len(), 0);
     }
+
+    #[test]
+    fn replace_by_nonce() {
+        let mut p = Pool::new(10);
+        let keypair = KeyPair::gen_keypair();
+        let privkey = keypair.privkey();
+        let other = KeyPair::gen_keypair();
+
+        let tx1 = generate_tx(vec![1], "1", 99, privkey);
+        let tx2 = generate_tx(vec![2], "1", 99, privkey);
+        let tx3 = generate_tx(vec![3], "1", 99, other.privkey());
+
+        assert_eq!(p.enqueue(tx1.clone()), EnqueueResult::Added);
+        assert_eq!(p.enqueue(tx2.clone()), EnqueueResult::Replaced(tx1.crypt_hash()));
+        assert_eq!(p.enqueue(tx3.clone()), EnqueueResult::Added);
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.pending_of(&pubkey_to_address(keypair.pubkey())), 1);
+        assert_eq!(p.package_backword_compatible(0), vec![tx2, tx3]);
+    }
+
+    #[test]
+    fn sender_limit() {
+        let mut p = Pool::new_with_limit(10, 0, 2);
+        let keypair = KeyPair::gen_keypair();
+        let privkey = keypair.privkey();
+        let other = KeyPair::gen_keypair();
+
+        assert_eq!(p.enqueue(generate_tx(vec![1], "1", 99, privkey)), EnqueueResult::Added);
+        assert_eq!(p.enqueue(generate_tx(vec![2], "2", 99, privkey)), EnqueueResult::Added);
+        assert_eq!(p.enqueue(generate_tx(vec![3], "3", 99, privkey)), EnqueueResult::SenderLimit);
+        assert_eq!(p.enqueue(generate_tx(vec![3], "1", 99, privkey)).is_ok(), true);
+        assert_eq!(p.enqueue(generate_tx(vec![3], "3", 99, other.privkey())), EnqueueResult::Added);
+        assert_eq!(p.len(), 3);
+    }
+
+    #[test]
+    fn evict_oldest() {
+        let mut p = Pool::new_with_limit(10, 2, 0);
+        let keypair = KeyPair::gen_keypair();
+        let privkey = keypair.privkey();
+
+        let tx1 = generate_tx(vec![1], "1", 99, privkey);
+        let tx2 = generate_tx(vec![2], "2", 99, privkey);
+        let tx3 = generate_tx(vec![3], "3", 99, privkey);
+
+        assert_eq!(p.enqueue(tx1.clone()), EnqueueResult::Added);
+        assert_eq!(p.enqueue(tx2.clone()), EnqueueResult::Added);
+        assert_eq!(p.enqueue(tx3.clone()), EnqueueResult::Evicted(tx1.crypt_hash()));
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.package_backword_compatible(0), vec![tx2, tx3]);
+    }
+
+    #[test]
+    fn remove_expired() {
+        let mut p = Pool::new(10);
+        let keypair = KeyPair::gen_keypair();
+        let privkey = keypair.privkey();
+
+        let tx1 = generate_tx(vec![1], "1", 5, privkey);
+        let tx2 = generate_tx(vec![2], "2", 6, privkey);
+        let tx3 = generate_tx(vec![3], "3", 0, privkey);
+
+        p.enqueue(tx1.clone());
+        p.enqueue(tx2.clone());
+        p.enqueue(tx3.clone());
+        assert_eq!(p.remove_expired(4), vec![]);
+        assert_eq!(p.remove_expired(5), vec![tx1.crypt_hash()]);
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.remove_expired(100), vec![tx2.crypt_hash()]);
+        assert_eq!(p.len(), 1);
+        assert_eq!(p.pending_of(&pubkey_to_address(keypair.pubkey())), 1);
+    }
+
+    #[test]
+    fn update_keeps_order() {
+        let mut p = Pool::new(10);
+        let keypair = KeyPair::gen_keypair();
+        let privkey = keypair.privkey();
+
+        let txs: Vec<SignedTransaction> = (0..6).map(|i| generate_tx(vec![i], &i.to_string(), 99, privkey)).collect();
+        for tx in &txs {
+            p.enqueue(tx.clone());
+        }
+        p.update(&vec![txs[1].clone(), txs[4].clone()]);
+        let mut hashes = HashSet::new();
+        hashes.insert(txs[0].crypt_hash());
+        p.update_with_hash(&hashes);
+        assert_eq!(p.len(), 3);
+        assert_eq!(p.package_backword_compatible(0), vec![txs[2].clone(), txs[3].clone(), txs[5].clone()]);
+    }
 }
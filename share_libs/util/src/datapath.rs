@@ -55,6 +55,20 @@ impl DataPath {
         return data_path + "/proof.bin";
     }
 
+    /// evidence of equivocating validators
+    pub fn evidence_path() -> String {
+        let data_path = DataPath::root_node_path();
+
+        return data_path + "/evidence.log";
+    }
+
+    /// raft log path
+    pub fn raft_log_path() -> String {
+        let data_path = DataPath::root_node_path();
+
+        return data_path + "/raft";
+    }
+
     /// wal log path
     pub fn wal_path() -> String {
         let data_path = DataPath::root_node_path();
@@ -376,6 +376,8 @@ pub struct DatabaseConfig {
     pub columns: Option<u32>,
     /// Should we keep WAL enabled?
     pub wal: bool,
+    /// Sync the WAL to disk before a write returns, so it survives a crash of the machine
+    pub sync: bool,
 }
 
 impl DatabaseConfig {
@@ -401,6 +403,7 @@ impl Default for DatabaseConfig {
             compaction: CompactionProfile::default(),
             columns: None,
             wal: true,
+            sync: false,
         }
     }
 }
@@ -506,6 +509,9 @@ impl Database {
         if !config.wal {
             write_opts.disable_wal(true);
         }
+        if config.sync {
+            write_opts.set_sync(true);
+        }
         let mut read_opts = ReadOptions::new();
         read_opts.set_verify_checksums(false);
 
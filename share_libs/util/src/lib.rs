@@ -61,6 +61,7 @@ pub mod cache;
 pub mod crypto;
 pub mod datapath;
 pub mod panichandler;
+pub mod wal;
 
 
 pub use ansi_term::{Colour, Style};
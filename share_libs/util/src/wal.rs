@@ -0,0 +1,393 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Records of the consensus write ahead logs.
+//!
+//! A file starts with `MAGIC` and the `VERSION` of its format, then a record is
+//! `[len: u32][type: u8][crc: u32][payload]`, little endian, where `crc` is the
+//! CRC-32 of the type and the payload. Reading stops at the first record which
+//! is cut short or fails its check, what follows it is a torn write.
+//!
+//! Files without the header are of the unversioned format, `[len: u32][type: u8][payload]`
+//! records, and are rewritten when opened. A later version is refused.
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+pub const MAGIC: &'static [u8] = b"CITAWAL";
+pub const VERSION: u8 = 1;
+/// `MAGIC` and `VERSION`
+pub const FILE_HEADER_LEN: usize = 8;
+pub const HEADER_LEN: usize = 9;
+/// Record header of the unversioned format, without the crc
+const LEGACY_HEADER_LEN: usize = 5;
+/// `never`, `always` (the default) or the number of records between syncs
+pub const WAL_SYNC: &'static str = "WAL_SYNC";
+
+/// When appended records are synced to the disk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncPolicy {
+    /// Leave it to the OS, a crash of the machine may lose the latest records
+    Never,
+    Always,
+    Every(usize),
+}
+
+impl SyncPolicy {
+    pub fn parse(policy: &str) -> Option<SyncPolicy> {
+        match policy {
+            "never" => Some(SyncPolicy::Never),
+            "always" => Some(SyncPolicy::Always),
+            n => n.parse().ok().map(|n| if n <= 1 { SyncPolicy::Always } else { SyncPolicy::Every(n) }),
+        }
+    }
+
+    pub fn from_env() -> SyncPolicy {
+        match env::var(WAL_SYNC) {
+            Ok(policy) => {
+                SyncPolicy::parse(&policy).unwrap_or_else(|| {
+                                                              warn!("unknown {} {}, sync always", WAL_SYNC, policy);
+                                                              SyncPolicy::Always
+                                                          })
+            }
+            Err(_) => SyncPolicy::Always,
+        }
+    }
+}
+
+/// CRC-32 (IEEE 802.3)
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn put_u32(buf: &mut Vec<u8>, n: u32) {
+    buf.extend_from_slice(&[n as u8, (n >> 8) as u8, (n >> 16) as u8, (n >> 24) as u8]);
+}
+
+fn get_u32(buf: &[u8]) -> u32 {
+    buf[0] as u32 | (buf[1] as u32) << 8 | (buf[2] as u32) << 16 | (buf[3] as u32) << 24
+}
+
+fn checksum(mtype: u8, payload: &[u8]) -> u32 {
+    let mut data = Vec::with_capacity(payload.len() + 1);
+    data.push(mtype);
+    data.extend_from_slice(payload);
+    crc32(&data)
+}
+
+pub fn encode(mtype: u8, payload: &[u8]) -> Vec<u8> {
+    let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+    put_u32(&mut record, payload.len() as u32);
+    record.push(mtype);
+    put_u32(&mut record, checksum(mtype, payload));
+    record.extend_from_slice(payload);
+    record
+}
+
+fn file_header() -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    header.push(VERSION);
+    header
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Decoded {
+    pub records: Vec<(u8, Vec<u8>)>,
+    /// Length of the intact prefix, what follows it is dropped
+    pub valid_len: usize,
+    /// Read from a file of the unversioned format
+    pub legacy: bool,
+}
+
+pub fn decode(buf: &[u8]) -> Decoded {
+    let mut records = Vec::new();
+    let mut index = 0;
+    while index + HEADER_LEN <= buf.len() {
+        let len = get_u32(&buf[index..]) as usize;
+        let mtype = buf[index + 4];
+        let crc = get_u32(&buf[index + 5..]);
+        let start = index + HEADER_LEN;
+        if len > buf.len() - start {
+            break;
+        }
+        let payload = &buf[start..start + len];
+        if checksum(mtype, payload) != crc {
+            break;
+        }
+        records.push((mtype, payload.to_vec()));
+        index = start + len;
+    }
+    Decoded {
+        records: records,
+        valid_len: index,
+        legacy: false,
+    }
+}
+
+fn decode_legacy(buf: &[u8]) -> Decoded {
+    let mut records = Vec::new();
+    let mut index = 0;
+    while index + LEGACY_HEADER_LEN <= buf.len() {
+        let len = get_u32(&buf[index..]) as usize;
+        let mtype = buf[index + 4];
+        let start = index + LEGACY_HEADER_LEN;
+        if len > buf.len() - start {
+            break;
+        }
+        records.push((mtype, buf[start..start + len].to_vec()));
+        index = start + len;
+    }
+    Decoded {
+        records: records,
+        valid_len: index,
+        legacy: true,
+    }
+}
+
+/// Records of a whole file, by the format its header names. `valid_len` counts
+/// the header, it is 0 for a file which is empty or whose header is torn.
+pub fn decode_file(buf: &[u8]) -> io::Result<Decoded> {
+    let header = file_header();
+    if buf.len() < FILE_HEADER_LEN && header.starts_with(buf) {
+        return Ok(decode(&[]));
+    }
+    if !buf.starts_with(MAGIC) {
+        return Ok(decode_legacy(buf));
+    }
+    if buf[MAGIC.len()] != VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("wal of version {}, this node reads version {}", buf[MAGIC.len()], VERSION)));
+    }
+    let mut decoded = decode(&buf[FILE_HEADER_LEN..]);
+    decoded.valid_len += FILE_HEADER_LEN;
+    Ok(decoded)
+}
+
+/// Records of the file at `path`, without repairing it
+pub fn read(path: &Path) -> io::Result<Decoded> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    decode_file(&buf)
+}
+
+/// Replace the file at `path` with one record. A crash leaves either the old
+/// file or the new one.
+pub fn replace(path: &Path, mtype: u8, payload: &[u8]) -> io::Result<()> {
+    rewrite(path, &[(mtype, payload.to_vec())])
+}
+
+/// Replace the file at `path` with `records`, atomically as `replace`
+pub fn rewrite(path: &Path, records: &[(u8, Vec<u8>)]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    {
+        let mut buf = file_header();
+        for &(mtype, ref payload) in records {
+            buf.extend(encode(mtype, payload));
+        }
+        let mut file = File::create(&tmp)?;
+        file.write_all(&buf)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp, path)?;
+    if let Some(dir) = path.parent() {
+        // the rename is durable once the directory is
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// An append only file of records
+#[derive(Debug)]
+pub struct Log {
+    file: File,
+    path: PathBuf,
+    policy: SyncPolicy,
+    unsynced: usize,
+}
+
+impl Log {
+    /// Open or create the log at `path`. A log of the unversioned format is
+    /// rewritten in the current one, a log of a later version is an error.
+    pub fn open(path: &Path, policy: SyncPolicy) -> io::Result<Log> {
+        let mut log = Log {
+            file: OpenOptions::new().read(true).write(true).create(true).open(path)?,
+            path: path.to_path_buf(),
+            policy: policy,
+            unsynced: 0,
+        };
+        let decoded = log.read_all()?;
+        if decoded.legacy {
+            warn!("wal {:?} moves {} records to version {}", path, decoded.records.len(), VERSION);
+            rewrite(path, &decoded.records)?;
+            log.file = OpenOptions::new().read(true).write(true).open(path)?;
+        } else if decoded.valid_len < FILE_HEADER_LEN {
+            log.file.set_len(0)?;
+            log.file.seek(SeekFrom::Start(0))?;
+            log.file.write_all(&file_header())?;
+            if policy != SyncPolicy::Never {
+                log.file.sync_all()?;
+            }
+        }
+        Ok(log)
+    }
+
+    fn read_all(&mut self) -> io::Result<Decoded> {
+        let mut buf = Vec::new();
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.read_to_end(&mut buf)?;
+        decode_file(&buf)
+    }
+
+    pub fn append(&mut self, mtype: u8, payload: &[u8]) -> io::Result<usize> {
+        self.file.seek(SeekFrom::End(0))?;
+        self.file.write_all(&encode(mtype, payload))?;
+        self.unsynced += 1;
+        let sync = match self.policy {
+            SyncPolicy::Never => false,
+            SyncPolicy::Always => true,
+            SyncPolicy::Every(n) => self.unsynced >= n,
+        };
+        if sync {
+            self.file.sync_data()?;
+            self.unsynced = 0;
+        }
+        Ok(payload.len())
+    }
+
+    /// All the intact records, a torn tail is cut off so that appends follow them
+    pub fn load(&mut self) -> io::Result<Vec<(u8, Vec<u8>)>> {
+        let decoded = self.read_all()?;
+        let len = self.file.metadata()?.len() as usize;
+        if decoded.legacy || decoded.valid_len < FILE_HEADER_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("wal {:?} lost its header", self.path)));
+        }
+        if decoded.valid_len < len {
+            warn!("wal drops {} bytes after {} records", len - decoded.valid_len, decoded.records.len());
+            self.file.set_len(decoded.valid_len as u64)?;
+            self.file.sync_all()?;
+        }
+        Ok(decoded.records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate mktemp;
+    use super::*;
+    use std::fs::{File, OpenOptions};
+    use std::io::Write;
+
+    #[test]
+    fn crc() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn torn_tail() {
+        let mut buf = encode(1, b"propose");
+        buf.extend(encode(2, b"vote"));
+        let intact = buf.len();
+        let third = encode(3, b"state");
+        for cut in 0..third.len() {
+            let mut torn = buf.clone();
+            torn.extend_from_slice(&third[..cut]);
+            let decoded = decode(&torn);
+            assert_eq!(decoded.records, vec![(1, b"propose".to_vec()), (2, b"vote".to_vec())]);
+            assert_eq!(decoded.valid_len, intact);
+        }
+
+        // garbage with a plausible length
+        let mut flipped = buf.clone();
+        flipped[intact - 1] ^= 1;
+        assert_eq!(decode(&flipped).records, vec![(1, b"propose".to_vec())]);
+    }
+
+    #[test]
+    fn repair_on_load() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let path = dir.as_ref().join("1.log");
+        {
+            let mut log = Log::open(&path, SyncPolicy::Every(2)).unwrap();
+            log.append(1, b"a").unwrap();
+            log.append(2, b"b").unwrap();
+        }
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&[7, 0, 0]).unwrap();
+
+        let mut log = Log::open(&path, SyncPolicy::Always).unwrap();
+        assert_eq!(log.load().unwrap(), vec![(1, b"a".to_vec()), (2, b"b".to_vec())]);
+        log.append(3, b"c").unwrap();
+        assert_eq!(read(&path).unwrap().records.len(), 3);
+
+        replace(&path, 4, b"d").unwrap();
+        assert_eq!(read(&path).unwrap().records, vec![(4, b"d".to_vec())]);
+    }
+
+    #[test]
+    fn file_versions() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let path = dir.as_ref().join("1.log");
+
+        // unversioned records, the last one torn
+        let mut legacy = vec![7, 0, 0, 0, 1];
+        legacy.extend_from_slice(b"propose");
+        legacy.extend_from_slice(&[4, 0, 0, 0, 2]);
+        legacy.extend_from_slice(b"vote");
+        legacy.extend_from_slice(&[5, 0, 0, 0, 3, b's']);
+        File::create(&path).unwrap().write_all(&legacy).unwrap();
+        assert!(read(&path).unwrap().legacy);
+        {
+            let mut log = Log::open(&path, SyncPolicy::Always).unwrap();
+            assert_eq!(log.load().unwrap(), vec![(1, b"propose".to_vec()), (2, b"vote".to_vec())]);
+            log.append(3, b"state").unwrap();
+        }
+        let decoded = read(&path).unwrap();
+        assert!(!decoded.legacy);
+        assert_eq!(decoded.records.len(), 3);
+
+        // a torn header is an empty log
+        File::create(&path).unwrap().write_all(&MAGIC[..3]).unwrap();
+        assert!(Log::open(&path, SyncPolicy::Always).unwrap().load().unwrap().is_empty());
+        assert_eq!(read(&path).unwrap().valid_len, FILE_HEADER_LEN);
+
+        let mut later = MAGIC.to_vec();
+        later.push(VERSION + 1);
+        later.extend(encode(1, b"propose"));
+        File::create(&path).unwrap().write_all(&later).unwrap();
+        assert!(Log::open(&path, SyncPolicy::Always).is_err());
+        assert!(read(&path).is_err());
+    }
+
+    #[test]
+    fn sync_policy() {
+        assert_eq!(SyncPolicy::parse("never"), Some(SyncPolicy::Never));
+        assert_eq!(SyncPolicy::parse("always"), Some(SyncPolicy::Always));
+        assert_eq!(SyncPolicy::parse("1"), Some(SyncPolicy::Always));
+        assert_eq!(SyncPolicy::parse("16"), Some(SyncPolicy::Every(16)));
+        assert_eq!(SyncPolicy::parse("sometimes"), None);
+    }
+}
@@ -19,7 +19,7 @@ extern crate pubsub;
 extern crate dotenv;
 
 use dotenv::dotenv;
-use pubsub::start_pubsub;
+use pubsub::{start_pubsub, sub_channel};
 use std::env;
 use std::sync::mpsc::channel;
 use std::thread;
@@ -35,12 +35,12 @@ fn main() {
     let mut count = 0;
     let start = SystemTime::now();
     let max = args[1].parse::<u64>().unwrap();
-    let (tx_sub, rx_sub) = channel();
+    let (tx_sub, rx_sub) = sub_channel();
     let (tx_pub, rx_pub) = channel();
     start_pubsub("request", vec!["response"], tx_sub, rx_pub);
 
     thread::spawn(move || {
-        let (tx_sub, rx_sub) = channel();
+        let (tx_sub, rx_sub) = sub_channel();
         let (tx_pub, rx_pub) = channel();
         start_pubsub("response", vec!["request"], tx_sub, rx_pub);
         loop {
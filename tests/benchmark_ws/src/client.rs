@@ -22,6 +22,7 @@ use rustc_hex::FromHex;
 use test::Bencher;
 use util::*;
 use util::crypto::CreateKey;
+use uuid::Uuid;
 
 #[derive(Clone, Debug)]
 pub enum RpcMethod {
@@ -58,7 +59,8 @@ impl Client {
         let mut tx = Transaction::new();
         tx.set_data(data);
         tx.set_to(address);
-        tx.set_nonce("0".to_string());
+        // the same sender must not reuse a nonce, or the pool replaces the pending tx
+        tx.set_nonce(Uuid::new_v4().to_string());
         tx.set_valid_until_block(curh + 100);
         tx.set_quota(quota);
         tx.sign(*pv).take_transaction_with_sig()
@@ -27,6 +27,7 @@ extern crate dotenv;
 extern crate bincode;
 extern crate cpuprofiler;
 extern crate common_types;
+extern crate network;
 
 #[macro_use]
 extern crate serde_derive;
@@ -36,6 +37,7 @@ extern crate logger;
 
 mod generate_block;
 mod call_chain;
+mod network_throughput;
 
 use call_chain::Callchain;
 use clap::App;
@@ -43,6 +45,7 @@ use core::db;
 use core::libchain::Genesis;
 use cpuprofiler::PROFILER;
 use generate_block::Generateblock;
+use network_throughput::network_throughput;
 use std::{time, thread};
 use std::sync::Arc;
 use std::sync::mpsc::channel;
@@ -50,10 +53,11 @@ use util::H256;
 use util::datapath::DataPath;
 use util::kvdb::{Database, DatabaseConfig};
 
+const CONTRACT_CODE: &'static str = "60606040523415600e57600080fd5b5b5b5b60948061001f6000396000f30060606040526000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff1680635524107714603d575b600080fd5b3415604757600080fd5b605b6004808035906020019091905050605d565b005b806000819055505b505600a165627a7a72305820c471b4376626da2540b2374e8b4110501051c426ff46814a6170ce9e219e49a80029";
 
 //创建合约交易性能
 fn create_contract(block_tx_num: i32, call: Callchain, pre_hash: H256, flag_prof_start: u64, flag_prof_duration: u64, flag: i32) {
-    let code = "60606040523415600e57600080fd5b5b5b5b60948061001f6000396000f30060606040526000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff1680635524107714603d575b600080fd5b3415604757600080fd5b605b6004808035906020019091905050605d565b005b806000819055505b505600a165627a7a72305820c471b4376626da2540b2374e8b4110501051c426ff46814a6170ce9e219e49a80029";
+    let code = CONTRACT_CODE;
     let mut contract_address = "".to_string();
     if flag != 0 {
         contract_address = "ffffffffffffffffffffffffffffffffffffffff".to_string();
@@ -91,7 +95,7 @@ fn create_contract(block_tx_num: i32, call: Callchain, pre_hash: H256, flag_prof
 #[allow(unused_assignments)]
 fn send_contract_tx(block_tx_num: i32, call: Callchain, pre_hash: H256, flag_prof_start: u64, flag_prof_duration: u64) {
     //构造创建合约的交易交易
-    let mut code = "60606040523415600e57600080fd5b5b5b5b60948061001f6000396000f30060606040526000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff1680635524107714603d575b600080fd5b3415604757600080fd5b605b6004808035906020019091905050605d565b005b806000819055505b505600a165627a7a72305820c471b4376626da2540b2374e8b4110501051c426ff46814a6170ce9e219e49a80029";
+    let mut code = CONTRACT_CODE;
     let mut contract_address = "".to_string();
     let mut txs = Vec::new();
     let mut hash = H256::default();
@@ -138,6 +142,14 @@ fn send_contract_tx(block_tx_num: i32, call: Callchain, pre_hash: H256, flag_pro
 }
 
 
+//网络传输性能
+fn send_over_network(block_tx_num: i32, pre_hash: H256) {
+    let txs = (0..block_tx_num).map(|_| Generateblock::generate_tx(CONTRACT_CODE, "".to_string())).collect();
+    let (_, block) = Generateblock::build_block(txs, pre_hash, 1);
+    network_throughput(&block);
+}
+
+
 fn profifer(flag_prof_start: u64, flag_prof_duration: u64) {
     //start profiling
     let start = flag_prof_start;
@@ -162,7 +174,7 @@ fn main() {
         .about("CITA Block Chain Node powered by Rust")
         .arg_from_usage("--tx_num=[4000] 'transation num in block'")
         .arg_from_usage("-g, --genesis=[FILE] 'Sets a custom config file'")
-        .arg_from_usage("-m, --method=[method] 'create | call | store'")
+        .arg_from_usage("-m, --method=[method] 'create | call | store | network'")
         .arg_from_usage("--flag_prof_start=[1] 'prof start time'")
         .arg_from_usage("--flag_prof_duration=[1] 'prof run time'")
         .arg_from_usage("-c, --config=[FILE] 'Sets a check config file'")
@@ -187,6 +199,7 @@ fn main() {
     let pre_hash = call.get_pre_hash();
     match method {
         "create" => create_contract(block_tx_num, call.clone(), pre_hash, flag_prof_start, flag_prof_duration, 0),
+        "network" => send_over_network(block_tx_num, pre_hash),
         "store" => create_contract(block_tx_num, call.clone(), pre_hash, flag_prof_start, flag_prof_duration, 1),
         "call" | _ => send_contract_tx(block_tx_num, call.clone(), pre_hash, flag_prof_start, flag_prof_duration),
     }
@@ -0,0 +1,154 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Throughput of the network layer on the msgs of this scenario: every tx as
+//! auth relays it to the other nodes, and the block as chain broadcasts it.
+//! They are queued one at a time to a client connected to a server on loopback,
+//! with batching and compression on and off.
+
+use core::libchain::block::Block;
+use crypto::{CreateKey, KeyPair};
+use libproto::{communication, Request};
+use libproto::router::{AuthTx, ChainBlock, Route};
+use network::config::{NetConfig, PeerConfig};
+use network::connection::Connection;
+use network::nodes::NodeList;
+use network::reputation::{Reputation, ReputationConfig};
+use network::server::{MySender, start_server};
+use network::stats::InboundStats;
+use protobuf::Message;
+use protobuf::core::parse_from_bytes;
+use std::sync::Arc;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant};
+use util::RwLock;
+
+/// Each run listens on the next port, the servers of the previous runs are still up
+const PORT: u64 = 41000;
+/// Origin of the msgs the client sends
+const CLIENT_ID: u32 = 1;
+/// Seconds to wait for the connection, and for the server to get every msg
+const TIMEOUT: u64 = 60;
+
+fn config(id_card: u32, port: u64, signer: &KeyPair, batch: bool, compress: bool, peer: Option<u64>) -> NetConfig {
+    NetConfig {
+        id_card: Some(id_card),
+        port: Some(port),
+        max_peer: None,
+        peers: peer.map(|port| {
+                            vec![PeerConfig {
+                                     id_card: Some(0),
+                                     ip: Some("127.0.0.1".to_string()),
+                                     port: Some(port),
+                                 }]
+                        }),
+        bootstrap: None,
+        ip: None,
+        peers_file: None,
+        signer: Some(signer.privkey().hex()),
+        plaintext: None,
+        max_frame_size: None,
+        checksum: None,
+        compress: Some(compress),
+        batch: Some(batch),
+        ban_threshold: None,
+        ban_secs: None,
+        admin_rpc: None,
+    }
+}
+
+fn secs(elapsed: Duration) -> f64 {
+    elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9
+}
+
+fn measure(name: &str, msgs: &[Vec<u8>], port: u64, batch: bool, compress: bool) {
+    let (server_key, client_key) = (KeyPair::gen_keypair(), KeyPair::gen_keypair());
+    let mut nodes = NodeList::new();
+    nodes.update(0, vec![server_key.address(), client_key.address()].into_iter().collect());
+    let nodes = Arc::new(RwLock::new(nodes));
+
+    let (tx, rx) = channel();
+    let sender = MySender::new(tx);
+    let inbound = Arc::new(RwLock::new(InboundStats::new()));
+    let server_reputation = Arc::new(RwLock::new(Reputation::new(ReputationConfig::default())));
+    start_server(&config(0, port, &server_key, batch, compress, None), nodes.clone(), sender.clone(), sender.clone(), sender, inbound, server_reputation);
+
+    let client_reputation = Arc::new(RwLock::new(Reputation::new(ReputationConfig::default())));
+    let con = Connection::new(&config(CLIENT_ID, port + 1, &client_key, batch, compress, Some(port)), nodes, client_reputation);
+    let peer = &con.peers[0];
+    let start = Instant::now();
+    while !peer.is_connected() {
+        assert!(start.elapsed() < Duration::from_secs(TIMEOUT), "client did not connect to port {}", port);
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let bytes: usize = msgs.iter().map(|msg| msg.len()).sum();
+    let writes = peer.writes();
+    let start = Instant::now();
+    for msg in msgs {
+        // the queue to the peer is full, wait for the connection to take some
+        while !peer.send(msg.clone()) {
+            assert!(peer.is_connected(), "connection to port {} lost", port);
+            thread::yield_now();
+        }
+    }
+    for _ in msgs {
+        rx.recv_timeout(Duration::from_secs(TIMEOUT)).expect("server did not get every msg");
+    }
+    let elapsed = secs(start.elapsed());
+    info!("{}, batching {}, compression {}: {} msgs, {} bytes, {:.0} msgs/s, {:.1} MB/s, {} write calls",
+          name,
+          if batch { "on" } else { "off" },
+          if compress { "on" } else { "off" },
+          msgs.len(),
+          bytes,
+          msgs.len() as f64 / elapsed,
+          bytes as f64 / elapsed / 1e6,
+          peer.writes() - writes);
+}
+
+/// The msg as the client writes it, see `connection::broadcast`
+fn relayed(msg: Vec<u8>) -> Vec<u8> {
+    let mut msg = parse_from_bytes::<communication::Message>(&msg).unwrap();
+    msg.set_origin(CLIENT_ID);
+    msg.write_to_bytes().unwrap()
+}
+
+/// Measure the txs of `block` one by one, and the block itself
+pub fn network_throughput(block: &Block) {
+    let tx_msgs: Vec<Vec<u8>> = block.body
+        .protobuf()
+        .get_transactions()
+        .iter()
+        .map(|tx| {
+                 let mut request = Request::new();
+                 request.set_un_tx(tx.get_transaction_with_sig().clone());
+                 relayed(AuthTx::msg(&request).write_to_bytes().unwrap())
+             })
+        .collect();
+    let block_msgs = vec![relayed(ChainBlock::msg(&block.protobuf()).write_to_bytes().unwrap())];
+
+    info!("===============start network===============");
+    let mut port = PORT;
+    for &(batch, compress) in &[(false, false), (true, false), (false, true), (true, true)] {
+        measure("txs", &tx_msgs, port, batch, compress);
+        measure("block", &block_msgs, port + 2, batch, compress);
+        port += 4;
+    }
+    info!("===============end network===============");
+}
@@ -17,7 +17,7 @@
 
 extern crate pubsub;
 
-use pubsub::start_pubsub;
+use pubsub::{start_pubsub, sub_channel};
 use std::env;
 use std::sync::mpsc::channel;
 use std::thread;
@@ -32,12 +32,12 @@ fn main() {
     let max = args[1].parse::<u64>().unwrap();
     let size = args[2].parse::<usize>().unwrap();
     println!{"test count {:?}, test size: {:?}", max, size};
-    let (tx_sub, rx_sub) = channel();
+    let (tx_sub, rx_sub) = sub_channel();
     let (tx_pub, rx_pub) = channel();
     start_pubsub("latency_req", vec!["latency_res"], tx_sub, rx_pub);
 
     thread::spawn(move || {
-        let (tx_sub, rx_sub) = channel();
+        let (tx_sub, rx_sub) = sub_channel();
         let (tx_pub, rx_pub) = channel();
         start_pubsub("latency_res", vec!["latency_req"], tx_sub, rx_pub);
         loop {
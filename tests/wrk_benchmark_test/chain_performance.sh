@@ -36,4 +36,8 @@ elif [ $category == 3 ]; then
                   --config ${BINARY_DIR}/node0/chain.json                                                        \
                   --method store --tx_num=$tx_num                                                                \
                   --flag_prof_start=$flag_prof_start --flag_prof_duration=$flag_prof_duration
+elif [ $category == 4 ]; then
+    ${BINARY_DIR}/bin/chain_performance --genesis ${BINARY_DIR}/node0/genesis.json                               \
+                  --config ${BINARY_DIR}/node0/chain.json                                                        \
+                  --method network --tx_num=$tx_num
 fi
@@ -0,0 +1,100 @@
+// CITA
+// Copyright 2016-2017 Cryptape Technologies LLC.
+
+// This program is free software: you can redistribute it
+// and/or modify it under the terms of the GNU General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option) any
+// later version.
+
+// This program is distributed in the hope that it will be
+// useful, but WITHOUT ANY WARRANTY; without even the implied
+// warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR
+// PURPOSE. See the GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Chain, auth, jsonrpc, consensus and network of a node as threads of one
+//! process, talking over the in-process pubsub, so no broker is needed.
+//! For dev and CI, run it in a node directory made by the admintool.
+//!
+//! It is left out of the workspace, as the in-process backend would take over the
+//! pubsub of every service built along with it. Build it with
+//! `cargo build --manifest-path tools/allinone/Cargo.toml`.
+
+extern crate auth;
+extern crate chain;
+extern crate consensus_tendermint;
+extern crate jsonrpc;
+extern crate network;
+#[macro_use]
+extern crate log;
+extern crate clap;
+extern crate dotenv;
+extern crate logger;
+extern crate pubsub;
+extern crate util;
+
+use clap::App;
+use std::process;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use util::panichandler::set_panic_handler;
+
+/// Seconds to wait for the services to subscribe
+const START_TIMEOUT: u64 = 30;
+
+fn start<F: FnOnce() + Send + 'static>(name: &str, service: F) -> JoinHandle<()> {
+    info!("start {}", name);
+    thread::Builder::new().name(name.to_string()).spawn(service).expect("spawn service thread")
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    // Always print backtrace on panic.
+    ::std::env::set_var("RUST_BACKTRACE", "full");
+
+    // a panic in any service exits the process
+    set_panic_handler();
+
+    logger::init();
+    info!("CITA:allinone");
+    let matches = App::new("allinone")
+        .version("0.1")
+        .author("Cryptape")
+        .about("CITA node with all services in one process")
+        .args_from_usage("--auth=[FILE] 'Sets the auth config file'")
+        .args_from_usage("--network=[FILE] 'Sets the network config file'")
+        .args_from_usage("--consensus=[FILE] 'Sets the consensus config file'")
+        .args_from_usage("--jsonrpc=[FILE] 'Sets the jsonrpc config file'")
+        .args_from_usage("--genesis=[FILE] 'Sets the genesis config file'")
+        .args_from_usage("--chain=[FILE] 'Sets the chain config file'")
+        .get_matches();
+
+    let path = |name: &str, default: &str| matches.value_of(name).unwrap_or(default).to_string();
+    let auth_path = path("auth", "auth.json");
+    let network_path = path("network", "network.toml");
+    let consensus_path = path("consensus", "consensus.json");
+    let jsonrpc_path = path("jsonrpc", "jsonrpc.json");
+    let genesis_path = path("genesis", "genesis.json");
+    let chain_path = path("chain", "chain.json");
+
+    let mut services = vec![
+        start("auth", move || auth::run(&auth_path, false)),
+        start("network", move || network::run(&network_path, false)),
+        start("consensus", move || consensus_tendermint::run(&consensus_path, 0, 0)),
+        start("jsonrpc", move || jsonrpc::run(&jsonrpc_path)),
+    ];
+
+    // chain announces its status once it starts, the others must be listening by then
+    if !pubsub::wait_bound(&["auth", "network", "network_tx", "consensus", "jsonrpc"], Duration::from_secs(START_TIMEOUT)) {
+        error!("services did not subscribe within {}s", START_TIMEOUT);
+        process::exit(1);
+    }
+    services.push(start("chain", move || chain::run(&genesis_path, &chain_path)));
+
+    for service in services {
+        let _ = service.join();
+    }
+}